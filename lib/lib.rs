@@ -1,5 +1,7 @@
 use proc_macro::TokenStream;
 use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
 
 /// Attribute macro that wraps a function with the sole purpose of benchmarking the duration of its execution,
 /// and printing the gathered metrics through Rust's logging API.
@@ -7,7 +9,8 @@ use quote::quote;
 /// This macro will introduce minimal overhead in **debug** builds; wrapping the selected functions in closures
 /// that track their execution time, and printing the tracked result in a `trace!()` call upon their return. This
 /// is *disabled* in **release** builds, which makes the macro a zero-cost abstraction in productive environments
-/// (not that said cost was much at all to begin with).
+/// (not that said cost was much at all to begin with); unless the `trace-time-release` feature is enabled, which
+/// keeps the instrumentation in optimized binaries as well.
 /// <br/><br/>
 /// **Heavily** inspired on: https://stackoverflow.com/a/60732300.
 ///
@@ -59,8 +62,52 @@ use quote::quote;
 ///  TRACE prolice > Tracing time for `fn get_pr_reviews()`...
 ///  TRACE prolice > Time elapsed for `fn get_pr_reviews()` was: Duration { seconds: 1, nanoseconds: 268012488 }
 /// ```
+///
+/// ## Custom sink example
+///
+/// Timings can be routed to a custom sink (ie: a histogram collector) on top of the `trace!()` call, by naming
+/// a function that takes the traced function's name and its elapsed [`time::Duration`]:
+///
+/// ```rust
+/// use prpolice_lib::*;
+///
+/// fn record_timing(name: &str, elapsed: time::Duration) {
+///     println!("{} took {:?}", name, elapsed);
+/// }
+///
+/// #[prolice_trace_time(sink = "record_timing")]
+/// fn get_answer() -> u64 {
+///     42
+/// }
+/// ```
+///
+/// ## Argument logging example
+///
+/// When the same function runs concurrently (ie: once per PR), its traces can be told apart by logging some of
+/// its arguments along with them. Arguments are formatted with `{:?}`; those that do not implement [`Debug`] are
+/// logged as `<non-Debug>` instead:
+///
+/// ```rust
+/// use prpolice_lib::*;
+///
+/// #[prolice_trace_time(log = [repo_name, pr_number])]
+/// fn get_pr_title(repo_name: &str, pr_number: u64) -> String {
+///     format!("{}#{}", repo_name, pr_number)
+/// }
+/// ```
+/// This will output:
+/// ```text
+///  TRACE prolice > Tracing time for `fn get_pr_title(repo_name: "prolice", pr_number: 42)`...
+///  TRACE prolice > Time elapsed for `fn get_pr_title(repo_name: "prolice", pr_number: 42)` was: Duration { seconds: 0, nanoseconds: 1870 }
+/// ```
 #[proc_macro_attribute]
-pub fn prolice_trace_time(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn prolice_trace_time(attr: TokenStream, item: TokenStream) -> TokenStream {
+    // parse the passed attribute arguments (a custom sink and/or arguments to be logged), if any
+    let TraceTimeArgs {
+        sink,
+        log,
+    } = syn::parse_macro_input!(attr as TraceTimeArgs);
+
     // parse the passed item as a function
     let func = syn::parse_macro_input!(item as syn::ItemFn);
 
@@ -72,14 +119,61 @@ pub fn prolice_trace_time(_attr: TokenStream, item: TokenStream) -> TokenStream
         block,
     } = func;
 
+    // every argument to be logged must be one of the function's own (named) parameters
+    if let Some(unknown_arg) = log.iter().find(|arg| !has_param(&sig, arg)) {
+        return TokenStream::from(
+            syn::Error::new_spanned(unknown_arg, "not a parameter of the traced function")
+                .to_compile_error(),
+        );
+    }
+
     // determine async-ness of function
     let is_async_fn = sig.asyncness.is_some();
 
     // extract function name for prettier output
     let name = format!("{}", sig.ident);
 
-    // determine type of build (debug/release)
-    let release_build = !cfg!(debug_assertions);
+    // determine type of build (debug/release); release builds may opt back into time tracking
+    let release_build = !cfg!(debug_assertions) && !cfg!(feature = "trace-time-release");
+
+    // route the elapsed time to the custom sink too, if any
+    let sink_call = match sink {
+        Some(sink) => quote! { #sink(#name, elapsed); },
+        None => quote! {},
+    };
+
+    // format the arguments to be logged upon entry (before the function's body gets to move them); falling back
+    // to a placeholder for those that do not implement Debug - through autoref-based specialization, since
+    // trait implementations cannot be inspected from within a macro
+    let logged_args_format =
+        log.iter().map(|arg| format!("{}: {{}}", arg)).collect::<Vec<String>>().join(", ");
+    let logged_args = if log.is_empty() {
+        quote! { let logged_args = ""; }
+    } else {
+        quote! {
+            struct TracedArg<'a, T>(&'a T);
+
+            trait DebugTracedArg {
+                fn to_trace(&self) -> String;
+            }
+            impl<'a, T: std::fmt::Debug> DebugTracedArg for TracedArg<'a, T> {
+                fn to_trace(&self) -> String {
+                    format!("{:?}", self.0)
+                }
+            }
+
+            trait NonDebugTracedArg {
+                fn to_trace(&self) -> String;
+            }
+            impl<'a, T> NonDebugTracedArg for &TracedArg<'a, T> {
+                fn to_trace(&self) -> String {
+                    String::from("<non-Debug>")
+                }
+            }
+
+            let logged_args = format!(#logged_args_format, #((&TracedArg(&#log)).to_trace()),*);
+        }
+    };
 
     // wrap body only if function is async, otherwise just put it in the middle of the time-tracking
     let block = if release_build {
@@ -89,9 +183,14 @@ pub fn prolice_trace_time(_attr: TokenStream, item: TokenStream) -> TokenStream
             use log::trace;
             use time::Instant;
 
+            #logged_args
+            trace!("Tracing time for `fn {}({})`...", #name, logged_args);
+
             let start = Instant::now();
             let result = async move { #block }.await;
-            trace!("Time elapsed for `fn {}()` was: {:?}", #name, start.elapsed());
+            let elapsed = start.elapsed();
+            trace!("Time elapsed for `fn {}({})` was: {:?}", #name, logged_args, elapsed);
+            #sink_call
             result
         }
     } else {
@@ -99,9 +198,14 @@ pub fn prolice_trace_time(_attr: TokenStream, item: TokenStream) -> TokenStream
             use log::trace;
             use time::Instant;
 
+            #logged_args
+            trace!("Tracing time for `fn {}({})`...", #name, logged_args);
+
             let start = Instant::now();
             let result = { #block };
-            trace!("Time elapsed for `fn {}()` was: {:?}", #name, start.elapsed());
+            let elapsed = start.elapsed();
+            trace!("Time elapsed for `fn {}({})` was: {:?}", #name, logged_args, elapsed);
+            #sink_call
             result
         }
     };
@@ -118,3 +222,55 @@ pub fn prolice_trace_time(_attr: TokenStream, item: TokenStream) -> TokenStream
     // convert the output from a `proc_macro2::TokenStream` to a `proc_macro::TokenStream`
     TokenStream::from(output)
 }
+
+/// The arguments `prolice_trace_time` accepts: `sink = "path::to::fn"` and/or `log = [arg_a, arg_b]`.
+struct TraceTimeArgs {
+    sink: Option<syn::Path>,
+    log: Vec<syn::Ident>,
+}
+
+impl Parse for TraceTimeArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = TraceTimeArgs {
+            sink: None,
+            log: Vec::new(),
+        };
+
+        while !input.is_empty() {
+            let key: syn::Ident = input.parse()?;
+            input.parse::<syn::Token![=]>()?;
+
+            if key == "sink" {
+                let sink: syn::LitStr = input.parse()?;
+                args.sink = Some(sink.parse()?);
+            } else if key == "log" {
+                let content;
+                syn::bracketed!(content in input);
+                args.log = Punctuated::<syn::Ident, syn::Token![,]>::parse_terminated(&content)?
+                    .into_iter()
+                    .collect();
+            } else {
+                return Err(syn::Error::new_spanned(
+                    key,
+                    "unsupported argument; only `sink = \"path::to::fn\"` and `log = [arg, ...]` are supported",
+                ));
+            }
+
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+/// Determines whether the function with signature `sig` has a (plainly named) parameter called `arg`.
+fn has_param(sig: &syn::Signature, arg: &syn::Ident) -> bool {
+    sig.inputs.iter().any(|input| match input {
+        syn::FnArg::Typed(pat_type) => {
+            matches!(&*pat_type.pat, syn::Pat::Ident(pat_ident) if pat_ident.ident == *arg)
+        }
+        syn::FnArg::Receiver(_) => false,
+    })
+}
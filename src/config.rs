@@ -0,0 +1,320 @@
+//! Support for a `.prolice.toml` configuration file, whose entries stand in for any CLI arg that was not
+//! explicitly passed (ie: an often-repeated `--owner` or `--exclude-path`). Precedence goes: explicit CLI
+//! arg > configuration file > built-in default.
+//! <br/><br/>
+//! Every entry is keyed after its CLI arg's long name, in snake_case (ie: `sample_size = 50` stands in for
+//! `--sample-size 50`). Flags take a boolean, and args that may be repeated take a list as well.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::{App, ArgSettings};
+use serde::Deserialize;
+
+/// The configuration file that is looked up in the current working directory, unless another one is
+/// explicitly pointed at.
+pub const DEFAULT_CONFIG_FILE: &str = ".prolice.toml";
+
+/// A single configuration entry's value.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ConfigValue {
+    Flag(bool),
+    Integer(i64),
+    Float(f64),
+    Text(String),
+    List(Vec<String>),
+}
+
+/// A parsed configuration file, whose entries have yet to be merged into the CLI args.
+#[derive(Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Config {
+    entries: BTreeMap<String, ConfigValue>,
+}
+
+/// What a configuration entry needs to know about the CLI arg it stands in for.
+#[derive(Debug)]
+pub struct ConfigurableArg {
+    name: String,
+    short: Option<char>,
+    takes_value: bool,
+    multiple_occurrences: bool,
+    conflicts: Vec<String>,
+}
+
+impl ConfigurableArg {
+    /// Describes every arg of the `app`.
+    pub fn from_app(app: &App) -> Vec<Self> {
+        app.get_arguments()
+            .map(|arg| ConfigurableArg {
+                name: arg.get_long().unwrap_or_else(|| arg.get_name()).to_string(),
+                short: arg.get_short(),
+                takes_value: arg.is_set(ArgSettings::TakesValue),
+                multiple_occurrences: arg.is_set(ArgSettings::MultipleOccurrences),
+                conflicts: app
+                    .get_arg_conflicts_with(arg)
+                    .iter()
+                    .map(|conflict| {
+                        conflict.get_long().unwrap_or_else(|| conflict.get_name()).to_string()
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+
+    fn conflicts_with(&self, other: &ConfigurableArg) -> bool {
+        self.conflicts.contains(&other.name) || other.conflicts.contains(&self.name)
+    }
+}
+
+impl Config {
+    /// Loads the configuration file pointed at by the `config_arg` CLI arg of `cli_args` (if any), or the
+    /// [`DEFAULT_CONFIG_FILE`] of the current working directory otherwise. Only an explicitly pointed at
+    /// file is required to exist.
+    pub fn load(cli_args: &[String], config_arg: &str) -> Result<Option<Self>, String> {
+        let path = match Self::explicit_path(cli_args, config_arg) {
+            Some(path) => path,
+            None => {
+                let default_path = PathBuf::from(DEFAULT_CONFIG_FILE);
+
+                if !default_path.is_file() {
+                    return Ok(None);
+                }
+
+                default_path
+            }
+        };
+
+        Self::from_file(&path).map(Some)
+    }
+
+    /// Parses the configuration file at `path`.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let raw_config = fs::read_to_string(path).map_err(|e| {
+            format!("Could not read configuration file [{}]: {}", path.display(), e)
+        })?;
+
+        Config::from_toml(&raw_config)
+            .map_err(|e| format!("Invalid configuration file [{}]: {}", path.display(), e))
+    }
+
+    fn from_toml(raw_config: &str) -> Result<Self, String> {
+        toml::from_str(raw_config).map_err(|e| e.to_string())
+    }
+
+    /// Returns whether an entry stands in for the CLI arg `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(&name.replace('-', "_"))
+    }
+
+    /// Appends an arg to `cli_args` for every entry standing in for a CLI arg that was not explicitly
+    /// passed there (nor conflicts with one that was). Entries that do not match any of the
+    /// `configurable_args`, or whose value does not fit theirs, are rejected.
+    pub fn merge_into_args(
+        &self, mut cli_args: Vec<String>, configurable_args: &[ConfigurableArg],
+    ) -> Result<Vec<String>, String> {
+        let explicit_args = explicit_arg_names(&cli_args, configurable_args);
+
+        let explicit_args: Vec<&ConfigurableArg> = configurable_args
+            .iter()
+            .filter(|arg| explicit_args.contains(arg.name.as_str()))
+            .collect();
+
+        let mut merged_args: Vec<String> = Vec::new();
+
+        for (key, value) in &self.entries {
+            let arg = configurable_args
+                .iter()
+                .find(|arg| arg.name.replace('-', "_") == *key)
+                .ok_or_else(|| format!("unknown entry [{}]", key))?;
+
+            if explicit_args.iter().any(|explicit_arg| {
+                explicit_arg.name == arg.name || explicit_arg.conflicts_with(arg)
+            }) {
+                continue; // explicit CLI args take precedence
+            }
+
+            let values: Vec<String> = match (value, arg.takes_value) {
+                (ConfigValue::Flag(toggle), false) => {
+                    if *toggle {
+                        merged_args.push(format!("--{}", arg.name));
+                    }
+                    continue;
+                }
+                (ConfigValue::Flag(_), true) => {
+                    return Err(format!("entry [{}] must be a value, not a boolean", key))
+                }
+                (_, false) => return Err(format!("entry [{}] must be a boolean", key)),
+                (ConfigValue::Integer(value), true) => vec![value.to_string()],
+                (ConfigValue::Float(value), true) => vec![value.to_string()],
+                (ConfigValue::Text(value), true) => vec![value.clone()],
+                (ConfigValue::List(values), true) => {
+                    if !arg.multiple_occurrences && values.len() != 1 {
+                        return Err(format!("entry [{}] takes a single value, not a list", key));
+                    }
+                    values.clone()
+                }
+            };
+
+            merged_args.extend(values.into_iter().map(|value| format!("--{}={}", arg.name, value)));
+        }
+
+        cli_args.extend(merged_args);
+
+        Ok(cli_args)
+    }
+
+    fn explicit_path(cli_args: &[String], config_arg: &str) -> Option<PathBuf> {
+        let long = format!("--{}", config_arg);
+        let long_with_value = format!("{}=", long);
+
+        cli_args.iter().enumerate().skip(1).find_map(|(index, cli_arg)| {
+            if *cli_arg == long {
+                cli_args.get(index + 1).map(PathBuf::from)
+            } else if cli_arg.starts_with(&long_with_value) {
+                Some(PathBuf::from(&cli_arg[long_with_value.len()..]))
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// Names every one of the `configurable_args` that was explicitly passed among the `cli_args` (the
+/// first of which is the binary's name), either by its long name or its short one.
+fn explicit_arg_names<'a>(
+    cli_args: &[String], configurable_args: &'a [ConfigurableArg],
+) -> HashSet<&'a str> {
+    let mut explicit_args = HashSet::new();
+    let mut cli_args = cli_args.iter().skip(1);
+
+    while let Some(cli_arg) = cli_args.next() {
+        if cli_arg == "--" {
+            break; // anything past this point is a positional value
+        }
+
+        if let Some(long) = cli_arg.strip_prefix("--") {
+            let (name, inline_value) = match long.find('=') {
+                Some(index) => (&long[..index], true),
+                None => (long, false),
+            };
+
+            if let Some(arg) = configurable_args.iter().find(|arg| arg.name == name) {
+                explicit_args.insert(arg.name.as_str());
+
+                if arg.takes_value && !inline_value {
+                    cli_args.next(); // skips its value
+                }
+            }
+        } else if let Some(shorts) = cli_arg.strip_prefix('-') {
+            // several shorts may be stacked together (ie: '-sl'), up until one that takes a value
+            for (index, short) in shorts.char_indices() {
+                if let Some(arg) = configurable_args.iter().find(|arg| arg.short == Some(short)) {
+                    explicit_args.insert(arg.name.as_str());
+
+                    if arg.takes_value {
+                        if index + short.len_utf8() == shorts.len() {
+                            cli_args.next(); // skips its value
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    explicit_args
+}
+
+#[cfg(test)]
+mod config_tests {
+    use clap::{App, Arg};
+
+    use crate::prolice_config::{Config, ConfigurableArg};
+
+    fn app() -> App<'static> {
+        App::new("prolice")
+            .arg(Arg::new("owner").long("owner").short('O').takes_value(true))
+            .arg(
+                Arg::new("sample-size")
+                    .long("sample-size")
+                    .short('S')
+                    .takes_value(true)
+                    .default_value("100")
+                    .conflicts_with("pr-number"),
+            )
+            .arg(Arg::new("pr-number").long("pr-number").short('P').takes_value(true))
+            .arg(
+                Arg::new("exclude-path")
+                    .long("exclude-path")
+                    .takes_value(true)
+                    .multiple_occurrences(true),
+            )
+            .arg(Arg::new("silent-mode").long("silent-mode").short('s'))
+    }
+
+    fn merged_matches(raw_config: &str, cli_args: &[&str]) -> clap::ArgMatches {
+        let config = Config::from_toml(raw_config).unwrap();
+        let cli_args = cli_args.iter().map(|cli_arg| cli_arg.to_string()).collect();
+
+        let merged_args =
+            config.merge_into_args(cli_args, &ConfigurableArg::from_app(&app())).unwrap();
+
+        app().try_get_matches_from(merged_args).unwrap()
+    }
+
+    #[test]
+    fn explicit_cli_args_take_precedence_over_the_config_file() {
+        let raw_config = r#"
+            owner = "rust-lang"
+            sample_size = 50
+            exclude_path = ["vendor/**", "**/*.lock"]
+            silent_mode = true
+        "#;
+
+        let matches = merged_matches(raw_config, &["prolice"]);
+        assert_eq!(matches.value_of("owner"), Some("rust-lang"));
+        assert_eq!(matches.value_of("sample-size"), Some("50"));
+        assert_eq!(
+            matches.values_of("exclude-path").unwrap().collect::<Vec<&str>>(),
+            vec!["vendor/**", "**/*.lock"]
+        );
+        assert!(matches.is_present("silent-mode"));
+
+        for cli_args in [
+            vec!["prolice", "--sample-size", "10"],
+            vec!["prolice", "--sample-size=10"],
+            vec!["prolice", "-S", "10"],
+            vec!["prolice", "-sS10"],
+        ]
+        .iter()
+        {
+            let matches = merged_matches(raw_config, cli_args);
+            assert_eq!(matches.value_of("sample-size"), Some("10"));
+            assert_eq!(matches.value_of("owner"), Some("rust-lang"));
+        }
+
+        // entries conflicting with an explicit CLI arg are left out too
+        let matches = merged_matches(raw_config, &["prolice", "--pr-number", "7"]);
+        assert_eq!(matches.occurrences_of("sample-size"), 0);
+        assert_eq!(matches.value_of("pr-number"), Some("7"));
+    }
+
+    #[test]
+    fn unknown_or_mistyped_entries_are_rejected() {
+        let configurable_args = ConfigurableArg::from_app(&app());
+        let merge = |raw_config: &str| {
+            Config::from_toml(raw_config)
+                .unwrap()
+                .merge_into_args(vec!["prolice".to_string()], &configurable_args)
+        };
+
+        assert!(merge("repo = \"rust\"").is_err());
+        assert!(merge("owner = true").is_err());
+        assert!(merge("silent_mode = \"yes\"").is_err());
+        assert!(merge("owner = [\"rust-lang\", \"tokio-rs\"]").is_err());
+        assert_eq!(merge("silent_mode = false").unwrap(), vec!["prolice".to_string()]);
+    }
+}
@@ -1,32 +1,42 @@
 //!A universal, project-wide error wrapper that is also able to retain the nested cause of an [`Error`].
 
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_json::json;
 use thiserror::Error;
 
+use crate::github::client::retry::is_rate_limit_message;
+
 #[derive(Error, Debug)]
 pub enum AnalyzeError {
-    // :# prints causes as well using anyhow's default formatting of causes
-    #[error("Error during async task execution; nested = {0:#}")]
-    AsyncTaskError(anyhow::Error),
-    #[error("Error parsing diff for [{repo_name}/{pr_number}]; nested = {nested:#?}")]
+    #[error("Error during async task execution")]
+    AsyncTaskError(#[source] anyhow::Error),
+    #[error("Error parsing diff for [{repo_name}/{pr_number}]")]
     DiffParseError {
         repo_name: String,
         pr_number: u64,
         #[source]
         nested: anyhow::Error,
     },
-    #[error("GitHub API error: {msg}; nested = {nested:#?}")]
+    #[error("Diff for [{repo_name}/{pr_number}] is too large to be served by GitHub: {reason}")]
+    DiffTooLarge {
+        repo_name: String,
+        pr_number: u64,
+        reason: String,
+    },
+    #[error("GitHub API error: {msg}")]
     GitHubAPIError {
         msg: String,
         #[source]
         nested: anyhow::Error,
     },
-    #[error("GitHub API response body error: {msg}; nested = {nested:#?}")]
+    #[error("GitHub API response body error: {msg}")]
     GitHubAPIResponseBodyError {
         msg: String,
         #[source]
         nested: anyhow::Error,
     },
-    #[error("JSON parse error: {msg}; nested = {nested:#?}")]
+    #[error("JSON parse error: {msg}")]
     JsonParseError {
         msg: String,
         #[source]
@@ -35,7 +45,7 @@ pub enum AnalyzeError {
     #[error("Parsed commits' JSON produced an array with zero elements! At least one commit should exist in a PR.")]
     NoCommitsFoundError,
     #[error(
-        "An unrecoverable error has occurred in one or more data-fetching steps for [{repo_name}]/[{pr_number}] and operation had to be aborted mid-process; nested = {nested:#?}"
+        "An unrecoverable error has occurred in one or more data-fetching steps for [{repo_name}]/[{pr_number}] and operation had to be aborted mid-process"
     )]
     PullRequestDataRetrievalError {
         repo_name: String,
@@ -43,19 +53,34 @@ pub enum AnalyzeError {
         #[source]
         nested: anyhow::Error,
     },
-    #[error("Incomplete data for PR #{pr_number}: {reason}")]
-    PullRequestIncompleteDataError {
-        reason: String,
+    #[error("Could not retrieve PR#[{pr_number}] for repository [{repo_name}]")]
+    PullRequestNotFound {
+        repo_name: String,
+        pr_number: u64,
+        #[source]
+        nested: anyhow::Error,
+    },
+    #[error(
+        "Skipped retrieving PR#[{pr_number}] for repository [{repo_name}], as the analysis was interrupted"
+    )]
+    PullRequestSkippedOnInterruption {
+        repo_name: String,
         pr_number: u64,
     },
     #[error(
-        "Could not retrieve PR#[{pr_number}] for repository [{repo_name}]; nested = {nested:#?}"
+        "Gave up on retrieving PR#[{pr_number}] for repository [{repo_name}] after [{timeout_secs}] seconds"
     )]
-    PullRequestNotFound {
+    PullRequestTimeoutError {
         repo_name: String,
         pr_number: u64,
-        #[source]
-        nested: anyhow::Error,
+        timeout_secs: u64,
+    },
+    #[error(
+        "Request to [{url}] was still rejected by GitHub's rate-limiting (or abuse detection) mechanisms after every retry (status [{status}])"
+    )]
+    RateLimitedError {
+        url: String,
+        status: u16,
     },
     #[error("Repository initialization error = {0}")]
     RepositoryNotFoundError(String),
@@ -63,6 +88,140 @@ pub enum AnalyzeError {
     Other(#[from] anyhow::Error),
 }
 
+impl AnalyzeError {
+    /// Returns the name of this error's variant (ie: `RepositoryNotFoundError`); a stable identifier that
+    /// automation can branch on, unlike its message.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AnalyzeError::AsyncTaskError(_) => "AsyncTaskError",
+            AnalyzeError::DiffParseError {
+                ..
+            } => "DiffParseError",
+            AnalyzeError::DiffTooLarge {
+                ..
+            } => "DiffTooLarge",
+            AnalyzeError::GitHubAPIError {
+                ..
+            } => "GitHubAPIError",
+            AnalyzeError::GitHubAPIResponseBodyError {
+                ..
+            } => "GitHubAPIResponseBodyError",
+            AnalyzeError::JsonParseError {
+                ..
+            } => "JsonParseError",
+            AnalyzeError::NoCommitsFoundError => "NoCommitsFoundError",
+            AnalyzeError::PullRequestDataRetrievalError {
+                ..
+            } => "PullRequestDataRetrievalError",
+            AnalyzeError::PullRequestNotFound {
+                ..
+            } => "PullRequestNotFound",
+            AnalyzeError::PullRequestSkippedOnInterruption {
+                ..
+            } => "PullRequestSkippedOnInterruption",
+            AnalyzeError::PullRequestTimeoutError {
+                ..
+            } => "PullRequestTimeoutError",
+            AnalyzeError::RateLimitedError {
+                ..
+            } => "RateLimitedError",
+            AnalyzeError::RepositoryNotFoundError(_) => "RepositoryNotFoundError",
+            AnalyzeError::Other(_) => "Other",
+        }
+    }
+
+    /// Returns whether this error comes down to GitHub's rate-limiting (or abuse detection) mechanisms
+    /// rejecting some request, however deep among its nested causes; as opposed to a failure that
+    /// retrying later would not fix.
+    pub fn is_rate_limited(&self) -> bool {
+        let nested = match self {
+            AnalyzeError::RateLimitedError {
+                ..
+            } => return true,
+            AnalyzeError::AsyncTaskError(nested)
+            | AnalyzeError::DiffParseError {
+                nested,
+                ..
+            }
+            | AnalyzeError::GitHubAPIError {
+                nested,
+                ..
+            }
+            | AnalyzeError::GitHubAPIResponseBodyError {
+                nested,
+                ..
+            }
+            | AnalyzeError::JsonParseError {
+                nested,
+                ..
+            }
+            | AnalyzeError::PullRequestDataRetrievalError {
+                nested,
+                ..
+            }
+            | AnalyzeError::PullRequestNotFound {
+                nested,
+                ..
+            }
+            | AnalyzeError::Other(nested) => nested,
+            _ => return false,
+        };
+
+        nested.chain().any(|cause| {
+            if let Some(analyze_error) = cause.downcast_ref::<AnalyzeError>() {
+                return analyze_error.is_rate_limited();
+            }
+
+            // typed requests fail with GitHub's own error message, rather than with a rejected response
+            match cause.downcast_ref::<octocrab::GitHubError>() {
+                Some(github_error) => is_rate_limit_message(&github_error.message),
+                None => false,
+            }
+        })
+    }
+
+    /// Returns this error's message followed by those of its nested causes, fit to be shown to users.
+    /// Only the first line of each cause is kept, as some (ie: octocrab's) carry a whole backtrace past
+    /// it; and the query strings of any URL are left out, as they may well carry credentials (ie: an
+    /// `access_token`).
+    pub fn message(&self) -> String {
+        lazy_static! {
+            static ref URL_QUERY_REGEX: Regex =
+                Regex::new(r#"(https?://[^\s?#()\[\]"']*)[?#][^\s()\[\]"']*"#).unwrap();
+        }
+
+        let mut message = self.to_string();
+        let mut cause = std::error::Error::source(self);
+
+        while let Some(nested) = cause {
+            let nested_message = nested.to_string();
+            let nested_message = nested_message.lines().next().unwrap_or_default();
+
+            // causes often repeat their own causes' messages, which need not be repeated again
+            if !message.contains(nested_message) {
+                message.push_str(": ");
+                message.push_str(nested_message);
+            }
+
+            cause = nested.source();
+        }
+
+        URL_QUERY_REGEX.replace_all(&message, "$1").into_owned()
+    }
+
+    /// Renders this error as a single line of JSON; that is, `{"error":{"kind":...,"message":...}}`. See
+    /// [`AnalyzeError::message`].
+    pub fn to_json(&self) -> String {
+        json!({
+            "error": {
+                "kind": self.kind(),
+                "message": self.message(),
+            }
+        })
+        .to_string()
+    }
+}
+
 #[macro_export]
 /// Wraps a dynamic error type into an [`anyhow::Error`]. Useful in a plethora of cases for constructing
 /// [`AnalyzeError`]s.
@@ -71,3 +230,40 @@ macro_rules! nested {
         anyhow::Error::new($source)
     };
 }
+
+#[cfg(test)]
+mod error_tests {
+    use anyhow::{anyhow, Context};
+
+    use crate::error::AnalyzeError;
+
+    #[test]
+    fn credentials_in_request_urls_never_make_it_into_the_json_error() {
+        let nested = Err::<(), _>(anyhow!(
+            "HTTP Error: error sending request for url \
+                (https://api.github.com/search/repositories?q=user:owner&access_token=ghp_s3cr3t): \
+                connection refused\n\nFound at 0 octocrab::Octocrab::execute"
+        ))
+        .context("Rejected by GitHub")
+        .unwrap_err();
+
+        let error = AnalyzeError::GitHubAPIError {
+            msg: "Error searching for owner's [owner] repositories in \
+                [https://api.github.com/search/repositories?q=user:owner&access_token=ghp_s3cr3t]."
+                .to_string(),
+            nested,
+        };
+
+        let object: serde_json::Value = serde_json::from_str(&error.to_json()).unwrap();
+        let message = object["error"]["message"].as_str().unwrap();
+
+        assert!(!message.contains("s3cr3t"));
+        assert!(!message.contains("Found at"));
+        // the causes themselves are kept, just not their backtraces
+        assert!(message.contains("Rejected by GitHub: HTTP Error: error sending request"));
+        assert!(
+            message.contains("(https://api.github.com/search/repositories): connection refused")
+        );
+        assert!(message.contains("[https://api.github.com/search/repositories]."));
+    }
+}
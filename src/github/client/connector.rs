@@ -3,10 +3,13 @@
 
 use async_trait::async_trait;
 use deadpool::managed::Object;
-use log::error;
+use log::{error, trace};
 use octocrab::Octocrab;
 
+use crate::error::AnalyzeError;
 use crate::github::client::pool::{GitHubConnectionPool, GitHubPoolError};
+use crate::github::json::rate_limit::{RateLimit, RateLimitBudget};
+use crate::nested;
 
 pub type GitHubConnection = Object<Octocrab, GitHubPoolError>;
 
@@ -25,3 +28,64 @@ pub trait GitHubConnector {
         })
     }
 }
+
+/// Builds an absolute URL for a GitHub REST API `route` (ie: `repos/rust-lang/rust/pulls/1/reviews`) on
+/// top of the `github_connection`'s base URL; which may point either to github.com or to a GitHub
+/// Enterprise instance.
+pub fn github_api_url(github_connection: &Octocrab, route: &str) -> String {
+    format!(
+        "{}/{}",
+        github_connection.base_url.as_str().trim_end_matches('/'),
+        route.trim_start_matches('/')
+    )
+}
+
+/// Builds the absolute URL of GitHub's GraphQL API endpoint for the `github_connection`. GitHub Enterprise
+/// instances serve it under `/api/graphql`, next to (instead of under) their `/api/v3` REST API.
+pub fn github_graphql_url(github_connection: &Octocrab) -> String {
+    graphql_url_for(github_connection.base_url.as_str())
+}
+
+fn graphql_url_for(base_url: &str) -> String {
+    let base_url = base_url.trim_end_matches('/');
+
+    match base_url.strip_suffix("/api/v3") {
+        Some(enterprise_url) => format!("{}/api/graphql", enterprise_url),
+        None => format!("{}/graphql", base_url),
+    }
+}
+
+/// Retrieves the REST API's remaining rate-limit budget for the `github_connection`'s token. Querying it
+/// does not count against the budget itself.
+/// <br/><br/>
+/// GitHub Enterprise instances with rate limiting disabled do not serve this endpoint at all, and thus
+/// end up in an error.
+pub async fn get_rate_limit_budget(
+    github_connection: &Octocrab,
+) -> Result<RateLimitBudget, AnalyzeError> {
+    let url = github_api_url(github_connection, "rate_limit");
+
+    let rate_limit: RateLimit = github_connection.get(&url, None::<&()>).await.map_err(|e| {
+        trace!("Error = {:?}", e);
+        AnalyzeError::GitHubAPIError {
+            msg: format!("Could not retrieve the rate-limit budget from [{}].", url),
+            nested: nested!(e),
+        }
+    })?;
+
+    Ok(rate_limit.resources.core)
+}
+
+#[cfg(test)]
+mod connector_tests {
+    use crate::github::client::connector::graphql_url_for;
+
+    #[test]
+    fn graphql_url_sits_next_to_enterprise_rest_apis() {
+        assert_eq!(graphql_url_for("https://api.github.com/"), "https://api.github.com/graphql");
+        assert_eq!(
+            graphql_url_for("https://github.example.com/api/v3/"),
+            "https://github.example.com/api/graphql"
+        );
+    }
+}
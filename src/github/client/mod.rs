@@ -3,3 +3,5 @@
 pub mod pool;
 
 pub mod connector;
+
+pub mod retry;
@@ -1,53 +1,108 @@
 //! A connection pool manager for GitHub.
 //!
 //! In addition to being the caretaker of the available pool of connections (both creating new and recycling
-//! old ones); it stores the `Personal Access Token` to access GitHub's REST API.
+//! old ones); it stores the `Personal Access Token` to access GitHub's REST API, and optionally the base URL
+//! of a GitHub Enterprise instance's REST API (ie: `https://github.mycorp.com/api/v3`) to be used instead of
+//! github.com's.
 //! <br/><br/>
 //!
 //! ### Usage example:
 //!
-//! ```rust
-//! use crate::github::client::pool::{GitHubConnectionPool, GitHubConnectionPoolManager, GitHubPoolError};
+//! ```ignore
+//! use crate::github::client::pool::{GitHubConnectionPool, GitHubConnectionPoolManager};
 //!
 //! let github_token = "MY_AWESOME_PERSONAL_ACCESS_TOKEN";
+//! let github_base_url = None; // or Some(String::from("https://github.mycorp.com/api/v3"))
 //! let connection_pool_size = 16;
 //!
 //! // initialize GitHub's connection pool -
 //! GitHubConnectionPool::new(
-//!     GitHubConnectionPoolManager::new(github_token),
+//!     GitHubConnectionPoolManager::new(github_token, github_base_url),
 //!     connection_pool_size
 //! );
 //! ```
 //!
 //! See more: [https://docs.github.com/en/github/authenticating-to-github/creating-a-personal-access-token](https://docs.github.com/en/github/authenticating-to-github/creating-a-personal-access-token)
 
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
 use async_trait::async_trait;
-use log::trace;
+use deadpool::managed::RecycleError;
+use log::{debug, trace};
 use octocrab::Octocrab;
 
+use crate::github::client::connector::github_api_url;
+
+/// How long a successful liveness check vouches for every pooled connection. All of them share the same
+/// token (and thus go stale all at once), so checking any one of them within this window is enough.
+pub const LIVENESS_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long a liveness check may take before the connection is deemed dead.
+const LIVENESS_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Debug)]
 pub enum GitHubPoolError {}
 
 pub struct GitHubConnectionPoolManager {
     github_personal_token_param: String,
+    github_base_url: Option<String>,
+    last_liveness_check: Mutex<Option<Instant>>,
 }
 impl GitHubConnectionPoolManager {
-    /// Instantiates a new [`GitHubConnectionPoolManager`].
-    pub fn new(github_personal_token_param: &str) -> Self {
+    /// Instantiates a new [`GitHubConnectionPoolManager`]. If no `base_url` is supplied, github.com's
+    /// REST API is targeted.
+    pub fn new(github_personal_token_param: &str, base_url: Option<String>) -> Self {
         GitHubConnectionPoolManager {
             github_personal_token_param: github_personal_token_param.to_string(),
+            github_base_url: base_url.as_deref().map(normalize_base_url),
+            last_liveness_check: Mutex::new(None),
         }
     }
 
-    /// Retrieves a GitHub client configured with a particular pre-loaded personal token.
+    /// Determines if a liveness check is due (that is, none was performed within the last
+    /// [`LIVENESS_CHECK_INTERVAL`]); in which case, it is claimed right away, so that connections being
+    /// recycled concurrently do not check all at once.
+    fn claim_liveness_check(&self) -> bool {
+        let mut last_liveness_check = self.last_liveness_check.lock().unwrap();
+
+        match *last_liveness_check {
+            Some(checked_at) if checked_at.elapsed() < LIVENESS_CHECK_INTERVAL => false,
+            _ => {
+                *last_liveness_check = Some(Instant::now());
+                true
+            }
+        }
+    }
+
+    /// Releases a claimed liveness check that failed, so that the next recycled connection checks again.
+    fn release_liveness_check(&self) {
+        *self.last_liveness_check.lock().unwrap() = None;
+    }
+
+    /// Retrieves a GitHub client configured with a particular pre-loaded personal token (and base URL,
+    /// if any).
     fn get_github_client(&self) -> Octocrab {
-        Octocrab::builder()
-            .personal_token(self.github_personal_token_param.clone())
-            .build()
-            .expect("Could not build GitHub client. Aborting operation.")
+        let builder = Octocrab::builder().personal_token(self.github_personal_token_param.clone());
+
+        let builder = match &self.github_base_url {
+            Some(github_base_url) => builder
+                .base_url(github_base_url.as_str())
+                .expect("Could not parse GitHub base URL. Aborting operation."),
+            None => builder,
+        };
+
+        builder.build().expect("Could not build GitHub client. Aborting operation.")
     }
 }
 
+/// Makes sure a base URL ends with a trailing slash. Otherwise, its last path segment gets replaced
+/// (rather than extended) when routes are joined onto it - ie: `https://github.mycorp.com/api/v3` joined
+/// with `repos` yields `https://github.mycorp.com/api/repos`.
+pub fn normalize_base_url(base_url: &str) -> String {
+    format!("{}/", base_url.trim_end_matches('/'))
+}
+
 pub type GitHubConnectionPool = deadpool::managed::Pool<Octocrab, GitHubPoolError>;
 
 #[async_trait]
@@ -59,10 +114,102 @@ impl deadpool::managed::Manager<Octocrab, GitHubPoolError> for GitHubConnectionP
         Ok(self.get_github_client())
     }
 
+    /// Connections are handed back out only if they are still alive: every
+    /// [`LIVENESS_CHECK_INTERVAL`], a recycled connection performs a lightweight authenticated request
+    /// (`GET /rate_limit`; which, unlike `GET /user`, is served to every kind of token - GitHub Actions' and
+    /// GitHub Apps' included - and does not count against the rate limit either). If it fails (ie: the
+    /// token was revoked mid-run), the connection gets discarded and the pool creates a fresh one instead.
     async fn recycle(
-        &self, _old: &mut Octocrab,
+        &self, old: &mut Octocrab,
     ) -> deadpool::managed::RecycleResult<GitHubPoolError> {
         trace!("Recycling connection back into the pool...");
-        Ok(())
+
+        if !self.claim_liveness_check() {
+            return Ok(());
+        }
+
+        let url = github_api_url(old, "rate_limit");
+        let liveness_check = tokio::time::timeout(
+            LIVENESS_CHECK_TIMEOUT,
+            old.get::<serde_json::Value, _, ()>(&url, None),
+        )
+        .await;
+
+        let failure = match liveness_check {
+            Ok(Ok(_)) => return Ok(()),
+            Ok(Err(e)) => format!("Connection failed its liveness check against [{}]: {}", url, e),
+            Err(_) => format!(
+                "Connection failed its liveness check against [{}]: no response within {} seconds",
+                url,
+                LIVENESS_CHECK_TIMEOUT.as_secs()
+            ),
+        };
+
+        debug!("{}; discarding it.", failure);
+        self.release_liveness_check();
+        Err(RecycleError::Message(failure))
+    }
+}
+
+#[cfg(test)]
+mod pool_tests {
+    use deadpool::managed::Manager;
+
+    use crate::github::client::pool::{normalize_base_url, GitHubConnectionPoolManager};
+    use crate::github::utils::fixtures::{fake_github_api, http_response};
+
+    #[test]
+    fn base_urls_are_normalized_to_a_single_trailing_slash() {
+        assert_eq!(
+            normalize_base_url("https://github.mycorp.com/api/v3"),
+            "https://github.mycorp.com/api/v3/"
+        );
+        assert_eq!(
+            normalize_base_url("https://github.mycorp.com/api/v3//"),
+            "https://github.mycorp.com/api/v3/"
+        );
+    }
+
+    #[tokio::test]
+    async fn connections_failing_their_liveness_check_are_not_recycled() {
+        let base_url = fake_github_api(|_: &str| {
+            http_response("401 Unauthorized", &[], r#"{ "message": "Bad credentials" }"#)
+        })
+        .await;
+        let manager = GitHubConnectionPoolManager::new("revoked-token", Some(base_url));
+        let mut connection = manager.create().await.unwrap();
+
+        assert!(manager.recycle(&mut connection).await.is_err());
+        // a failed check is not vouched for; the next recycled connection checks again
+        assert!(manager.recycle(&mut connection).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn live_connections_are_recycled_and_checked_once_per_interval() {
+        let base_url =
+            fake_github_api(|_: &str| http_response("200 OK", &[], r#"{ "login": "octocat" }"#))
+                .await;
+        let manager = GitHubConnectionPoolManager::new("token", Some(base_url));
+        let mut connection = manager.create().await.unwrap();
+
+        assert!(manager.recycle(&mut connection).await.is_ok());
+        assert!(!manager.claim_liveness_check());
+    }
+
+    #[tokio::test]
+    async fn connections_of_tokens_without_a_user_pass_their_liveness_check() {
+        // GitHub Actions' (and GitHub Apps') tokens are not tied to any user
+        let base_url = fake_github_api(|request: &str| {
+            if request.starts_with("GET /rate_limit") {
+                return http_response("200 OK", &[], r#"{ "resources": {} }"#);
+            }
+            let body = r#"{ "message": "Resource not accessible by integration" }"#;
+            http_response("403 Forbidden", &[], body)
+        })
+        .await;
+        let manager = GitHubConnectionPoolManager::new("actions-token", Some(base_url));
+        let mut connection = manager.create().await.unwrap();
+
+        assert!(manager.recycle(&mut connection).await.is_ok());
     }
 }
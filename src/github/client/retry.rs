@@ -0,0 +1,466 @@
+//! Retry utilities for GitHub requests that got rejected by GitHub's rate-limiting (or abuse detection)
+//! mechanisms.
+//! <br/><br/>
+//! GitHub signals these rejections with either a `429 Too Many Requests` or a `403 Forbidden` status,
+//! usually accompanied by a `Retry-After` header (abuse detection) or by `X-RateLimit-Remaining: 0` plus
+//! an `X-RateLimit-Reset` epoch timestamp (primary rate limit). Both are honored before falling back
+//! to a plain exponential backoff.
+//! <br/><br/>
+//! Some requests are also worth retrying when GitHub fails to serve them on its own side (ie: a
+//! `502 Bad Gateway` while rendering a large diff); see [`RetryPolicy::get_through_server_errors`].
+//!
+//! See more: [https://docs.github.com/en/rest/guides/best-practices-for-integrators#dealing-with-abuse-rate-limits](https://docs.github.com/en/rest/guides/best-practices-for-integrators#dealing-with-abuse-rate-limits)
+
+use std::future::Future;
+use std::time::Duration;
+
+use chrono::Utc;
+use log::{debug, trace, warn};
+use octocrab::Octocrab;
+use reqwest::header::HeaderMap;
+use reqwest::{RequestBuilder, Response, StatusCode};
+use strum_macros::{Display, EnumString, EnumVariantNames};
+
+use crate::error::AnalyzeError;
+
+/// How many times a rate-limited request gets retried by default before giving up.
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// How many times a successful response with an empty body is re-requested by default before accepting
+/// it as legitimately empty.
+pub const DEFAULT_EMPTY_RESPONSE_RETRIES: u32 = 1;
+
+const RETRY_AFTER_HEADER: &str = "retry-after";
+const RATE_LIMIT_REMAINING_HEADER: &str = "x-ratelimit-remaining";
+const RATE_LIMIT_RESET_HEADER: &str = "x-ratelimit-reset";
+
+/// What becomes of a PR whose data could not be retrieved because some of its requests were still
+/// rejected by GitHub's rate-limiting (or abuse detection) mechanisms once their retries were exhausted.
+#[derive(Display, EnumString, EnumVariantNames, Debug, Copy, Clone, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum AbusePolicy {
+    /// The PR is re-queued, and retrieved once more after every other PR in its sample is done; giving
+    /// GitHub some time to calm down. It is only discarded if rejected again.
+    Retry,
+    /// The PR is discarded, just like any other PR whose retrieval failed; shrinking the sample.
+    Skip,
+    /// The whole analysis is aborted, rather than carrying on with a sample that may end up shrinking
+    /// considerably.
+    Fail,
+}
+
+/// Determines how (and how many times) requests rejected by GitHub's rate-limiting mechanisms are retried.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Maximum amount of retries after the original attempt. Zero disables retrying altogether.
+    pub max_retries: u32,
+    /// Delay before the first retry when GitHub does not say how long to wait; it doubles on every
+    /// subsequent retry.
+    pub base_delay: Duration,
+    /// Longest delay that is worth waiting for. Requests that would need to wait any longer (ie: the
+    /// hourly rate limit resets in 40 minutes) are given up on right away.
+    pub max_delay: Duration,
+    /// Maximum amount of times a successful response with an empty body (which GitHub occasionally sends
+    /// out transiently while under load) is re-requested before accepting it as legitimately empty. Only
+    /// honored by [`RetryPolicy::get_non_empty`].
+    pub empty_response_retries: u32,
+    /// What becomes of the PRs whose requests are still rejected once retries are exhausted. Not honored
+    /// by the [`RetryPolicy`] itself, but by whoever retrieves those PRs (see [`is_rate_limited_response`]).
+    pub abuse_policy: AbusePolicy,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: DEFAULT_MAX_RETRIES,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            empty_response_retries: DEFAULT_EMPTY_RESPONSE_RETRIES,
+            abuse_policy: AbusePolicy::Skip,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns whether a PR's retrieval failing with `error` should abort the whole analysis; which is
+    /// only the case under [`AbusePolicy::Fail`], and only for rate-limiting rejections.
+    pub fn aborts_on(&self, error: &AnalyzeError) -> bool {
+        self.abuse_policy == AbusePolicy::Fail && error.is_rate_limited()
+    }
+
+    /// Performs a `GET` request against `url`, retrying it for as long as GitHub keeps rejecting it
+    /// for rate-limiting reasons (and this [`RetryPolicy`] allows it).
+    /// <br/><br/>
+    /// Once retries are exhausted, the last (rejected) response is handed back as-is; so callers deal
+    /// with it just like they would with any other unsuccessful response.
+    pub async fn get(&self, github_connection: &Octocrab, url: &str) -> octocrab::Result<Response> {
+        self.get_with_headers(github_connection, url, HeaderMap::new()).await
+    }
+
+    /// Same as [`RetryPolicy::get`], but also re-requesting successful responses that come back with an
+    /// empty body (up to `empty_response_retries` times) before handing them back; for those endpoints
+    /// where an empty body should be the exception rather than the rule.
+    pub async fn get_non_empty(
+        &self, github_connection: &Octocrab, url: &str,
+    ) -> octocrab::Result<Response> {
+        let mut attempt = 0;
+
+        loop {
+            let response = self.get(github_connection, url).await?;
+
+            if attempt >= self.empty_response_retries
+                || !is_empty_response(response.status(), response.content_length())
+            {
+                return Ok(response);
+            }
+
+            let delay = self.exponential_delay(attempt);
+
+            debug!(
+                "No content received from [{}]. Retrying in {:?} (retry {}/{})...",
+                url,
+                delay,
+                attempt + 1,
+                self.empty_response_retries
+            );
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Same as [`RetryPolicy::get`], but sending some extra `headers` along with the request (ie: the
+    /// `Accept` media type that some of GitHub's preview APIs require).
+    pub async fn get_with_headers(
+        &self, github_connection: &Octocrab, url: &str, headers: HeaderMap,
+    ) -> octocrab::Result<Response> {
+        self.execute(github_connection, url, || {
+            github_connection.request_builder(url, reqwest::Method::GET).headers(headers.clone())
+        })
+        .await
+    }
+
+    /// Same as [`RetryPolicy::get_with_headers`], but also retrying - with exponential backoff, up to
+    /// `max_retries` times - requests that GitHub failed to serve because of a transient server-side
+    /// error (see [`is_transient_server_error`]). Once retries are exhausted, the last failed response is
+    /// handed back as-is.
+    pub async fn get_through_server_errors(
+        &self, github_connection: &Octocrab, url: &str, headers: HeaderMap,
+    ) -> octocrab::Result<Response> {
+        let mut attempt = 0;
+
+        loop {
+            let response = self.get_with_headers(github_connection, url, headers.clone()).await?;
+
+            if attempt >= self.max_retries || !is_transient_server_error(response.status()) {
+                return Ok(response);
+            }
+
+            let delay = self.exponential_delay(attempt);
+
+            warn!(
+                "Request to [{}] failed on GitHub's side (status [{}]). Retrying in {:?} (retry {}/{})...",
+                url,
+                response.status(),
+                delay,
+                attempt + 1,
+                self.max_retries
+            );
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Same as [`RetryPolicy::get`], but `POST`ing a JSON `body` instead (ie: a GraphQL query).
+    pub async fn post_json(
+        &self, github_connection: &Octocrab, url: &str, body: &serde_json::Value,
+    ) -> octocrab::Result<Response> {
+        self.execute(github_connection, url, || {
+            github_connection.request_builder(url, reqwest::Method::POST).json(body)
+        })
+        .await
+    }
+
+    /// Sends the request built by `build_request` (afresh on every attempt) against `url`, retrying it
+    /// for as long as GitHub keeps rejecting it for rate-limiting reasons.
+    async fn execute(
+        &self, github_connection: &Octocrab, url: &str, build_request: impl Fn() -> RequestBuilder,
+    ) -> octocrab::Result<Response> {
+        let mut attempt = 0;
+
+        loop {
+            let response = github_connection.execute(build_request()).await?;
+
+            if attempt >= self.max_retries
+                || !is_rate_limited(response.status(), response.headers())
+            {
+                return Ok(response);
+            }
+
+            let delay =
+                match self.backoff_delay(attempt, response.headers(), Utc::now().timestamp()) {
+                    Some(delay) => delay,
+                    None => return Ok(response),
+                };
+
+            warn!(
+                "Request to [{}] was rate-limited by GitHub (status [{}]). Retrying in {:?} (retry {}/{})...",
+                url,
+                response.status(),
+                delay,
+                attempt + 1,
+                self.max_retries
+            );
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Runs an `operation` built on top of [`Octocrab`]'s typed API, retrying it for as long as it fails
+    /// with a rate-limiting related [`GitHubError`](octocrab::GitHubError) (and this [`RetryPolicy`]
+    /// allows it).
+    /// <br/><br/>
+    /// [`Octocrab`] turns unsuccessful responses into errors before handing them back, so response headers
+    /// are lost by then; plain exponential backoff is used instead.
+    pub async fn run<T, F, Fut>(&self, mut operation: F) -> octocrab::Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = octocrab::Result<T>>,
+    {
+        let mut attempt = 0;
+
+        loop {
+            match operation().await {
+                Err(octocrab::Error::GitHub {
+                    source,
+                    ..
+                }) if attempt < self.max_retries && is_rate_limit_message(&source.message) => {
+                    let delay = self.exponential_delay(attempt);
+
+                    warn!(
+                        "Request was rate-limited by GitHub. Retrying in {:?} (retry {}/{})...",
+                        delay,
+                        attempt + 1,
+                        self.max_retries
+                    );
+                    trace!("Error = {:?}", source);
+
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Calculates how long to wait before retrying a rate-limited request for the `attempt`-th time
+    /// (zero-based), based on the rejected response's `headers` and the current `now` epoch timestamp
+    /// (in seconds).
+    /// <br/><br/>
+    /// Returns [`None`] if the request is not worth retrying because GitHub asks to wait longer than
+    /// this [`RetryPolicy`]'s `max_delay`.
+    fn backoff_delay(&self, attempt: u32, headers: &HeaderMap, now: i64) -> Option<Duration> {
+        let requested_delay = header_value::<u64>(headers, RETRY_AFTER_HEADER)
+            .map(Duration::from_secs)
+            .or_else(|| {
+                if header_value::<u64>(headers, RATE_LIMIT_REMAINING_HEADER) == Some(0) {
+                    header_value::<i64>(headers, RATE_LIMIT_RESET_HEADER)
+                        .map(|reset| Duration::from_secs((reset - now).max(0) as u64))
+                } else {
+                    None
+                }
+            });
+
+        match requested_delay {
+            Some(delay) if delay > self.max_delay => None,
+            Some(delay) => Some(delay),
+            None => Some(self.exponential_delay(attempt)),
+        }
+    }
+
+    /// Doubles `base_delay` for every prior `attempt`, capped at `max_delay`.
+    fn exponential_delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.checked_pow(attempt).unwrap_or(u32::MAX);
+
+        self.base_delay.checked_mul(factor).unwrap_or(self.max_delay).min(self.max_delay)
+    }
+}
+
+/// Determines whether a response was rejected by GitHub's rate-limiting (or abuse detection) mechanisms.
+/// A bare `403` is not enough on its own, since it is also returned for plain permission issues.
+fn is_rate_limited(status: StatusCode, headers: &HeaderMap) -> bool {
+    match status {
+        StatusCode::TOO_MANY_REQUESTS => true,
+        StatusCode::FORBIDDEN => {
+            headers.contains_key(RETRY_AFTER_HEADER)
+                || header_value::<u64>(headers, RATE_LIMIT_REMAINING_HEADER) == Some(0)
+        }
+        _ => false,
+    }
+}
+
+/// Determines whether a `response` handed back by a [`RetryPolicy`] was still rejected by GitHub's
+/// rate-limiting (or abuse detection) mechanisms; that is, whether it was given up on.
+pub fn is_rate_limited_response(response: &Response) -> bool {
+    is_rate_limited(response.status(), response.headers())
+}
+
+/// Determines whether a response failed because of a (most likely transient) error on GitHub's side;
+/// as opposed to one caused by the request itself, which no retry can fix.
+fn is_transient_server_error(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Determines whether a response was successful, yet came back without any content.
+fn is_empty_response(status: StatusCode, content_length: Option<u64>) -> bool {
+    status.is_success() && content_length == Some(0)
+}
+
+/// Determines whether a [`GitHubError`](octocrab::GitHubError)'s message is about rate-limiting - ie:
+/// "API rate limit exceeded for user ID 1." or "You have triggered an abuse detection mechanism.".
+pub fn is_rate_limit_message(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+
+    message.contains("rate limit") || message.contains("abuse")
+}
+
+fn header_value<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod retry_tests {
+    use std::time::Duration;
+
+    use reqwest::header::{HeaderMap, HeaderValue};
+    use reqwest::StatusCode;
+
+    use crate::error::AnalyzeError;
+    use crate::github::client::retry::{
+        is_empty_response, is_rate_limit_message, is_rate_limited, is_transient_server_error,
+        AbusePolicy, RetryPolicy,
+    };
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        pairs.iter().for_each(|(name, value)| {
+            headers.insert(*name, HeaderValue::from_static(value));
+        });
+        headers
+    }
+
+    #[test]
+    fn forbidden_responses_are_only_rate_limited_when_github_says_so() {
+        assert!(is_rate_limited(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new()));
+        assert!(is_rate_limited(StatusCode::FORBIDDEN, &headers(&[("retry-after", "30")])));
+        assert!(is_rate_limited(
+            StatusCode::FORBIDDEN,
+            &headers(&[("x-ratelimit-remaining", "0"), ("x-ratelimit-reset", "1614600000")])
+        ));
+        assert!(!is_rate_limited(
+            StatusCode::FORBIDDEN,
+            &headers(&[("x-ratelimit-remaining", "42")])
+        ));
+        assert!(!is_rate_limited(StatusCode::NOT_FOUND, &headers(&[("retry-after", "30")])));
+    }
+
+    #[test]
+    fn backoff_honors_retry_after_and_rate_limit_reset_headers() {
+        let retry_policy = RetryPolicy::default();
+
+        assert_eq!(
+            retry_policy.backoff_delay(0, &headers(&[("retry-after", "30")]), 0),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            retry_policy.backoff_delay(
+                0,
+                &headers(&[("x-ratelimit-remaining", "0"), ("x-ratelimit-reset", "1614600010")]),
+                1614600000
+            ),
+            Some(Duration::from_secs(10))
+        );
+        // the hourly rate limit is still far from resetting; not worth waiting for
+        assert_eq!(
+            retry_policy.backoff_delay(
+                0,
+                &headers(&[("x-ratelimit-remaining", "0"), ("x-ratelimit-reset", "1614602400")]),
+                1614600000
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_up_to_max_delay() {
+        let retry_policy = RetryPolicy {
+            max_retries: 10,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(10),
+            ..Default::default()
+        };
+
+        let delays: Vec<Option<Duration>> = (0..5)
+            .map(|attempt| retry_policy.backoff_delay(attempt, &HeaderMap::new(), 0))
+            .collect();
+
+        assert_eq!(
+            delays,
+            vec![1, 2, 4, 8, 10]
+                .into_iter()
+                .map(|secs| Some(Duration::from_secs(secs)))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn only_successful_responses_without_content_are_empty() {
+        assert!(is_empty_response(StatusCode::OK, Some(0)));
+        assert!(!is_empty_response(StatusCode::OK, Some(2)));
+        // chunked responses do not announce their length upfront
+        assert!(!is_empty_response(StatusCode::OK, None));
+        assert!(!is_empty_response(StatusCode::BAD_GATEWAY, Some(0)));
+    }
+
+    #[test]
+    fn only_server_side_failures_are_transient() {
+        assert!(is_transient_server_error(StatusCode::BAD_GATEWAY));
+        assert!(is_transient_server_error(StatusCode::GATEWAY_TIMEOUT));
+        assert!(!is_transient_server_error(StatusCode::NOT_ACCEPTABLE));
+        assert!(!is_transient_server_error(StatusCode::NOT_FOUND));
+        assert!(!is_transient_server_error(StatusCode::OK));
+    }
+
+    #[test]
+    fn only_failing_policies_abort_and_only_on_rate_limiting() {
+        let rate_limited = AnalyzeError::RateLimitedError {
+            url: "https://api.github.com/repos/owner/repository/pulls/1/reviews".to_string(),
+            status: StatusCode::FORBIDDEN.as_u16(),
+        };
+        let unrelated = AnalyzeError::RepositoryNotFoundError("repository".to_string());
+        let policy = |abuse_policy| RetryPolicy {
+            abuse_policy,
+            ..Default::default()
+        };
+
+        assert!(policy(AbusePolicy::Fail).aborts_on(&rate_limited));
+        assert!(!policy(AbusePolicy::Fail).aborts_on(&unrelated));
+        assert!(!policy(AbusePolicy::Skip).aborts_on(&rate_limited));
+        assert!(!policy(AbusePolicy::Retry).aborts_on(&rate_limited));
+    }
+
+    #[test]
+    fn rate_limit_messages_are_recognized() {
+        assert!(is_rate_limit_message("API rate limit exceeded for user ID 1."));
+        assert!(is_rate_limit_message("You have triggered an abuse detection mechanism."));
+        assert!(!is_rate_limit_message("Not Found"));
+    }
+}
@@ -61,8 +61,8 @@ pub struct Tree {
 pub struct Verification {
     pub verified: bool,
     pub reason: String,
-    pub signature: ::serde_json::Value,
-    pub payload: ::serde_json::Value,
+    pub signature: Option<String>, // null for unsigned commits
+    pub payload: Option<String>,   // ^ditto
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -149,3 +149,35 @@ pub struct Parent {
     #[serde(rename = "html_url")]
     pub html_url: String,
 }
+
+#[cfg(test)]
+mod commit_tests {
+    use crate::github::json::commit::Verification;
+
+    #[test]
+    fn verification_of_signed_and_unsigned_commits_is_deserialized() {
+        let signed: Verification = serde_json::from_str(
+            r#"{
+                "verified": true,
+                "reason": "valid",
+                "signature": "-----BEGIN PGP SIGNATURE-----\n...\n-----END PGP SIGNATURE-----",
+                "payload": "tree 6dcb09b5b57875f334f61aebed695e2e4193db5e\n..."
+            }"#,
+        )
+        .unwrap();
+
+        assert!(signed.verified);
+        assert_eq!(signed.reason, "valid");
+        assert!(signed.signature.unwrap().starts_with("-----BEGIN PGP SIGNATURE-----"));
+        assert!(signed.payload.unwrap().starts_with("tree "));
+
+        let unsigned: Verification = serde_json::from_str(
+            r#"{ "verified": false, "reason": "unsigned", "signature": null, "payload": null }"#,
+        )
+        .unwrap();
+
+        assert!(!unsigned.verified);
+        assert_eq!(unsigned.signature, None);
+        assert_eq!(unsigned.payload, None);
+    }
+}
@@ -0,0 +1,158 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// Custom wrapper for a commit's combined status; that is, the outcome of every status reported through
+/// GitHub's (older) statuses API, still used by many external CI services.
+pub struct CombinedStatus {
+    /// One of 'success', 'pending' or 'failure'. Commits without any status are reported as 'pending'
+    /// too, so `total_count` must be looked at first.
+    pub state: String,
+    pub total_count: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// Custom wrapper for the check runs reported on a commit through GitHub's checks API (ie: by GitHub
+/// Actions).
+pub struct CheckRuns {
+    pub total_count: u64,
+    pub check_runs: Vec<CheckRun>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// A single check run.
+pub struct CheckRun {
+    /// One of 'queued', 'in_progress' or 'completed' (among others, for runs that are waiting on something).
+    pub status: String,
+    /// Missing until the run is completed; one of 'success', 'failure', 'neutral', 'cancelled', 'skipped',
+    /// 'timed_out', 'action_required' or 'stale' after that.
+    pub conclusion: Option<String>,
+    #[serde(default)]
+    pub started_at: Option<DateTime<Utc>>,
+    /// Missing until the run is completed.
+    #[serde(default)]
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// The outcome of every check (be it a status or a check run) reported on a commit; ordered from least to
+/// most concerning, so that the outcomes of several kinds of checks collapse into their worst one.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecksOutcome {
+    /// No checks are configured at all; which is not the same as them having failed.
+    NoChecks,
+    Passing,
+    /// Some checks had not finished yet.
+    Pending,
+    Failing,
+}
+
+impl CombinedStatus {
+    pub fn outcome(&self) -> ChecksOutcome {
+        match self.state.as_str() {
+            _ if self.total_count == 0 => ChecksOutcome::NoChecks,
+            "success" => ChecksOutcome::Passing,
+            "pending" => ChecksOutcome::Pending,
+            _ => ChecksOutcome::Failing,
+        }
+    }
+}
+
+impl CheckRuns {
+    /// Collapses these check runs into their worst outcome as of `by` (ie: when their PR got merged); runs
+    /// that had not completed by then count as pending, and those that had not even started are left out.
+    /// Their current outcome is taken if `by` is [`None`].
+    pub fn outcome(&self, by: Option<DateTime<Utc>>) -> ChecksOutcome {
+        let happened_by = |at: Option<DateTime<Utc>>| match (at, by) {
+            (Some(at), Some(by)) => at <= by,
+            (None, Some(_)) => false,
+            (_, None) => true,
+        };
+
+        self.check_runs
+            .iter()
+            .filter(|check_run| check_run.started_at.is_none() || happened_by(check_run.started_at))
+            .map(|check_run| match (check_run.status.as_str(), check_run.conclusion.as_deref()) {
+                _ if !happened_by(check_run.completed_at) => ChecksOutcome::Pending,
+                ("completed", Some("success"))
+                | ("completed", Some("neutral"))
+                | ("completed", Some("skipped")) => ChecksOutcome::Passing,
+                ("completed", _) => ChecksOutcome::Failing,
+                _ => ChecksOutcome::Pending,
+            })
+            .max()
+            .unwrap_or(ChecksOutcome::NoChecks)
+    }
+}
+
+#[cfg(test)]
+mod commit_checks_tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::github::json::commit_checks::{CheckRuns, ChecksOutcome, CombinedStatus};
+
+    #[test]
+    fn check_runs_collapse_into_their_worst_outcome() {
+        let check_runs: CheckRuns = serde_json::from_str(
+            r#"{ "total_count": 3, "check_runs": [
+                { "name": "build", "status": "completed", "conclusion": "success" },
+                { "name": "lint", "status": "completed", "conclusion": "skipped" },
+                { "name": "test", "status": "in_progress", "conclusion": null }
+            ] }"#,
+        )
+        .unwrap();
+
+        assert_eq!(check_runs.outcome(None), ChecksOutcome::Pending);
+        assert_eq!(
+            CheckRuns {
+                total_count: 0,
+                check_runs: Vec::new(),
+            }
+            .outcome(None),
+            ChecksOutcome::NoChecks
+        );
+    }
+
+    #[test]
+    fn check_runs_are_judged_as_they_stood_by_the_given_date() {
+        let check_runs: CheckRuns = serde_json::from_str(
+            r#"{ "total_count": 3, "check_runs": [
+                { "name": "build", "status": "completed", "conclusion": "success",
+                  "started_at": "2021-03-01T09:00:00Z", "completed_at": "2021-03-01T09:30:00Z" },
+                { "name": "test", "status": "completed", "conclusion": "success",
+                  "started_at": "2021-03-01T09:00:00Z", "completed_at": "2021-03-01T11:00:00Z" },
+                { "name": "deploy", "status": "completed", "conclusion": "failure",
+                  "started_at": "2021-03-01T12:00:00Z", "completed_at": "2021-03-01T12:30:00Z" }
+            ] }"#,
+        )
+        .unwrap();
+
+        // 'test' was still running, and 'deploy' had not even started
+        assert_eq!(
+            check_runs.outcome(Some(Utc.ymd(2021, 3, 1).and_hms(10, 0, 0))),
+            ChecksOutcome::Pending
+        );
+        assert_eq!(
+            check_runs.outcome(Some(Utc.ymd(2021, 3, 1).and_hms(11, 30, 0))),
+            ChecksOutcome::Passing
+        );
+        assert_eq!(check_runs.outcome(None), ChecksOutcome::Failing);
+    }
+
+    #[test]
+    fn commits_without_statuses_have_no_checks_rather_than_pending_ones() {
+        let status = |state: &str, total_count: u64| {
+            CombinedStatus {
+                state: state.to_string(),
+                total_count,
+            }
+            .outcome()
+        };
+
+        assert_eq!(status("pending", 0), ChecksOutcome::NoChecks);
+        assert_eq!(status("pending", 2), ChecksOutcome::Pending);
+        assert_eq!(status("success", 2), ChecksOutcome::Passing);
+        assert_eq!(status("failure", 2), ChecksOutcome::Failing);
+    }
+}
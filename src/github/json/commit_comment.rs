@@ -1,7 +1,8 @@
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use serde::Serialize;
 
-#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 /// Custom wrapper for a GitHub's commit's comment (that is, a comment on a portion of the unified diff
 /// from a particular [`PullRequest`]).
@@ -25,9 +26,9 @@ pub struct CommitComment {
     pub user: User,
     pub body: String,
     #[serde(rename = "created_at")]
-    pub created_at: String,
+    pub created_at: DateTime<Utc>,
     #[serde(rename = "updated_at")]
-    pub updated_at: String,
+    pub updated_at: DateTime<Utc>,
     #[serde(rename = "html_url")]
     pub html_url: String,
     #[serde(rename = "pull_request_url")]
@@ -113,3 +114,76 @@ pub struct Html {
 pub struct PullRequest {
     pub href: String,
 }
+
+#[cfg(test)]
+mod commit_comment_tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::github::json::commit_comment::CommitComment;
+
+    /// A review comment, verbatim as GitHub's API documentation lists it.
+    const COMMIT_COMMENT_JSON: &str = r#"{
+        "url": "https://api.github.com/repos/octocat/Hello-World/pulls/comments/1",
+        "pull_request_review_id": 42,
+        "id": 10,
+        "node_id": "MDI0OlB1bGxSZXF1ZXN0UmV2aWV3Q29tbWVudDEw",
+        "diff_hunk": "@@ -16,33 +16,40 @@ public class Connection : IConnection...",
+        "path": "file1.txt",
+        "position": 1,
+        "original_position": 4,
+        "commit_id": "6dcb09b5b57875f334f61aebed695e2e4193db5e",
+        "original_commit_id": "9c48853fa3dc5c1c3d6f1f1cd1f2743e72652840",
+        "in_reply_to_id": 8,
+        "user": {
+            "login": "octocat",
+            "id": 1,
+            "node_id": "MDQ6VXNlcjE=",
+            "avatar_url": "https://github.com/images/error/octocat_happy.gif",
+            "gravatar_id": "",
+            "url": "https://api.github.com/users/octocat",
+            "html_url": "https://github.com/octocat",
+            "followers_url": "https://api.github.com/users/octocat/followers",
+            "following_url": "https://api.github.com/users/octocat/following{/other_user}",
+            "gists_url": "https://api.github.com/users/octocat/gists{/gist_id}",
+            "starred_url": "https://api.github.com/users/octocat/starred{/owner}{/repo}",
+            "subscriptions_url": "https://api.github.com/users/octocat/subscriptions",
+            "organizations_url": "https://api.github.com/users/octocat/orgs",
+            "repos_url": "https://api.github.com/users/octocat/repos",
+            "events_url": "https://api.github.com/users/octocat/events{/privacy}",
+            "received_events_url": "https://api.github.com/users/octocat/received_events",
+            "type": "User",
+            "site_admin": false
+        },
+        "body": "Great stuff!",
+        "created_at": "2011-04-14T16:00:49Z",
+        "updated_at": "2011-04-14T16:00:49Z",
+        "html_url": "https://github.com/octocat/Hello-World/pull/1#discussion-diff-1",
+        "pull_request_url": "https://api.github.com/repos/octocat/Hello-World/pulls/1",
+        "author_association": "NONE",
+        "_links": {
+            "self": {
+                "href": "https://api.github.com/repos/octocat/Hello-World/pulls/comments/1"
+            },
+            "html": {
+                "href": "https://github.com/octocat/Hello-World/pull/1#discussion-diff-1"
+            },
+            "pull_request": {
+                "href": "https://api.github.com/repos/octocat/Hello-World/pulls/1"
+            }
+        },
+        "start_line": 1,
+        "original_start_line": 1,
+        "start_side": "RIGHT",
+        "line": 2,
+        "original_line": 2,
+        "side": "RIGHT"
+    }"#;
+
+    #[test]
+    fn timestamps_are_parsed_from_githubs_iso_8601_format() {
+        let commit_comment: CommitComment = serde_json::from_str(COMMIT_COMMENT_JSON).unwrap();
+
+        assert_eq!(commit_comment.created_at, Utc.ymd(2011, 4, 14).and_hms(16, 0, 49));
+        assert_eq!(commit_comment.updated_at, Utc.ymd(2011, 4, 14).and_hms(16, 0, 49));
+    }
+}
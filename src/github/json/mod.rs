@@ -1,6 +1,11 @@
 //! JSON wrappers for common GithHub model objects.
 
 pub mod commit;
+pub mod commit_checks;
 pub mod commit_comment;
 pub mod page;
+pub mod rate_limit;
+pub mod reaction;
 pub mod review;
+pub mod review_thread;
+pub mod timeline_event;
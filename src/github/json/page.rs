@@ -1,3 +1,6 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use reqwest::header::{HeaderMap, LINK};
 use reqwest::Url;
 use serde::Deserialize;
 use serde::Serialize;
@@ -13,3 +16,72 @@ pub struct Page<T> {
     pub first: Option<Url>,
     pub last: Option<Url>,
 }
+
+impl<T> Page<T> {
+    /// Fills in the links to the neighbouring pages, which GitHub sends along in the response's `Link`
+    /// header (ie: `<https://api.github.com/...&page=2>; rel="next"`) rather than in its body.
+    pub fn with_links(mut self, headers: &HeaderMap) -> Self {
+        lazy_static! {
+            static ref LINK_REGEX: Regex = Regex::new(r#"<([^>]+)>;\s*rel="(\w+)""#).unwrap();
+        }
+
+        for link_header in headers.get_all(LINK).iter().filter_map(|value| value.to_str().ok()) {
+            for captures in LINK_REGEX.captures_iter(link_header) {
+                let url = match Url::parse(&captures[1]) {
+                    Ok(url) => Some(url),
+                    Err(_) => continue,
+                };
+
+                match &captures[2] {
+                    "next" => self.next = url,
+                    "prev" => self.prev = url,
+                    "first" => self.first = url,
+                    "last" => self.last = url,
+                    _ => {}
+                }
+            }
+        }
+
+        self
+    }
+}
+
+/// Reads the link to the page that follows a paginated response's out of its `headers` (see
+/// [`Page::with_links`]); [`None`] if the response was the last page.
+pub fn next_page_url(headers: &HeaderMap) -> Option<Url> {
+    Page::<()>::default().with_links(headers).next
+}
+
+#[cfg(test)]
+mod page_tests {
+    use reqwest::header::{HeaderMap, HeaderValue, LINK};
+
+    use crate::github::json::page::Page;
+
+    #[test]
+    fn links_are_read_from_the_link_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            LINK,
+            HeaderValue::from_static(
+                "<https://api.github.com/search/repositories?q=user:octocat&page=2>; rel=\"next\", \
+                 <https://api.github.com/search/repositories?q=user:octocat&page=5>; rel=\"last\"",
+            ),
+        );
+
+        let page = Page::<u64>::default().with_links(&headers);
+
+        assert_eq!(
+            page.next.map(|url| url.to_string()),
+            Some(String::from("https://api.github.com/search/repositories?q=user:octocat&page=2"))
+        );
+        assert_eq!(
+            page.last.map(|url| url.to_string()),
+            Some(String::from("https://api.github.com/search/repositories?q=user:octocat&page=5"))
+        );
+        assert!(page.prev.is_none());
+        assert!(page.first.is_none());
+
+        assert!(Page::<u64>::default().with_links(&HeaderMap::new()).next.is_none());
+    }
+}
@@ -0,0 +1,32 @@
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// Custom wrapper for GitHub's rate-limit status (as returned by its `/rate_limit` endpoint), which
+/// [`Octocrab`](octocrab::Octocrab) does not model.
+pub struct RateLimit {
+    pub resources: RateLimitResources,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// The rate-limit budgets of every API category. Only the REST API's (`core`) one is of interest.
+pub struct RateLimitResources {
+    pub core: RateLimitBudget,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+/// The budget of requests of a single API category, within its current rate-limit window.
+pub struct RateLimitBudget {
+    pub limit: u64,
+    pub remaining: u64,
+    /// When the current window ends (and `remaining` is reset back to `limit`), as an epoch timestamp.
+    pub reset: i64,
+}
+
+impl RateLimitBudget {
+    /// Returns the instant at which `remaining` gets reset back to `limit`.
+    pub fn reset_at(&self) -> DateTime<Utc> {
+        Utc.timestamp(self.reset, 0)
+    }
+}
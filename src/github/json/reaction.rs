@@ -0,0 +1,18 @@
+use octocrab::models::User;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// Custom wrapper for a GitHub's reaction (ie: a 👍 on a [`PullRequest`](octocrab::models::pulls::PullRequest)'s
+/// body).
+pub struct Reaction {
+    pub id: u64,
+    pub node_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<User>,
+    /// One of '+1', '-1', 'laugh', 'confused', 'heart', 'hooray', 'rocket' or 'eyes'. Kept as a plain
+    /// string, so that reactions added by GitHub in the future do not break parsing (see the story told
+    /// in [`Analyzer`](crate::github::utils::analyzer::Analyzer)'s reviews fetch).
+    pub content: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
@@ -0,0 +1,79 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// Custom wrapper for GitHub's GraphQL response to a PR's review threads query. Review threads (and their
+/// resolution state) are not served by GitHub's REST API at all.
+pub struct ReviewThreadsRoot {
+    /// Missing whenever the query failed as a whole (ie: the PR could not be found).
+    pub data: Option<ReviewThreadsData>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewThreadsData {
+    pub repository: Option<ReviewThreadsRepository>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewThreadsRepository {
+    #[serde(rename = "pullRequest")]
+    pub pull_request: Option<ReviewThreadsPullRequest>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewThreadsPullRequest {
+    #[serde(rename = "reviewThreads")]
+    pub review_threads: ReviewThreadConnection,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewThreadConnection {
+    pub nodes: Vec<ReviewThread>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// A single review thread: a conversation started by a review comment on a portion of the diff.
+pub struct ReviewThread {
+    #[serde(rename = "isResolved")]
+    pub is_resolved: bool,
+}
+
+impl ReviewThreadsRoot {
+    /// Unwraps the review threads out of the response's nesting, if they were served at all.
+    pub fn into_review_threads(self) -> Option<Vec<ReviewThread>> {
+        self.data?.repository?.pull_request.map(|pull_request| pull_request.review_threads.nodes)
+    }
+}
+
+#[cfg(test)]
+mod review_thread_tests {
+    use crate::github::json::review_thread::{ReviewThread, ReviewThreadsRoot};
+
+    #[test]
+    fn review_threads_are_unwrapped_unless_the_query_failed() {
+        let served: ReviewThreadsRoot = serde_json::from_str(
+            r#"{ "data": { "repository": { "pullRequest": { "reviewThreads": { "nodes": [
+                { "isResolved": true }, { "isResolved": false }
+            ] } } } } }"#,
+        )
+        .unwrap();
+        let failed: ReviewThreadsRoot = serde_json::from_str(
+            r#"{ "data": { "repository": { "pullRequest": null } },
+                 "errors": [{ "type": "NOT_FOUND", "message": "Could not resolve to a PullRequest." }] }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            served.into_review_threads(),
+            Some(vec![
+                ReviewThread {
+                    is_resolved: true
+                },
+                ReviewThread {
+                    is_resolved: false
+                }
+            ])
+        );
+        assert_eq!(failed.into_review_threads(), None);
+    }
+}
@@ -0,0 +1,50 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Timeline event recorded whenever a draft PR gets marked as ready for review.
+pub const READY_FOR_REVIEW_EVENT: &str = "ready_for_review";
+/// Timeline event recorded whenever a PR gets converted (back) into a draft.
+pub const CONVERT_TO_DRAFT_EVENT: &str = "convert_to_draft";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// Custom wrapper for a single event in a GitHub issue's (or PR's) timeline. Only the fields shared by
+/// every kind of event are kept; since each kind carries a wildly different payload.
+pub struct TimelineEvent {
+    /// One of the many kinds of timeline events (ie: 'commented', 'ready_for_review'...). Kept as a plain
+    /// string, so that events added by GitHub in the future do not break parsing.
+    pub event: String,
+    /// Missing on some kinds of events (ie: 'committed', which carry their commit's own dates instead).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl TimelineEvent {
+    /// Determines whether the event switched its PR's draft status one way or the other.
+    pub fn is_draft_transition(&self) -> bool {
+        self.event == READY_FOR_REVIEW_EVENT || self.event == CONVERT_TO_DRAFT_EVENT
+    }
+}
+
+#[cfg(test)]
+mod timeline_event_tests {
+    use crate::github::json::timeline_event::TimelineEvent;
+
+    #[test]
+    fn events_of_any_kind_are_parsed_but_only_draft_transitions_are_told_apart() {
+        let events: Vec<TimelineEvent> = serde_json::from_str(
+            r#"[
+                { "event": "committed", "sha": "7638417db6d59f3c431d3e1f261cc637155684cd" },
+                { "event": "ready_for_review", "created_at": "2021-03-01T16:00:00Z" },
+                { "event": "commented", "created_at": "2021-03-01T17:00:00Z", "body": "LGTM" },
+                { "event": "convert_to_draft", "created_at": "2021-03-01T20:00:00Z" }
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(events[0].created_at, None);
+        assert_eq!(
+            events.iter().map(TimelineEvent::is_draft_transition).collect::<Vec<bool>>(),
+            vec![false, true, false, true]
+        );
+    }
+}
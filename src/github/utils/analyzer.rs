@@ -1,63 +1,198 @@
 //! [`Repository`] and [`PullRequest`] analyzing utilities.
 
 use std::convert::TryFrom;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
+use anyhow::anyhow;
 use chrono::{DateTime, Utc};
-use deadpool::managed::Pool;
 use futures::future::join_all;
+use lazy_static::lazy_static;
 use log::{debug, error, info, trace, warn};
 use octocrab::models::issues::Comment;
 use octocrab::models::pulls::PullRequest;
 use octocrab::models::Repository;
-use octocrab::{params, Octocrab, Page};
-use reqwest::Url;
+use octocrab::{params, Page};
+use regex::Regex;
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT};
+use reqwest::{Response, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use serde_json::json;
 use time::Instant;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::Semaphore;
 use tokio::task::JoinHandle;
 use tokio::try_join;
 use unidiff::PatchSet;
 
 use prpolice_lib::prolice_trace_time;
 
+use crate::error::AnalyzeError;
 use crate::github;
-use crate::github::client::connector::{GitHubConnection, GitHubConnector};
-use crate::github::client::pool::{GitHubConnectionPool, GitHubPoolError};
+use crate::github::client::connector::{
+    github_api_url, github_graphql_url, GitHubConnection, GitHubConnector,
+};
+use crate::github::client::pool::GitHubConnectionPool;
+use crate::github::client::retry::{is_rate_limited_response, AbusePolicy, RetryPolicy};
 use crate::github::json::commit::CommitRoot;
+use crate::github::json::commit_checks::{CheckRuns, ChecksOutcome, CombinedStatus};
 use crate::github::json::commit_comment::CommitComment;
+use crate::github::json::page::next_page_url;
+use crate::github::json::reaction::Reaction;
 use crate::github::json::review::Review;
-use crate::github::utils::pull_request_data::{PullRequestData, PullRequestDataResult};
-use crate::github::utils::repository_data::RepositoryData;
+use crate::github::json::review_thread::{ReviewThread, ReviewThreadsRoot};
+use crate::github::json::timeline_event::TimelineEvent;
+use crate::github::utils::failure_dump::FailureDump;
+use crate::github::utils::pull_request_cache::{PullRequestCache, RawPullRequestData};
+use crate::github::utils::pull_request_data::{
+    has_merge_title, PullRequestData, PullRequestDataResult,
+};
+use crate::github::utils::repository_data::PullRequestFlow;
+use crate::github::utils::retrieval_settings::RetrievalSettings;
 use crate::nested;
-use crate::prolice_error::AnalyzeError;
+use crate::report::progress_bar::ProgressBar;
+use crate::scoring::scoring_settings::{FlowRatioSource, ScoringSettings};
+
+/// The maximum amount of items that GitHub is willing to return in a single page.
+pub const MAX_PAGE_SIZE: u16 = 100;
+
+/// The maximum amount of pages that each listing of a repository-wide [`PullRequestFlow`] (openings and
+/// closures) may walk through; see [`FlowRatioSource::Repository`]. Busy repositories may open thousands
+/// of PRs throughout a sample's time span, and listing every one of them would eat up the rate-limit
+/// budget meant for the sample itself; so their flow is given up on instead.
+pub const MAX_FLOW_PAGES: u32 = 10;
+
+/// The amount of requests it takes to retrieve a single [`PullRequest`]'s data (comments, commit
+/// comments, reviews, commits, reactions and diff) when nothing optional is retrieved along with it and
+/// nothing needs to be re-requested; assuming none of them spans several pages. See
+/// [`Analyzer::requests_per_pr`].
+const BASE_REQUESTS_PER_PR: u64 = 6;
+
+/// The amount of a [`PullRequest`]'s requests (reviews, commit comments and commits) that are re-requested
+/// whenever they come back empty; see [`RetryPolicy::get_non_empty`].
+const NON_EMPTY_REQUESTS_PER_PR: u64 = 3;
+
+/// GitHub's GraphQL query for a PR's review threads; which its REST API does not serve.
+const REVIEW_THREADS_QUERY: &str =
+    "query($owner: String!, $repo: String!, $number: Int!, $first: Int!) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      reviewThreads(first: $first) { nodes { isResolved } }
+    }
+  }
+}";
+
+/// How many seconds a single [`PullRequest`]'s data is waited for by default before giving up on it.
+pub const DEFAULT_PR_TIMEOUT_SECS: u64 = 300;
 
 /// A builder for an [`Analyzer`] instance.
 pub struct AnalyzerBuilder {
     owner: String,
     repository_name: String,
     github_personal_access_token: String,
-    connection_pool: &'static GitHubConnectionPool,
+    connection_pool: GitHubConnectionPool,
+    retrieval_settings: RetrievalSettings,
+    scoring_settings: Arc<ScoringSettings>,
+    retry_policy: RetryPolicy,
+    pull_request_cache: Option<PullRequestCache>,
+    failure_dump: Option<FailureDump>,
+    pr_timeout: Duration,
+    inflight_limit: Arc<Semaphore>,
+    interruption: Interruption,
 }
 
 impl GitHubConnector for AnalyzerBuilder {
     // AnalyzerBuilder uses a single connection to initialize a proper Repository instance from the
     // supplied repository_name, and thus implements GitHub connection for easier access to the pool
     fn get_connection_pool(&self) -> &GitHubConnectionPool {
-        self.connection_pool
+        &self.connection_pool
     }
 }
 
 impl AnalyzerBuilder {
+    /// Starts building an [`Analyzer`] that retrieves data through the `connection_pool`; which is shared
+    /// with (rather than copied into) every [`Analyzer`] built, so that they all abide by the same bound
+    /// on concurrent connections.
     pub fn new(
         owner: &str, repository_name: &str, github_personal_access_token: &str,
-        connection_pool: &'static GitHubConnectionPool,
+        connection_pool: &GitHubConnectionPool,
     ) -> Self {
         AnalyzerBuilder {
             owner: owner.to_string(),
             repository_name: repository_name.to_string(),
             github_personal_access_token: github_personal_access_token.to_string(),
-            connection_pool,
+            connection_pool: connection_pool.clone(),
+            retrieval_settings: RetrievalSettings::default(),
+            scoring_settings: Arc::new(ScoringSettings::default()),
+            retry_policy: RetryPolicy::default(),
+            pull_request_cache: None,
+            failure_dump: None,
+            pr_timeout: Duration::from_secs(DEFAULT_PR_TIMEOUT_SECS),
+            // as many PRs at once as there are connections, unless told otherwise
+            inflight_limit: Arc::new(Semaphore::new(connection_pool.status().max_size)),
+            interruption: Interruption::default(),
         }
     }
 
+    /// Sets the [`RetrievalSettings`] that the built [`Analyzer`] will honor while sampling PRs.
+    pub fn retrieval_settings(mut self, retrieval_settings: RetrievalSettings) -> Self {
+        self.retrieval_settings = retrieval_settings;
+        self
+    }
+
+    /// Sets the [`ScoringSettings`] that every [`PullRequestData`] retrieved by the built [`Analyzer`]
+    /// will be scored with.
+    pub fn scoring_settings(mut self, scoring_settings: ScoringSettings) -> Self {
+        self.scoring_settings = Arc::new(scoring_settings);
+        self
+    }
+
+    /// Sets the [`RetryPolicy`] that the built [`Analyzer`] will follow whenever GitHub rate-limits
+    /// its requests.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sets the [`PullRequestCache`] that the built [`Analyzer`] will consult before fetching any PR's
+    /// data from GitHub (and store freshly fetched data into). PRs are always fetched if left unset.
+    pub fn pull_request_cache(mut self, pull_request_cache: PullRequestCache) -> Self {
+        self.pull_request_cache = Some(pull_request_cache);
+        self
+    }
+
+    /// Sets the [`FailureDump`] into which the built [`Analyzer`] writes every raw response from GitHub that
+    /// it could not parse. Such responses are only logged (at trace level) if left unset.
+    pub fn failure_dump(mut self, failure_dump: FailureDump) -> Self {
+        self.failure_dump = Some(failure_dump);
+        self
+    }
+
+    /// Sets how long the built [`Analyzer`] waits for any single [`PullRequest`]'s data to be fetched
+    /// before giving up on it (and failing just that [`PullRequest`]).
+    pub fn pr_timeout(mut self, pr_timeout: Duration) -> Self {
+        self.pr_timeout = pr_timeout;
+        self
+    }
+
+    /// Sets the [`Semaphore`] whose permits bound how many [`PullRequest`]s the built [`Analyzer`]s retrieve
+    /// at once; sharing it among several builders makes the bound hold across every repository analyzed
+    /// concurrently. By default, there are as many permits as connections in the pool.
+    pub fn inflight_limit(mut self, inflight_limit: Arc<Semaphore>) -> Self {
+        self.inflight_limit = inflight_limit;
+        self
+    }
+
+    /// Sets the [`Interruption`] upon which the built [`Analyzer`]s stop retrieving the [`PullRequest`]s
+    /// they have not started retrieving yet; sharing it among several builders lets a single interrupt
+    /// wind down every repository analyzed concurrently. It is never raised if left unset.
+    pub fn interruption(mut self, interruption: Interruption) -> Self {
+        self.interruption = interruption;
+        self
+    }
+
     /// Instantiates a new [`Analyzer`] instance under the given `owner` - which can be either an individual
     /// or an organization - and for the target `repository_name`.
     ///
@@ -70,19 +205,13 @@ impl AnalyzerBuilder {
 
         let github_connection = self.get_github_client().await;
 
-        let repository_page = github_connection
-            .orgs(&self.owner)
-            .list_repos()
-            .repo_type(params::repos::Type::All)
-            .sort(params::repos::Sort::Pushed)
-            .send()
-            .await;
+        let organization_repositories =
+            self.list_organization_repositories(&github_connection).await;
 
-        if let Ok(repository_page) = repository_page {
+        if let Ok(organization_repositories) = organization_repositories {
             // we found the owner as an organization; now we will query the target repository...
 
-            let repository = repository_page
-                .items
+            let repository = organization_repositories
                 .into_iter()
                 .find(|repo| repo.name.eq_ignore_ascii_case(&self.repository_name));
 
@@ -92,6 +221,14 @@ impl AnalyzerBuilder {
                     repository,
                     &self.github_personal_access_token,
                     &self.connection_pool,
+                    self.retrieval_settings.clone(),
+                    self.scoring_settings.clone(),
+                    self.retry_policy,
+                    self.pull_request_cache.clone(),
+                    self.failure_dump.clone(),
+                    self.pr_timeout,
+                    self.inflight_limit.clone(),
+                    self.interruption.clone(),
                 ))
             } else {
                 Err(AnalyzeError::RepositoryNotFoundError(format!(
@@ -111,6 +248,14 @@ impl AnalyzerBuilder {
                 repository,
                 &self.github_personal_access_token,
                 &self.connection_pool,
+                self.retrieval_settings.clone(),
+                self.scoring_settings.clone(),
+                self.retry_policy,
+                self.pull_request_cache.clone(),
+                self.failure_dump.clone(),
+                self.pr_timeout,
+                self.inflight_limit.clone(),
+                self.interruption.clone(),
             ));
         }
 
@@ -120,46 +265,195 @@ impl AnalyzerBuilder {
         )));
     }
 
-    async fn find_personal_repository(
-        &self, github_connection: &GitHubConnection,
-    ) -> Result<Option<Repository>, AnalyzeError> {
-        let url = format!(
-            "{github_base_url}search/repositories?q=user:{user}&access_token={personal_access_token}",
-            github_base_url = github_connection.base_url.as_str(),
-            user = self.owner,
-            personal_access_token = self.github_personal_access_token
-        );
+    /// Instantiates one [`Analyzer`] for every repository under the given `owner` - which must be an
+    /// organization. This builder's `repository_name` is ignored.
+    /// <br/><br/>
+    /// Archived repositories (no longer being worked on) and empty ones (no commits, and thus no PRs) are
+    /// skipped.
+    pub async fn init_all(&self) -> Result<Vec<Analyzer>, AnalyzeError> {
+        debug!("Initializing Analyzers for every repository under {}...", self.owner);
 
-        let builder = github_connection.request_builder(&url, reqwest::Method::GET);
-        let response = github_connection
-            .execute(builder)
-            .await
-            .map_err(|e| {
+        let github_connection = self.get_github_client().await;
+
+        let organization_repositories =
+            self.list_organization_repositories(&github_connection).await.map_err(|e| {
                 trace!("Error = {:?}", e);
                 AnalyzeError::GitHubAPIError {
                     msg: format!(
-                        "Error searching for owner's [{}] repositories in [{}].",
-                        self.owner, &url
+                        "Could not list repositories under organization [{}] (is it misspelled, or an individual user?)",
+                        &self.owner
                     ),
                     nested: nested!(e),
                 }
+            })?;
+
+        let analyzers: Vec<Analyzer> = organization_repositories
+            .into_iter()
+            .filter(|repository| {
+                let is_analyzable = is_analyzable_repository(repository);
+
+                if !is_analyzable {
+                    debug!(
+                        "[{}]/[{}] skipped for being either archived or empty.",
+                        &self.owner, repository.name
+                    );
+                }
+
+                is_analyzable
             })
-            .unwrap();
+            .map(|repository| {
+                Analyzer::new(
+                    &self.owner,
+                    repository,
+                    &self.github_personal_access_token,
+                    &self.connection_pool,
+                    self.retrieval_settings.clone(),
+                    self.scoring_settings.clone(),
+                    self.retry_policy,
+                    self.pull_request_cache.clone(),
+                    self.failure_dump.clone(),
+                    self.pr_timeout,
+                    self.inflight_limit.clone(),
+                    self.interruption.clone(),
+                )
+            })
+            .collect();
+
+        if analyzers.is_empty() {
+            return Err(AnalyzeError::RepositoryNotFoundError(format!(
+                "Could not find any non-archived, non-empty repository under organization [{}]",
+                &self.owner
+            )));
+        }
+
+        Ok(analyzers)
+    }
+
+    /// Lists every [`Repository`] under the `owner` organization, walking through as many pages as
+    /// necessary.
+    async fn list_organization_repositories(
+        &self, github_connection: &GitHubConnection,
+    ) -> octocrab::Result<Vec<Repository>> {
+        let mut repositories: Vec<Repository> = Vec::new();
+        let mut page: u32 = 1;
+
+        loop {
+            trace!(
+                "Retrieving page [{}] of repositories for organization [{}]...",
+                page,
+                &self.owner
+            );
+
+            let page_items = self
+                .retry_policy
+                .run(|| async move {
+                    github_connection
+                        .orgs(&self.owner)
+                        .list_repos()
+                        .repo_type(params::repos::Type::All)
+                        .sort(params::repos::Sort::Pushed)
+                        .per_page(MAX_PAGE_SIZE as u8)
+                        .page(page)
+                        .send()
+                        .await
+                })
+                .await?
+                .items;
+
+            let is_last_page = page_items.len() < MAX_PAGE_SIZE as usize;
+
+            repositories.extend(page_items);
+
+            if is_last_page {
+                return Ok(repositories);
+            }
+
+            page += 1;
+        }
+    }
+
+    /// Searches for the `repository_name` among the `owner`'s personal repositories (ignoring case, just
+    /// like the organization lookup does), following the search results' pages for as long as necessary.
+    async fn find_personal_repository(
+        &self, github_connection: &GitHubConnection,
+    ) -> Result<Option<Repository>, AnalyzeError> {
+        // (the token already goes along in the connection's headers; never in a URL that may get logged)
+        let mut url = github_api_url(
+            github_connection,
+            &format!(
+                "search/repositories?q=user:{user}&per_page={page_size}",
+                user = self.owner,
+                page_size = MAX_PAGE_SIZE,
+            ),
+        );
+
+        loop {
+            let page = match self.search_personal_repositories(github_connection, &url).await? {
+                Some(page) => page,
+                None => return Ok(None),
+            };
+
+            let next_page = page.next.clone();
+
+            let target_repo = page
+                .items
+                .into_iter()
+                .find(|repo| repo.name.eq_ignore_ascii_case(&self.repository_name));
+
+            match (target_repo, next_page) {
+                (Some(target_repo), _) => return Ok(Some(target_repo)),
+                (None, Some(next_page)) => url = next_page.to_string(),
+                (None, None) => return Ok(None),
+            }
+        }
+    }
+
+    /// Retrieves a single page of the `owner`'s personal repositories' search results from `url`; or
+    /// [`None`] if GitHub sent no content back.
+    async fn search_personal_repositories(
+        &self, github_connection: &GitHubConnection, url: &str,
+    ) -> Result<Option<github::json::page::Page<Repository>>, AnalyzeError> {
+        trace!("Searching for owner's [{}] repositories in [{}]...", self.owner, url);
+
+        let response = self.retry_policy.get(github_connection, url).await.map_err(|e| {
+            trace!("Error = {:?}", e);
+            AnalyzeError::GitHubAPIError {
+                msg: format!(
+                    "Error searching for owner's [{}] repositories in [{}].",
+                    self.owner, url
+                ),
+                nested: nested!(e),
+            }
+        })?;
+
+        // an unsuccessful search (ie: a 500) says nothing about whether the repository exists or not
+        let response = response.error_for_status().map_err(|e| {
+            trace!("Error = {:?}", e);
+            AnalyzeError::GitHubAPIError {
+                msg: format!(
+                    "GitHub rejected the search for owner's [{}] repositories in [{}].",
+                    self.owner, url
+                ),
+                nested: nested!(e),
+            }
+        })?;
 
         if response.content_length().is_some() && response.content_length().unwrap() == 0 {
             warn!(
                 "No content received while searching for owner's [{}] repositories in [{}].",
-                self.owner, &url
+                self.owner, url
             );
             return Ok(None);
         }
 
+        let headers = response.headers().clone();
+
         let raw_response_text = response.text().await.map_err(|e| {
             trace!("Error = {:?}", e);
             AnalyzeError::GitHubAPIResponseBodyError {
                 msg: format!(
                     "Error retrieving repositories' JSON for owner's [{}] repositories in [{}].",
-                    self.owner, &url
+                    self.owner, url
                 ),
                 nested: nested!(e),
             }
@@ -169,19 +463,99 @@ impl AnalyzerBuilder {
             serde_json::from_str(&raw_response_text).map_err(|e| {
                 trace!("Error = {:?}", e);
                 trace!("Raw response = {}", raw_response_text);
+                if let Some(failure_dump) = &self.failure_dump {
+                    failure_dump.store(url, &raw_response_text);
+                }
                 AnalyzeError::JsonParseError {
                     msg: format!(
                         "Error mapping repositories' JSON for owner's [{}] repositories in [{}].",
-                        self.owner, &url
+                        self.owner, url
                     ),
                     nested: nested!(e),
                 }
             })?;
 
-        let target_repo =
-            parsed_json.items.into_iter().find(|repo| repo.name == self.repository_name);
+        Ok(Some(parsed_json.with_links(&headers)))
+    }
+}
+
+/// Determines whether a [`Repository`] is worth analyzing; ie: it is neither archived nor empty.
+fn is_analyzable_repository(repository: &Repository) -> bool {
+    !repository.archived.unwrap_or(false) && repository.size != Some(0)
+}
+
+/// Determines whether a failed diff request was rejected for the diff being beyond GitHub's size limits
+/// (ie: a `406 Not Acceptable` saying "Sorry, the diff exceeded the maximum number of lines (20000)");
+/// returning GitHub's explanation if so. Unlike transient failures, no retry can ever get it served.
+fn diff_too_large_reason(status: StatusCode, raw_response_text: &str) -> Option<String> {
+    if status != StatusCode::NOT_ACCEPTABLE && status != StatusCode::UNPROCESSABLE_ENTITY {
+        return None;
+    }
+
+    let github_error: serde_json::Value = serde_json::from_str(raw_response_text).ok()?;
+    let message = github_error["message"].as_str().unwrap_or_default();
+
+    let has_too_large_code = github_error["errors"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .any(|error| error["code"] == "too_large");
+    let has_too_large_message = {
+        let message = message.to_ascii_lowercase();
+        message.contains("too large") || message.contains("exceeded the maximum")
+    };
+
+    if has_too_large_code || has_too_large_message {
+        Some(message.to_string())
+    } else {
+        None
+    }
+}
+
+/// Hands a PR's data `response` back, unless GitHub's rate-limiting (or abuse detection) mechanisms still
+/// rejected it after every retry; in which case it gets turned into an [`AnalyzeError::RateLimitedError`],
+/// so that the PR's failure can be dealt with according to the [`AbusePolicy`].
+fn reject_rate_limited(response: Response, url: &str) -> Result<Response, AnalyzeError> {
+    if is_rate_limited_response(&response) {
+        warn!("Gave up on [{}], as GitHub kept rejecting it for rate-limiting reasons.", url);
+        return Err(AnalyzeError::RateLimitedError {
+            url: url.to_string(),
+            status: response.status().as_u16(),
+        });
+    }
+
+    Ok(response)
+}
+
+/// Spawns a task that only starts running `future` once it gets hold of one of `inflight_limit`'s
+/// permits; releasing it as soon as `future` completes.
+fn spawn_limited<F>(inflight_limit: Arc<Semaphore>, future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::spawn(async move {
+        // the semaphore is never closed, so acquiring a permit cannot fail
+        let _permit = inflight_limit.acquire_owned().await.unwrap();
+        future.await
+    })
+}
+
+/// A flag that gets raised once an analysis is interrupted (ie: by the user hitting Ctrl+C); from then
+/// on, every [`Analyzer`] sharing it gives up on the [`PullRequest`]s it has not started retrieving yet.
+/// Those already being retrieved are let finish, so that they still make it into the (partial) sample.
+#[derive(Debug, Clone, Default)]
+pub struct Interruption(Arc<AtomicBool>);
 
-        return Ok(target_repo);
+impl Interruption {
+    /// Raises this [`Interruption`], for good.
+    pub fn raise(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns whether this [`Interruption`] has been raised.
+    pub fn is_raised(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
     }
 }
 
@@ -190,12 +564,20 @@ pub struct Analyzer {
     owner: String,
     repository: Repository,
     github_personal_access_token: String,
-    connection_pool: &'static GitHubConnectionPool,
+    connection_pool: GitHubConnectionPool,
+    retrieval_settings: RetrievalSettings,
+    scoring_settings: Arc<ScoringSettings>,
+    retry_policy: RetryPolicy,
+    pull_request_cache: Option<PullRequestCache>,
+    failure_dump: Option<FailureDump>,
+    pr_timeout: Duration,
+    inflight_limit: Arc<Semaphore>,
+    interruption: Interruption,
 }
 
 impl GitHubConnector for Analyzer {
     fn get_connection_pool(&self) -> &GitHubConnectionPool {
-        self.connection_pool
+        &self.connection_pool
     }
 }
 
@@ -205,78 +587,169 @@ impl Clone for Analyzer {
             &self.owner,
             self.repository.clone(),
             &self.github_personal_access_token,
-            self.connection_pool,
+            &self.connection_pool,
+            self.retrieval_settings.clone(),
+            self.scoring_settings.clone(),
+            self.retry_policy,
+            self.pull_request_cache.clone(),
+            self.failure_dump.clone(),
+            self.pr_timeout,
+            self.inflight_limit.clone(),
+            self.interruption.clone(),
         )
     }
 
     fn clone_from(&mut self, source: &Self) {
         self.owner = source.owner.clone();
         self.repository = source.repository.clone();
-        self.connection_pool = source.connection_pool;
+        self.connection_pool = source.connection_pool.clone();
+        self.retrieval_settings = source.retrieval_settings.clone();
+        self.scoring_settings = source.scoring_settings.clone();
+        self.retry_policy = source.retry_policy;
+        self.pull_request_cache = source.pull_request_cache.clone();
+        self.failure_dump = source.failure_dump.clone();
+        self.pr_timeout = source.pr_timeout;
+        self.inflight_limit = source.inflight_limit.clone();
+        self.interruption = source.interruption.clone();
     }
 }
 
 impl Analyzer {
+    /// Lists the [`PullRequest`]s that would make up a sample of `sample_size` from this [`Analyzer`]'s
+    /// [`Repository`], without retrieving any of their data; so that filters can be validated cheaply
+    /// before a full analysis (see [`Analyzer::stream_repo_data`]).
+    /// <br/><br/>
+    /// Unless the [`ScoringSettings`] include merge-PRs, they are left out; but since their commits are
+    /// not retrieved, they are told apart by their title alone (see
+    /// [`MergePrHeuristic::TitlePrefix`](crate::scoring::scoring_settings::MergePrHeuristic::TitlePrefix)).
+    pub async fn list_repo_sample(&self, sample_size: u16) -> Vec<PullRequest> {
+        let github_connection = self.get_github_client().await;
+
+        self.list_pull_requests(&github_connection, sample_size)
+            .await
+            .into_iter()
+            .filter(|pr| self.scoring_settings.include_merge_prs || !has_merge_title(&pr.title))
+            .collect()
+    }
+
     /// Retrieves a set amount of [`PullRequest`]s - in the form of [`PullRequestDataResult`], from
-    /// this [`Analyzer`]'s [`Repository`].
+    /// this [`Analyzer`]'s [`Repository`]; sending each one of them through `result_sender` as soon as
+    /// its retrieval completes (and thus in no particular order), alongside its position in the listing.
+    /// The channel gets closed once every [`PullRequest`] has been sent.
     /// The number of retrieved [`PullRequest`]s is determined by the `sample_size` parameter.
-    pub async fn retrieve_repo_data(&self, sample_size: u8) -> RepositoryData {
+    /// <br/><br/>
+    /// When `show_progress` is `true`, a [`ProgressBar`] tracks how many [`PullRequest`]s have been
+    /// retrieved so far (and how many of them failed).
+    /// <br/><br/>
+    /// Once this [`Analyzer`]'s [`Interruption`] is raised, every [`PullRequest`] whose retrieval has not
+    /// started yet is sent as [`AnalyzeError::PullRequestSkippedOnInterruption`] instead.
+    /// <br/><br/>
+    /// [`PullRequest`]s that GitHub's rate-limiting (or abuse detection) mechanisms kept rejecting are
+    /// sent just like any other failed one; unless the [`RetryPolicy`]'s [`AbusePolicy`] is
+    /// [`AbusePolicy::Retry`], in which case they are retrieved once more (and only then sent) after every
+    /// other [`PullRequest`] is done.
+    pub async fn stream_repo_data(
+        &self, sample_size: u16, show_progress: bool,
+        result_sender: UnboundedSender<(usize, PullRequestDataResult)>,
+    ) {
         let start = Instant::now();
 
         // crawl all pull-requests under repository
         let repo = self.repository();
         let github_connection = self.get_github_client().await;
 
-        let prs = github_connection
-            .pulls(&self.owner, &repo.name)
-            .media_type(octocrab::params::pulls::MediaType::Full)
-            .list()
-            // filtering parameters
-            .state(params::State::Closed)
-            .sort(params::pulls::Sort::Created)
-            .direction(params::Direction::Descending)
-            .per_page(sample_size)
-            .page(1u32)
-            .send()
-            .await
-            .unwrap_or_else(|e| {
-                error!("Could not retrieve PRs for repository [{}]. Aborting operation.", &repo.name);
-                panic!(e)
-            })
-            .items;
+        let prs = self.list_pull_requests(&github_connection, sample_size).await;
 
         info!("Analyzing repository [{}] using a sample of [{}] PRs...", repo.name, prs.len());
 
-        let analysis_tasks: Vec<JoinHandle<PullRequestDataResult>> = prs
-            .iter()
-            .map(|pr| {
-                let pr = pr.clone(); // async processing needs its own unshared pr reference for the whole duration of the thread
-                let child_pr_analyzer = self.clone();
+        let analysis_tasks: Vec<JoinHandle<PullRequestDataResult>> =
+            prs.iter().map(|pr| self.spawn_pr_data_retrieval(pr)).collect();
 
-                tokio::spawn(async move { child_pr_analyzer.retrieve_pr_data_from(&pr).await })
-            })
-            .collect();
+        let progress_bar: Option<ProgressBar> = if show_progress {
+            Some(ProgressBar::new(&repo.name, analysis_tasks.len()))
+        } else {
+            None
+        };
+
+        // errors are moved into the channel alongside everything else; only their messages are kept
+        // for the final report (but for PRs skipped upon interruption, which did not fail)
+        let report = |position: usize, result: PullRequestDataResult| -> Option<String> {
+            let error_message = match &result {
+                Err(AnalyzeError::PullRequestSkippedOnInterruption {
+                    ..
+                }) => None,
+                result => result.as_ref().err().map(AnalyzeError::message),
+            };
+
+            if let Some(progress_bar) = &progress_bar {
+                progress_bar.advance(error_message.is_some());
+            }
+
+            result_sender.send((position, result)).unwrap_or_else(|_| {
+                trace!("PR-data receiver was dropped before every PR was retrieved.")
+            });
+
+            error_message
+        };
+
+        let retries_rate_limited = self.retry_policy.abuse_policy == AbusePolicy::Retry;
+
+        // PRs rejected by GitHub's rate-limiting mechanisms are held back (by position), if they are to
+        // be retried
+        let outcomes: Vec<Result<Option<String>, usize>> =
+            join_all(analysis_tasks.into_iter().enumerate().map(|(position, analysis_task)| {
+                let report = &report;
+
+                async move {
+                    let result = Analyzer::join_pr_data_retrieval(analysis_task).await;
+
+                    match result {
+                        Err(e) if retries_rate_limited && e.is_rate_limited() => Err(position),
+                        result => Ok(report(position, result)),
+                    }
+                }
+            }))
+            .await;
+
+        let mut results: Vec<Option<String>> = Vec::new();
+        let mut rate_limited_positions: Vec<usize> = Vec::new();
+        for outcome in outcomes {
+            match outcome {
+                Ok(error_message) => results.push(error_message),
+                Err(position) => rate_limited_positions.push(position),
+            }
+        }
+
+        // the retry pass only starts once every other PR is done, which gives GitHub some time to calm down
+        if !rate_limited_positions.is_empty() {
+            warn!(
+                "[{}] PRs from [{}] were rejected by GitHub's rate-limiting (or abuse detection) mechanisms. Retrying them...",
+                rate_limited_positions.len(),
+                repo.name
+            );
+
+            results.extend(
+                join_all(
+                    rate_limited_positions.into_iter().map(|position| {
+                        let report = &report;
+                        let retry_task = self.spawn_pr_data_retrieval(&prs[position]);
+
+                        async move {
+                            report(position, Analyzer::join_pr_data_retrieval(retry_task).await)
+                        }
+                    }),
+                )
+                .await,
+            );
+        }
+
+        if let Some(progress_bar) = &progress_bar {
+            progress_bar.finish();
+        }
 
-        let results: Vec<PullRequestDataResult> = join_all(analysis_tasks)
-            .await
-            .into_iter()
-            .map(|async_task_operation_result| {
-                async_task_operation_result.unwrap_or_else(|e| {
-                    error!(
-                        "There was a problem during async PR-data-retrieval task. Aborting operation.",
-                    );
-                    trace!("Error = {:?}", e);
-                    Err(AnalyzeError::AsyncTaskError(nested!(e)))
-                })
-            })
-            .collect();
         info!("Finished fetching [{}] sample PRs for [{}].", results.len(), repo.name);
 
-        let errors: Vec<&AnalyzeError> = results
-            .iter()
-            .filter(|result| result.is_err())
-            .map(|pull_request_error_result| pull_request_error_result.as_ref().err().unwrap())
-            .collect();
+        let errors: Vec<&String> = results.iter().flatten().collect();
 
         if !errors.is_empty() {
             error!("There were [{}] PRs whose data-retrieval process ended in error and therefore could not be successfully fetched:", errors.len());
@@ -287,8 +760,337 @@ impl Analyzer {
 
         let duration = start.elapsed();
         info!("Time elapsed retrieving data for [{}] was: {:?}", repo.name, duration);
+    }
+
+    /// Spawns the retrieval of a `pr`'s data (see [`spawn_limited`]). Every PR gets its task up front, but
+    /// only so many of them retrieve data at once; the rest would otherwise race for the pool's connections
+    /// (and time out waiting for them).
+    fn spawn_pr_data_retrieval(&self, pr: &PullRequest) -> JoinHandle<PullRequestDataResult> {
+        let pr = pr.clone(); // async processing needs its own unshared pr reference for the whole duration of the thread
+        let child_pr_analyzer = self.clone();
+
+        spawn_limited(self.inflight_limit.clone(), async move {
+            // PRs still waiting for their turn once interrupted are given up on right away
+            if child_pr_analyzer.interruption.is_raised() {
+                return Err(AnalyzeError::PullRequestSkippedOnInterruption {
+                    repo_name: child_pr_analyzer.repository.name.clone(),
+                    pr_number: pr.number,
+                });
+            }
+
+            child_pr_analyzer.retrieve_pr_data_from(&pr).await
+        })
+    }
+
+    /// Awaits a PR's data `retrieval_task` (see [`Analyzer::spawn_pr_data_retrieval`]); a task that
+    /// panicked (or got cancelled) ends up in an [`AnalyzeError::AsyncTaskError`].
+    async fn join_pr_data_retrieval(
+        retrieval_task: JoinHandle<PullRequestDataResult>,
+    ) -> PullRequestDataResult {
+        retrieval_task.await.unwrap_or_else(|e| {
+            error!("There was a problem during async PR-data-retrieval task. Aborting operation.");
+            trace!("Error = {:?}", e);
+            Err(AnalyzeError::AsyncTaskError(nested!(e)))
+        })
+    }
+
+    /// Lists up to `sample_size` [`PullRequest`]s from this [`Analyzer`]'s [`Repository`] that match the
+    /// [`RetrievalSettings`]' state, and fall inside its creation date window, selected authors and
+    /// selected labels (if any); in the [`RetrievalSettings`]' sort order. PRs that were closed without
+    /// being merged are left out, unless the [`ScoringSettings`] include them.
+    /// <br/><br/>
+    /// GitHub caps every page at [`MAX_PAGE_SIZE`] items, so bigger samples are accumulated by walking
+    /// through as many pages as necessary. If the repository runs out of [`PullRequest`]s before the
+    /// sample is complete, whatever was found so far is returned.
+    /// <br/><br/>
+    /// Unless the [`RetrievalSettings`]' [`SampleStrategy`] is [`SampleStrategy::Recent`], a larger set of
+    /// candidates gets listed first (see [`SampleStrategy::candidate_pool_size`]); out of which the
+    /// sample is then picked.
+    ///
+    /// [`SampleStrategy`]: crate::github::utils::retrieval_settings::SampleStrategy
+    /// [`SampleStrategy::Recent`]: crate::github::utils::retrieval_settings::SampleStrategy::Recent
+    /// [`SampleStrategy::candidate_pool_size`]: crate::github::utils::retrieval_settings::SampleStrategy::candidate_pool_size
+    async fn list_pull_requests(
+        &self, github_connection: &GitHubConnection, sample_size: u16,
+    ) -> Vec<PullRequest> {
+        let repo = self.repository();
+        let pool_size = self.retrieval_settings.sample_strategy.candidate_pool_size(sample_size);
+        let per_page = pool_size.min(MAX_PAGE_SIZE as usize) as u8;
+
+        let mut prs: Vec<PullRequest> = Vec::with_capacity(pool_size);
+        let mut page: u32 = 1;
+
+        while prs.len() < pool_size {
+            trace!("Retrieving page [{}] of PRs for repository [{}]...", page, &repo.name);
+
+            let page_result = self
+                .retry_policy
+                .run(|| async move {
+                    github_connection
+                        .pulls(&self.owner, &repo.name)
+                        .media_type(octocrab::params::pulls::MediaType::Full)
+                        .list()
+                        // filtering parameters
+                        .state(self.retrieval_settings.state.as_param())
+                        .sort(self.retrieval_settings.sort.as_param())
+                        .direction(self.retrieval_settings.direction.as_param())
+                        .per_page(per_page)
+                        .page(page)
+                        .send()
+                        .await
+                })
+                .await;
+
+            let page_items = match page_result {
+                Ok(page_result) => page_result.items,
+                Err(e) if page == 1 => {
+                    error!(
+                        "Could not retrieve PRs for repository [{}]. Aborting operation.",
+                        &repo.name
+                    );
+                    panic!("{}", e)
+                }
+                Err(e) => {
+                    // we already have a partial sample; losing it all over a single failed page would be a waste
+                    warn!(
+                        "Could not retrieve page [{}] of PRs for repository [{}]. Continuing with a partial sample of [{}] PRs.",
+                        page,
+                        &repo.name,
+                        prs.len()
+                    );
+                    trace!("Error = {:?}", e);
+                    break;
+                }
+            };
+
+            let is_last_page = page_items.len() < per_page as usize;
+
+            // when PRs are listed by creation date, once a page reaches past the date window, no further
+            // page can hold PRs inside of it
+            let is_past_date_window = page_items
+                .last()
+                .into_iter()
+                .any(|pr| self.retrieval_settings.is_past_date_window(pr.created_at));
+
+            prs.extend(
+                page_items
+                    .into_iter()
+                    .filter(|pr| self.retrieval_settings.is_within_date_range(pr.created_at))
+                    .filter(|pr| {
+                        self.scoring_settings.include_closed_unmerged
+                            || pr.closed_at.is_none()
+                            || pr.merged_at.is_some()
+                    })
+                    // filtering out unwanted authors this early spares fetching their PRs' data
+                    .filter(|pr| self.retrieval_settings.is_selected_author(&pr.user.login))
+                    .filter(|pr| {
+                        let pr_labels: Vec<&str> = pr
+                            .labels
+                            .iter()
+                            .flatten()
+                            .map(|label| label.name.as_str())
+                            .collect();
+
+                        self.retrieval_settings.is_selected_label_set(&pr_labels)
+                    }),
+            );
+
+            if is_last_page {
+                debug!(
+                    "Repository [{}] ran out of PRs after [{}] page(s); sample holds [{}] PRs.",
+                    &repo.name,
+                    page,
+                    prs.len()
+                );
+                break;
+            }
+
+            if is_past_date_window {
+                debug!(
+                    "Repository [{}] reached PRs older than the requested date window after [{}] page(s); sample holds [{}] PRs.",
+                    &repo.name,
+                    page,
+                    prs.len()
+                );
+                break;
+            }
+
+            page += 1;
+        }
+
+        prs.truncate(pool_size);
+        let sample = self.retrieval_settings.select_sample(prs, sample_size as usize);
+
+        // the sample's stats tell as much, but a sample smaller than requested deserves a heads-up of its own
+        if sample.len() < sample_size as usize {
+            warn!(
+                "Repository [{}] listed [{}] out of the [{}] requested PRs; the analysis will go on with a smaller sample.",
+                &repo.name,
+                sample.len(),
+                sample_size
+            );
+        }
+
+        sample
+    }
+
+    /// Retrieves the [`PullRequestFlow`] of this [`Analyzer`]'s [`Repository`] between `since` and `until`,
+    /// as reported by GitHub: every [`PullRequest`] opened within that time span, and every one closed
+    /// within it (regardless of when it was opened). Unlike the sample's, it is not narrowed down by the
+    /// [`RetrievalSettings`]' authors, labels nor state.
+    /// <br/><br/>
+    /// Unless the [`ScoringSettings`] include merge-PRs, they are left out; but since telling them apart
+    /// by their commits would mean fetching those for every listed [`PullRequest`], they are told apart
+    /// by their title alone (see
+    /// [`MergePrHeuristic::TitlePrefix`](crate::scoring::scoring_settings::MergePrHeuristic::TitlePrefix)).
+    pub async fn retrieve_pull_request_flow(
+        &self, since: DateTime<Utc>, until: DateTime<Utc>,
+    ) -> Result<PullRequestFlow, AnalyzeError> {
+        let github_connection = self.get_github_client().await;
+        let is_within_time_span = |instant: &DateTime<Utc>| *instant >= since && *instant <= until;
+        let is_counted = |pr: &PullRequest| {
+            self.scoring_settings.include_merge_prs || !has_merge_title(&pr.title)
+        };
+
+        // PRs are listed newest-first by creation date, so the first one opened before the time span
+        // marks the end of the openings -
+        let opened_prs = self
+            .list_pull_requests_while(
+                &github_connection,
+                params::State::All,
+                params::pulls::Sort::Created,
+                |pr| pr.created_at >= since,
+            )
+            .await?;
+
+        // a PR's last update can never be older than its closure, so the first one last updated before
+        // the time span marks the end of the closures -
+        let closed_prs = self
+            .list_pull_requests_while(
+                &github_connection,
+                params::State::Closed,
+                params::pulls::Sort::Updated,
+                |pr| pr.updated_at.unwrap_or(pr.created_at) >= since,
+            )
+            .await?;
+
+        let pull_request_flow = PullRequestFlow {
+            opened: opened_prs
+                .iter()
+                .filter(|pr| is_counted(pr))
+                .map(|pr| pr.created_at)
+                .filter(is_within_time_span)
+                .collect(),
+            closed: closed_prs
+                .iter()
+                .filter(|pr| is_counted(pr))
+                .filter_map(|pr| pr.closed_at)
+                .filter(is_within_time_span)
+                .collect(),
+        };
+
+        debug!(
+            "Repository [{}] had [{}] PRs opened and [{}] PRs closed between [{}] and [{}].",
+            self.repository().name,
+            pull_request_flow.opened.len(),
+            pull_request_flow.closed.len(),
+            since,
+            until
+        );
+
+        Ok(pull_request_flow)
+    }
+
+    /// Gathers the flow over which the Pull Request Flow Ratio of a sample of this [`Analyzer`]'s
+    /// [`Repository`] is calculated, according to its [`FlowRatioSource`]. Repository-wide flows span from
+    /// the first sampled PR's opening to the last sampled PR's closure (or opening); should they fail to be
+    /// retrieved (or the analysis be interrupted, so that it is not held up any further), the sample's own
+    /// flow is used instead.
+    pub async fn retrieve_sample_flow(
+        &self, pull_requests_data: &[PullRequestData],
+    ) -> PullRequestFlow {
+        let sample: Vec<&PullRequestData> = pull_requests_data.iter().collect();
+        let sample_flow = PullRequestFlow::of_sample(&sample);
+
+        let since = sample_flow.opened.iter().min();
+        let until = sample_flow.opened.iter().chain(sample_flow.closed.iter()).max();
+
+        match (self.scoring_settings.flow_ratio_source, since, until) {
+            (FlowRatioSource::Repository, Some(since), Some(until))
+                if !self.interruption.is_raised() =>
+            {
+                self.retrieve_pull_request_flow(*since, *until).await.unwrap_or_else(|e| {
+                    warn!(
+                        "Could not retrieve the PR flow of repository [{}]; its flow ratio will be calculated over the sample instead.",
+                        self.repository().name
+                    );
+                    debug!("{}", e.message());
+                    sample_flow
+                })
+            }
+            _ => sample_flow,
+        }
+    }
+
+    /// Lists this [`Analyzer`]'s [`Repository`]'s [`PullRequest`]s in `state`, newest-first by `sort`;
+    /// walking through as many pages as necessary until one of them reaches a [`PullRequest`] that does
+    /// not satisfy `predicate` (or the [`Repository`] runs out of them). Fails if that takes more than
+    /// [`MAX_FLOW_PAGES`] pages.
+    async fn list_pull_requests_while(
+        &self, github_connection: &GitHubConnection, state: params::State,
+        sort: params::pulls::Sort, predicate: impl Fn(&PullRequest) -> bool,
+    ) -> Result<Vec<PullRequest>, AnalyzeError> {
+        let repo = self.repository();
+
+        let mut prs: Vec<PullRequest> = Vec::new();
+        let mut page: u32 = 1;
+
+        loop {
+            trace!("Retrieving page [{}] of PRs for repository [{}]...", page, &repo.name);
+
+            let page_items = self
+                .retry_policy
+                .run(|| async move {
+                    github_connection
+                        .pulls(&self.owner, &repo.name)
+                        .list()
+                        .state(state)
+                        .sort(sort)
+                        .direction(params::Direction::Descending)
+                        .per_page(MAX_PAGE_SIZE as u8)
+                        .page(page)
+                        .send()
+                        .await
+                })
+                .await
+                .map_err(|e| AnalyzeError::GitHubAPIError {
+                    msg: format!(
+                        "Could not retrieve page [{}] of PRs for repository [{}].",
+                        page, &repo.name
+                    ),
+                    nested: nested!(e),
+                })?
+                .items;
 
-        return results;
+            let is_last_page = page_items.len() < MAX_PAGE_SIZE as usize;
+            let is_exhausted = page_items.iter().any(|pr| !predicate(pr));
+
+            prs.extend(page_items);
+
+            if is_last_page || is_exhausted {
+                return Ok(prs);
+            }
+
+            if page >= MAX_FLOW_PAGES {
+                return Err(AnalyzeError::Other(anyhow!(
+                    "Listing the PRs of repository [{}] takes more than [{}] pages.",
+                    &repo.name,
+                    MAX_FLOW_PAGES
+                )));
+            }
+
+            page += 1;
+        }
     }
 
     /// Retrieves all relevant data structures from a particular [`Repository`]'s [`PullRequest`] based
@@ -301,15 +1103,19 @@ impl Analyzer {
 
         info!("Analyzing repository [{}]'s PR#[{}]...", repo.name, pr_number);
 
-        let github_connection = self.get_github_client().await;
-        let pr = github_connection.pulls(owner, &repo.name).get(pr_number).await.map_err(|e| {
-            error!("There was a problem during initial PR-retrieval task. Aborting operation.");
-            AnalyzeError::PullRequestNotFound {
-                repo_name: repo.name.to_string(),
-                pr_number,
-                nested: nested!(e),
-            }
-        })?;
+        let github_connection = &*self.get_github_client().await;
+        let pr = self
+            .retry_policy
+            .run(|| async move { github_connection.pulls(owner, &repo.name).get(pr_number).await })
+            .await
+            .map_err(|e| {
+                error!("There was a problem during initial PR-retrieval task. Aborting operation.");
+                AnalyzeError::PullRequestNotFound {
+                    repo_name: repo.name.to_string(),
+                    pr_number,
+                    nested: nested!(e),
+                }
+            })?;
 
         let result = self.retrieve_pr_data_from(&pr).await;
 
@@ -332,116 +1138,109 @@ impl Analyzer {
         // start with doing the analysis task(s) that don't require further remote API calls
         let main_message = Analyzer::get_pr_message(&pr);
 
-        let merged_at = Analyzer::get_merged_date(&pr)?;
-        let closed_at = Analyzer::get_closed_date(&pr)?;
+        let merged_at = Analyzer::get_merged_date(&pr);
+        let closed_at = Analyzer::get_closed_date(&pr);
+
+        // once those are done, turn to the task(s) that require remote API calls (unless they were cached);
+        // open PRs are never cached, lest they get frozen in whatever state they were first retrieved
+        let pull_request_cache =
+            self.pull_request_cache.as_ref().filter(|_| PullRequestCache::is_cacheable(pr));
+        let cached_raw_data = pull_request_cache
+            .and_then(|cache| cache.load(&self.owner, &repo.name, pr.number))
+            .filter(|raw_data| {
+                // entries lacking some of the data requested this time around are fetched all over again
+                let covers_request = raw_data.covers(&self.retrieval_settings);
+                if !covers_request {
+                    debug!(
+                        "Cached data for [{}]/[{}] lacks some of the requested data; it will be fetched again.",
+                        repo.name, pr.number
+                    );
+                }
+                covers_request
+            });
 
-        // once those are done, start preparing those task(s) that do require remote API calls
-        // (they will be fired all in parallel to save time)
-        let comments_fetch_task = tokio::spawn({
-            trace!("Starting get_pr_comments() async task...");
+        let raw_data = match cached_raw_data {
+            Some(raw_data) => {
+                debug!("Using cached data for [{}]/[{}].", repo.name, pr.number);
+                Ok(raw_data)
+            }
+            None => {
+                let raw_data = Analyzer::with_timeout(
+                    self.pr_timeout,
+                    &repo.name,
+                    pr.number,
+                    self.fetch_raw_pr_data(pr),
+                )
+                .await;
 
-            let repo_name = repo.name.clone();
-            let pr_number = pr.number;
-            let github_connection = self.get_github_client().await;
-            let owner = self.owner.clone();
+                if let (Some(cache), Ok(raw_data)) = (pull_request_cache, &raw_data) {
+                    cache.store(&self.owner, &repo.name, pr.number, raw_data);
+                }
 
-            async move {
-                Analyzer::get_pr_comments(github_connection, owner, repo_name, pr_number)
-                    .await
-                    .unwrap()
-            }
-        });
-
-        let commit_comments_fetch_task = tokio::spawn({
-            trace!("Starting get_pr_commit_comments() async task...");
-
-            let pr_review_comments_url = pr.review_comments_url.clone();
-            let github_connection = self.get_github_client().await;
-
-            async move {
-                Analyzer::get_pr_commit_comments(github_connection, pr_review_comments_url)
-                    .await
-                    .unwrap()
-            }
-        });
-
-        let reviews_fetch_task = tokio::spawn({
-            trace!("Starting get_pr_reviews() async task...");
-
-            let repo_name = repo.name.clone();
-            let pr_number = pr.number;
-            let github_connection = self.get_github_client().await;
-            let owner = self.owner.clone();
-
-            async move {
-                Analyzer::get_pr_reviews(github_connection, owner, repo_name, pr_number)
-                    .await
-                    .unwrap()
-            }
-        });
-
-        let diff_fetch_task = tokio::spawn({
-            trace!("Starting get_pr_diff() async task...");
-
-            let repo_name = repo.name.clone();
-            let pr_number = pr.number;
-            let github_connection = self.get_github_client().await;
-            let owner = self.owner.clone();
-
-            async move {
-                Analyzer::get_pr_diff(github_connection, owner, repo_name, pr_number).await.unwrap()
+                raw_data
             }
-        });
-
-        let commits_fetch_task = tokio::spawn({
-            trace!("Starting get_pr_commits() async task...");
-
-            let pr_commits_url = pr.commits_url.clone();
-            let github_connection = self.get_github_client().await;
+        };
 
-            async move { Analyzer::get_pr_commits(github_connection, pr_commits_url).await.unwrap() }
+        let raw_data = raw_data.and_then(|raw_data| {
+            let patch_set = Analyzer::parse_diff(&repo.name, pr.number, &raw_data.diff)?;
+            Ok((raw_data, patch_set))
         });
 
-        let concurrent_fetches = try_join!(
-            comments_fetch_task,
-            commit_comments_fetch_task,
-            reviews_fetch_task,
-            diff_fetch_task,
-            commits_fetch_task
-        );
-
-        return match concurrent_fetches {
-            Ok((
-                comments_fetched,
-                commit_comments_fetched,
-                reviews_fetched,
-                diff_fetched,
-                commits_fetched,
-            )) => {
+        return match raw_data {
+            Ok((raw_data, patch_set)) => {
                 let duration = start.elapsed();
                 debug!(
                     "Time elapsed retrieving inner data structures for [{}]/[{}] was: {:?}. Processing results...",
                     repo.name, pr.number, duration
                 );
 
-                trace!("PR body: {}", main_message);
+                trace!("PR body: {:?}", main_message);
 
-                let comments = comments_fetched.items;
+                let comments = raw_data.comments;
                 trace!("Comments: {}", serde_json::to_string_pretty(&comments).unwrap());
 
-                let reviews = reviews_fetched;
+                let reviews = raw_data.reviews;
                 trace!("Reviews: {}", serde_json::to_string_pretty(&reviews).unwrap());
 
-                let commit_comments = commit_comments_fetched;
+                let commit_comments = raw_data.commit_comments;
                 trace!(
                     "Commit comments: {}",
                     serde_json::to_string_pretty(&commit_comments).unwrap()
                 );
 
-                let commits = commits_fetched;
+                let commits = raw_data.commits;
                 trace!("Commits: {}", serde_json::to_string_pretty(&commits).unwrap());
 
-                let patch_set = diff_fetched;
+                let reactions = raw_data.reactions;
+                trace!("Reactions: {}", serde_json::to_string_pretty(&reactions).unwrap());
+
+                // threads are only scored when requested; if they are missing by then (ie: GitHub did
+                // not serve them) they are counted as none
+                let review_threads = if self.retrieval_settings.with_threads {
+                    Some(raw_data.review_threads.unwrap_or_default())
+                } else {
+                    None
+                };
+                trace!("Review threads: {:?}", review_threads);
+
+                // unlike threads, a missing timeline is not taken for an empty one (ie: a PR that never
+                // switched back and forth from draft); its draft transitions are left unknown instead
+                let timeline = if self.retrieval_settings.with_timeline {
+                    raw_data.timeline
+                } else {
+                    None
+                };
+                trace!("Timeline: {:?}", timeline);
+
+                // unlike threads and the timeline, missing checks cannot be taken for none at all (which
+                // has a meaning of its own); so they are left unknown
+                let checks = if self.retrieval_settings.with_checks {
+                    raw_data.checks
+                } else {
+                    None
+                };
+                trace!("Checks: {:?}", checks);
+
                 let modifications: u64 = patch_set
                     .files()
                     .iter()
@@ -458,15 +1257,21 @@ impl Analyzer {
                     pr.number,
                     &pr.user.login,
                     &pr.title,
-                    &main_message,
+                    main_message.as_deref(),
+                    pr.draft,
                     comments,
                     commit_comments,
                     commits,
                     reviews,
+                    reactions,
+                    review_threads,
+                    timeline,
+                    checks,
                     patch_set,
                     pr.created_at,
                     merged_at,
                     closed_at,
+                    self.scoring_settings.clone(),
                 );
 
                 Ok(result)
@@ -482,52 +1287,382 @@ impl Analyzer {
         };
     }
 
-    /// The literal PR body; the first message, and arguably the comment that should have the most info of
-    /// all (or at least a good summary of the changes).
-    fn get_pr_message(pr: &PullRequest) -> String {
-        let default = String::from("");
-        pr.body
-            .as_ref()
-            .unwrap_or_else(|| {
-                warn!("No PR body could be retrieved. Analysis for it will be empty.");
-                &default
+    /// Awaits a [`PullRequest`]'s data `fetch` for up to `timeout`; turning it into an
+    /// [`AnalyzeError::PullRequestTimeoutError`] if it takes any longer. Sub-fetches that are still
+    /// running by then are left to finish on their own, but their results are discarded.
+    async fn with_timeout<T>(
+        timeout: Duration, repo_name: &str, pr_number: u64,
+        fetch: impl Future<Output = Result<T, AnalyzeError>>,
+    ) -> Result<T, AnalyzeError> {
+        tokio::time::timeout(timeout, fetch).await.unwrap_or_else(|_| {
+            warn!(
+                "Gave up on [{}]/[{}] after waiting [{}] seconds for its data.",
+                repo_name,
+                pr_number,
+                timeout.as_secs()
+            );
+            Err(AnalyzeError::PullRequestTimeoutError {
+                repo_name: repo_name.to_string(),
+                pr_number,
+                timeout_secs: timeout.as_secs(),
             })
-            .clone()
+        })
     }
 
-    /// The [`DateTime`] at which the [`PullRequest`] has been merged.
-    fn get_merged_date(pr: &PullRequest) -> Result<DateTime<Utc>, AnalyzeError> {
-        match pr.merged_at {
-            Some(date) => Ok(date),
-            None => Err(AnalyzeError::PullRequestIncompleteDataError {
-                reason: "No merged date. Only properly merged PRs can be analyzed in full."
-                    .to_string(),
-                pr_number: pr.number,
-            }),
-        }
+    /// Fetches every raw data structure of a particular [`Repository`]'s [`PullRequest`] from GitHub.
+    async fn fetch_raw_pr_data(
+        &self, pr: &PullRequest,
+    ) -> Result<RawPullRequestData, AnalyzeError> {
+        let repo = self.repository();
+
+        // all task(s) require remote API calls, so they will be fired all in parallel to save time
+        let comments_fetch_task = tokio::spawn({
+            trace!("Starting get_pr_comments() async task...");
+
+            let repo_name = repo.name.clone();
+            let pr_number = pr.number;
+            let github_connection = self.get_github_client().await;
+            let retry_policy = self.retry_policy;
+            let owner = self.owner.clone();
+
+            async move {
+                Analyzer::get_pr_comments(
+                    github_connection,
+                    owner,
+                    repo_name.clone(),
+                    pr_number,
+                    retry_policy,
+                )
+                .await
+                .map_err(|e| AnalyzeError::GitHubAPIError {
+                    msg: format!("Error fetching comments for [{}]/[{}].", repo_name, pr_number),
+                    nested: nested!(e),
+                })
+            }
+        });
+
+        let commit_comments_fetch_task = tokio::spawn({
+            trace!("Starting get_pr_commit_comments() async task...");
+
+            let pr_review_comments_url = pr.review_comments_url.clone();
+            let github_connection = self.get_github_client().await;
+            let retry_policy = self.retry_policy;
+            let failure_dump = self.failure_dump.clone();
+
+            async move {
+                Analyzer::get_pr_commit_comments(
+                    github_connection,
+                    pr_review_comments_url,
+                    retry_policy,
+                    failure_dump,
+                )
+                .await
+            }
+        });
+
+        let reviews_fetch_task = tokio::spawn({
+            trace!("Starting get_pr_reviews() async task...");
+
+            let repo_name = repo.name.clone();
+            let pr_number = pr.number;
+            let github_connection = self.get_github_client().await;
+            let retry_policy = self.retry_policy;
+            let failure_dump = self.failure_dump.clone();
+            let owner = self.owner.clone();
+
+            async move {
+                Analyzer::get_pr_reviews(
+                    github_connection,
+                    owner,
+                    repo_name,
+                    pr_number,
+                    retry_policy,
+                    failure_dump,
+                )
+                .await
+            }
+        });
+
+        let diff_fetch_task = tokio::spawn({
+            trace!("Starting get_pr_diff() async task...");
+
+            let repo_name = repo.name.clone();
+            let pr_number = pr.number;
+            let github_connection = self.get_github_client().await;
+            let retry_policy = self.retry_policy;
+            let owner = self.owner.clone();
+
+            async move {
+                Analyzer::get_pr_diff(github_connection, owner, repo_name, pr_number, retry_policy)
+                    .await
+            }
+        });
+
+        let commits_fetch_task = tokio::spawn({
+            trace!("Starting get_pr_commits() async task...");
+
+            let pr_commits_url = pr.commits_url.clone();
+            let github_connection = self.get_github_client().await;
+            let retry_policy = self.retry_policy;
+            let failure_dump = self.failure_dump.clone();
+
+            async move {
+                Analyzer::get_pr_commits(
+                    github_connection,
+                    pr_commits_url,
+                    retry_policy,
+                    failure_dump,
+                )
+                .await
+            }
+        });
+
+        let reactions_fetch_task = tokio::spawn({
+            trace!("Starting get_pr_reactions() async task...");
+
+            let repo_name = repo.name.clone();
+            let pr_number = pr.number;
+            let github_connection = self.get_github_client().await;
+            let retry_policy = self.retry_policy;
+            let failure_dump = self.failure_dump.clone();
+            let owner = self.owner.clone();
+
+            async move {
+                Analyzer::get_pr_reactions(
+                    github_connection,
+                    owner,
+                    repo_name,
+                    pr_number,
+                    retry_policy,
+                    failure_dump,
+                )
+                .await
+            }
+        });
+
+        let review_threads_fetch_task = tokio::spawn({
+            trace!("Starting get_pr_review_threads() async task...");
+
+            let repo_name = repo.name.clone();
+            let pr_number = pr.number;
+            // only enabled fetches take up a connection at all
+            let github_connection = if self.retrieval_settings.with_threads {
+                Some(self.get_github_client().await)
+            } else {
+                None
+            };
+            let retry_policy = self.retry_policy;
+            let failure_dump = self.failure_dump.clone();
+            let owner = self.owner.clone();
+
+            async move {
+                let github_connection = match github_connection {
+                    Some(github_connection) => github_connection,
+                    None => return Ok(None),
+                };
+
+                // review threads are a nice-to-have; failing to fetch them does not fail the whole PR, unless
+                // it is down to rate-limiting, which is up to the abuse policy to deal with
+                Analyzer::get_pr_review_threads(
+                    github_connection,
+                    owner,
+                    repo_name.clone(),
+                    pr_number,
+                    retry_policy, failure_dump)
+                .await
+                .or_else(|e| {
+                    if e.is_rate_limited() {
+                        return Err(e);
+                    }
+
+                    warn!(
+                        "Could not retrieve review threads for [{}]/[{}]; they will be counted as none.",
+                        repo_name, pr_number
+                    );
+                    trace!("Error = {:?}", e);
+                    Ok(None)
+                })
+            }
+        });
+
+        let timeline_fetch_task = tokio::spawn({
+            trace!("Starting get_pr_timeline() async task...");
+
+            let repo_name = repo.name.clone();
+            let pr_number = pr.number;
+            // only enabled fetches take up a connection at all
+            let github_connection = if self.retrieval_settings.with_timeline {
+                Some(self.get_github_client().await)
+            } else {
+                None
+            };
+            let retry_policy = self.retry_policy;
+            let failure_dump = self.failure_dump.clone();
+            let owner = self.owner.clone();
+
+            async move {
+                let github_connection = match github_connection {
+                    Some(github_connection) => github_connection,
+                    None => return Ok(None),
+                };
+
+                // just like review threads, the timeline is a nice-to-have
+                Analyzer::get_pr_timeline(
+                    github_connection,
+                    owner,
+                    repo_name.clone(),
+                    pr_number,
+                    retry_policy, failure_dump)
+                .await
+                .or_else(|e| {
+                    if e.is_rate_limited() {
+                        return Err(e);
+                    }
+
+                    warn!(
+                        "Could not retrieve the timeline for [{}]/[{}]; its draft transitions will be unknown.",
+                        repo_name, pr_number
+                    );
+                    trace!("Error = {:?}", e);
+                    Ok(None)
+                })
+            }
+        });
+
+        let checks_fetch_task = tokio::spawn({
+            trace!("Starting get_pr_checks() async task...");
+
+            let repo_name = repo.name.clone();
+            let pr_number = pr.number;
+            let head_sha = pr.head.sha.clone();
+            let merged_at = pr.merged_at;
+            // only enabled fetches take up a connection at all
+            let github_connection = if self.retrieval_settings.with_checks {
+                Some(self.get_github_client().await)
+            } else {
+                None
+            };
+            let retry_policy = self.retry_policy;
+            let failure_dump = self.failure_dump.clone();
+            let owner = self.owner.clone();
+
+            async move {
+                let github_connection = match github_connection {
+                    Some(github_connection) => github_connection,
+                    None => return Ok(None),
+                };
+
+                // checks are a nice-to-have too
+                Analyzer::get_pr_checks(
+                    github_connection,
+                    owner,
+                    repo_name.clone(),
+                    head_sha,
+                    merged_at,
+                    retry_policy, failure_dump)
+                .await
+                .or_else(|e| {
+                    if e.is_rate_limited() {
+                        return Err(e);
+                    }
+
+                    warn!(
+                        "Could not retrieve the checks for [{}]/[{}]; whether it was merged on green will be unknown.",
+                        repo_name, pr_number
+                    );
+                    trace!("Error = {:?}", e);
+                    Ok(None)
+                })
+            }
+        });
+
+        let concurrent_fetches = try_join!(
+            Analyzer::join_fetch_task(comments_fetch_task),
+            Analyzer::join_fetch_task(commit_comments_fetch_task),
+            Analyzer::join_fetch_task(reviews_fetch_task),
+            Analyzer::join_fetch_task(diff_fetch_task),
+            Analyzer::join_fetch_task(commits_fetch_task),
+            Analyzer::join_fetch_task(reactions_fetch_task),
+            Analyzer::join_fetch_task(review_threads_fetch_task),
+            Analyzer::join_fetch_task(timeline_fetch_task),
+            Analyzer::join_fetch_task(checks_fetch_task)
+        );
+
+        let (
+            comments_fetched,
+            commit_comments,
+            reviews,
+            diff,
+            commits,
+            reactions,
+            review_threads,
+            timeline,
+            checks,
+        ) = concurrent_fetches?;
+
+        Ok(RawPullRequestData {
+            comments: comments_fetched.items,
+            commit_comments,
+            reviews,
+            commits,
+            reactions,
+            review_threads,
+            timeline,
+            checks,
+            diff,
+        })
     }
 
-    /// The [`DateTime`] at which the [`PullRequest`] has been closed.
-    fn get_closed_date(pr: &PullRequest) -> Result<DateTime<Utc>, AnalyzeError> {
-        match pr.closed_at {
-            Some(date) => Ok(date),
-            None => Err(AnalyzeError::PullRequestIncompleteDataError {
-                reason: "No closed date. Only properly closed PRs can be analyzed in full."
-                    .to_string(),
-                pr_number: pr.number,
-            }),
+    /// Awaits a spawned data-fetching task, flattening both the task's own outcome and the fetch's result
+    /// into a single [`Result`]; so that a failed (or even panicked) fetch only fails its own PR instead
+    /// of bringing the whole analysis down.
+    async fn join_fetch_task<T>(
+        fetch_task: JoinHandle<Result<T, AnalyzeError>>,
+    ) -> Result<T, AnalyzeError> {
+        fetch_task.await.map_err(|e| {
+            trace!("Error = {:?}", e);
+            AnalyzeError::AsyncTaskError(nested!(e))
+        })?
+    }
+
+    /// The literal PR body; the first message, and arguably the comment that should have the most info of
+    /// all (or at least a good summary of the changes). [`None`] if the PR has none at all, which is told
+    /// apart from an empty one (see [`ScoreType::MissingDescription`](crate::scoring::score::ScoreType::MissingDescription)).
+    fn get_pr_message(pr: &PullRequest) -> Option<String> {
+        if pr.body.is_none() {
+            warn!("No PR body could be retrieved. It will be scored as a missing description.");
         }
+
+        pr.body.clone()
+    }
+
+    /// The [`DateTime`] at which the [`PullRequest`] has been merged; [`None`] if it is still open, or if it
+    /// was closed without being merged (in which case, only merge-dependent metrics are left out).
+    fn get_merged_date(pr: &PullRequest) -> Option<DateTime<Utc>> {
+        pr.merged_at
+    }
+
+    /// The [`DateTime`] at which the [`PullRequest`] has been closed; [`None`] if it is still open.
+    fn get_closed_date(pr: &PullRequest) -> Option<DateTime<Utc>> {
+        pr.closed_at
     }
 
     /// 'comments' are the normal text snippets in a PR (they were submitted clicking on the 'Comment' button,
     /// instead of the 'Approve' or 'Request changes' buttons).
-    #[prolice_trace_time]
+    #[prolice_trace_time(log = [repo_name, pr_number])]
     async fn get_pr_comments(
         github_connection: GitHubConnection, owner: String, repo_name: String, pr_number: u64,
+        retry_policy: RetryPolicy,
     ) -> octocrab::Result<Page<Comment>> {
         trace!("Retrieving comments for [{}]/[{}]...", repo_name, pr_number);
 
-        github_connection.issues(owner, repo_name).list_comments(pr_number).send().await
+        let (github_connection, owner, repo_name) = (&*github_connection, &owner, &repo_name);
+
+        retry_policy
+            .run(|| async move {
+                github_connection.issues(owner, repo_name).list_comments(pr_number).send().await
+            })
+            .await
     }
 
     /// 'reviews' are those comments that were specially submitted as a review. Commit comments (comments
@@ -535,9 +1670,10 @@ impl Analyzer {
     /// are listed in a trimmed format as "event summaries" (for lack of a better description) in GitHub's
     /// response. Those are worthless that way because they don't have a body, so we must fetch them in
     /// some other way.
-    #[prolice_trace_time]
+    #[prolice_trace_time(log = [repo_name, pr_number])]
     async fn get_pr_reviews(
         github_connection: GitHubConnection, owner: String, repo_name: String, pr_number: u64,
+        retry_policy: RetryPolicy, failure_dump: Option<FailureDump>,
     ) -> Result<Vec<Review>, AnalyzeError> {
         trace!("Retrieving reviews for [{}]/[{}]...", repo_name, pr_number);
 
@@ -562,26 +1698,25 @@ impl Analyzer {
          * files, but with the fix).
          * */
 
-        let url = format!(
-            "{github_base_url}repos/{owner}/{repo}/pulls/{pr}/reviews",
-            github_base_url = github_connection.base_url.as_str(),
-            owner = owner,
-            repo = repo_name,
-            pr = pr_number
+        let url = github_api_url(
+            &github_connection,
+            &format!(
+                "repos/{owner}/{repo}/pulls/{pr}/reviews",
+                owner = owner,
+                repo = repo_name,
+                pr = pr_number
+            ),
         );
 
-        let builder = github_connection.request_builder(&url, reqwest::Method::GET);
-        let response = github_connection
-            .execute(builder)
-            .await
-            .map_err(|e| {
-                trace!("Error = {:?}", e);
-                AnalyzeError::GitHubAPIError {
-                    msg: format!("Error fetching reviews for PR in [{}].", &url),
-                    nested: nested!(e),
-                }
-            })
-            .unwrap();
+        let response = retry_policy.get_non_empty(&github_connection, &url).await.map_err(|e| {
+            trace!("Error = {:?}", e);
+            AnalyzeError::GitHubAPIError {
+                msg: format!("Error fetching reviews for PR in [{}].", &url),
+                nested: nested!(e),
+            }
+        })?;
+
+        let response = reject_rate_limited(response, &url)?;
 
         if response.content_length().is_some() && response.content_length().unwrap() == 0 {
             warn!("No content received while fetching reviews for PR in [{}].", &url);
@@ -599,6 +1734,9 @@ impl Analyzer {
         let parsed_json: Vec<Review> = serde_json::from_str(&raw_response_text).map_err(|e| {
             trace!("Error = {:?}", e);
             trace!("Raw response = {}", raw_response_text);
+            if let Some(failure_dump) = &failure_dump {
+                failure_dump.store(&url, &raw_response_text);
+            }
             AnalyzeError::JsonParseError {
                 msg: format!("Error mapping reviews' JSON for PR in [{}].", url),
                 nested: nested!(e),
@@ -608,75 +1746,453 @@ impl Analyzer {
         Ok(parsed_json)
     }
 
-    /// 'commit comments' are comments on a portion of the unified diff.
-    /// See more: https://stackoverflow.com/a/16200750
-    #[prolice_trace_time]
-    async fn get_pr_commit_comments(
-        github_connection: GitHubConnection, pr_review_comments_url: Url,
-    ) -> Result<Vec<CommitComment>, AnalyzeError> {
-        trace!("Retrieving commit comments for PR in [{}]...", pr_review_comments_url);
+    /// 'reactions' are the emojis (👍, 👎, 🎉...) people leave on the PR's body; a lightweight form of
+    /// engagement that never shows up as a textual comment. Every page of them is followed through.
+    #[prolice_trace_time(log = [repo_name, pr_number])]
+    async fn get_pr_reactions(
+        github_connection: GitHubConnection, owner: String, repo_name: String, pr_number: u64,
+        retry_policy: RetryPolicy, failure_dump: Option<FailureDump>,
+    ) -> Result<Vec<Reaction>, AnalyzeError> {
+        trace!("Retrieving reactions for [{}]/[{}]...", repo_name, pr_number);
+
+        // PRs are issues as far as reactions are concerned
+        let mut url = github_api_url(
+            &github_connection,
+            &format!(
+                "repos/{owner}/{repo}/issues/{pr}/reactions?per_page={per_page}",
+                owner = owner,
+                repo = repo_name,
+                pr = pr_number,
+                per_page = MAX_PAGE_SIZE
+            ),
+        );
 
-        let url = pr_review_comments_url.as_str();
-        let builder = github_connection.request_builder(url, reqwest::Method::GET);
-        let response = github_connection
-            .execute(builder)
-            .await
-            .map_err(|e| {
-                trace!("Error = {:?}", e);
-                AnalyzeError::GitHubAPIError {
-                    msg: format!("Error fetching commit comments for PR in [{}].", url),
-                    nested: nested!(e),
-                }
-            })
-            .unwrap();
+        // reactions were long a preview API, only served under its own media type (older GitHub
+        // Enterprise instances still require it)
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            ACCEPT,
+            HeaderValue::from_static("application/vnd.github.squirrel-girl-preview+json"),
+        );
 
-        if response.content_length().is_some() && response.content_length().unwrap() == 0 {
-            warn!("No content received while fetching commit comments for PR in [{}].", url);
-            return Ok(Vec::new());
-        }
+        let mut reactions = Vec::new();
 
-        let raw_response_text = response.text().await.map_err(|e| {
-            trace!("Error = {:?}", e);
-            AnalyzeError::GitHubAPIResponseBodyError {
-                msg: format!("Error retrieving commit comments' JSON for PR in [{}].", url),
-                nested: nested!(e),
+        loop {
+            let response = retry_policy
+                .get_with_headers(&github_connection, &url, headers.clone())
+                .await
+                .map_err(|e| {
+                    trace!("Error = {:?}", e);
+                    AnalyzeError::GitHubAPIError {
+                        msg: format!("Error fetching reactions for PR in [{}].", &url),
+                        nested: nested!(e),
+                    }
+                })?;
+
+            let response = reject_rate_limited(response, &url)?;
+
+            if response.status() == StatusCode::NOT_FOUND {
+                warn!("Reactions are not available for PR in [{}].", &url);
+                return Ok(reactions);
             }
-        })?;
 
-        let parsed_json: Vec<CommitComment> =
-            serde_json::from_str(&raw_response_text).map_err(|e| {
+            if response.content_length().is_some() && response.content_length().unwrap() == 0 {
+                warn!("No content received while fetching reactions for PR in [{}].", &url);
+                return Ok(reactions);
+            }
+
+            let next_page = next_page_url(response.headers());
+
+            let raw_response_text = response.text().await.map_err(|e| {
                 trace!("Error = {:?}", e);
-                trace!("Raw response = {}", raw_response_text);
-                AnalyzeError::JsonParseError {
-                    msg: format!("Error mapping commit comments' JSON for PR in [{}].", url),
+                AnalyzeError::GitHubAPIResponseBodyError {
+                    msg: format!("Error retrieving reactions' JSON for PR in [{}].", &url),
                     nested: nested!(e),
                 }
             })?;
 
-        Ok(parsed_json)
+            let parsed_json: Vec<Reaction> =
+                serde_json::from_str(&raw_response_text).map_err(|e| {
+                    trace!("Error = {:?}", e);
+                    trace!("Raw response = {}", raw_response_text);
+                    if let Some(failure_dump) = &failure_dump {
+                        failure_dump.store(&url, &raw_response_text);
+                    }
+                    AnalyzeError::JsonParseError {
+                        msg: format!("Error mapping reactions' JSON for PR in [{}].", url),
+                        nested: nested!(e),
+                    }
+                })?;
+
+            reactions.extend(parsed_json);
+
+            match next_page {
+                Some(next_page) => url = next_page.to_string(),
+                None => return Ok(reactions),
+            }
+        }
     }
 
-    /// 'commits' are snapshots of the codebase at a given time. The unified diff of all commits in a
-    /// branch constitutes a [`PullRequest`]'s content.
-    #[prolice_trace_time]
-    async fn get_pr_commits(
-        github_connection: GitHubConnection, pr_commits_url: Url,
-    ) -> Result<Vec<CommitRoot>, AnalyzeError> {
-        trace!("Retrieving commits for PR in [{}]...", pr_commits_url);
+    /// Review threads are conversations started by review comments on a portion of the unified diff;
+    /// which reviewers may then mark as resolved. Only GitHub's GraphQL API serves them (and only their
+    /// current resolution state), so [`None`] is returned if it serves no data for the PR at all. Only
+    /// the first [`MAX_PAGE_SIZE`] threads are taken into account.
+    #[prolice_trace_time(log = [repo_name, pr_number])]
+    async fn get_pr_review_threads(
+        github_connection: GitHubConnection, owner: String, repo_name: String, pr_number: u64,
+        retry_policy: RetryPolicy, failure_dump: Option<FailureDump>,
+    ) -> Result<Option<Vec<ReviewThread>>, AnalyzeError> {
+        trace!("Retrieving review threads for [{}]/[{}]...", repo_name, pr_number);
+
+        let url = github_graphql_url(&github_connection);
+        let query = json!({
+            "query": REVIEW_THREADS_QUERY,
+            "variables": {
+                "owner": owner,
+                "repo": repo_name,
+                "number": pr_number,
+                "first": MAX_PAGE_SIZE,
+            }
+        });
 
-        let url = pr_commits_url.as_str();
-        let builder = github_connection.request_builder(url, reqwest::Method::GET);
-        let response = github_connection
-            .execute(builder)
-            .await
-            .map_err(|e| {
+        let response =
+            retry_policy.post_json(&github_connection, &url, &query).await.map_err(|e| {
                 trace!("Error = {:?}", e);
                 AnalyzeError::GitHubAPIError {
-                    msg: format!("Error fetching commits for PR in [{}].", url),
+                    msg: format!(
+                        "Error fetching review threads for [{}]/[{}].",
+                        repo_name, pr_number
+                    ),
                     nested: nested!(e),
                 }
-            })
-            .unwrap();
+            })?;
+
+        let response = reject_rate_limited(response, &url)?;
+
+        if !response.status().is_success() {
+            warn!(
+                "Review threads are not available for [{}]/[{}] (status [{}]).",
+                repo_name,
+                pr_number,
+                response.status()
+            );
+            return Ok(None);
+        }
+
+        let raw_response_text = response.text().await.map_err(|e| {
+            trace!("Error = {:?}", e);
+            AnalyzeError::GitHubAPIResponseBodyError {
+                msg: format!(
+                    "Error retrieving review threads' JSON for [{}]/[{}].",
+                    repo_name, pr_number
+                ),
+                nested: nested!(e),
+            }
+        })?;
+
+        let parsed_json: ReviewThreadsRoot =
+            serde_json::from_str(&raw_response_text).map_err(|e| {
+                trace!("Error = {:?}", e);
+                trace!("Raw response = {}", raw_response_text);
+                if let Some(failure_dump) = &failure_dump {
+                    failure_dump.store(
+                        &format!("graphql/{}/{}/{}/review_threads", owner, repo_name, pr_number),
+                        &raw_response_text,
+                    );
+                }
+                AnalyzeError::JsonParseError {
+                    msg: format!(
+                        "Error mapping review threads' JSON for [{}]/[{}].",
+                        repo_name, pr_number
+                    ),
+                    nested: nested!(e),
+                }
+            })?;
+
+        Ok(parsed_json.into_review_threads())
+    }
+
+    /// A PR's timeline lists every event that took place on it; out of which only those that switched it
+    /// from draft to ready for review (or the other way around) are kept. [`None`] is returned if GitHub
+    /// does not serve the timeline at all. Every page of it is followed through; should any but the first
+    /// one fail, the events gathered by then are kept.
+    #[prolice_trace_time(log = [repo_name, pr_number])]
+    async fn get_pr_timeline(
+        github_connection: GitHubConnection, owner: String, repo_name: String, pr_number: u64,
+        retry_policy: RetryPolicy, failure_dump: Option<FailureDump>,
+    ) -> Result<Option<Vec<TimelineEvent>>, AnalyzeError> {
+        trace!("Retrieving timeline for [{}]/[{}]...", repo_name, pr_number);
+
+        // PRs are issues as far as the timeline is concerned
+        let mut url = github_api_url(
+            &github_connection,
+            &format!(
+                "repos/{owner}/{repo}/issues/{pr}/timeline?per_page={per_page}",
+                owner = owner,
+                repo = repo_name,
+                pr = pr_number,
+                per_page = MAX_PAGE_SIZE
+            ),
+        );
+
+        // the timeline was long a preview API, only served under its own media type (older GitHub
+        // Enterprise instances still require it)
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            ACCEPT,
+            HeaderValue::from_static("application/vnd.github.mockingbird-preview+json"),
+        );
+
+        let mut draft_transitions: Option<Vec<TimelineEvent>> = None;
+
+        loop {
+            let response = retry_policy
+                .get_with_headers(&github_connection, &url, headers.clone())
+                .await
+                .map_err(|e| {
+                    trace!("Error = {:?}", e);
+                    AnalyzeError::GitHubAPIError {
+                        msg: format!("Error fetching timeline for PR in [{}].", &url),
+                        nested: nested!(e),
+                    }
+                })?;
+
+            let response = reject_rate_limited(response, &url)?;
+
+            if !response.status().is_success() {
+                warn!(
+                    "Timeline is not available for PR in [{}] (status [{}]).",
+                    &url,
+                    response.status()
+                );
+                return Ok(draft_transitions);
+            }
+
+            let next_page = next_page_url(response.headers());
+
+            let raw_response_text = response.text().await.map_err(|e| {
+                trace!("Error = {:?}", e);
+                AnalyzeError::GitHubAPIResponseBodyError {
+                    msg: format!("Error retrieving timeline's JSON for PR in [{}].", &url),
+                    nested: nested!(e),
+                }
+            })?;
+
+            let parsed_json: Vec<TimelineEvent> = serde_json::from_str(&raw_response_text)
+                .map_err(|e| {
+                    trace!("Error = {:?}", e);
+                    trace!("Raw response = {}", raw_response_text);
+                    if let Some(failure_dump) = &failure_dump {
+                        failure_dump.store(&url, &raw_response_text);
+                    }
+                    AnalyzeError::JsonParseError {
+                        msg: format!("Error mapping timeline's JSON for PR in [{}].", url),
+                        nested: nested!(e),
+                    }
+                })?;
+
+            draft_transitions
+                .get_or_insert_with(Vec::new)
+                .extend(parsed_json.into_iter().filter(|event| event.is_draft_transition()));
+
+            match next_page {
+                Some(next_page) => url = next_page.to_string(),
+                None => return Ok(draft_transitions),
+            }
+        }
+    }
+
+    /// Checks on a PR's head commit come in two flavours: statuses (reported through GitHub's older
+    /// statuses API, still used by many external CI services) and check runs (ie: GitHub Actions'). Both
+    /// are retrieved and collapsed into their worst [`ChecksOutcome`]; [`None`] if GitHub serves neither of
+    /// them. Every page of check runs is followed through.
+    /// <br/><br/>
+    /// Check runs are judged as they stood by the time the PR got `merged_at` (if it did); but statuses
+    /// only tell how they stand now, so they are taken as such.
+    #[prolice_trace_time(log = [repo_name, head_sha])]
+    async fn get_pr_checks(
+        github_connection: GitHubConnection, owner: String, repo_name: String, head_sha: String,
+        merged_at: Option<DateTime<Utc>>, retry_policy: RetryPolicy,
+        failure_dump: Option<FailureDump>,
+    ) -> Result<Option<ChecksOutcome>, AnalyzeError> {
+        trace!("Retrieving checks for [{}]@[{}]...", repo_name, head_sha);
+
+        let commit_route = format!(
+            "repos/{owner}/{repo}/commits/{sha}",
+            owner = owner,
+            repo = repo_name,
+            sha = head_sha
+        );
+
+        let combined_status: Option<CombinedStatus> = Analyzer::get_checks_json(
+            &github_connection,
+            &github_api_url(&github_connection, &format!("{}/status", commit_route)),
+            retry_policy,
+            &failure_dump,
+        )
+        .await?
+        .map(|(combined_status, _)| combined_status);
+
+        let mut check_runs: Option<CheckRuns> = None;
+        let mut next_page = Some(github_api_url(
+            &github_connection,
+            &format!("{}/check-runs?per_page={}", commit_route, MAX_PAGE_SIZE),
+        ));
+
+        while let Some(url) = next_page {
+            let (page, page_after) = match Analyzer::get_checks_json::<CheckRuns>(
+                &github_connection,
+                &url,
+                retry_policy,
+                &failure_dump,
+            )
+            .await?
+            {
+                Some(page) => page,
+                None => break,
+            };
+
+            match &mut check_runs {
+                Some(check_runs) => check_runs.check_runs.extend(page.check_runs),
+                None => check_runs = Some(page),
+            }
+            next_page = page_after.map(|page_after| page_after.to_string());
+        }
+
+        Ok(combined_status
+            .map(|combined_status| combined_status.outcome())
+            .into_iter()
+            .chain(check_runs.map(|check_runs| check_runs.outcome(merged_at)))
+            .max())
+    }
+
+    /// Retrieves (and parses) either kind of checks reported on a commit, alongside the link to their next
+    /// page (if any); [`None`] if GitHub does not serve them.
+    async fn get_checks_json<T: DeserializeOwned>(
+        github_connection: &GitHubConnection, url: &str, retry_policy: RetryPolicy,
+        failure_dump: &Option<FailureDump>,
+    ) -> Result<Option<(T, Option<Url>)>, AnalyzeError> {
+        // the checks API was long a preview API, only served under its own media type (older GitHub
+        // Enterprise instances still require it); statuses do not mind it
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            ACCEPT,
+            HeaderValue::from_static("application/vnd.github.antiope-preview+json"),
+        );
+
+        let response =
+            retry_policy.get_with_headers(github_connection, url, headers).await.map_err(|e| {
+                trace!("Error = {:?}", e);
+                AnalyzeError::GitHubAPIError {
+                    msg: format!("Error fetching checks for commit in [{}].", url),
+                    nested: nested!(e),
+                }
+            })?;
+
+        let response = reject_rate_limited(response, url)?;
+
+        if !response.status().is_success() {
+            warn!(
+                "Checks are not available for commit in [{}] (status [{}]).",
+                url,
+                response.status()
+            );
+            return Ok(None);
+        }
+
+        let next_page = next_page_url(response.headers());
+
+        let raw_response_text = response.text().await.map_err(|e| {
+            trace!("Error = {:?}", e);
+            AnalyzeError::GitHubAPIResponseBodyError {
+                msg: format!("Error retrieving checks' JSON for commit in [{}].", url),
+                nested: nested!(e),
+            }
+        })?;
+
+        let parsed_json: T = serde_json::from_str(&raw_response_text).map_err(|e| {
+            trace!("Error = {:?}", e);
+            trace!("Raw response = {}", raw_response_text);
+            if let Some(failure_dump) = failure_dump {
+                failure_dump.store(url, &raw_response_text);
+            }
+            AnalyzeError::JsonParseError {
+                msg: format!("Error mapping checks' JSON for commit in [{}].", url),
+                nested: nested!(e),
+            }
+        })?;
+
+        Ok(Some((parsed_json, next_page)))
+    }
+
+    /// 'commit comments' are comments on a portion of the unified diff.
+    /// See more: https://stackoverflow.com/a/16200750
+    #[prolice_trace_time(log = [pr_review_comments_url])]
+    async fn get_pr_commit_comments(
+        github_connection: GitHubConnection, pr_review_comments_url: Url,
+        retry_policy: RetryPolicy, failure_dump: Option<FailureDump>,
+    ) -> Result<Vec<CommitComment>, AnalyzeError> {
+        trace!("Retrieving commit comments for PR in [{}]...", pr_review_comments_url);
+
+        let url = pr_review_comments_url.as_str();
+        let response = retry_policy.get_non_empty(&github_connection, url).await.map_err(|e| {
+            trace!("Error = {:?}", e);
+            AnalyzeError::GitHubAPIError {
+                msg: format!("Error fetching commit comments for PR in [{}].", url),
+                nested: nested!(e),
+            }
+        })?;
+
+        let response = reject_rate_limited(response, url)?;
+
+        if response.content_length().is_some() && response.content_length().unwrap() == 0 {
+            warn!("No content received while fetching commit comments for PR in [{}].", url);
+            return Ok(Vec::new());
+        }
+
+        let raw_response_text = response.text().await.map_err(|e| {
+            trace!("Error = {:?}", e);
+            AnalyzeError::GitHubAPIResponseBodyError {
+                msg: format!("Error retrieving commit comments' JSON for PR in [{}].", url),
+                nested: nested!(e),
+            }
+        })?;
+
+        let parsed_json: Vec<CommitComment> =
+            serde_json::from_str(&raw_response_text).map_err(|e| {
+                trace!("Error = {:?}", e);
+                trace!("Raw response = {}", raw_response_text);
+                if let Some(failure_dump) = &failure_dump {
+                    failure_dump.store(url, &raw_response_text);
+                }
+                AnalyzeError::JsonParseError {
+                    msg: format!("Error mapping commit comments' JSON for PR in [{}].", url),
+                    nested: nested!(e),
+                }
+            })?;
+
+        Ok(parsed_json)
+    }
+
+    /// 'commits' are snapshots of the codebase at a given time. The unified diff of all commits in a
+    /// branch constitutes a [`PullRequest`]'s content.
+    #[prolice_trace_time(log = [pr_commits_url])]
+    async fn get_pr_commits(
+        github_connection: GitHubConnection, pr_commits_url: Url, retry_policy: RetryPolicy,
+        failure_dump: Option<FailureDump>,
+    ) -> Result<Vec<CommitRoot>, AnalyzeError> {
+        trace!("Retrieving commits for PR in [{}]...", pr_commits_url);
+
+        let url = pr_commits_url.as_str();
+        let response = retry_policy.get_non_empty(&github_connection, url).await.map_err(|e| {
+            trace!("Error = {:?}", e);
+            AnalyzeError::GitHubAPIError {
+                msg: format!("Error fetching commits for PR in [{}].", url),
+                nested: nested!(e),
+            }
+        })?;
+
+        let response = reject_rate_limited(response, url)?;
 
         if response.content_length().is_some() && response.content_length().unwrap() == 0 {
             warn!("No content received while fetching commits for PR in [{}].", url);
@@ -695,6 +2211,9 @@ impl Analyzer {
             serde_json::from_str(&raw_response_text).map_err(|e| {
                 trace!("Error = {:?}", e);
                 trace!("Raw response = {}", raw_response_text);
+                if let Some(failure_dump) = &failure_dump {
+                    failure_dump.store(url, &raw_response_text);
+                }
                 AnalyzeError::JsonParseError {
                     msg: format!("Error mapping commits' JSON for PR in [{}].", url),
                     nested: nested!(e),
@@ -708,27 +2227,92 @@ impl Analyzer {
         Ok(parsed_json)
     }
 
-    /// Returns a specific [`PullRequest`]'s diff.
-    #[prolice_trace_time]
+    /// Returns a specific [`PullRequest`]'s unified diff, as raw text.
+    /// <br/><br/>
+    /// Large diffs are prone to failing on GitHub's side every now and then (ie: with a `502 Bad
+    /// Gateway`), so those failures are retried. Diffs that are beyond GitHub's size limits, though, can
+    /// never be served; they end up in [`AnalyzeError::DiffTooLarge`] instead.
+    #[prolice_trace_time(log = [repo_name, pr_number])]
     async fn get_pr_diff(
         github_connection: GitHubConnection, owner: String, repo_name: String, pr_number: u64,
-    ) -> Result<PatchSet, AnalyzeError> {
+        retry_policy: RetryPolicy,
+    ) -> Result<String, AnalyzeError> {
         trace!("Retrieving diff for [{}]/[{}]...", repo_name, pr_number);
 
-        let diff =
-            github_connection.pulls(owner, &repo_name).get_diff(pr_number).await.map_err(|e| {
-                AnalyzeError::GitHubAPIError {
-                    msg: format!(
-                        "Could not retrieve diff for [{}/{}]. Aborting operation.",
-                        repo_name, pr_number
-                    ),
-                    nested: nested!(e),
-                }
+        let url = github_api_url(
+            &github_connection,
+            &format!(
+                "repos/{owner}/{repo}/pulls/{pr}",
+                owner = owner,
+                repo = repo_name,
+                pr = pr_number
+            ),
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(ACCEPT, HeaderValue::from_static("application/vnd.github.v3.diff"));
+
+        let response = retry_policy
+            .get_through_server_errors(&github_connection, &url, headers)
+            .await
+            .map_err(|e| AnalyzeError::GitHubAPIError {
+                msg: format!(
+                    "Could not retrieve diff for [{}/{}]. Aborting operation.",
+                    repo_name, pr_number
+                ),
+                nested: nested!(e),
             })?;
 
+        let response = reject_rate_limited(response, &url)?;
+
+        let status = response.status();
+        let raw_response_text = response.text().await.map_err(|e| {
+            trace!("Error = {:?}", e);
+            AnalyzeError::GitHubAPIResponseBodyError {
+                msg: format!("Error retrieving diff for [{}/{}].", repo_name, pr_number),
+                nested: nested!(e),
+            }
+        })?;
+
+        if status.is_success() {
+            return Ok(raw_response_text);
+        }
+
+        trace!("Raw response = {}", raw_response_text);
+
+        match diff_too_large_reason(status, &raw_response_text) {
+            Some(reason) => Err(AnalyzeError::DiffTooLarge {
+                repo_name,
+                pr_number,
+                reason,
+            }),
+            None => Err(AnalyzeError::GitHubAPIError {
+                msg: format!(
+                    "Could not retrieve diff for [{}/{}] (status [{}]). Aborting operation.",
+                    repo_name, pr_number, status
+                ),
+                nested: anyhow!("{}", raw_response_text),
+            }),
+        }
+    }
+
+    /// Parses a [`PullRequest`]'s raw unified `diff`.
+    fn parse_diff(repo_name: &str, pr_number: u64, diff: &str) -> Result<PatchSet, AnalyzeError> {
+        lazy_static! {
+            // paths may well contain " and " themselves, so each side is anchored to its own prefix
+            static ref BINARY_FILES_REGEX: Regex = Regex::new(
+                r"(?m)^Binary files (?P<source>/dev/null|a/.+?) and (?P<target>/dev/null|b/.+) differ$"
+            )
+            .unwrap();
+        }
+
+        // binary files carry no '---'/'+++' headers, so unidiff would skip them altogether; giving them
+        // one turns them into hunk-less patched files instead
+        let diff = BINARY_FILES_REGEX.replace_all(diff, "--- $source\n+++ $target");
+
         let mut patch = PatchSet::new();
         patch.parse(diff).map_err(|e| AnalyzeError::DiffParseError {
-            repo_name,
+            repo_name: repo_name.to_string(),
             pr_number,
             nested: nested!(e),
         })?;
@@ -745,21 +2329,1151 @@ impl Analyzer {
     /// that has read access for the intended targets.
     fn new(
         owner: &str, repository: Repository, github_personal_access_token: &str,
-        connection_pool: &'static Pool<Octocrab, GitHubPoolError>,
+        connection_pool: &GitHubConnectionPool, retrieval_settings: RetrievalSettings,
+        scoring_settings: Arc<ScoringSettings>, retry_policy: RetryPolicy,
+        pull_request_cache: Option<PullRequestCache>, failure_dump: Option<FailureDump>,
+        pr_timeout: Duration, inflight_limit: Arc<Semaphore>, interruption: Interruption,
     ) -> Self {
         Analyzer {
             owner: owner.to_string(),
             repository,
             github_personal_access_token: github_personal_access_token.to_string(),
-            connection_pool,
+            connection_pool: connection_pool.clone(),
+            retrieval_settings,
+            scoring_settings,
+            retry_policy,
+            pull_request_cache,
+            failure_dump,
+            pr_timeout,
+            inflight_limit,
+            interruption,
         }
     }
 
+    /// Estimates the amount of requests it takes to retrieve a single [`PullRequest`]'s data under the
+    /// given `retrieval_settings` and `retry_policy`: its base ones, plus whatever optional data gets
+    /// retrieved along with them, plus every re-request of empty responses and every retry of a failing
+    /// diff they may take. It errs on the side of caution, as all of those retries rarely happen at once.
+    pub fn requests_per_pr(
+        retrieval_settings: &RetrievalSettings, retry_policy: &RetryPolicy,
+    ) -> u64 {
+        let optional_requests = retrieval_settings.with_threads as u64
+            + retrieval_settings.with_timeline as u64
+            + 2 * retrieval_settings.with_checks as u64;
+        let re_requests = NON_EMPTY_REQUESTS_PER_PR * retry_policy.empty_response_retries as u64;
+        let diff_retries = retry_policy.max_retries as u64;
+
+        BASE_REQUESTS_PER_PR + optional_requests + re_requests + diff_retries
+    }
+
+    /// Estimates the amount of requests it takes to analyze a sample of `sample_size` [`PullRequest`]s
+    /// from a single [`Repository`] under the given `retrieval_settings`, `scoring_settings` and
+    /// `retry_policy`: listing them (or rather, their candidates), plus retrieving each one's data (see
+    /// [`Analyzer::requests_per_pr`]), plus listing the repository-wide [`PullRequestFlow`] if asked to.
+    /// Cached [`PullRequest`]s are not taken into account, so it errs on the side of caution.
+    pub fn planned_requests(
+        sample_size: u16, retrieval_settings: &RetrievalSettings,
+        scoring_settings: &ScoringSettings, retry_policy: &RetryPolicy,
+    ) -> u64 {
+        let pool_size = retrieval_settings.sample_strategy.candidate_pool_size(sample_size);
+        let listing_pages = (pool_size as f64 / MAX_PAGE_SIZE as f64).ceil() as u64;
+        let flow_pages = match scoring_settings.flow_ratio_source {
+            FlowRatioSource::Sample => 0,
+            FlowRatioSource::Repository => 2 * MAX_FLOW_PAGES as u64,
+        };
+
+        listing_pages
+            + flow_pages
+            + sample_size as u64 * Analyzer::requests_per_pr(retrieval_settings, retry_policy)
+    }
+
     pub fn repository(&self) -> &Repository {
         &self.repository
     }
 
+    pub fn scoring_settings(&self) -> &ScoringSettings {
+        &self.scoring_settings
+    }
+
+    pub fn retrieval_settings(&self) -> &RetrievalSettings {
+        &self.retrieval_settings
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
     pub fn owner(&self) -> &str {
         &self.owner
     }
 }
+
+#[cfg(test)]
+mod analyzer_tests {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use chrono::{TimeZone, Utc};
+    use futures::future::join_all;
+    use octocrab::models::issues::Comment;
+    use reqwest::StatusCode;
+    use serde_json::json;
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+    use tokio::sync::Semaphore;
+    use tokio::task::JoinHandle;
+
+    use crate::error::AnalyzeError;
+    use crate::github::client::pool::{GitHubConnectionPool, GitHubConnectionPoolManager};
+    use crate::github::client::retry::{AbusePolicy, RetryPolicy};
+    use crate::github::json::commit_checks::ChecksOutcome;
+    use crate::github::utils::analyzer::{
+        diff_too_large_reason, spawn_limited, Analyzer, AnalyzerBuilder, Interruption,
+        BASE_REQUESTS_PER_PR, MAX_FLOW_PAGES, MAX_PAGE_SIZE,
+    };
+    use crate::github::utils::fixtures::{
+        comment, commit_comment, commit_root, fake_github_api, has_metric, http_response,
+        pull_request_json, reaction, repository_json, review, user_json, CannedGitHubApi,
+        GitHubApiResponder, PullRequestDataFixture, SINGLE_LINE_DIFF,
+    };
+    use crate::github::utils::pull_request_data::{PullRequestData, PullRequestDataResult};
+    use crate::github::utils::repository_data::PullRequestFlow;
+    use crate::github::utils::retrieval_settings::{RetrievalSettings, SampleStrategy};
+    use crate::scoring::scorable::Scorable;
+    use crate::scoring::score::ScoreType;
+    use crate::scoring::scoring_settings::{FlowRatioSource, ScoringSettings};
+
+    /// Answers like GitHub does for a personal (ie: non-organization) `owner`, whose search results span
+    /// two pages; "second-page-repository" being only listed in the second one.
+    fn personal_owner_api(request: &str) -> String {
+        let path = request.split_whitespace().nth(1).unwrap_or_default();
+        let host =
+            request.lines().find_map(|header| header.strip_prefix("host: ")).unwrap_or_default();
+
+        if path.starts_with("/orgs/") {
+            return http_response("404 Not Found", &[], r#"{"message":"Not Found"}"#);
+        }
+
+        if path.starts_with("/search/repositories") && path.contains("page=2") {
+            let body = json!({ "items": [repository_json("owner", "second-page-repository")] });
+            return http_response("200 OK", &[], &body.to_string());
+        }
+
+        if path.starts_with("/search/repositories") {
+            let body = json!({ "items": [repository_json("owner", "First-Page-Repository")] });
+            // the search's query gets carried over to the next page, just like GitHub does
+            let next_link = format!("link: <http://{}{}&page=2>; rel=\"next\"", host, path);
+            return http_response("200 OK", &[next_link], &body.to_string());
+        }
+
+        http_response("404 Not Found", &[], r#"{"message":"Not Found"}"#)
+    }
+
+    async fn init_against(
+        base_url: String, repository_name: &str,
+    ) -> Result<Analyzer, AnalyzeError> {
+        // a PR's data is fetched through several connections at once
+        let connection_pool = GitHubConnectionPool::new(
+            GitHubConnectionPoolManager::new("token", Some(base_url)),
+            BASE_REQUESTS_PER_PR as usize + 1,
+        );
+
+        AnalyzerBuilder::new("owner", repository_name, "token", &connection_pool)
+            .retry_policy(RetryPolicy {
+                max_retries: 0,
+                ..Default::default()
+            })
+            .init()
+            .await
+    }
+
+    #[tokio::test]
+    async fn personal_repository_lookup_ignores_case() {
+        let analyzer =
+            init_against(fake_github_api(personal_owner_api).await, "first-page-repository")
+                .await
+                .unwrap();
+
+        assert_eq!(analyzer.repository().name, "First-Page-Repository");
+    }
+
+    #[tokio::test]
+    async fn personal_repository_lookup_follows_the_search_results_pages() {
+        let analyzer =
+            init_against(fake_github_api(personal_owner_api).await, "Second-Page-Repository")
+                .await
+                .unwrap();
+        assert_eq!(analyzer.repository().name, "second-page-repository");
+
+        let missing =
+            init_against(fake_github_api(personal_owner_api).await, "third-page-repository").await;
+        assert!(matches!(missing, Err(AnalyzeError::RepositoryNotFoundError(_))));
+    }
+
+    #[tokio::test]
+    async fn unknown_repositories_are_reported_as_json_error_objects() {
+        let error = init_against(fake_github_api(personal_owner_api).await, "unknown-repository")
+            .await
+            .err()
+            .unwrap();
+
+        let object: serde_json::Value = serde_json::from_str(&error.to_json()).unwrap();
+        assert_eq!(
+            object,
+            json!({
+                "error": {
+                    "kind": "RepositoryNotFoundError",
+                    "message": "Repository initialization error = Could not find repository \
+                        [unknown-repository] under owner [owner] (is it misspelled?)",
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn empty_reviews_are_re_requested_before_being_accepted() {
+        static REVIEWS_REQUESTS: AtomicUsize = AtomicUsize::new(0);
+
+        // the first answer comes back empty, just like GitHub's transient ones under load
+        let base_url = fake_github_api(|_: &str| {
+            if REVIEWS_REQUESTS.fetch_add(1, Ordering::SeqCst) == 0 {
+                return http_response("200 OK", &[], "");
+            }
+
+            let body = json!([{
+                "id": 80,
+                "node_id": "MDE3OlB1bGxSZXF1ZXN0UmV2aWV3ODA=",
+                "html_url": "https://github.com/owner/repository/pull/1#pullrequestreview-80",
+                "user": user_json("reviewer"),
+                "body": "Dummy review",
+                "state": "APPROVED",
+            }]);
+            http_response("200 OK", &[], &body.to_string())
+        })
+        .await;
+
+        let connection_pool =
+            GitHubConnectionPool::new(GitHubConnectionPoolManager::new("token", Some(base_url)), 1);
+        let retry_policy = RetryPolicy {
+            base_delay: Duration::from_millis(1),
+            ..Default::default()
+        };
+
+        let reviews = Analyzer::get_pr_reviews(
+            connection_pool.get().await.unwrap(),
+            String::from("owner"),
+            String::from("repository"),
+            1,
+            retry_policy,
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(reviews.len(), 1);
+        assert_eq!(REVIEWS_REQUESTS.load(Ordering::SeqCst), 2);
+    }
+
+    /// Answers like GitHub does for a PR whose reactions, timeline and check runs span two pages each;
+    /// the second one linked from the first one through its `Link` header.
+    fn paginated_pr_api(request: &str) -> String {
+        let path = request.split_whitespace().nth(1).unwrap_or_default();
+        let host =
+            request.lines().find_map(|header| header.strip_prefix("host: ")).unwrap_or_default();
+        let is_second_page = path.contains("page=2");
+
+        let body = match path.split('?').next().unwrap_or_default() {
+            "/repos/owner/repository/issues/1/reactions" => {
+                let content = if is_second_page {
+                    "heart"
+                } else {
+                    "+1"
+                };
+                json!([{ "id": 1, "node_id": "MDg6UmVhY3Rpb24x", "user": user_json("reviewer"), "content": content,
+                    "created_at": "2021-03-01T10:00:00Z" }])
+            }
+            "/repos/owner/repository/issues/1/timeline" => {
+                let event = if is_second_page {
+                    "ready_for_review"
+                } else {
+                    "commented"
+                };
+                json!([{ "event": event, "created_at": "2021-03-01T10:00:00Z" }])
+            }
+            route if route.ends_with("/check-runs") => {
+                let conclusion = if is_second_page {
+                    "failure"
+                } else {
+                    "success"
+                };
+                json!({ "total_count": 2, "check_runs": [
+                    { "name": "test", "status": "completed", "conclusion": conclusion }
+                ] })
+            }
+            _ => return http_response("404 Not Found", &[], r#"{"message":"Not Found"}"#),
+        };
+
+        if is_second_page {
+            return http_response("200 OK", &[], &body.to_string());
+        }
+
+        let next_link = format!("link: <http://{}{}&page=2>; rel=\"next\"", host, path);
+        http_response("200 OK", &[next_link], &body.to_string())
+    }
+
+    #[tokio::test]
+    async fn paginated_pr_data_is_followed_through_every_page() {
+        let connection_pool = GitHubConnectionPool::new(
+            GitHubConnectionPoolManager::new(
+                "token",
+                Some(fake_github_api(paginated_pr_api).await),
+            ),
+            1,
+        );
+        let owner = || String::from("owner");
+        let repo_name = || String::from("repository");
+
+        let reactions = Analyzer::get_pr_reactions(
+            connection_pool.get().await.unwrap(),
+            owner(),
+            repo_name(),
+            1,
+            RetryPolicy::default(),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(reactions.len(), 2);
+
+        let timeline = Analyzer::get_pr_timeline(
+            connection_pool.get().await.unwrap(),
+            owner(),
+            repo_name(),
+            1,
+            RetryPolicy::default(),
+            None,
+        )
+        .await
+        .unwrap();
+        // the only draft transition lies in the second page
+        assert_eq!(timeline.unwrap().len(), 1);
+
+        let checks = Analyzer::get_pr_checks(
+            connection_pool.get().await.unwrap(),
+            owner(),
+            repo_name(),
+            String::from("6dcb09b5b57875f334f61aebed695e2e4193db5e"),
+            None,
+            RetryPolicy::default(),
+            None,
+        )
+        .await
+        .unwrap();
+        // so does the only failing check run
+        assert_eq!(checks, Some(ChecksOutcome::Failing));
+    }
+
+    #[tokio::test]
+    async fn failed_owner_lookup_is_reported_as_an_error_instead_of_panicking() {
+        let server_error = init_against(
+            fake_github_api(|_: &str| http_response("500 Internal Server Error", &[], "")).await,
+            "repository",
+        )
+        .await;
+        assert!(matches!(server_error, Err(AnalyzeError::GitHubAPIError { .. })));
+
+        // nothing listens on a port that was just released, so the connection itself fails
+        let unreachable_address =
+            TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap();
+        let network_error =
+            init_against(format!("http://{}/", unreachable_address), "repository").await;
+        assert!(matches!(network_error, Err(AnalyzeError::GitHubAPIError { .. })));
+    }
+
+    #[tokio::test]
+    async fn failed_fetch_only_fails_its_own_pull_request() {
+        let mut canned_api = CannedGitHubApi::default()
+            .json("/orgs/owner/repos", json!([repository_json("owner", "repository")]))
+            .json(
+                "/repos/owner/repository/pulls",
+                json!((1..=3)
+                    .map(|pr_number| pull_request_json("owner", "repository", pr_number, "author"))
+                    .collect::<Vec<_>>()),
+            );
+        for pr_number in 1..=3 {
+            let pr_route = format!("/repos/owner/repository/pulls/{}", pr_number);
+            canned_api = canned_api
+                .json(&pr_route, pull_request_json("owner", "repository", pr_number, "author"))
+                .diff(&pr_route, SINGLE_LINE_DIFF)
+                .json(&format!("/repos/owner/repository/issues/{}/comments", pr_number), json!([]))
+                .json(&format!("{}/comments", pr_route), json!([]))
+                .json(&format!("{}/reviews", pr_route), json!([]))
+                .json(
+                    &format!("{}/commits", pr_route),
+                    json!([commit_root("Add bar", Utc.ymd(2021, 3, 1).and_hms(9, 0, 0), 1)]),
+                )
+                .json(
+                    &format!("/repos/owner/repository/issues/{}/reactions", pr_number),
+                    json!([]),
+                );
+        }
+
+        // GitHub fails to serve the second PR's reviews, and only those
+        let base_url = fake_github_api(move |request: &str| {
+            if request.starts_with("GET /repos/owner/repository/pulls/2/reviews") {
+                return http_response(
+                    "500 Internal Server Error",
+                    &[],
+                    r#"{"message":"Server Error"}"#,
+                );
+            }
+            canned_api.respond(request)
+        })
+        .await;
+        let connection_pool = GitHubConnectionPool::new(
+            GitHubConnectionPoolManager::new("token", Some(base_url)),
+            BASE_REQUESTS_PER_PR as usize + 1,
+        );
+
+        let analyzer = AnalyzerBuilder::new("owner", "repository", "token", &connection_pool)
+            .retry_policy(RetryPolicy {
+                max_retries: 0,
+                empty_response_retries: 0,
+                ..Default::default()
+            })
+            .init()
+            .await
+            .unwrap();
+
+        let (result_sender, mut result_receiver) = mpsc::unbounded_channel();
+        analyzer.stream_repo_data(3, false, result_sender).await;
+
+        let mut results = Vec::new();
+        while let Some((_, result)) = result_receiver.recv().await {
+            results.push(result);
+        }
+
+        let mut retrieved_pr_numbers: Vec<u64> = results
+            .iter()
+            .filter_map(|result| result.as_ref().ok())
+            .map(|pr| pr.pr_number())
+            .collect();
+        retrieved_pr_numbers.sort_unstable();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(retrieved_pr_numbers, vec![1, 3]);
+        assert!(results.iter().any(|result| matches!(
+            result,
+            Err(AnalyzeError::PullRequestDataRetrievalError {
+                pr_number: 2,
+                ..
+            })
+        )));
+    }
+
+    #[tokio::test]
+    async fn panicked_fetch_is_reported_as_an_error_instead_of_bringing_everything_down() {
+        let panicking_comments_fetch: JoinHandle<Result<Vec<Comment>, AnalyzeError>> =
+            tokio::spawn(async { panic!("unexpected response") });
+
+        let result = Analyzer::join_fetch_task(panicking_comments_fetch).await;
+
+        assert!(matches!(result, Err(AnalyzeError::AsyncTaskError(_))));
+    }
+
+    #[tokio::test]
+    async fn slow_fetch_trips_the_timeout_and_fails_its_pull_request() {
+        let slow_fetch = async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok::<(), AnalyzeError>(())
+        };
+        let fast_fetch = async { Ok::<(), AnalyzeError>(()) };
+
+        let slow_result =
+            Analyzer::with_timeout(Duration::from_millis(10), "dummy-repo", 1, slow_fetch).await;
+        let fast_result =
+            Analyzer::with_timeout(Duration::from_millis(10), "dummy-repo", 2, fast_fetch).await;
+
+        assert!(matches!(
+            slow_result,
+            Err(AnalyzeError::PullRequestTimeoutError {
+                pr_number: 1,
+                ..
+            })
+        ));
+        assert!(fast_result.is_ok());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn no_more_tasks_than_permitted_run_at_once() {
+        let inflight_limit = Arc::new(Semaphore::new(2));
+        let inflight = Arc::new(AtomicUsize::new(0));
+        let max_inflight = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<JoinHandle<()>> = (0..10)
+            .map(|_| {
+                let inflight = inflight.clone();
+                let max_inflight = max_inflight.clone();
+
+                spawn_limited(inflight_limit.clone(), async move {
+                    let now_inflight = inflight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_inflight.fetch_max(now_inflight, Ordering::SeqCst);
+
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+
+                    inflight.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+
+        assert!(join_all(tasks).await.iter().all(Result::is_ok));
+        assert_eq!(max_inflight.load(Ordering::SeqCst), 2);
+        assert_eq!(inflight_limit.available_permits(), 2);
+    }
+
+    async fn fetch_diff(base_url: String, max_retries: u32) -> Result<String, AnalyzeError> {
+        let connection_pool =
+            GitHubConnectionPool::new(GitHubConnectionPoolManager::new("token", Some(base_url)), 1);
+        let retry_policy = RetryPolicy {
+            max_retries,
+            base_delay: Duration::from_millis(1),
+            ..Default::default()
+        };
+
+        Analyzer::get_pr_diff(
+            connection_pool.get().await.unwrap(),
+            String::from("owner"),
+            String::from("dummy-repo"),
+            42,
+            retry_policy,
+        )
+        .await
+    }
+
+    /// Answers like GitHub does for a diff beyond its size limits.
+    fn oversized_diff_api(_: &str) -> String {
+        let body = json!({
+            "message": "Sorry, the diff exceeded the maximum number of lines (20000)",
+            "errors": [{ "resource": "PullRequest", "field": "diff", "code": "too_large" }],
+            "documentation_url": "https://docs.github.com/rest/reference/pulls#get-a-pull-request"
+        });
+        http_response("406 Not Acceptable", &[], &body.to_string())
+    }
+
+    static FLAKY_DIFF_FAILS_NEXT: AtomicBool = AtomicBool::new(true);
+
+    /// Answers like GitHub does for a large diff while under load: failing with a `502 Bad Gateway`
+    /// every other time.
+    fn flaky_diff_api(_: &str) -> String {
+        if FLAKY_DIFF_FAILS_NEXT.fetch_xor(true, Ordering::SeqCst) {
+            http_response("502 Bad Gateway", &[], "<html>Unicorn!</html>")
+        } else {
+            http_response("200 OK", &[], SINGLE_LINE_DIFF)
+        }
+    }
+
+    #[tokio::test]
+    async fn oversized_diffs_are_told_apart_from_transient_failures() {
+        let oversized = fetch_diff(fake_github_api(oversized_diff_api).await, 3).await;
+
+        assert!(matches!(
+            oversized,
+            Err(AnalyzeError::DiffTooLarge { ref reason, .. }) if reason.contains("20000")
+        ));
+        assert_eq!(diff_too_large_reason(StatusCode::BAD_GATEWAY, "<html>Unicorn!</html>"), None);
+        assert_eq!(
+            diff_too_large_reason(StatusCode::NOT_ACCEPTABLE, r#"{ "message": "Not Acceptable" }"#),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn transient_diff_failures_are_retried() {
+        let base_url = fake_github_api(flaky_diff_api).await;
+
+        assert_eq!(fetch_diff(base_url.clone(), 1).await.unwrap(), SINGLE_LINE_DIFF);
+        assert!(matches!(fetch_diff(base_url, 0).await, Err(AnalyzeError::GitHubAPIError { .. })));
+    }
+
+    #[tokio::test]
+    async fn pull_requests_are_scored_end_to_end_out_of_canned_responses() {
+        let at = |hour: u32| Utc.ymd(2021, 3, 1).and_hms(hour, 0, 0);
+        let pr_route = "/repos/owner/repository/pulls/1";
+
+        let base_url = CannedGitHubApi::default()
+            .json("/orgs/owner/repos", json!([repository_json("owner", "repository")]))
+            .json(pr_route, pull_request_json("owner", "repository", 1, "author"))
+            .diff(pr_route, SINGLE_LINE_DIFF)
+            .json(
+                "/repos/owner/repository/issues/1/comments",
+                json!([comment("reviewer", "Looks good", at(12))]),
+            )
+            .json(
+                &format!("{}/comments", pr_route),
+                json!([commit_comment("reviewer", "src/foo.rs", at(12))]),
+            )
+            .json(
+                // GitHub used to serve dismissed reviews in a state that could not be parsed
+                &format!("{}/reviews", pr_route),
+                json!([
+                    review("reviewer", "APPROVED", Some(at(13))),
+                    review("former-reviewer", "DISMISSED", Some(at(14))),
+                ]),
+            )
+            .json(&format!("{}/commits", pr_route), json!([commit_root("Add bar", at(9), 1)]))
+            .json("/repos/owner/repository/issues/1/reactions", json!([reaction("reviewer", "+1")]))
+            .serve()
+            .await;
+
+        let score = init_against(base_url, "repository")
+            .await
+            .unwrap()
+            .retrieve_pr_data(1)
+            .await
+            .unwrap()
+            .get_score();
+
+        for expected in &[
+            ScoreType::AmountOfReviewers(2),
+            ScoreType::FilesChanged(1),
+            ScoreType::NumberOfCommits(1),
+            ScoreType::PullRequestSize(1),
+            ScoreType::ReactionCount(1),
+            ScoreType::TimeToMerge(1),
+        ] {
+            assert!(score.score_types().contains(expected), "missing {:?}", expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn failing_checks_on_either_api_keep_a_merge_from_being_green() {
+        let pr_route = "/repos/owner/repository/pulls/1";
+        let commit_route =
+            "/repos/owner/repository/commits/6dcb09b5b57875f334f61aebed695e2e4193db5e";
+
+        let base_url = CannedGitHubApi::default()
+            .json("/orgs/owner/repos", json!([repository_json("owner", "repository")]))
+            .json(pr_route, pull_request_json("owner", "repository", 1, "author"))
+            .diff(pr_route, SINGLE_LINE_DIFF)
+            .json("/repos/owner/repository/issues/1/comments", json!([]))
+            .json(&format!("{}/comments", pr_route), json!([]))
+            .json(&format!("{}/reviews", pr_route), json!([]))
+            .json(
+                &format!("{}/commits", pr_route),
+                json!([commit_root("Add bar", Utc.ymd(2021, 3, 1).and_hms(9, 0, 0), 1)]),
+            )
+            .json("/repos/owner/repository/issues/1/reactions", json!([]))
+            .json(
+                &format!("{}/status", commit_route),
+                json!({ "state": "success", "total_count": 1 }),
+            )
+            .json(
+                &format!("{}/check-runs", commit_route),
+                json!({ "total_count": 1, "check_runs": [
+                    { "name": "test", "status": "completed", "conclusion": "failure" }
+                ] }),
+            )
+            .serve()
+            .await;
+        // checks take two more connections on top of the PR's usual ones
+        let connection_pool = GitHubConnectionPool::new(
+            GitHubConnectionPoolManager::new("token", Some(base_url)),
+            BASE_REQUESTS_PER_PR as usize + 3,
+        );
+
+        let green_merge_ratio = |with_checks: bool| {
+            let connection_pool = connection_pool.clone();
+
+            async move {
+                AnalyzerBuilder::new("owner", "repository", "token", &connection_pool)
+                    .retrieval_settings(RetrievalSettings {
+                        with_checks,
+                        ..Default::default()
+                    })
+                    .init()
+                    .await
+                    .unwrap()
+                    .retrieve_pr_data(1)
+                    .await
+                    .unwrap()
+                    .get_score()
+                    .score_types()
+                    .iter()
+                    .find_map(|score_type| match score_type {
+                        ScoreType::GreenMergeRatio(value) => Some(*value),
+                        _ => None,
+                    })
+            }
+        };
+
+        assert_eq!(green_merge_ratio(true).await, Some(0.0));
+        assert_eq!(green_merge_ratio(false).await, None);
+    }
+
+    #[tokio::test]
+    async fn failed_timeline_fetch_leaves_the_time_in_draft_unknown() {
+        let pr_route = "/repos/owner/repository/pulls/1";
+        let canned_api = CannedGitHubApi::default()
+            .json("/orgs/owner/repos", json!([repository_json("owner", "repository")]))
+            .json(pr_route, pull_request_json("owner", "repository", 1, "author"))
+            .diff(pr_route, SINGLE_LINE_DIFF)
+            .json("/repos/owner/repository/issues/1/comments", json!([]))
+            .json(&format!("{}/comments", pr_route), json!([]))
+            .json(&format!("{}/reviews", pr_route), json!([]))
+            .json(
+                &format!("{}/commits", pr_route),
+                json!([commit_root("Add bar", Utc.ymd(2021, 3, 1).and_hms(9, 0, 0), 1)]),
+            )
+            .json("/repos/owner/repository/issues/1/reactions", json!([]));
+
+        let time_in_draft = |canned_api: CannedGitHubApi| async move {
+            // the timeline takes one more connection on top of the PR's usual ones
+            let connection_pool = GitHubConnectionPool::new(
+                GitHubConnectionPoolManager::new("token", Some(canned_api.serve().await)),
+                BASE_REQUESTS_PER_PR as usize + 2,
+            );
+
+            AnalyzerBuilder::new("owner", "repository", "token", &connection_pool)
+                .retry_policy(RetryPolicy {
+                    max_retries: 0,
+                    ..Default::default()
+                })
+                .retrieval_settings(RetrievalSettings {
+                    with_timeline: true,
+                    ..Default::default()
+                })
+                .init()
+                .await
+                .unwrap()
+                .retrieve_pr_data(1)
+                .await
+                .unwrap()
+                .get_time_in_draft()
+        };
+
+        // GitHub not serving the timeline at all (ie: a 404) is not the same as an empty timeline
+        assert_eq!(time_in_draft(canned_api.clone()).await, None);
+        assert_eq!(
+            time_in_draft(canned_api.json("/repos/owner/repository/issues/1/timeline", json!([])))
+                .await,
+            Some(0)
+        );
+    }
+
+    /// A PR just like [`pull_request_json`]'s, but closed without ever being merged.
+    fn abandoned_pull_request_json(pr_number: u64) -> serde_json::Value {
+        let mut pr = pull_request_json("owner", "repository", pr_number, "author");
+        pr["merged_at"] = serde_json::Value::Null;
+        pr
+    }
+
+    #[tokio::test]
+    async fn closed_unmerged_prs_are_scored_for_every_metric_but_merge_dependent_ones() {
+        let pr_route = "/repos/owner/repository/pulls/1";
+
+        let base_url = CannedGitHubApi::default()
+            .json("/orgs/owner/repos", json!([repository_json("owner", "repository")]))
+            .json(pr_route, abandoned_pull_request_json(1))
+            .diff(pr_route, SINGLE_LINE_DIFF)
+            .json("/repos/owner/repository/issues/1/comments", json!([]))
+            .json(&format!("{}/comments", pr_route), json!([]))
+            .json(&format!("{}/reviews", pr_route), json!([]))
+            .json(
+                &format!("{}/commits", pr_route),
+                json!([commit_root("Add bar", Utc.ymd(2021, 3, 1).and_hms(9, 0, 0), 1)]),
+            )
+            .json("/repos/owner/repository/issues/1/reactions", json!([]))
+            .serve()
+            .await;
+
+        let score = init_against(base_url, "repository")
+            .await
+            .unwrap()
+            .retrieve_pr_data(1)
+            .await
+            .unwrap()
+            .get_score();
+
+        assert!(score.score_types().contains(&ScoreType::PullRequestLeadTime(1)));
+        assert!(score.score_types().contains(&ScoreType::PullRequestSize(1)));
+        assert!(!has_metric(score.score_types(), ScoreType::TimeToMerge(0)));
+    }
+
+    #[tokio::test]
+    async fn closed_unmerged_prs_are_left_out_of_samples_unless_included() {
+        let base_url = CannedGitHubApi::default()
+            .json("/orgs/owner/repos", json!([repository_json("owner", "repository")]))
+            .json(
+                "/repos/owner/repository/pulls",
+                json!([
+                    pull_request_json("owner", "repository", 1, "author"),
+                    abandoned_pull_request_json(2),
+                ]),
+            )
+            .serve()
+            .await;
+        let connection_pool =
+            GitHubConnectionPool::new(GitHubConnectionPoolManager::new("token", Some(base_url)), 1);
+
+        let sampled_pr_numbers = |include_closed_unmerged: bool| {
+            let connection_pool = connection_pool.clone();
+
+            async move {
+                AnalyzerBuilder::new("owner", "repository", "token", &connection_pool)
+                    .scoring_settings(ScoringSettings {
+                        include_closed_unmerged,
+                        ..Default::default()
+                    })
+                    .init()
+                    .await
+                    .unwrap()
+                    // a sample bigger than the listing, so that it fits in a single (and last) page
+                    .list_repo_sample(3)
+                    .await
+                    .iter()
+                    .map(|pr| pr.number)
+                    .collect::<Vec<u64>>()
+            }
+        };
+
+        assert_eq!(sampled_pr_numbers(false).await, vec![1]);
+        assert_eq!(sampled_pr_numbers(true).await, vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn prs_not_being_retrieved_yet_are_skipped_once_interrupted() {
+        // only the listing is served; no PR gets as far as having its data retrieved
+        let base_url = CannedGitHubApi::default()
+            .json("/orgs/owner/repos", json!([repository_json("owner", "repository")]))
+            .json(
+                "/repos/owner/repository/pulls",
+                json!([
+                    pull_request_json("owner", "repository", 1, "author"),
+                    pull_request_json("owner", "repository", 2, "author"),
+                ]),
+            )
+            .serve()
+            .await;
+        let connection_pool =
+            GitHubConnectionPool::new(GitHubConnectionPoolManager::new("token", Some(base_url)), 1);
+
+        let interruption = Interruption::default();
+        let analyzer = AnalyzerBuilder::new("owner", "repository", "token", &connection_pool)
+            .interruption(interruption.clone())
+            .init()
+            .await
+            .unwrap();
+        interruption.raise();
+
+        let (result_sender, mut result_receiver) = mpsc::unbounded_channel();
+        analyzer.stream_repo_data(3, false, result_sender).await;
+
+        let mut skipped_pr_numbers: Vec<u64> = Vec::new();
+        while let Some((_, result)) = result_receiver.recv().await {
+            match result {
+                Err(AnalyzeError::PullRequestSkippedOnInterruption {
+                    pr_number,
+                    ..
+                }) => skipped_pr_numbers.push(pr_number),
+                _ => panic!("every PR should have been skipped upon interruption"),
+            }
+        }
+        skipped_pr_numbers.sort_unstable();
+
+        assert_eq!(skipped_pr_numbers, vec![1, 2]);
+    }
+
+    /// Streams a sample of a single PR (its timeline included) whose `rejected_route` GitHub's abuse
+    /// detection mechanisms reject the first time it is requested (and never again), under `abuse_policy`;
+    /// returns the PR's streamed result.
+    async fn stream_pr_rejected_once_for_abuse(
+        rejected_route: &'static str, abuse_policy: AbusePolicy,
+    ) -> PullRequestDataResult {
+        let pr_route = "/repos/owner/repository/pulls/1";
+        let canned_api = CannedGitHubApi::default()
+            .json("/orgs/owner/repos", json!([repository_json("owner", "repository")]))
+            .json(
+                "/repos/owner/repository/pulls",
+                json!([pull_request_json("owner", "repository", 1, "author")]),
+            )
+            .json(pr_route, pull_request_json("owner", "repository", 1, "author"))
+            .diff(pr_route, SINGLE_LINE_DIFF)
+            .json("/repos/owner/repository/issues/1/comments", json!([]))
+            .json(&format!("{}/comments", pr_route), json!([]))
+            .json(&format!("{}/reviews", pr_route), json!([]))
+            .json(
+                &format!("{}/commits", pr_route),
+                json!([commit_root("Add bar", Utc.ymd(2021, 3, 1).and_hms(9, 0, 0), 1)]),
+            )
+            .json("/repos/owner/repository/issues/1/reactions", json!([]))
+            .json("/repos/owner/repository/issues/1/timeline", json!([]));
+
+        let rejected_already = AtomicBool::new(false);
+        let base_url = fake_github_api(move |request: &str| {
+            let path = request.split_whitespace().nth(1).unwrap_or_default();
+            let asks_for_rejected_route = path.split('?').next() == Some(rejected_route);
+
+            if asks_for_rejected_route && !rejected_already.swap(true, Ordering::SeqCst) {
+                let body = json!({ "message": "You have triggered an abuse detection mechanism." });
+                return http_response(
+                    "403 Forbidden",
+                    &["retry-after: 60".to_string()],
+                    &body.to_string(),
+                );
+            }
+
+            canned_api.respond(request)
+        })
+        .await;
+        let connection_pool = GitHubConnectionPool::new(
+            GitHubConnectionPoolManager::new("token", Some(base_url)),
+            BASE_REQUESTS_PER_PR as usize + 1,
+        );
+
+        let analyzer = AnalyzerBuilder::new("owner", "repository", "token", &connection_pool)
+            .retry_policy(RetryPolicy {
+                max_retries: 0,
+                abuse_policy,
+                ..Default::default()
+            })
+            .retrieval_settings(RetrievalSettings {
+                with_timeline: true,
+                ..Default::default()
+            })
+            .init()
+            .await
+            .unwrap();
+
+        let (result_sender, mut result_receiver) = mpsc::unbounded_channel();
+        analyzer.stream_repo_data(3, false, result_sender).await;
+
+        let (_, result) = result_receiver.recv().await.unwrap();
+        assert!(result_receiver.recv().await.is_none());
+
+        result
+    }
+
+    #[tokio::test]
+    async fn prs_rejected_for_abuse_are_retrieved_again_only_if_retried() {
+        let reviews_route = "/repos/owner/repository/pulls/1/reviews";
+
+        let retried = stream_pr_rejected_once_for_abuse(reviews_route, AbusePolicy::Retry).await;
+        assert_eq!(retried.unwrap().pr_number(), 1);
+
+        let skipped = stream_pr_rejected_once_for_abuse(reviews_route, AbusePolicy::Skip).await;
+        assert!(matches!(skipped, Err(e) if e.is_rate_limited()));
+    }
+
+    #[tokio::test]
+    async fn nice_to_have_data_rejected_for_abuse_is_left_to_the_abuse_policy() {
+        // unlike any other failure to retrieve the timeline, this one is not merely downgraded to none
+        let timeline_route = "/repos/owner/repository/issues/1/timeline";
+
+        let retried = stream_pr_rejected_once_for_abuse(timeline_route, AbusePolicy::Retry).await;
+        assert_eq!(retried.unwrap().pr_number(), 1);
+
+        let skipped = stream_pr_rejected_once_for_abuse(timeline_route, AbusePolicy::Skip).await;
+        assert!(matches!(skipped, Err(e) if e.is_rate_limited()));
+    }
+
+    #[test]
+    fn requests_per_pr_follow_optional_data_and_retries() {
+        let without_retries = RetryPolicy {
+            max_retries: 0,
+            empty_response_retries: 0,
+            ..Default::default()
+        };
+        let requests_per_pr = |retrieval_settings, retry_policy| {
+            Analyzer::requests_per_pr(&retrieval_settings, &retry_policy)
+        };
+
+        assert_eq!(requests_per_pr(RetrievalSettings::default(), without_retries), 6);
+        assert_eq!(
+            requests_per_pr(
+                RetrievalSettings {
+                    with_threads: true,
+                    with_timeline: true,
+                    with_checks: true,
+                    ..Default::default()
+                },
+                without_retries
+            ),
+            6 + 1 + 1 + 2
+        );
+        // reviews, commit comments and commits may be re-requested once each; the diff retried thrice
+        assert_eq!(
+            requests_per_pr(RetrievalSettings::default(), RetryPolicy::default()),
+            6 + 3 + 3
+        );
+    }
+
+    #[test]
+    fn planned_requests_cover_listing_pages_and_every_pull_request() {
+        let retry_policy = RetryPolicy {
+            max_retries: 0,
+            empty_response_retries: 0,
+            ..Default::default()
+        };
+        let planned_requests = |sample_size, sample_strategy| {
+            let retrieval_settings = RetrievalSettings {
+                sample_strategy,
+                ..Default::default()
+            };
+            Analyzer::planned_requests(
+                sample_size,
+                &retrieval_settings,
+                &ScoringSettings::default(),
+                &retry_policy,
+            )
+        };
+
+        assert_eq!(planned_requests(1, SampleStrategy::Recent), 1 + 6);
+        assert_eq!(planned_requests(100, SampleStrategy::Recent), 1 + 600);
+        assert_eq!(planned_requests(101, SampleStrategy::Recent), 2 + 606);
+        // only the listing of candidates gets pricier
+        assert_eq!(planned_requests(100, SampleStrategy::Random), 5 + 600);
+
+        // repository-wide flows take up to as many pages per listing (openings and closures)
+        let with_repository_flow = Analyzer::planned_requests(
+            100,
+            &RetrievalSettings::default(),
+            &ScoringSettings {
+                flow_ratio_source: FlowRatioSource::Repository,
+                ..Default::default()
+            },
+            &retry_policy,
+        );
+        assert_eq!(with_repository_flow, 1 + 2 * MAX_FLOW_PAGES as u64 + 600);
+    }
+
+    #[tokio::test]
+    async fn repository_flows_spanning_too_many_pages_fall_back_to_the_sample_flow() {
+        // every page of PRs comes back full, as if the repository never ran out of them
+        let canned_api = CannedGitHubApi::default()
+            .json("/orgs/owner/repos", json!([repository_json("owner", "repository")]))
+            .json(
+                "/repos/owner/repository/pulls",
+                json!((1..=MAX_PAGE_SIZE as u64)
+                    .map(|pr_number| pull_request_json("owner", "repository", pr_number, "author"))
+                    .collect::<Vec<serde_json::Value>>()),
+            );
+        let listed_pages = Arc::new(AtomicUsize::new(0));
+        let listed_pages_by_api = listed_pages.clone();
+        let base_url = fake_github_api(move |request: &str| {
+            let path = request.split_whitespace().nth(1).unwrap_or_default();
+            if path.split('?').next() == Some("/repos/owner/repository/pulls") {
+                listed_pages_by_api.fetch_add(1, Ordering::SeqCst);
+            }
+
+            canned_api.respond(request)
+        })
+        .await;
+        let connection_pool =
+            GitHubConnectionPool::new(GitHubConnectionPoolManager::new("token", Some(base_url)), 1);
+
+        let analyzer = AnalyzerBuilder::new("owner", "repository", "token", &connection_pool)
+            .scoring_settings(ScoringSettings {
+                flow_ratio_source: FlowRatioSource::Repository,
+                ..Default::default()
+            })
+            .init()
+            .await
+            .unwrap();
+
+        // (listed PRs were all opened within the sample's time span)
+        let sample = vec![PullRequestDataFixture {
+            created_at: Utc.ymd(2021, 3, 1).and_hms(9, 0, 0),
+            merged_at: Some(Utc.ymd(2021, 3, 2).and_hms(9, 0, 0)),
+            closed_at: Some(Utc.ymd(2021, 3, 2).and_hms(9, 0, 0)),
+            ..Default::default()
+        }
+        .build()];
+        let flow = analyzer.retrieve_sample_flow(&sample).await;
+
+        assert_eq!(
+            flow,
+            PullRequestFlow::of_sample(&sample.iter().collect::<Vec<&PullRequestData>>())
+        );
+        // openings were given up on, so closures never got listed at all
+        assert_eq!(listed_pages.load(Ordering::SeqCst), MAX_FLOW_PAGES as usize);
+    }
+
+    #[tokio::test]
+    async fn repository_flows_are_not_retrieved_once_interrupted() {
+        let listed_pages = Arc::new(AtomicUsize::new(0));
+        let listed_pages_by_api = listed_pages.clone();
+        let canned_api = CannedGitHubApi::default()
+            .json("/orgs/owner/repos", json!([repository_json("owner", "repository")]))
+            .json("/repos/owner/repository/pulls", json!([]));
+        let base_url = fake_github_api(move |request: &str| {
+            let path = request.split_whitespace().nth(1).unwrap_or_default();
+            if path.split('?').next() == Some("/repos/owner/repository/pulls") {
+                listed_pages_by_api.fetch_add(1, Ordering::SeqCst);
+            }
+
+            canned_api.respond(request)
+        })
+        .await;
+        let connection_pool =
+            GitHubConnectionPool::new(GitHubConnectionPoolManager::new("token", Some(base_url)), 1);
+        let interruption = Interruption::default();
+
+        let analyzer = AnalyzerBuilder::new("owner", "repository", "token", &connection_pool)
+            .scoring_settings(ScoringSettings {
+                flow_ratio_source: FlowRatioSource::Repository,
+                ..Default::default()
+            })
+            .interruption(interruption.clone())
+            .init()
+            .await
+            .unwrap();
+        interruption.raise();
+
+        let sample = vec![PullRequestDataFixture::default().build()];
+        let flow = analyzer.retrieve_sample_flow(&sample).await;
+
+        assert_eq!(
+            flow,
+            PullRequestFlow::of_sample(&sample.iter().collect::<Vec<&PullRequestData>>())
+        );
+        assert_eq!(listed_pages.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn binary_files_are_parsed_as_hunkless_patched_files() {
+        let diff = format!(
+            "diff --git a/assets/logo.png b/assets/logo.png\n\
+            new file mode 100644\n\
+            index 0000000..1111111\n\
+            Binary files /dev/null and b/assets/logo.png differ\n{}",
+            SINGLE_LINE_DIFF
+        );
+
+        let patch_set = Analyzer::parse_diff("dummy-repo", 1, &diff).unwrap();
+
+        assert_eq!(patch_set.files().len(), 2);
+        assert_eq!(patch_set.files()[0].path(), "assets/logo.png");
+        assert!(patch_set.files()[0].hunks().is_empty());
+        assert_eq!(patch_set.files()[1].hunks().len(), 1);
+    }
+
+    #[test]
+    fn binary_files_whose_paths_contain_and_are_parsed_whole() {
+        let diff = "diff --git a/assets/rock and roll.png b/assets/rock and roll.png\n\
+            index 1111111..2222222 100644\n\
+            Binary files a/assets/rock and roll.png and b/assets/rock and roll.png differ\n\
+            diff --git a/assets/black and white.png b/assets/black and white.png\n\
+            deleted file mode 100644\n\
+            index 1111111..0000000\n\
+            Binary files a/assets/black and white.png and /dev/null differ\n";
+
+        let patch_set = Analyzer::parse_diff("dummy-repo", 1, diff).unwrap();
+
+        assert_eq!(patch_set.files().len(), 2);
+        assert_eq!(patch_set.files()[0].source_file, "a/assets/rock and roll.png");
+        assert_eq!(patch_set.files()[0].target_file, "b/assets/rock and roll.png");
+        assert_eq!(patch_set.files()[1].source_file, "a/assets/black and white.png");
+        assert_eq!(patch_set.files()[1].target_file, "/dev/null");
+    }
+}
@@ -0,0 +1,142 @@
+//! On-disk dump of every raw GitHub response that could not be parsed, so that deserialization bugs
+//! (see the story told in [`Analyzer`](crate::github::utils::analyzer::Analyzer)'s reviews fetch) can be
+//! diagnosed - and reported - without having to enable trace-level logging.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use log::{info, trace, warn};
+
+/// A directory into which raw responses that failed JSON parsing get written, one file per request.
+/// <br/><br/>
+/// Just like the [`PullRequestCache`](crate::github::utils::pull_request_cache::PullRequestCache), the
+/// dump is strictly best-effort: responses that cannot be written are logged, never turned into errors.
+#[derive(Debug, Clone)]
+pub struct FailureDump {
+    dump_dir: PathBuf,
+}
+
+impl FailureDump {
+    /// Creates a dump rooted at `dump_dir`; which is created on first use, if missing.
+    pub fn new(dump_dir: &Path) -> Self {
+        FailureDump {
+            dump_dir: dump_dir.to_path_buf(),
+        }
+    }
+
+    /// Writes the `raw_response_text` served for `source` (usually, the request's URL), replacing any
+    /// previous dump of the very same `source`. Each page of a paginated `source` is dumped on its own.
+    pub fn store(&self, source: &str, raw_response_text: &str) {
+        let dump_path = self.dump_path(source);
+
+        let write_result = fs::create_dir_all(&self.dump_dir)
+            .and_then(|_| fs::write(&dump_path, raw_response_text));
+
+        match write_result {
+            Ok(_) => info!("Unparseable response dumped into [{}].", dump_path.display()),
+            Err(e) => {
+                warn!("Could not dump unparseable response into [{}].", dump_path.display());
+                trace!("Error = {:?}", e);
+            }
+        }
+    }
+
+    fn dump_path(&self, source: &str) -> PathBuf {
+        // the scheme and host are the same for every request, so they are left out of the file's name
+        let route = source.find("://").map_or(source, |i| &source[i + 3..]);
+        let route = route.find('/').map_or(route, |i| &route[i + 1..]);
+        // and so is the query; which may well carry credentials (ie: an `access_token`) that must never
+        // end up in a file name meant to be attached to bug reports. Save for the page, since every page
+        // of a listing shares its route
+        let (route, query) = match route.find(&['?', '#'][..]) {
+            Some(i) => (&route[..i], route[i + 1..].split('#').next().unwrap_or_default()),
+            None => (route, ""),
+        };
+        let page = query
+            .split('&')
+            .filter_map(|param| param.strip_prefix("page="))
+            .find(|page| !page.is_empty() && page.chars().all(|c| c.is_ascii_digit()));
+
+        let file_name: String = route
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        let file_name = match page {
+            Some(page) => format!("{}_page-{}", file_name.trim_matches('_'), page),
+            None => file_name.trim_matches('_').to_string(),
+        };
+
+        self.dump_dir.join(format!("{}.json", file_name))
+    }
+}
+
+#[cfg(test)]
+mod failure_dump_tests {
+    use std::fs;
+
+    use crate::github::utils::failure_dump::FailureDump;
+
+    #[test]
+    fn responses_are_dumped_into_one_file_per_request() {
+        let dump_dir =
+            std::env::temp_dir().join(format!("prolice-failures-{}", std::process::id()));
+        let failure_dump = FailureDump::new(&dump_dir);
+
+        failure_dump.store(
+            "https://api.github.com/repos/owner/repo/pulls/42/reviews",
+            r#"[{ "state": "DISMISSED" }]"#,
+        );
+
+        let dumped = fs::read_to_string(dump_dir.join("repos_owner_repo_pulls_42_reviews.json"));
+        assert_eq!(dumped.unwrap(), r#"[{ "state": "DISMISSED" }]"#);
+
+        fs::remove_dir_all(&dump_dir).unwrap();
+    }
+
+    #[test]
+    fn credentials_in_the_query_never_make_it_into_the_dump_path() {
+        let failure_dump = FailureDump::new(&std::env::temp_dir().join("prolice-failures"));
+
+        let dump_path = failure_dump.dump_path(
+            "https://api.github.com/search/repositories?q=user:owner&access_token=ghp_s3cr3t#top",
+        );
+
+        assert!(!dump_path.to_string_lossy().contains("s3cr3t"));
+        assert_eq!(dump_path.file_name().unwrap(), "search_repositories.json");
+    }
+
+    #[test]
+    fn every_page_of_a_listing_is_dumped_on_its_own() {
+        let failure_dump = FailureDump::new(&std::env::temp_dir().join("prolice-failures"));
+        let dump_name = |source: &str| {
+            failure_dump.dump_path(source).file_name().unwrap().to_string_lossy().into_owned()
+        };
+
+        assert_eq!(
+            dump_name("https://api.github.com/repos/owner/repo/pulls?state=closed&per_page=100"),
+            "repos_owner_repo_pulls.json"
+        );
+        assert_eq!(
+            dump_name("https://api.github.com/repos/owner/repo/pulls?state=closed&page=2"),
+            "repos_owner_repo_pulls_page-2.json"
+        );
+        assert_eq!(
+            dump_name(
+                "https://api.github.com/repos/owner/repo/pulls/42/commits?page=3&per_page=100"
+            ),
+            "repos_owner_repo_pulls_42_commits_page-3.json"
+        );
+        // pages that are not numbers cannot tell anything apart, but may well carry anything else
+        assert_eq!(
+            dump_name("https://api.github.com/repos/owner/repo/pulls?page=ghp_s3cr3t"),
+            "repos_owner_repo_pulls.json"
+        );
+    }
+}
@@ -0,0 +1,313 @@
+//! A fake GitHub API (and the JSON it answers with) to run analyses against, offline. Test-only; and
+//! self-contained, so that integration tests can share it too (see `tests/analyze_repo.rs`).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Returns a GitHub user's JSON representation for the given `login`.
+pub fn user_json(login: &str) -> serde_json::Value {
+    json!({
+        "login": login,
+        "id": 1,
+        "node_id": "MDQ6VXNlcjE=",
+        "avatar_url": "https://github.com/images/error/octocat_happy.gif",
+        "gravatar_id": "",
+        "url": format!("https://api.github.com/users/{}", login),
+        "html_url": format!("https://github.com/{}", login),
+        "followers_url": format!("https://api.github.com/users/{}/followers", login),
+        "following_url": format!("https://api.github.com/users/{}/following", login),
+        "gists_url": format!("https://api.github.com/users/{}/gists", login),
+        "starred_url": format!("https://api.github.com/users/{}/starred", login),
+        "subscriptions_url": format!("https://api.github.com/users/{}/subscriptions", login),
+        "organizations_url": format!("https://api.github.com/users/{}/orgs", login),
+        "repos_url": format!("https://api.github.com/users/{}/repos", login),
+        "events_url": format!("https://api.github.com/users/{}/events", login),
+        "received_events_url": format!("https://api.github.com/users/{}/received_events", login),
+        "type": "User",
+        "site_admin": false
+    })
+}
+
+/// Returns a GitHub repository's JSON representation for the given `name`, owned by `owner`.
+pub fn repository_json(owner: &str, name: &str) -> serde_json::Value {
+    let api_url = format!("https://api.github.com/repos/{}/{}", owner, name);
+    let mut repository = json!({
+        "id": 1296269,
+        "node_id": "MDEwOlJlcG9zaXRvcnkxMjk2MjY5",
+        "name": name,
+        "full_name": format!("{}/{}", owner, name),
+        "owner": user_json(owner),
+        "html_url": format!("https://github.com/{}/{}", owner, name),
+        "fork": false,
+        "url": api_url,
+    });
+
+    for resource in &[
+        "archive",
+        "assignees",
+        "blobs",
+        "branches",
+        "collaborators",
+        "comments",
+        "commits",
+        "compare",
+        "contents",
+        "contributors",
+        "deployments",
+        "downloads",
+        "events",
+        "forks",
+        "git_commits",
+        "git_refs",
+        "git_tags",
+        "issue_comment",
+        "issue_events",
+        "issues",
+        "keys",
+        "labels",
+        "languages",
+        "merges",
+        "milestones",
+        "notifications",
+        "pulls",
+        "releases",
+        "stargazers",
+        "statuses",
+        "subscribers",
+        "subscription",
+        "tags",
+        "teams",
+        "trees",
+        "hooks",
+    ] {
+        repository[format!("{}_url", resource)] = json!(format!("{}/{}", api_url, resource));
+    }
+
+    repository
+}
+
+/// Returns the JSON representation of `owner`'s `repository`'s PR number `pr_number`, opened by `author`;
+/// whose resources' URLs point back at a [`CannedGitHubApi`] (see [`BASE_URL_PLACEHOLDER`]).
+pub fn pull_request_json(
+    owner: &str, repository: &str, pr_number: u64, author: &str,
+) -> serde_json::Value {
+    let repository_url = format!("{}/repos/{}/{}", BASE_URL_PLACEHOLDER, owner, repository);
+    let pr_url = format!("{}/pulls/{}", repository_url, pr_number);
+    let issue_url = format!("{}/issues/{}", repository_url, pr_number);
+    let sha = "6dcb09b5b57875f334f61aebed695e2e4193db5e";
+    let branch = json!({
+        "label": format!("{}:feature", owner),
+        "ref": "feature",
+        "sha": sha,
+        "user": user_json(owner),
+    });
+
+    json!({
+        "url": pr_url,
+        "id": pr_number,
+        "node_id": "MDExOlB1bGxSZXF1ZXN0MQ==",
+        "html_url": format!("https://github.com/{}/{}/pull/{}", owner, repository, pr_number),
+        "diff_url": format!("https://github.com/{}/{}/pull/{}.diff", owner, repository, pr_number),
+        "patch_url": format!("https://github.com/{}/{}/pull/{}.patch", owner, repository, pr_number),
+        "issue_url": issue_url,
+        "commits_url": format!("{}/commits", pr_url),
+        "review_comments_url": format!("{}/comments", pr_url),
+        "review_comment_url": format!("{}/comments{{/number}}", pr_url),
+        "comments_url": format!("{}/comments", issue_url),
+        "statuses_url": format!("{}/statuses/{}", repository_url, sha),
+        "number": pr_number,
+        "state": "closed",
+        "title": "Dummy title",
+        "user": user_json(author),
+        "body": "Dummy body",
+        "created_at": "2021-03-01T10:00:00Z",
+        "closed_at": "2021-03-02T10:00:00Z",
+        "merged_at": "2021-03-02T10:00:00Z",
+        "assignees": [],
+        "requested_reviewers": [],
+        "requested_teams": [],
+        "rebaseable": null,
+        "head": branch,
+        "base": branch,
+        "draft": false
+    })
+}
+
+/// Returns the JSON representation of a commit with the given `message`, authored at `date` and with
+/// `parents` parent commits (more than one makes it a merge commit).
+pub fn commit_json(message: &str, date: DateTime<Utc>, parents: usize) -> serde_json::Value {
+    let git_actor = json!({
+        "name": "Dummy Author",
+        "email": "dummy-author@example.com",
+        "date": date,
+    });
+
+    json!({
+        "sha": "6dcb09b5b57875f334f61aebed695e2e4193db5e",
+        "node_id": "MDY6Q29tbWl0NmRjYjA5YjViNTc4NzVmMzM0ZjYxYWViZWQ2OTVlMmU0MTkzZGI1ZQ==",
+        "url": "https://api.github.com/repos/octocat/Hello-World/commits/6dcb09b5b57875f334f61aebed695e2e4193db5e",
+        "html_url": "https://github.com/octocat/Hello-World/commit/6dcb09b5b57875f334f61aebed695e2e4193db5e",
+        "comments_url": "https://api.github.com/repos/octocat/Hello-World/commits/6dcb09b5b57875f334f61aebed695e2e4193db5e/comments",
+        "commit": {
+            "author": git_actor,
+            "committer": git_actor,
+            "message": message,
+            "tree": {
+                "sha": "6dcb09b5b57875f334f61aebed695e2e4193db5e",
+                "url": "https://api.github.com/repos/octocat/Hello-World/tree/6dcb09b5b57875f334f61aebed695e2e4193db5e"
+            },
+            "url": "https://api.github.com/repos/octocat/Hello-World/git/commits/6dcb09b5b57875f334f61aebed695e2e4193db5e",
+            "comment_count": 0,
+            "verification": {
+                "verified": false,
+                "reason": "unsigned",
+                "signature": null,
+                "payload": null
+            }
+        },
+        "author": user_json("dummy-author"),
+        "committer": user_json("dummy-author"),
+        "parents": (0..parents)
+            .map(|_| json!({
+                "sha": "6dcb09b5b57875f334f61aebed695e2e4193db5e",
+                "url": "https://api.github.com/repos/octocat/Hello-World/commits/6dcb09b5b57875f334f61aebed695e2e4193db5e",
+                "html_url": "https://github.com/octocat/Hello-World/commit/6dcb09b5b57875f334f61aebed695e2e4193db5e"
+            }))
+            .collect::<Vec<serde_json::Value>>()
+    })
+}
+
+/// A unified diff adding a single line to a single (non-test) file.
+pub const SINGLE_LINE_DIFF: &str = "diff --git a/src/foo.rs b/src/foo.rs
+index 1111111..2222222 100644
+--- a/src/foo.rs
++++ b/src/foo.rs
+@@ -1,2 +1,3 @@
+ fn foo() {}
++fn bar() {}
+ fn baz() {}
+";
+
+/// Anything that can stand in for GitHub's API, answering whatever requests reach a [`fake_github_api`].
+/// Every fetch an analysis makes goes through its connection pool's base URL, so pointing that at a
+/// [`fake_github_api`] is all it takes to run it offline.
+pub trait GitHubApiResponder: Send + Sync + 'static {
+    /// Answers a raw `request` (ie: `GET /search/repositories?q=user:owner HTTP/1.1\r\nhost: ...`) with a
+    /// raw HTTP response (see [`http_response`]).
+    fn respond(&self, request: &str) -> String;
+}
+
+impl<F> GitHubApiResponder for F
+where
+    F: Fn(&str) -> String + Send + Sync + 'static,
+{
+    fn respond(&self, request: &str) -> String {
+        self(request)
+    }
+}
+
+/// Serves a fake GitHub API that answers every request through the `responder`; returns its base URL.
+pub async fn fake_github_api(responder: impl GitHubApiResponder) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let responder = Arc::new(responder);
+
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let responder = responder.clone();
+
+            tokio::spawn(async move {
+                let mut request = [0; 4096];
+                let read = socket.read(&mut request).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&request[..read]);
+
+                let _ = socket.write_all(responder.respond(&request).as_bytes()).await;
+            });
+        }
+    });
+
+    format!("http://{}/", address)
+}
+
+/// Canned answers for a [`fake_github_api`], keyed by route (ie: `/repos/owner/repository/pulls/1/reviews`,
+/// its query left out). Routes without an answer get a `404 Not Found`.
+/// <br/><br/>
+/// Some answers need to point back at the fake API itself (ie: a PR's `commits_url`); every occurrence
+/// of [`BASE_URL_PLACEHOLDER`] in them gets replaced with its actual base URL.
+#[derive(Debug, Clone, Default)]
+pub struct CannedGitHubApi {
+    json_responses: HashMap<String, String>,
+    diff_responses: HashMap<String, String>,
+}
+
+/// Stands for a [`CannedGitHubApi`]'s base URL (sans trailing slash) in its answers.
+pub const BASE_URL_PLACEHOLDER: &str = "{base_url}";
+
+impl CannedGitHubApi {
+    /// Answers requests for `route` with the JSON `body`.
+    pub fn json(mut self, route: &str, body: serde_json::Value) -> Self {
+        self.json_responses.insert(route.to_string(), body.to_string());
+        self
+    }
+
+    /// Answers requests for `route` that ask for a diff (through their `Accept` header) with `diff`; the
+    /// same route's JSON answer is still served to every other request.
+    pub fn diff(mut self, route: &str, diff: &str) -> Self {
+        self.diff_responses.insert(route.to_string(), diff.to_string());
+        self
+    }
+
+    /// Serves these canned answers through a [`fake_github_api`]; returns its base URL.
+    pub async fn serve(self) -> String {
+        fake_github_api(self).await
+    }
+}
+
+impl GitHubApiResponder for CannedGitHubApi {
+    fn respond(&self, request: &str) -> String {
+        let path = request.split_whitespace().nth(1).unwrap_or_default();
+        let route = path.split('?').next().unwrap_or_default();
+        let host = request
+            .lines()
+            .find_map(|header| header.strip_prefix("host: "))
+            .unwrap_or_default()
+            .trim();
+        let asks_for_diff = request.lines().any(|header| {
+            header.to_ascii_lowercase().starts_with("accept: ") && header.ends_with(".diff")
+        });
+
+        let body = if asks_for_diff {
+            self.diff_responses.get(route)
+        } else {
+            self.json_responses.get(route)
+        };
+
+        match body {
+            Some(body) => http_response(
+                "200 OK",
+                &[],
+                &body.replace(BASE_URL_PLACEHOLDER, &format!("http://{}", host)),
+            ),
+            None => http_response("404 Not Found", &[], r#"{"message":"Not Found"}"#),
+        }
+    }
+}
+
+/// Builds a raw HTTP response out of its `status_line` (ie: `200 OK`), extra `headers` and `body`.
+pub fn http_response(status_line: &str, headers: &[String], body: &str) -> String {
+    let mut response = format!("HTTP/1.1 {}\r\n", status_line);
+    for header in headers {
+        response.push_str(&format!("{}\r\n", header));
+    }
+    response.push_str(&format!(
+        "content-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    ));
+    response
+}
@@ -0,0 +1,262 @@
+//! Test-only helpers for crafting [`PullRequestData`]s (and the GitHub structures they wrap) without
+//! having to go through GitHub's API.
+
+use std::mem::discriminant;
+use std::sync::Arc;
+
+use chrono::{DateTime, TimeZone, Utc};
+use octocrab::models::issues::Comment;
+use serde_json::json;
+use unidiff::PatchSet;
+
+use crate::github::json::commit::CommitRoot;
+use crate::github::json::commit_checks::ChecksOutcome;
+use crate::github::json::commit_comment::CommitComment;
+use crate::github::json::reaction::Reaction;
+use crate::github::json::review::Review;
+use crate::github::json::review_thread::ReviewThread;
+use crate::github::json::timeline_event::TimelineEvent;
+use crate::github::utils::pull_request_data::PullRequestData;
+use crate::scoring::score::ScoreType;
+use crate::scoring::scoring_settings::ScoringSettings;
+
+// GitHub's answers are crafted alongside the structures they stand for
+pub use crate::github::utils::fake_github_api::{
+    commit_json, fake_github_api, http_response, pull_request_json, repository_json, user_json,
+    CannedGitHubApi, GitHubApiResponder, SINGLE_LINE_DIFF,
+};
+
+/// A [`PullRequestData`] in the making. Every field starts with a sensible dummy value, so tests only
+/// need to override whatever is relevant to them.
+pub struct PullRequestDataFixture {
+    pub repo_name: String,
+    pub pr_number: u64,
+    pub pr_author: String,
+    pub pr_title: String,
+    pub main_message: Option<String>,
+    pub draft: bool,
+    pub comments: Vec<Comment>,
+    pub commit_comments: Vec<CommitComment>,
+    pub commits: Vec<CommitRoot>,
+    pub reviews: Vec<Review>,
+    pub reactions: Vec<Reaction>,
+    pub review_threads: Option<Vec<ReviewThread>>,
+    pub timeline: Option<Vec<TimelineEvent>>,
+    pub checks: Option<ChecksOutcome>,
+    pub patch_set: PatchSet,
+    pub created_at: DateTime<Utc>,
+    pub merged_at: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub scoring_settings: ScoringSettings,
+}
+
+impl Default for PullRequestDataFixture {
+    fn default() -> Self {
+        PullRequestDataFixture {
+            repo_name: String::from("dummy-repo"),
+            pr_number: 1,
+            pr_author: String::from("dummy-author"),
+            pr_title: String::from("Dummy title"),
+            main_message: Some(String::new()),
+            draft: false,
+            comments: Vec::new(),
+            commit_comments: Vec::new(),
+            commits: Vec::new(),
+            reviews: Vec::new(),
+            reactions: Vec::new(),
+            review_threads: None,
+            timeline: None,
+            checks: None,
+            patch_set: PatchSet::new(),
+            created_at: Utc.ymd(2021, 3, 1).and_hms(10, 0, 0),
+            merged_at: Some(Utc.ymd(2021, 3, 2).and_hms(10, 0, 0)),
+            closed_at: Some(Utc.ymd(2021, 3, 2).and_hms(10, 0, 0)),
+            scoring_settings: ScoringSettings::default(),
+        }
+    }
+}
+
+impl PullRequestDataFixture {
+    /// Returns a fixture for a single-line PR holding a single commit, authored an hour before the PR was
+    /// opened; the bare minimum for its every per-PR metric to be scored.
+    pub fn scorable() -> Self {
+        PullRequestDataFixture {
+            commits: vec![commit_root("Dummy commit", Utc.ymd(2021, 3, 1).and_hms(9, 0, 0), 1)],
+            patch_set: patch_set(SINGLE_LINE_DIFF),
+            ..Default::default()
+        }
+    }
+
+    pub fn build(self) -> PullRequestData {
+        PullRequestData::new(
+            &self.repo_name,
+            self.pr_number,
+            &self.pr_author,
+            &self.pr_title,
+            self.main_message.as_deref(),
+            self.draft,
+            self.comments,
+            self.commit_comments,
+            self.commits,
+            self.reviews,
+            self.reactions,
+            self.review_threads,
+            self.timeline,
+            self.checks,
+            self.patch_set,
+            self.created_at,
+            self.merged_at,
+            self.closed_at,
+            Arc::new(self.scoring_settings),
+        )
+    }
+}
+
+/// Returns a single-line PR holding `amount_of_commits` commits, authored an hour apart from one another
+/// starting at midnight of its creation day.
+pub fn pr_with_commits(amount_of_commits: u32) -> PullRequestData {
+    PullRequestDataFixture {
+        commits: (0..amount_of_commits)
+            .map(|i| {
+                commit_root(
+                    &format!("Dummy commit #{}", i),
+                    Utc.ymd(2021, 3, 1).and_hms(i, 0, 0),
+                    1,
+                )
+            })
+            .collect(),
+        patch_set: patch_set(SINGLE_LINE_DIFF),
+        ..Default::default()
+    }
+    .build()
+}
+
+/// Determines whether the given `score_types` hold any value for the given `metric`, whatever it is (ie:
+/// `ScoreType::TimeToMerge(0)` stands for every TimeToMerge).
+pub fn has_metric(score_types: &[ScoreType], metric: ScoreType) -> bool {
+    score_types.iter().any(|score_type| discriminant(score_type) == discriminant(&metric))
+}
+
+/// Returns a [`CommitRoot`] with the given `message`, authored at `date` and with `parents` parent commits
+/// (more than one makes it a merge commit).
+pub fn commit_root(message: &str, date: DateTime<Utc>, parents: usize) -> CommitRoot {
+    serde_json::from_value(commit_json(message, date, parents)).unwrap()
+}
+
+/// Returns a [`Comment`] by `login`, with the given `body` and posted at `created_at`.
+pub fn comment(login: &str, body: &str, created_at: DateTime<Utc>) -> Comment {
+    serde_json::from_value(json!({
+        "id": 1,
+        "node_id": "MDEyOklzc3VlQ29tbWVudDE=",
+        "url": "https://api.github.com/repos/octocat/Hello-World/issues/comments/1",
+        "html_url": "https://github.com/octocat/Hello-World/issues/1347#issuecomment-1",
+        "body": body,
+        "user": user_json(login),
+        "created_at": created_at,
+        "updated_at": created_at,
+    }))
+    .unwrap()
+}
+
+/// Returns a [`CommitComment`] by `login` on the given file's `path`, posted at `created_at`.
+pub fn commit_comment(login: &str, path: &str, created_at: DateTime<Utc>) -> CommitComment {
+    serde_json::from_value(json!({
+        "url": "https://api.github.com/repos/octocat/Hello-World/pulls/comments/1",
+        "pull_request_review_id": 42,
+        "id": 10,
+        "node_id": "MDI0OlB1bGxSZXF1ZXN0UmV2aWV3Q29tbWVudDEw",
+        "diff_hunk": "@@ -1,1 +1,2 @@",
+        "path": path,
+        "position": 1,
+        "original_position": 1,
+        "commit_id": "6dcb09b5b57875f334f61aebed695e2e4193db5e",
+        "original_commit_id": "9c48853fa3dc5c1c3d6f1f1cd1f2743e72652840",
+        "user": user_json(login),
+        "body": "Dummy commit comment",
+        "created_at": created_at,
+        "updated_at": created_at,
+        "html_url": "https://github.com/octocat/Hello-World/pull/1#discussion-diff-1",
+        "pull_request_url": "https://api.github.com/repos/octocat/Hello-World/pulls/1",
+        "author_association": "MEMBER",
+        "_links": {
+            "self": { "href": "https://api.github.com/repos/octocat/Hello-World/pulls/comments/1" },
+            "html": { "href": "https://github.com/octocat/Hello-World/pull/1#discussion-diff-1" },
+            "pull_request": { "href": "https://api.github.com/repos/octocat/Hello-World/pulls/1" }
+        },
+        "start_line": null,
+        "original_start_line": null,
+        "start_side": null,
+        "line": 2,
+        "original_line": 2,
+        "side": "RIGHT",
+    }))
+    .unwrap()
+}
+
+/// Returns a [`Review`] by `login`, in the given `state` (ie: "APPROVED") and submitted at `submitted_at`
+/// (pending reviews have not been submitted yet).
+pub fn review(login: &str, state: &str, submitted_at: Option<DateTime<Utc>>) -> Review {
+    serde_json::from_value(json!({
+        "id": 80,
+        "node_id": "MDE3OlB1bGxSZXF1ZXN0UmV2aWV3ODA=",
+        "html_url": "https://github.com/octocat/Hello-World/pull/12#pullrequestreview-80",
+        "user": user_json(login),
+        "body": "Dummy review",
+        "state": state,
+        "submitted_at": submitted_at,
+    }))
+    .unwrap()
+}
+
+/// Returns a [`Reaction`] by `login`, with the given `content` (ie: "+1").
+pub fn reaction(login: &str, content: &str) -> Reaction {
+    serde_json::from_value(json!({
+        "id": 1,
+        "node_id": "MDg6UmVhY3Rpb24x",
+        "user": user_json(login),
+        "content": content,
+        "created_at": "2021-03-01T10:00:00Z",
+    }))
+    .unwrap()
+}
+
+/// Returns a [`TimelineEvent`] of the given kind (ie: "ready_for_review"), created at `created_at`.
+pub fn timeline_event(event: &str, created_at: DateTime<Utc>) -> TimelineEvent {
+    TimelineEvent {
+        event: event.to_string(),
+        created_at: Some(created_at),
+    }
+}
+
+/// A unified diff touching three files: one added, one modified and one removed.
+pub const THREE_FILE_DIFF: &str = "diff --git a/src/bar.rs b/src/bar.rs
+new file mode 100644
+index 0000000..1111111
+--- /dev/null
++++ b/src/bar.rs
+@@ -0,0 +1,2 @@
++fn bar() {}
++fn qux() {}
+diff --git a/src/foo.rs b/src/foo.rs
+index 1111111..2222222 100644
+--- a/src/foo.rs
++++ b/src/foo.rs
+@@ -1,2 +1,3 @@
+ fn foo() {}
++fn bar() {}
+ fn baz() {}
+diff --git a/tests/old_test.rs b/tests/old_test.rs
+deleted file mode 100644
+index 3333333..0000000
+--- a/tests/old_test.rs
++++ /dev/null
+@@ -1 +0,0 @@
+-fn old_test() {}
+";
+
+/// Returns a [`PatchSet`] parsed from the given unified `diff`.
+pub fn patch_set(diff: &str) -> PatchSet {
+    let mut patch_set = PatchSet::new();
+    patch_set.parse(diff).unwrap();
+    patch_set
+}
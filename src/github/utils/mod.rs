@@ -1,7 +1,21 @@
 //! [`PullRequest`](octocrab::models::pulls::PullRequest) wrapping & analyzing utilities.
 
+pub mod failure_dump;
+
+pub mod pull_request_cache;
+
 pub mod pull_request_data;
 
+pub mod pull_request_url;
+
 pub mod analyzer;
 
 pub mod repository_data;
+
+pub mod retrieval_settings;
+
+#[cfg(test)]
+pub mod fake_github_api;
+
+#[cfg(test)]
+pub mod fixtures;
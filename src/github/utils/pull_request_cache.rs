@@ -0,0 +1,229 @@
+//! On-disk cache for the raw data fetched for every [`PullRequest`](octocrab::models::pulls::PullRequest),
+//! so that re-analyzing the same PRs (ie: while iterating on metric definitions) does not need to hit
+//! GitHub's API - and burn its rate limit - all over again.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use log::{trace, warn};
+use octocrab::models::issues::Comment;
+use octocrab::models::pulls::PullRequest;
+use serde::{Deserialize, Serialize};
+
+use crate::github::json::commit::CommitRoot;
+use crate::github::json::commit_checks::ChecksOutcome;
+use crate::github::json::commit_comment::CommitComment;
+use crate::github::json::reaction::Reaction;
+use crate::github::json::review::Review;
+use crate::github::json::review_thread::ReviewThread;
+use crate::github::json::timeline_event::TimelineEvent;
+use crate::github::utils::retrieval_settings::RetrievalSettings;
+
+/// Every data structure fetched from GitHub for a single PR, before any processing takes place.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RawPullRequestData {
+    pub comments: Vec<Comment>,
+    pub commit_comments: Vec<CommitComment>,
+    pub reviews: Vec<Review>,
+    pub commits: Vec<CommitRoot>,
+    /// Entries cached before reactions were fetched at all are loaded without any.
+    #[serde(default)]
+    pub reactions: Vec<Reaction>,
+    /// Only fetched on demand; [`None`] if they were not (or could not be).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review_threads: Option<Vec<ReviewThread>>,
+    /// Only fetched on demand (and only its draft transitions are kept); [`None`] if it was not (or could
+    /// not be).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeline: Option<Vec<TimelineEvent>>,
+    /// Only fetched on demand (and only their overall outcome is kept); [`None`] if they were not (or could
+    /// not be).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checks: Option<ChecksOutcome>,
+    /// The PR's unified diff, as raw text; it gets re-parsed on every use.
+    pub diff: String,
+}
+
+impl RawPullRequestData {
+    /// Returns whether this data holds everything that the `retrieval_settings` ask for; which a cached
+    /// entry may not (ie: if it was cached before its review threads were requested at all).
+    pub fn covers(&self, retrieval_settings: &RetrievalSettings) -> bool {
+        (!retrieval_settings.with_threads || self.review_threads.is_some())
+            && (!retrieval_settings.with_timeline || self.timeline.is_some())
+            && (!retrieval_settings.with_checks || self.checks.is_some())
+    }
+}
+
+/// A directory-backed cache of [`RawPullRequestData`], keyed by `owner/repo/pr_number`.
+/// <br/><br/>
+/// The cache is strictly best-effort: entries that cannot be read (or written) are logged and treated as
+/// cache misses, never as analysis errors.
+/// <br/><br/>
+/// Only settled PRs are worth caching at all (see [`PullRequestCache::is_cacheable`]); open PRs keep
+/// changing, and their cached data would soon go stale.
+#[derive(Debug, Clone)]
+pub struct PullRequestCache {
+    cache_dir: PathBuf,
+    refresh: bool,
+}
+
+impl PullRequestCache {
+    /// Creates a cache rooted at `cache_dir`. When `refresh` is `true`, existing entries are ignored (and
+    /// overwritten with freshly fetched data).
+    pub fn new(cache_dir: &Path, refresh: bool) -> Self {
+        PullRequestCache {
+            cache_dir: cache_dir.to_path_buf(),
+            refresh,
+        }
+    }
+
+    /// Returns whether the `pr`'s data is worth caching (and reading back from the cache); which is only the
+    /// case once it got closed (or merged). Should it ever get reopened, its cached data is ignored until
+    /// it settles again, and then overwritten.
+    pub fn is_cacheable(pr: &PullRequest) -> bool {
+        pr.closed_at.is_some()
+    }
+
+    /// Retrieves the cached entry for `owner/repo_name/pr_number`, if any.
+    pub fn load(&self, owner: &str, repo_name: &str, pr_number: u64) -> Option<RawPullRequestData> {
+        if self.refresh {
+            return None;
+        }
+
+        let entry_path = self.entry_path(owner, repo_name, pr_number);
+        let raw_entry = fs::read_to_string(&entry_path).ok()?;
+
+        serde_json::from_str(&raw_entry)
+            .map_err(|e| {
+                warn!(
+                    "Ignoring unreadable cache entry [{}]; it will be fetched again.",
+                    entry_path.display()
+                );
+                trace!("Error = {:?}", e);
+            })
+            .ok()
+    }
+
+    /// Stores `raw_data` as the cached entry for `owner/repo_name/pr_number`, replacing any previous one.
+    pub fn store(
+        &self, owner: &str, repo_name: &str, pr_number: u64, raw_data: &RawPullRequestData,
+    ) {
+        let entry_path = self.entry_path(owner, repo_name, pr_number);
+
+        let write_result =
+            serde_json::to_string(raw_data).map_err(|e| e.to_string()).and_then(|raw_entry| {
+                entry_path
+                    .parent()
+                    .map_or(Ok(()), fs::create_dir_all)
+                    .and_then(|_| fs::write(&entry_path, raw_entry))
+                    .map_err(|e| e.to_string())
+            });
+
+        if let Err(e) = write_result {
+            warn!("Could not write cache entry [{}].", entry_path.display());
+            trace!("Error = {:?}", e);
+        }
+    }
+
+    fn entry_path(&self, owner: &str, repo_name: &str, pr_number: u64) -> PathBuf {
+        // GitHub treats owners and repositories case-insensitively, so should the cache
+        self.cache_dir
+            .join(owner.to_lowercase())
+            .join(repo_name.to_lowercase())
+            .join(format!("{}.json", pr_number))
+    }
+}
+
+#[cfg(test)]
+mod pull_request_cache_tests {
+    use std::fs;
+
+    use chrono::{TimeZone, Utc};
+
+    use octocrab::models::pulls::PullRequest;
+    use serde_json::json;
+
+    use crate::github::utils::fixtures::{
+        commit_root, pull_request_json, review, SINGLE_LINE_DIFF,
+    };
+    use crate::github::utils::pull_request_cache::{PullRequestCache, RawPullRequestData};
+    use crate::github::utils::retrieval_settings::RetrievalSettings;
+
+    fn raw_data() -> RawPullRequestData {
+        RawPullRequestData {
+            comments: Vec::new(),
+            commit_comments: Vec::new(),
+            reviews: vec![review(
+                "reviewer",
+                "APPROVED",
+                Some(Utc.ymd(2021, 3, 1).and_hms(10, 0, 0)),
+            )],
+            commits: vec![commit_root("Initial commit", Utc.ymd(2021, 3, 1).and_hms(9, 0, 0), 1)],
+            reactions: Vec::new(),
+            review_threads: None,
+            timeline: None,
+            checks: None,
+            diff: String::from(SINGLE_LINE_DIFF),
+        }
+    }
+
+    #[test]
+    fn stored_entries_are_loaded_back_unless_refreshing() {
+        let cache_dir = std::env::temp_dir().join(format!("prolice-cache-{}", std::process::id()));
+        let cache = PullRequestCache::new(&cache_dir, false);
+
+        assert!(cache.load("Owner", "Repo", 42).is_none());
+
+        cache.store("Owner", "Repo", 42, &raw_data());
+
+        let cached = cache.load("owner", "repo", 42).unwrap();
+        assert_eq!(cached.reviews, raw_data().reviews);
+        assert_eq!(cached.commits, raw_data().commits);
+        assert_eq!(cached.diff, SINGLE_LINE_DIFF);
+
+        assert!(PullRequestCache::new(&cache_dir, true).load("owner", "repo", 42).is_none());
+
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[test]
+    fn entries_only_cover_the_on_demand_data_they_hold() {
+        let without_timeline = raw_data();
+        let with_timeline = RawPullRequestData {
+            timeline: Some(Vec::new()),
+            ..raw_data()
+        };
+        let asking_for_timeline = RetrievalSettings {
+            with_timeline: true,
+            ..Default::default()
+        };
+
+        assert!(without_timeline.covers(&RetrievalSettings::default()));
+        assert!(!without_timeline.covers(&asking_for_timeline));
+        assert!(with_timeline.covers(&asking_for_timeline));
+        assert!(!with_timeline.covers(&RetrievalSettings {
+            with_checks: true,
+            ..asking_for_timeline
+        }));
+    }
+
+    /// Deserializes a [`PullRequest`] out of its `json`, as served by github.com.
+    fn pull_request(json: serde_json::Value) -> PullRequest {
+        serde_json::from_str(&json.to_string().replace("{base_url}", "https://api.github.com"))
+            .unwrap()
+    }
+
+    #[test]
+    fn only_settled_pull_requests_are_cacheable() {
+        let settled = pull_request(pull_request_json("owner", "repo", 1, "author"));
+
+        let mut open_json = pull_request_json("owner", "repo", 2, "author");
+        open_json["state"] = json!("open");
+        open_json["closed_at"] = json!(null);
+        open_json["merged_at"] = json!(null);
+        let open = pull_request(open_json);
+
+        assert!(PullRequestCache::is_cacheable(&settled));
+        assert!(!PullRequestCache::is_cacheable(&open));
+    }
+}
@@ -1,20 +1,35 @@
 //! Container for all relevant information for a particular [`PullRequest`](octocrab::models::pulls::PullRequest).
 
+use std::sync::Arc;
+
 use chrono::{DateTime, Utc};
 use itertools::Itertools;
 use lazy_static::lazy_static;
-use log::{debug, error, trace};
+use log::{debug, trace, warn};
 use octocrab::models::issues::Comment;
 use regex::Regex;
 use unidiff::Hunk;
 use unidiff::PatchSet;
 
+use crate::error::AnalyzeError;
 use crate::github::json::commit::CommitRoot;
+use crate::github::json::commit_checks::ChecksOutcome;
 use crate::github::json::commit_comment::CommitComment;
-use crate::github::json::review::Review;
-use crate::prolice_error::AnalyzeError;
+use crate::github::json::reaction::Reaction;
+use crate::github::json::review::{Review, ReviewState};
+use crate::github::json::review_thread::ReviewThread;
+use crate::github::json::timeline_event::{TimelineEvent, READY_FOR_REVIEW_EVENT};
 use crate::scoring::scorable::Scorable;
-use crate::scoring::score::{Score, ScoreType};
+use crate::scoring::score::{Score, ScoreFlag, ScoreType};
+use crate::scoring::scoring_settings::{MergePrHeuristic, MergeStyle, ScoringSettings};
+
+/// How soon after being opened an unreviewed PR must get merged to be deemed self-merged. See
+/// [`PullRequestData::is_likely_self_merged`].
+const SELF_MERGE_MAX_LEAD_TIME_MINUTES: i64 = 60;
+
+/// How long a commit message's subject line must be to be deemed descriptive. See
+/// [`PullRequestData::get_commit_message_quality`].
+const MIN_COMMIT_SUBJECT_LENGTH: usize = 15;
 
 /// A wrapper for an already-analyzed [`PullRequest`](octocrab::models::pulls::PullRequest). It contains
 /// all proper structures in order to retrieve useful metrics.
@@ -23,38 +38,53 @@ pub struct PullRequestData {
     pr_number: u64,
     pr_author: String,
     pr_title: String,
-    main_message: String,
+    main_message: Option<String>,
+    draft: bool,
     comments: Vec<Comment>,
     commit_comments: Vec<CommitComment>,
     commits: Vec<CommitRoot>,
     reviews: Vec<Review>,
+    reactions: Vec<Reaction>,
+    review_threads: Option<Vec<ReviewThread>>,
+    timeline: Option<Vec<TimelineEvent>>,
+    checks: Option<ChecksOutcome>,
     patch_set: PatchSet,
     created_at: DateTime<Utc>,
-    merged_at: DateTime<Utc>,
-    closed_at: DateTime<Utc>,
+    merged_at: Option<DateTime<Utc>>,
+    closed_at: Option<DateTime<Utc>>,
+    scoring_settings: Arc<ScoringSettings>,
 }
 
 impl PullRequestData {
     pub fn new(
-        repo_name: &str, pr_number: u64, pr_author: &str, pr_title: &str, main_message: &str,
-        comments: Vec<Comment>, commit_comments: Vec<CommitComment>, commits: Vec<CommitRoot>,
-        reviews: Vec<Review>, patch_set: PatchSet, created_at: DateTime<Utc>,
-        merged_at: DateTime<Utc>, closed_at: DateTime<Utc>,
+        repo_name: &str, pr_number: u64, pr_author: &str, pr_title: &str,
+        main_message: Option<&str>, draft: bool, comments: Vec<Comment>,
+        commit_comments: Vec<CommitComment>, commits: Vec<CommitRoot>, reviews: Vec<Review>,
+        reactions: Vec<Reaction>, review_threads: Option<Vec<ReviewThread>>,
+        timeline: Option<Vec<TimelineEvent>>, checks: Option<ChecksOutcome>, patch_set: PatchSet,
+        created_at: DateTime<Utc>, merged_at: Option<DateTime<Utc>>,
+        closed_at: Option<DateTime<Utc>>, scoring_settings: Arc<ScoringSettings>,
     ) -> Self {
         PullRequestData {
             repo_name: repo_name.to_string(),
             pr_number,
             pr_author: pr_author.to_string(),
             pr_title: pr_title.to_string(),
-            main_message: main_message.to_string(),
+            main_message: main_message.map(String::from),
+            draft,
             comments,
             commit_comments,
             commits,
             reviews,
+            reactions,
+            review_threads,
+            timeline,
+            checks,
             patch_set,
             created_at,
             merged_at,
             closed_at,
+            scoring_settings,
         }
     }
 
@@ -70,8 +100,11 @@ impl PullRequestData {
     pub fn pr_title(&self) -> &str {
         &self.pr_title
     }
-    pub fn main_message(&self) -> &str {
-        &self.main_message
+    pub fn main_message(&self) -> Option<&str> {
+        self.main_message.as_deref()
+    }
+    pub fn draft(&self) -> bool {
+        self.draft
     }
     pub fn comments(&self) -> &Vec<Comment> {
         &self.comments
@@ -85,15 +118,30 @@ impl PullRequestData {
     pub fn reviews(&self) -> &Vec<Review> {
         &self.reviews
     }
+    pub fn reactions(&self) -> &Vec<Reaction> {
+        &self.reactions
+    }
+    pub fn review_threads(&self) -> Option<&Vec<ReviewThread>> {
+        self.review_threads.as_ref()
+    }
+    pub fn timeline(&self) -> Option<&Vec<TimelineEvent>> {
+        self.timeline.as_ref()
+    }
+    pub fn checks(&self) -> Option<ChecksOutcome> {
+        self.checks
+    }
     pub fn patch_set(&self) -> &PatchSet {
         &self.patch_set
     }
     pub fn created_at(&self) -> DateTime<Utc> {
         self.created_at
     }
-    pub fn closed_at(&self) -> DateTime<Utc> {
+    pub fn closed_at(&self) -> Option<DateTime<Utc>> {
         self.closed_at
     }
+    pub fn scoring_settings(&self) -> &ScoringSettings {
+        &self.scoring_settings
+    }
 }
 
 impl PullRequestData {
@@ -101,12 +149,14 @@ impl PullRequestData {
     ///
     /// If result would be negative (because there were more deletions than additions), returned amount
     /// is effectively zero.
+    /// Excluded files (see [`ScoringSettings::is_excluded_file`]) are left out.
     pub fn get_amount_of_net_added_test_lines(&self) -> usize {
         self.patch_set
             .added_files()
             .iter()
             .chain(self.patch_set.modified_files().iter())
-            .filter(|patched_file| PullRequestData::is_test_file(&patched_file.target_file))
+            .filter(|patched_file| self.scoring_settings.is_test_file(&patched_file.target_file))
+            .filter(|patched_file| !self.scoring_settings.is_excluded_file(&patched_file.path()))
             .flat_map(|patched_file| {
                 trace!(
                     "[test-lines] Analyzing {} -> {} ...",
@@ -123,12 +173,14 @@ impl PullRequestData {
     ///
     /// If result would be negative (because there were more deletions than additions), returned amount
     /// is effectively zero.
+    /// Excluded files (see [`ScoringSettings::is_excluded_file`]) are left out.
     pub fn get_amount_of_net_added_non_test_lines(&self) -> usize {
         self.patch_set
             .added_files()
             .iter()
             .chain(self.patch_set.modified_files().iter())
-            .filter(|patched_file| !PullRequestData::is_test_file(&patched_file.target_file))
+            .filter(|patched_file| !self.scoring_settings.is_test_file(&patched_file.target_file))
+            .filter(|patched_file| !self.scoring_settings.is_excluded_file(&patched_file.path()))
             .flat_map(|patched_file| {
                 trace!(
                     "[non-test-lines] Analyzing {} -> {} ...",
@@ -142,10 +194,34 @@ impl PullRequestData {
     }
 
     /// Returns the amount of modified lines, irrespective of whether they were additions or deletions.
+    /// Excluded files (see [`ScoringSettings::is_excluded_file`]) are left out.
     pub fn get_amount_of_changes(&self) -> usize {
+        self.get_changed_hunks().map(|hunk| hunk.added() + hunk.removed()).sum()
+    }
+
+    /// Returns the ratio of modified lines that were deletions; or `0.0` if the PR modified no lines at
+    /// all. Excluded files (see [`ScoringSettings::is_excluded_file`]) are left out.
+    pub fn get_deletion_ratio(&self) -> f64 {
+        let (added, removed) = self.get_changed_hunks().fold((0, 0), |(added, removed), hunk| {
+            (added + hunk.added(), removed + hunk.removed())
+        });
+
+        if added + removed == 0 {
+            return 0.0;
+        }
+
+        f64::trunc((removed as f64 / (added + removed) as f64) * 100.0) / 100.0 // 2 decimals
+    }
+
+    /// Returns every [`Hunk`] in the PR's diff. Excluded files (see [`ScoringSettings::is_excluded_file`])
+    /// are left out.
+    fn get_changed_hunks(&self) -> impl Iterator<Item = &Hunk> {
         self.patch_set
             .files()
             .iter()
+            .filter(move |patched_file| {
+                !self.scoring_settings.is_excluded_file(&patched_file.path())
+            })
             .flat_map(|patched_file| {
                 trace!(
                     "[changes] Analyzing {} -> {} ...",
@@ -154,8 +230,40 @@ impl PullRequestData {
                 );
                 patched_file.hunks().iter()
             })
-            .map(|hunk| hunk.added() + hunk.removed())
-            .sum()
+    }
+
+    /// Returns the amount of binary files touched by the PR; which, carrying no lines, are told apart
+    /// by their lack of hunks.
+    pub fn get_amount_of_binary_files_changed(&self) -> usize {
+        self.patch_set.files().iter().filter(|patched_file| patched_file.hunks().is_empty()).count()
+    }
+
+    /// Returns the amount of comments (the PR's body included) carrying any escalation marker (see
+    /// [`ContentionWords`](crate::scoring::scoring_settings::ContentionWords)); or [`None`] unless contention
+    /// words were supplied. Fenced code blocks are ignored, so that - say - all-caps SQL does not count as
+    /// shouting.
+    pub fn get_contentious_discussion(&self) -> Option<u64> {
+        let contention_words = self.scoring_settings.contention_words.as_ref()?;
+
+        Some(
+            self.get_all_commentary()
+                .into_iter()
+                .filter(|commentary| {
+                    contention_words.is_contentious(&strip_fenced_code_blocks(commentary))
+                })
+                .count() as u64,
+        )
+    }
+
+    /// Returns the amount of characters in the PR's description (its body); zero if it has none.
+    pub fn get_description_length(&self) -> usize {
+        self.main_message.iter().map(|main_message| main_message.chars().count()).sum()
+    }
+
+    /// Determines whether the PR lacks a description (its body) altogether; as opposed to having an
+    /// empty one.
+    pub fn is_description_missing(&self) -> bool {
+        self.main_message.is_none()
     }
 
     /// Returns all comments posted by the PR's author.
@@ -165,13 +273,14 @@ impl PullRequestData {
     /// other; since no matter the intent behind it, it is still part of a PR's discussion, and it counts
     /// towards enriching (or polluting with noise) its overall quality.
     pub fn get_author_commentary(&self) -> Vec<&String> {
-        trace!("PR body: {}", self.main_message);
-        let author_main_pr_message: Vec<&String> = vec![&self.main_message];
+        trace!("PR body: {:?}", self.main_message);
+        // a missing body adds nothing to the commentary, just like an empty one
+        let author_main_pr_message: Vec<&String> = self.main_message.iter().collect();
 
         let author_comments: Vec<&String> = self
             .comments
             .iter()
-            .filter(|comment| comment.user.login == self.pr_author)
+            .filter(|comment| self.is_pr_author(&comment.user.login))
             .filter_map(|comment| comment.body.as_ref())
             .collect();
         trace!("Comments from [{}]: {:?}", self.pr_author, author_comments);
@@ -179,7 +288,7 @@ impl PullRequestData {
         let author_commit_comments: Vec<&String> = self
             .commit_comments
             .iter()
-            .filter(|commit_comment| commit_comment.user.login == self.pr_author)
+            .filter(|commit_comment| self.is_pr_author(&commit_comment.user.login))
             .map(|commit_comment| &commit_comment.body)
             .collect();
         trace!("Commit-comments from [{}]: {:?}", self.pr_author, author_commit_comments);
@@ -187,7 +296,7 @@ impl PullRequestData {
         let author_reviews: Vec<&String> = self
             .reviews
             .iter()
-            .filter(|review| review.user.login == self.pr_author)
+            .filter(|review| self.is_pr_author(&review.user.login))
             .filter_map(|review| review.body.as_ref())
             .collect();
         trace!("Reviews from [{}]: {:?}", self.pr_author, author_reviews);
@@ -203,8 +312,8 @@ impl PullRequestData {
 
     /// Returns all comments irrespective of their author(s).
     pub fn get_all_commentary(&self) -> Vec<&String> {
-        trace!("PR body: {}", self.main_message);
-        let main_pr_message: Vec<&String> = vec![&self.main_message];
+        trace!("PR body: {:?}", self.main_message);
+        let main_pr_message: Vec<&String> = self.main_message.iter().collect();
 
         let comments: Vec<&String> =
             self.comments.iter().filter_map(|comment| comment.body.as_ref()).collect();
@@ -237,41 +346,96 @@ impl PullRequestData {
         self.get_all_commentary().iter().map(|s| s.len()).sum()
     }
 
+    /// Determines whether this PR's commits were squashed; in which case its first commit dates back to
+    /// when its work got squashed rather than to when it started.
+    /// <br/><br/>
+    /// **Note:** The outcome depends on the [`MergeStyle`] selected in this PR's [`ScoringSettings`].
+    pub fn is_squashed(&self) -> bool {
+        match self.scoring_settings.merge_style {
+            MergeStyle::Auto => {
+                self.commits.len() == 1
+                    && matches!(self.get_first_commit_date(), Some(first_commit_at) if first_commit_at >= self.created_at)
+            }
+            MergeStyle::Squash => true,
+            MergeStyle::Merge => false,
+        }
+    }
+
     /// Determines whether this [`PullRequestData`] corresponds to a merge PR or not.
     /// Merge PRs are those that are basically used to update branches between environments (ie: merging
     /// the 'develop' branch into the 'master' branch).
     /// <br/><br/>
-    /// **Note:** This implementation is quite 'naive' and depends on proper naming conventions (aka
-    /// the PR's title must start with 'Merge'... - ie: "Merge develop into QA").
+    /// **Note:** The outcome depends on the [`MergePrHeuristic`] selected in this PR's [`ScoringSettings`].
     pub fn is_merge_pr(&self) -> bool {
-        self.pr_title.to_ascii_lowercase().starts_with("merge")
+        match self.scoring_settings.merge_pr_heuristic {
+            MergePrHeuristic::TitlePrefix => self.has_merge_title(),
+            MergePrHeuristic::MultipleParents => self.has_merge_head_commit(),
+            MergePrHeuristic::Both => self.has_merge_title() && self.has_merge_head_commit(),
+        }
+    }
+
+    /// Determines whether the PR's title starts with 'Merge'... - ie: "Merge develop into QA".
+    fn has_merge_title(&self) -> bool {
+        has_merge_title(&self.pr_title)
     }
 
-    /// Returns all the non-author participants of the [`PullRequest`](octocrab::models::pulls::PullRequest).
-    pub fn get_non_authoring_participants(&self) -> Vec<&String> {
+    /// Determines whether the PR's head (last) commit is a merge commit - or whether the PR carries no
+    /// net changes at all.
+    fn has_merge_head_commit(&self) -> bool {
+        let head_commit_has_multiple_parents =
+            self.commits.last().into_iter().any(|commit| commit.parents.len() > 1);
+
+        head_commit_has_multiple_parents || self.patch_set.is_empty()
+    }
+
+    /// Returns all the non-author participants of the [`PullRequest`](octocrab::models::pulls::PullRequest),
+    /// sorted by login (so that reports are reproducible across runs). GitHub logins are case-insensitive,
+    /// so they are told apart (and listed) lowercased. If the team's members are known (see
+    /// [`ScoringSettings::team_members`]), outside participants are left out.
+    pub fn get_non_authoring_participants(&self) -> Vec<String> {
         self.comments
             .iter()
             .map(|comment| &comment.user.login)
             .chain(self.reviews.iter().map(|review| &review.user.login))
             .chain(self.commit_comments.iter().map(|commit_comments| &commit_comments.user.login))
+            .map(|user| user.to_lowercase())
             .unique()
-            .filter(|user| user != &&self.pr_author)
+            .filter(|user| !self.is_pr_author(user))
+            .filter(|user| self.scoring_settings.is_team_member(user))
+            .sorted()
             .collect()
     }
 
-    /// Returns all the non-author reviewers of the [`PullRequest`](octocrab::models::pulls::PullRequest).
+    /// Returns all the reviewers of the [`PullRequest`](octocrab::models::pulls::PullRequest), sorted by
+    /// (lowercased) login. The PR's author is only among them if self-reviews are counted (see
+    /// [`ScoringSettings::count_self_reviews`]); and, just like participants, reviewers from outside the
+    /// team are left out if its members are known.
     /// <br/><br/>
-    /// This can be considered a smaller subset of the [`PullRequestData::get_non_authoring_participants()`]
-    /// universe.
-    pub fn get_non_authoring_reviewers(&self) -> Vec<&String> {
+    /// Save for self-reviews, this can be considered a smaller subset of the
+    /// [`PullRequestData::get_non_authoring_participants()`] universe.
+    pub fn get_reviewers(&self) -> Vec<String> {
         self.reviews
             .iter()
-            .map(|comment| &comment.user.login)
+            .filter(|review| self.is_counted_review(review))
+            .map(|review| review.user.login.to_lowercase())
+            .filter(|user| self.scoring_settings.is_team_member(user))
             .unique()
-            .filter(|user| user != &&self.pr_author)
+            .sorted()
             .collect()
     }
 
+    /// Determines whether a `review` counts as such for reviewer-centric metrics: those by anyone other
+    /// than the PR's author always do, while the author's own (self-reviews) only do if
+    /// [`ScoringSettings::count_self_reviews`] says so.
+    fn is_counted_review(&self, review: &Review) -> bool {
+        self.scoring_settings.count_self_reviews || !self.is_pr_author(&review.user.login)
+    }
+
+    /// Determines whether `login` is the PR's author's; ignoring case, just like GitHub does.
+    fn is_pr_author(&self, login: &str) -> bool {
+        login.eq_ignore_ascii_case(&self.pr_author)
+    }
+
     /// Returns all attachments posted by the PR's author.
     pub fn get_attachments_markdown(&self) -> Vec<String> {
         lazy_static! {
@@ -288,32 +452,397 @@ impl PullRequestData {
             .collect()
     }
 
-    /// Returns the [`PullRequest`](octocrab::models::pulls::PullRequest)'s first commit's [`DateTime`].
-    pub fn get_first_commit_date(&self) -> DateTime<Utc> {
+    /// Returns the ratio of checked-off items (`- [x]`) among all markdown task-list items (`- [ ]`)
+    /// found in the PR's body and its author's comments; or `0.0` if there are none. Items inside fenced
+    /// code blocks are ignored.
+    pub fn get_checklist_completion(&self) -> f64 {
+        lazy_static! {
+            static ref TASK_LIST_ITEM_REGEX: Regex =
+                Regex::new(r"(?m)^\s*[-*+]\s+\[([ xX])\]\s").unwrap();
+        }
+
+        let (checked_items, total_items) = self
+            .get_author_commentary()
+            .into_iter()
+            .map(|commentary| strip_fenced_code_blocks(commentary))
+            .flat_map(|commentary| {
+                TASK_LIST_ITEM_REGEX
+                    .captures_iter(&commentary)
+                    .map(|captures| !captures[1].trim().is_empty())
+                    .collect::<Vec<bool>>()
+            })
+            .fold((0, 0), |(checked_items, total_items), is_checked| {
+                (checked_items + is_checked as u64, total_items + 1)
+            });
+
+        if total_items == 0 {
+            return 0.0;
+        }
+
+        checked_items as f64 / total_items as f64
+    }
+
+    /// Returns the (deduplicated) issues that the PR's author declared the PR closes, by means of GitHub's
+    /// closing keywords - ie: "closes #123", "fixes owner/repo#456" or
+    /// "resolves https://github.com/owner/repo/issues/789". Mentions inside fenced code blocks are ignored.
+    /// <br/><br/>
+    /// Issues are told apart by their repository as well as by their number, since the same number may
+    /// well stand for a different issue in every repository. Bare references (ie: "#123") are the PR's
+    /// own repository's; which does not know its owner, so they are kept apart from full references.
+    pub fn get_linked_issues(&self) -> Vec<LinkedIssue> {
+        lazy_static! {
+            static ref LINKED_ISSUE_REGEX: Regex = Regex::new(
+                r"(?i)\b(?:close[sd]?|fix(?:e[sd])?|resolve[sd]?):?\s+(?:https?://[^/\s]+/(?P<url_owner>[\w.-]+)/(?P<url_repo>[\w.-]+)/issues/|(?P<owner>[\w.-]+)/(?P<repo>[\w.-]+)#|#)(?P<number>\d+)\b"
+            )
+            .unwrap();
+        }
+
+        self.get_author_commentary()
+            .into_iter()
+            .map(|commentary| strip_fenced_code_blocks(commentary))
+            .flat_map(|commentary| {
+                LINKED_ISSUE_REGEX
+                    .captures_iter(&commentary)
+                    .filter_map(|captures| {
+                        // GitHub's owners and repositories are case-insensitive
+                        let captured = |names: [&str; 2]| {
+                            names
+                                .iter()
+                                .find_map(|name| captures.name(name))
+                                .map(|capture| capture.as_str().to_lowercase())
+                        };
+
+                        Some(LinkedIssue {
+                            owner: captured(["url_owner", "owner"]),
+                            repo: captured(["url_repo", "repo"]),
+                            number: captures["number"].parse::<u64>().ok()?,
+                        })
+                    })
+                    .collect::<Vec<LinkedIssue>>()
+            })
+            .unique()
+            .collect()
+    }
+
+    /// Returns the ratio of the PR's commits whose message is descriptive: a subject line at least
+    /// [`MIN_COMMIT_SUBJECT_LENGTH`] characters long, followed by a body. Merge commits are left out, since
+    /// their messages are usually auto-generated; [`None`] is returned if no other commit is left.
+    pub fn get_commit_message_quality(&self) -> Option<f64> {
+        let (descriptive_commits, total_commits) = self
+            .commits
+            .iter()
+            .filter(|commit| !is_merge_commit(commit))
+            .fold((0, 0), |(descriptive_commits, total_commits), commit| {
+                let is_descriptive = is_descriptive_commit_message(&commit.commit.message);
+                (descriptive_commits + is_descriptive as u64, total_commits + 1)
+            });
+
+        if total_commits == 0 {
+            return None;
+        }
+
+        Some(f64::trunc((descriptive_commits as f64 / total_commits as f64) * 100.0) / 100.0)
+        // 2 decimals
+    }
+
+    /// Returns the ratio of the PR's activity (commits, comments, commit comments and submitted reviews,
+    /// regardless of who they came from) that falls outside of the team's
+    /// [`WorkingHours`](crate::scoring::scoring_settings::WorkingHours); or [`None`] if there was no
+    /// activity at all.
+    pub fn get_after_hours_activity_ratio(&self) -> Option<f64> {
+        let activity_dates: Vec<DateTime<Utc>> = self
+            .commits
+            .iter()
+            .map(|commit| commit.commit.author.date)
+            .chain(self.comments.iter().map(|comment| comment.created_at))
+            .chain(self.commit_comments.iter().map(|commit_comment| commit_comment.created_at))
+            .chain(self.reviews.iter().filter_map(|review| review.submitted_at))
+            .collect();
+
+        if activity_dates.is_empty() {
+            return None;
+        }
+
+        let after_hours_activity =
+            activity_dates.iter().filter(|date| self.scoring_settings.is_after_hours(date)).count();
+
+        Some(
+            f64::trunc((after_hours_activity as f64 / activity_dates.len() as f64) * 100.0) / 100.0,
+        ) // 2 decimals
+    }
+
+    /// Returns the amount of the PR's commits authored by someone other than the PR's author. Commits whose
+    /// author GitHub could not match to any of its users (ie: unknown e-mail addresses) are left out, since
+    /// there is no telling who they belong to.
+    pub fn get_authorship_mismatch(&self) -> u64 {
         self.commits
-            .get(0)
-            .unwrap_or_else(|| {
-                error!(
-                    "Could not retrieve first commit for PR [{}]/[{}]. Aborting operation.",
-                    self.repo_name, self.pr_number
-                );
-                panic!() // this is a fatal error that involves delving into the codebase (because a PR should be guaranteed at least a single commit).
+            .iter()
+            .filter_map(|commit| commit.author.as_ref())
+            .filter(|author| !self.is_pr_author(&author.login))
+            .count() as u64
+    }
+
+    /// Returns the ratio of the PR's commits whose signature GitHub could verify; or [`None`] if it carries
+    /// no commits at all.
+    pub fn get_signed_commit_ratio(&self) -> Option<f64> {
+        if self.commits.is_empty() {
+            return None;
+        }
+
+        let signed_commits =
+            self.commits.iter().filter(|commit| commit.commit.verification.verified).count();
+
+        Some(f64::trunc((signed_commits as f64 / self.commits.len() as f64) * 100.0) / 100.0)
+        // 2 decimals
+    }
+
+    /// Returns the ratio of the PR's changed files that got at least one inline comment from someone other
+    /// than its author; or `0.0` if the PR changed no files at all.
+    pub fn get_reviewed_file_coverage(&self) -> f64 {
+        let changed_files: Vec<String> =
+            self.patch_set.files().iter().map(|file| file.path()).collect();
+        if changed_files.is_empty() {
+            return 0.0;
+        }
+
+        let reviewed_files = self
+            .commit_comments
+            .iter()
+            .filter(|commit_comment| !self.is_pr_author(&commit_comment.user.login))
+            .map(|commit_comment| &commit_comment.path)
+            .filter(|path| changed_files.contains(path))
+            .unique()
+            .count();
+
+        f64::trunc((reviewed_files as f64 / changed_files.len() as f64) * 100.0) / 100.0
+        // 2 decimals
+    }
+
+    /// Returns the [`PullRequest`](octocrab::models::pulls::PullRequest)'s first commit's [`DateTime`];
+    /// or [`None`] if it carries no commits at all.
+    pub fn get_first_commit_date(&self) -> Option<DateTime<Utc>> {
+        self.commits.first().map(|commit| commit.commit.author.date)
+    }
+
+    /// Returns the [`DateTime`] of the earliest review (or commit comment) submitted by someone other
+    /// than the PR's author, if any. The author's own reviews are taken into account as well if
+    /// self-reviews are counted (see [`ScoringSettings::count_self_reviews`]).
+    pub fn get_first_review_date(&self) -> Option<DateTime<Utc>> {
+        let review_dates = self
+            .reviews
+            .iter()
+            .filter(|review| self.is_counted_review(review))
+            .filter_map(|review| review.submitted_at);
+
+        let commit_comment_dates = self
+            .commit_comments
+            .iter()
+            .filter(|commit_comment| !self.is_pr_author(&commit_comment.user.login))
+            .map(|commit_comment| commit_comment.created_at);
+
+        review_dates.chain(commit_comment_dates).min()
+    }
+
+    /// Returns the [`DateTime`] of the PR's earliest activity of any kind (comments, submitted reviews and
+    /// commit comments) by anyone but its author, reviewer or not; if any. The author's own activity (ie:
+    /// a description follow-up, or a bump) is left out, and so are commits; since they are the PR's own
+    /// work rather than a response to it.
+    pub fn get_first_response_date(&self) -> Option<DateTime<Utc>> {
+        let comment_dates = self
+            .comments
+            .iter()
+            .filter(|comment| !self.is_pr_author(&comment.user.login))
+            .map(|comment| comment.created_at);
+        let review_dates = self
+            .reviews
+            .iter()
+            .filter(|review| !self.is_pr_author(&review.user.login))
+            .filter_map(|review| review.submitted_at);
+        let commit_comment_dates = self
+            .commit_comments
+            .iter()
+            .filter(|commit_comment| !self.is_pr_author(&commit_comment.user.login))
+            .map(|commit_comment| commit_comment.created_at);
+
+        comment_dates.chain(review_dates).chain(commit_comment_dates).min()
+    }
+
+    /// Returns the [`DateTime`] of the earliest approval submitted by someone other than the PR's author,
+    /// if any. Self-reviews are taken into account only if they are counted (see
+    /// [`ScoringSettings::count_self_reviews`]); even though GitHub does not let authors approve their own
+    /// PRs.
+    pub fn get_first_approval_date(&self) -> Option<DateTime<Utc>> {
+        self.reviews
+            .iter()
+            .filter(|review| self.is_counted_review(review))
+            .filter(|review| review.state == Some(ReviewState::Approved))
+            .filter_map(|review| review.submitted_at)
+            .min()
+    }
+
+    /// Returns the amount of review rounds the PR went through.
+    /// <br/><br/>
+    /// All non-author reviews and all of the author's activity (commits and comments) are laid out in
+    /// chronological order. A review round is then any run of consecutive non-author reviews that is not
+    /// interrupted by the author's activity - ie: review, review, commit, review, comment, commit, review
+    /// amounts to three rounds. Pending reviews (not submitted yet) are ignored.
+    pub fn get_review_iterations(&self) -> u64 {
+        let reviewer_activity = self
+            .reviews
+            .iter()
+            .filter(|review| !self.is_pr_author(&review.user.login))
+            .filter_map(|review| review.submitted_at)
+            .map(|submitted_at| (submitted_at, true));
+
+        let author_activity = self
+            .commits
+            .iter()
+            .map(|commit| commit.commit.author.date)
+            .chain(
+                self.comments
+                    .iter()
+                    .filter(|comment| self.is_pr_author(&comment.user.login))
+                    .map(|comment| comment.created_at),
+            )
+            .map(|date| (date, false));
+
+        reviewer_activity
+            .chain(author_activity)
+            .sorted_by_key(|(date, _)| *date)
+            .map(|(_, is_review)| is_review)
+            .dedup()
+            .filter(|is_review| *is_review)
+            .count() as u64
+    }
+
+    /// Returns the average amount of hours the PR's author took to reply to reviewers, if they ever did.
+    /// <br/><br/>
+    /// Every non-author review, comment or commit comment is paired with the author's next comment (or
+    /// commit comment) after it; the gaps between each pair are then averaged. Reviewer activity the
+    /// author never replied to is left out, and so are pending reviews (not submitted yet).
+    pub fn get_author_response_latency(&self) -> Option<u64> {
+        let commit_comment_dates = self
+            .commit_comments
+            .iter()
+            .map(|commit_comment| (commit_comment.user.login.as_str(), commit_comment.created_at));
+
+        let (author_dates, reviewer_dates): (Vec<_>, Vec<_>) = self
+            .comments
+            .iter()
+            .map(|comment| (comment.user.login.as_str(), comment.created_at))
+            .chain(commit_comment_dates)
+            .partition(|(login, _)| self.is_pr_author(login));
+
+        let author_dates: Vec<DateTime<Utc>> =
+            author_dates.into_iter().map(|(_, date)| date).sorted().collect();
+
+        let response_gaps: Vec<i64> = reviewer_dates
+            .into_iter()
+            .map(|(_, date)| date)
+            .chain(
+                self.reviews
+                    .iter()
+                    .filter(|review| !self.is_pr_author(&review.user.login))
+                    .filter_map(|review| review.submitted_at),
+            )
+            .filter_map(|reviewer_date| {
+                author_dates
+                    .iter()
+                    .find(|author_date| **author_date > reviewer_date)
+                    .map(|author_date| (*author_date - reviewer_date).num_seconds())
             })
-            .commit
-            .author
-            .date
+            .collect();
+
+        if response_gaps.is_empty() {
+            None
+        } else {
+            let average_gap = response_gaps.iter().sum::<i64>() / response_gaps.len() as i64;
+            Some((average_gap / 3600) as u64) // seconds to (whole) hours
+        }
+    }
+
+    /// Determines whether the PR was closed without ever being merged (ie: abandoned, or rejected).
+    pub fn is_closed_unmerged(&self) -> bool {
+        self.closed_at.is_some() && self.merged_at.is_none()
     }
 
-    /// Determines if a [`PatchedFile`](unidiff::PatchedFile)'s affected file corresponds to a test suite
-    /// or not.
+    /// Determines whether the PR was most likely merged by its own author, without anyone else's review.
     /// <br/><br/>
-    /// **Note:** This implementation is quite 'naive' and depends on proper naming conventions (aka
-    /// the file must have the 'test' keyword somewhere in its name).
-    ///
-    /// **May trigger false positives if
-    /// the file contains the word within another unrelated word - ie: 'contest'**.
-    fn is_test_file(name: &str) -> bool {
-        name.to_ascii_lowercase().contains("test")
+    /// The sampled data does not name whoever merged a PR, so this is derived conservatively: the PR must
+    /// have been merged without any non-author reviews, *and* within [`SELF_MERGE_MAX_LEAD_TIME_MINUTES`]
+    /// of being opened (hardly enough for anyone else to have looked at it). Self-reviews are never taken
+    /// into account, since they are no second pair of eyes.
+    pub fn is_likely_self_merged(&self) -> bool {
+        matches!(self.merged_at, Some(merged_at)
+            if self.reviews.iter().all(|review| self.is_pr_author(&review.user.login))
+                && merged_at.signed_duration_since(self.created_at).num_minutes()
+                    < SELF_MERGE_MAX_LEAD_TIME_MINUTES)
+    }
+
+    /// Returns the amount of review threads that are still unresolved; or [`None`] if review threads were
+    /// not retrieved at all.
+    pub fn get_unresolved_threads(&self) -> Option<u64> {
+        self.review_threads.as_ref().map(|review_threads| {
+            review_threads.iter().filter(|review_thread| !review_thread.is_resolved).count() as u64
+        })
+    }
+
+    /// Returns the amount of hours the PR spent as a draft (ie: work in progress, not up for review yet);
+    /// or [`None`] if its timeline was not (or could not be) retrieved. PRs that were never drafts spent
+    /// zero hours as one.
+    /// <br/><br/>
+    /// A PR whose first draft transition marks it as ready for review (or that is still a draft, without
+    /// any transitions) was opened as a draft. Drafts that never got marked as ready count up until the
+    /// PR was closed - or up until now, if it is still open.
+    pub fn get_time_in_draft(&self) -> Option<u64> {
+        let timeline = self.timeline.as_ref()?;
+
+        let transitions: Vec<(&str, DateTime<Utc>)> = timeline
+            .iter()
+            .filter(|event| event.is_draft_transition())
+            .filter_map(|event| event.created_at.map(|date| (event.event.as_str(), date)))
+            .sorted_by_key(|(_, date)| *date)
+            .collect();
+
+        let opened_as_draft = match transitions.first() {
+            Some((event, _)) => *event == READY_FOR_REVIEW_EVENT,
+            None => self.draft,
+        };
+
+        let mut drafted_since = if opened_as_draft {
+            Some(self.created_at)
+        } else {
+            None
+        };
+        let mut seconds_in_draft: i64 = 0;
+
+        for (event, date) in transitions {
+            if event == READY_FOR_REVIEW_EVENT {
+                if let Some(since) = drafted_since.take() {
+                    seconds_in_draft += (date - since).num_seconds();
+                }
+            } else if drafted_since.is_none() {
+                drafted_since = Some(date);
+            }
+        }
+
+        if let Some(since) = drafted_since {
+            let until = self.closed_at.unwrap_or_else(Utc::now);
+            seconds_in_draft += (until - since).num_seconds();
+        }
+
+        Some((seconds_in_draft.max(0) / 3600) as u64) // seconds to (whole) hours
+    }
+
+    /// Returns whether the PR got merged with all of its checks passing: 1 if it did, 0 if any of them had
+    /// failed (or not finished yet). [`None`] if the PR was not merged, if its checks were not retrieved at
+    /// all, or if it had none configured; since there is nothing to tell about its checks then.
+    pub fn get_green_merge(&self) -> Option<f64> {
+        match (self.merged_at, self.checks?) {
+            (None, _) | (_, ChecksOutcome::NoChecks) => None,
+            (Some(_), ChecksOutcome::Passing) => Some(1.0),
+            (Some(_), ChecksOutcome::Pending) | (Some(_), ChecksOutcome::Failing) => Some(0.0),
+        }
     }
 
     /// Returns the count for the *net* amount of added lines in a [`Hunk`].
@@ -361,25 +890,123 @@ impl Scorable for PullRequestData {
         let non_authoring_participants = self.get_non_authoring_participants();
         debug!("non-authoring participants: {:?}", non_authoring_participants);
 
-        let non_authoring_reviewers = self.get_non_authoring_reviewers();
-        debug!("non-authoring reviewers: {:?}", non_authoring_reviewers);
+        let reviewers = self.get_reviewers();
+        debug!("reviewers: {:?}", reviewers);
 
         let attachments = self.get_attachments_markdown();
         debug!("author attachments: {:?}", attachments);
 
-        let pull_request_lead_time = (self.closed_at - self.created_at).num_days() as u64;
+        let pull_request_lead_time =
+            self.closed_at.map(|closed_at| (closed_at - self.created_at).num_days() as u64);
         debug!(
-            "created at: {}, closed at: {}, pull request lead time: {}",
+            "created at: {}, closed at: {:?}, pull request lead time: {:?}",
             self.created_at, self.closed_at, pull_request_lead_time
         );
 
+        let business_days_lead_time = self.closed_at.map(|closed_at| {
+            self.scoring_settings.count_business_days(&self.created_at, &closed_at)
+        });
+        debug!("business days lead time: {:?}", business_days_lead_time);
+
+        // squashed PRs' first commit is no older than the PR itself, so their lead time is as close as it
+        // gets to how long their work took to be merged
+        let squashed = self.merged_at.is_some() && self.is_squashed();
         let first_commit_at = self.get_first_commit_date();
-        let time_to_merge = (self.merged_at - first_commit_at).num_days() as u64;
+        if first_commit_at.is_none() && !squashed {
+            warn!(
+                "PR [{}]/[{}] carries no commits; its TimeToMerge will be left out.",
+                self.repo_name, self.pr_number
+            );
+        }
+        let time_to_merge = if squashed {
+            self.merged_at.map(|merged_at| (merged_at - self.created_at).num_days() as u64)
+        } else {
+            self.merged_at
+                .zip(first_commit_at)
+                .map(|(merged_at, first_commit_at)| (merged_at - first_commit_at).num_days() as u64)
+        };
+        debug!(
+            "first commit at: {:?}, merged at: {:?}, squashed: {}, time to merge: {:?}",
+            first_commit_at, self.merged_at, squashed, time_to_merge
+        );
+
+        let checklist_completion = self.get_checklist_completion();
+        debug!("checklist completion: {}", checklist_completion);
+
+        let commit_message_quality = self.get_commit_message_quality();
+        debug!("commit message quality: {:?}", commit_message_quality);
+
+        let authorship_mismatch = self.get_authorship_mismatch();
+        debug!("authorship mismatch: {}", authorship_mismatch);
+
+        let signed_commit_ratio = self.get_signed_commit_ratio();
+        debug!("signed commit ratio: {:?}", signed_commit_ratio);
+
+        let reviewed_file_coverage = self.get_reviewed_file_coverage();
+        debug!("reviewed file coverage: {}", reviewed_file_coverage);
+
+        let deletion_ratio = self.get_deletion_ratio();
+        debug!("deletion ratio: {}", deletion_ratio);
+
+        let contentious_discussion = self.get_contentious_discussion();
+        debug!("contentious discussion: {:?}", contentious_discussion);
+
+        let description_length = self.get_description_length();
+        debug!("description length: {}", description_length);
+
+        let is_description_missing = self.is_description_missing();
+        debug!("description missing: {}", is_description_missing);
+
+        let linked_issues = self.get_linked_issues();
+        debug!("linked issues: {:?}", linked_issues);
+
+        let binary_files_changed = self.get_amount_of_binary_files_changed();
+        debug!("binary files changed: {}", binary_files_changed);
+
+        let review_iterations = self.get_review_iterations();
+        debug!("review iterations: {}", review_iterations);
+
+        let first_review_at = self.get_first_review_date();
+        let time_to_first_review = first_review_at
+            .map(|first_review_at| (first_review_at - self.created_at).num_hours().max(0) as u64);
         debug!(
-            "first commit at: {}, merged at: {}, time to merge: {}",
-            first_commit_at, self.merged_at, time_to_merge
+            "created at: {}, first reviewed at: {:?}, time to first review: {:?}",
+            self.created_at, first_review_at, time_to_first_review
         );
 
+        let first_response_at = self.get_first_response_date();
+        let first_response_wait = first_response_at.map(|first_response_at| {
+            (first_response_at - self.created_at).num_hours().max(0) as u64
+        });
+        debug!(
+            "created at: {}, first responded at: {:?}, first response wait: {:?}",
+            self.created_at, first_response_at, first_response_wait
+        );
+
+        let first_approval_at = self.get_first_approval_date();
+        let time_to_approval = first_approval_at.map(|first_approval_at| {
+            (first_approval_at - self.created_at).num_hours().max(0) as u64
+        });
+        debug!(
+            "created at: {}, first approved at: {:?}, time to approval: {:?}",
+            self.created_at, first_approval_at, time_to_approval
+        );
+
+        let author_response_latency = self.get_author_response_latency();
+        debug!("author response latency: {:?}", author_response_latency);
+
+        let unresolved_threads = self.get_unresolved_threads();
+        debug!("unresolved threads: {:?}", unresolved_threads);
+
+        let after_hours_activity_ratio = self.get_after_hours_activity_ratio();
+        debug!("after hours activity ratio: {:?}", after_hours_activity_ratio);
+
+        let time_in_draft = self.get_time_in_draft();
+        debug!("draft: {}, time in draft: {:?}", self.draft, time_in_draft);
+
+        let green_merge = self.get_green_merge();
+        debug!("checks: {:?}, green merge: {:?}", self.checks, green_merge);
+
         // having processed a PR's attributes, prepare individual scoring of important attributes
         let mut scorables: Vec<ScoreType> = Vec::new();
 
@@ -389,15 +1016,95 @@ impl Scorable for PullRequestData {
                 // are present and accounted for at compilation time; which means a developer doesn't
                 // have to worry about forgetting to include potential new ScoreType(s) into the scoring
                 // process
+                ScoreType::AfterHoursActivityRatio(_) => match after_hours_activity_ratio {
+                    Some(after_hours_activity_ratio) => scorables
+                        .push(ScoreType::AfterHoursActivityRatio(after_hours_activity_ratio)),
+                    None => trace!(
+                        "AfterHoursActivityRatio metric not applicable to Pull Request(s) without any activity."
+                    ),
+                },
                 ScoreType::AmountOfParticipants(_) => scorables
                     .push(ScoreType::AmountOfParticipants(non_authoring_participants.len() as u64)),
                 ScoreType::AmountOfReviewers(_) => scorables
-                    .push(ScoreType::AmountOfReviewers(non_authoring_reviewers.len() as u64)),
+                    .push(ScoreType::AmountOfReviewers(reviewers.len() as u64)),
                 ScoreType::Attachments(_) => {
                     scorables.push(ScoreType::Attachments(attachments.len() as u64))
                 }
                 ScoreType::AuthorCommentaryToChangesRatio(_) => scorables
                     .push(ScoreType::AuthorCommentaryToChangesRatio(commentary_to_changes_ratio)),
+                ScoreType::AuthorResponseLatency(_) => match author_response_latency {
+                    Some(author_response_latency) => {
+                        scorables.push(ScoreType::AuthorResponseLatency(author_response_latency))
+                    }
+                    None => trace!(
+                        "AuthorResponseLatency metric not applicable to Pull Request(s) without reviewer-to-author exchanges."
+                    ),
+                },
+                ScoreType::AuthorshipMismatch(_) => {
+                    scorables.push(ScoreType::AuthorshipMismatch(authorship_mismatch))
+                }
+                ScoreType::BinaryFilesChanged(_) => {
+                    scorables.push(ScoreType::BinaryFilesChanged(binary_files_changed))
+                }
+                ScoreType::BusinessDaysLeadTime(_) => match business_days_lead_time {
+                    Some(business_days_lead_time) => {
+                        scorables.push(ScoreType::BusinessDaysLeadTime(business_days_lead_time))
+                    }
+                    None => trace!(
+                        "BusinessDaysLeadTime metric not applicable to open Pull Request(s)."
+                    ),
+                },
+                ScoreType::ChecklistCompletion(_) => {
+                    scorables.push(ScoreType::ChecklistCompletion(checklist_completion))
+                }
+                ScoreType::CommitMessageQuality(_) => match commit_message_quality {
+                    Some(commit_message_quality) => {
+                        scorables.push(ScoreType::CommitMessageQuality(commit_message_quality))
+                    }
+                    None => trace!(
+                        "CommitMessageQuality metric not applicable to Pull Request(s) without any non-merge commit."
+                    ),
+                },
+                ScoreType::ContentiousDiscussion(_) => match contentious_discussion {
+                    Some(contentious_discussion) => {
+                        scorables.push(ScoreType::ContentiousDiscussion(contentious_discussion))
+                    }
+                    None => trace!(
+                        "ContentiousDiscussion metric not scored, as no contention words were supplied."
+                    ),
+                },
+                ScoreType::DeletionRatio(_) => {
+                    scorables.push(ScoreType::DeletionRatio(deletion_ratio))
+                }
+                ScoreType::DescriptionLength(_) => {
+                    scorables.push(ScoreType::DescriptionLength(description_length))
+                }
+                ScoreType::FilesChanged(_) => {
+                    scorables.push(ScoreType::FilesChanged(self.patch_set.files().len()))
+                }
+                ScoreType::FirstResponseWait(_) => match first_response_wait {
+                    Some(first_response_wait) => {
+                        scorables.push(ScoreType::FirstResponseWait(first_response_wait))
+                    }
+                    None => trace!(
+                        "FirstResponseWait metric not applicable to Pull Request(s) without any activity."
+                    ),
+                },
+                ScoreType::GreenMergeRatio(_) => match green_merge {
+                    Some(green_merge) => scorables.push(ScoreType::GreenMergeRatio(green_merge)),
+                    None => trace!(
+                        "GreenMergeRatio metric not applicable to unmerged Pull Request(s), nor to those without (retrieved) checks."
+                    ),
+                },
+                ScoreType::LinkedIssues(_) => {
+                    scorables.push(ScoreType::LinkedIssues(linked_issues.len() as u64))
+                }
+                ScoreType::MissingDescription(_) => scorables.push(ScoreType::MissingDescription(
+                    if is_description_missing { 1.0 } else { 0.0 },
+                )),
+                ScoreType::NumberOfCommits(_) => {
+                    scorables.push(ScoreType::NumberOfCommits(self.commits.len() as u64))
+                }
                 ScoreType::PullRequestsDiscussionSize(_) => {
                     scorables.push(ScoreType::PullRequestsDiscussionSize(all_comments))
                 }
@@ -406,21 +1113,1306 @@ impl Scorable for PullRequestData {
                         "PullRequestFlowRatio metric not applicable to individual Pull Request(s); only to Repository(ies)."
                     )
                 }
-                ScoreType::PullRequestLeadTime(_) => {
-                    scorables.push(ScoreType::PullRequestLeadTime(pull_request_lead_time))
-                }
+                ScoreType::PullRequestLeadTime(_) => match pull_request_lead_time {
+                    Some(pull_request_lead_time) => {
+                        scorables.push(ScoreType::PullRequestLeadTime(pull_request_lead_time))
+                    }
+                    None => trace!(
+                        "PullRequestLeadTime metric not applicable to open Pull Request(s)."
+                    ),
+                },
                 ScoreType::PullRequestSize(_) => {
                     scorables.push(ScoreType::PullRequestSize(changes_added))
                 }
+                ScoreType::ReactionCount(_) => {
+                    scorables.push(ScoreType::ReactionCount(self.reactions.len() as u64))
+                }
+                ScoreType::RejectionRate(_) => {
+                    trace!(
+                        "RejectionRate metric not applicable to individual Pull Request(s); only to Repository(ies)."
+                    )
+                }
+                ScoreType::ReviewIterations(_) => {
+                    scorables.push(ScoreType::ReviewIterations(review_iterations))
+                }
+                ScoreType::ReviewParticipationBreadth(_) => {
+                    trace!(
+                        "ReviewParticipationBreadth metric not applicable to individual Pull Request(s); only to Repository(ies)."
+                    )
+                }
+                ScoreType::ReviewedFileCoverage(_) => {
+                    scorables.push(ScoreType::ReviewedFileCoverage(reviewed_file_coverage))
+                }
+                ScoreType::ReviewerDiversity(_) => {
+                    trace!(
+                        "ReviewerDiversity metric not applicable to individual Pull Request(s); only to Repository(ies)."
+                    )
+                }
+                ScoreType::SelfMergedPrs(_) => {
+                    trace!(
+                        "SelfMergedPrs metric not applicable to individual Pull Request(s); only to Repository(ies)."
+                    )
+                }
+                ScoreType::SignedCommitRatio(_) => match signed_commit_ratio {
+                    Some(signed_commit_ratio) => {
+                        scorables.push(ScoreType::SignedCommitRatio(signed_commit_ratio))
+                    }
+                    None => trace!(
+                        "SignedCommitRatio metric not applicable to Pull Request(s) without commits."
+                    ),
+                },
                 ScoreType::TestToCodeRatio(_) => {
                     scorables.push(ScoreType::TestToCodeRatio(test_to_code_ratio))
                 }
-                ScoreType::TimeToMerge(_) => scorables.push(ScoreType::TimeToMerge(time_to_merge)),
+                ScoreType::TimeInDraft(_) => match time_in_draft {
+                    Some(time_in_draft) => scorables.push(ScoreType::TimeInDraft(time_in_draft)),
+                    None => trace!(
+                        "TimeInDraft metric not applicable unless the timeline is retrieved."
+                    ),
+                },
+                ScoreType::TimeToApproval(_) => match time_to_approval {
+                    Some(time_to_approval) => {
+                        scorables.push(ScoreType::TimeToApproval(time_to_approval))
+                    }
+                    None => trace!(
+                        "TimeToApproval metric not applicable to Pull Request(s) without non-author approvals."
+                    ),
+                },
+                ScoreType::TimeToFirstReview(_) => match time_to_first_review {
+                    Some(time_to_first_review) => {
+                        scorables.push(ScoreType::TimeToFirstReview(time_to_first_review))
+                    }
+                    None => trace!(
+                        "TimeToFirstReview metric not applicable to Pull Request(s) without non-author reviews."
+                    ),
+                },
+                ScoreType::TimeToMerge(_) => match time_to_merge {
+                    Some(time_to_merge) => scorables.push(ScoreType::TimeToMerge(time_to_merge)),
+                    None => trace!(
+                        "TimeToMerge metric not applicable to open (or commitless) Pull Request(s)."
+                    ),
+                },
+                ScoreType::UnresolvedThreads(_) => match unresolved_threads {
+                    Some(unresolved_threads) => {
+                        scorables.push(ScoreType::UnresolvedThreads(unresolved_threads))
+                    }
+                    None => trace!(
+                        "UnresolvedThreads metric not applicable unless review threads are retrieved."
+                    ),
+                },
             }
         }
 
-        Score::new(scorables)
+        let score = Score::new(scorables)
+            .graded(&self.scoring_settings.grade_weights, &self.scoring_settings.grading_config);
+
+        if squashed {
+            score.flagged(ScoreFlag::SquashedTimeToMerge)
+        } else {
+            score
+        }
     }
 }
 
 pub type PullRequestDataResult = Result<PullRequestData, AnalyzeError>;
+
+/// An issue that a PR declares it closes (see [`PullRequestData::get_linked_issues`]). Its `owner` and
+/// `repo` are [`None`] for bare references (ie: "#123"), which point at the PR's own repository.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LinkedIssue {
+    pub owner: Option<String>,
+    pub repo: Option<String>,
+    pub number: u64,
+}
+
+/// Determines whether a PR's `title` starts with 'Merge'... - ie: "Merge develop into QA".
+pub fn has_merge_title(title: &str) -> bool {
+    title.to_ascii_lowercase().starts_with("merge")
+}
+
+/// Determines whether a commit is a merge commit: either it has multiple parents, or its message is one
+/// of those auto-generated by git or GitHub (ie: "Merge branch 'main' into feature", "Merge pull request
+/// #42 from owner/branch"); which squashed or rebased merges keep around.
+fn is_merge_commit(commit: &CommitRoot) -> bool {
+    lazy_static! {
+        static ref MERGE_COMMIT_MESSAGE_REGEX: Regex =
+            Regex::new(r"^Merge (?:branch|remote-tracking branch|pull request #\d+) ").unwrap();
+    }
+
+    commit.parents.len() > 1 || MERGE_COMMIT_MESSAGE_REGEX.is_match(commit.commit.message.trim())
+}
+
+/// Determines whether a commit `message` carries both a subject line at least
+/// [`MIN_COMMIT_SUBJECT_LENGTH`] characters long and a (non-blank) body after it.
+fn is_descriptive_commit_message(message: &str) -> bool {
+    let mut lines = message.trim().lines();
+    let subject = lines.next().unwrap_or_default().trim();
+    let has_body = lines.any(|line| !line.trim().is_empty());
+
+    subject.chars().count() >= MIN_COMMIT_SUBJECT_LENGTH && has_body
+}
+
+/// Removes fenced code blocks (delimited by either ``` or ~~~) from a markdown `text`. Unterminated blocks
+/// run until the end of the text.
+fn strip_fenced_code_blocks(text: &str) -> String {
+    let mut inside_code_block = false;
+
+    text.lines()
+        .filter(|line| {
+            let trimmed_line = line.trim_start();
+
+            if trimmed_line.starts_with("```") || trimmed_line.starts_with("~~~") {
+                inside_code_block = !inside_code_block;
+                return false;
+            }
+
+            !inside_code_block
+        })
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod pull_request_data_tests {
+    use chrono::{FixedOffset, TimeZone, Utc};
+    use glob::Pattern;
+
+    use crate::github::json::commit_checks::ChecksOutcome;
+    use crate::github::json::review::Review;
+    use crate::github::json::review_thread::ReviewThread;
+    use crate::github::utils::fixtures::{
+        comment, commit_comment, commit_root, has_metric, patch_set, pr_with_commits, reaction,
+        review, timeline_event, PullRequestDataFixture, SINGLE_LINE_DIFF, THREE_FILE_DIFF,
+    };
+    use crate::github::utils::pull_request_data::{LinkedIssue, PullRequestData};
+    use crate::scoring::scorable::Scorable;
+    use crate::scoring::score::{ScoreFlag, ScoreType};
+    use crate::scoring::scoring_settings::{
+        ContentionWords, Holidays, MergePrHeuristic, MergeStyle, ScoringSettings, TeamMembers,
+        Timezone,
+    };
+
+    fn pr_with(
+        title: &str, head_commit_parents: usize, heuristic: MergePrHeuristic,
+    ) -> PullRequestData {
+        PullRequestDataFixture {
+            pr_title: title.to_string(),
+            commits: vec![commit_root(
+                "Dummy commit",
+                Utc.ymd(2021, 3, 1).and_hms(9, 0, 0),
+                head_commit_parents,
+            )],
+            patch_set: patch_set(SINGLE_LINE_DIFF),
+            scoring_settings: ScoringSettings {
+                merge_pr_heuristic: heuristic,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+        .build()
+    }
+
+    #[test]
+    fn title_prefix_heuristic_only_looks_at_the_title() {
+        assert!(
+            pr_with("Merge develop into master", 1, MergePrHeuristic::TitlePrefix).is_merge_pr()
+        );
+        assert!(!pr_with("Sync develop → main", 2, MergePrHeuristic::TitlePrefix).is_merge_pr());
+    }
+
+    #[test]
+    fn multiple_parents_heuristic_only_looks_at_the_head_commit() {
+        assert!(pr_with("Sync develop → main", 2, MergePrHeuristic::MultipleParents).is_merge_pr());
+        assert!(!pr_with("Merge-sort implementation", 1, MergePrHeuristic::MultipleParents)
+            .is_merge_pr());
+    }
+
+    #[test]
+    fn multiple_parents_heuristic_flags_prs_without_changes() {
+        let pr = PullRequestDataFixture {
+            commits: vec![commit_root("Dummy commit", Utc.ymd(2021, 3, 1).and_hms(9, 0, 0), 1)],
+            scoring_settings: ScoringSettings {
+                merge_pr_heuristic: MergePrHeuristic::MultipleParents,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+        .build();
+
+        assert!(pr.is_merge_pr());
+    }
+
+    #[test]
+    fn both_heuristic_requires_title_and_head_commit_to_agree() {
+        assert!(pr_with("Merge develop into master", 2, MergePrHeuristic::Both).is_merge_pr());
+        assert!(!pr_with("Merge-sort implementation", 1, MergePrHeuristic::Both).is_merge_pr());
+        assert!(!pr_with("Sync develop → main", 2, MergePrHeuristic::Both).is_merge_pr());
+    }
+
+    fn reviewed_pr(reviews: Vec<Review>) -> PullRequestData {
+        PullRequestDataFixture {
+            pr_author: String::from("author"),
+            created_at: Utc.ymd(2021, 3, 1).and_hms(10, 0, 0),
+            reviews,
+            ..PullRequestDataFixture::scorable()
+        }
+        .build()
+    }
+
+    #[test]
+    fn time_to_first_review_ignores_the_authors_own_reviews() {
+        let pr = reviewed_pr(vec![
+            review("author", "COMMENTED", Some(Utc.ymd(2021, 3, 1).and_hms(11, 0, 0))),
+            review("reviewer-b", "APPROVED", Some(Utc.ymd(2021, 3, 2).and_hms(10, 0, 0))),
+            review("reviewer-a", "CHANGES_REQUESTED", Some(Utc.ymd(2021, 3, 1).and_hms(15, 0, 0))),
+            review("reviewer-c", "PENDING", None),
+        ]);
+
+        assert!(pr.get_score().score().contains(&ScoreType::TimeToFirstReview(5)));
+    }
+
+    #[test]
+    fn first_response_wait_counts_any_activity_from_anyone_but_the_author() {
+        let day = |h: u32| Utc.ymd(2021, 3, 1).and_hms(h, 0, 0);
+
+        let pr = PullRequestDataFixture {
+            pr_author: String::from("author"),
+            created_at: day(10),
+            commits: vec![commit_root("Dummy commit", day(11), 1)],
+            comments: vec![
+                comment("author", "Fixes the flaky build", day(11)),
+                comment("bystander", "Any news?", day(14)),
+            ],
+            commit_comments: vec![commit_comment("reviewer-a", "src/main.rs", day(16))],
+            reviews: vec![review("reviewer-a", "APPROVED", Some(day(17)))],
+            ..Default::default()
+        }
+        .build();
+        assert!(pr.get_score().score().contains(&ScoreType::FirstResponseWait(4)));
+
+        // the author bumping their own PR is no response to it
+        let bumped_pr = PullRequestDataFixture {
+            pr_author: String::from("author"),
+            created_at: day(10),
+            comments: vec![comment("Author", "Any news?", day(14))],
+            reviews: vec![review("author", "COMMENTED", Some(day(15)))],
+            commit_comments: vec![commit_comment("author", "src/main.rs", day(16))],
+            ..Default::default()
+        }
+        .build();
+        assert_eq!(bumped_pr.get_first_response_date(), None);
+
+        // PRs nobody ever responded to are left out, rather than charged an arbitrary wait
+        let ignored_pr = reviewed_pr(vec![]);
+        assert_eq!(ignored_pr.get_first_response_date(), None);
+        assert!(!has_metric(&ignored_pr.get_score().score(), ScoreType::FirstResponseWait(0)));
+    }
+
+    #[test]
+    fn self_reviews_only_count_towards_reviewer_metrics_when_told_so() {
+        let pr = |count_self_reviews: bool| {
+            PullRequestDataFixture {
+                pr_author: String::from("author"),
+                created_at: Utc.ymd(2021, 3, 1).and_hms(10, 0, 0),
+                reviews: vec![
+                    review("author", "COMMENTED", Some(Utc.ymd(2021, 3, 1).and_hms(11, 0, 0))),
+                    review("reviewer-a", "APPROVED", Some(Utc.ymd(2021, 3, 1).and_hms(15, 0, 0))),
+                ],
+                scoring_settings: ScoringSettings {
+                    count_self_reviews,
+                    ..Default::default()
+                },
+                ..PullRequestDataFixture::scorable()
+            }
+            .build()
+        };
+
+        let excluding = pr(false).get_score().score();
+        assert!(excluding.contains(&ScoreType::AmountOfReviewers(1)));
+        assert!(excluding.contains(&ScoreType::TimeToFirstReview(5)));
+
+        let counting = pr(true).get_score().score();
+        assert!(counting.contains(&ScoreType::AmountOfReviewers(2)));
+        assert!(counting.contains(&ScoreType::TimeToFirstReview(1)));
+
+        // commentary volume and author-reviewer exchanges are unaffected either way
+        for score_type in excluding.iter().filter(|score_type| {
+            matches!(
+                score_type,
+                ScoreType::PullRequestsDiscussionSize(_)
+                    | ScoreType::AuthorCommentaryToChangesRatio(_)
+                    | ScoreType::ReviewIterations(_)
+            )
+        }) {
+            assert!(counting.contains(score_type));
+        }
+    }
+
+    #[test]
+    fn time_to_approval_waits_for_the_first_approval_rather_than_any_review() {
+        let pr = reviewed_pr(vec![
+            review("reviewer-a", "COMMENTED", Some(Utc.ymd(2021, 3, 1).and_hms(12, 0, 0))),
+            review("reviewer-a", "APPROVED", Some(Utc.ymd(2021, 3, 1).and_hms(18, 0, 0))),
+            review("reviewer-b", "APPROVED", Some(Utc.ymd(2021, 3, 2).and_hms(9, 0, 0))),
+        ]);
+        let score = pr.get_score().score();
+
+        assert!(score.contains(&ScoreType::TimeToFirstReview(2)));
+        assert!(score.contains(&ScoreType::TimeToApproval(8)));
+
+        let unapproved = reviewed_pr(vec![review(
+            "reviewer-a",
+            "COMMENTED",
+            Some(Utc.ymd(2021, 3, 1).and_hms(12, 0, 0)),
+        )]);
+
+        assert!(!has_metric(&unapproved.get_score().score(), ScoreType::TimeToApproval(0)));
+    }
+
+    #[test]
+    fn time_to_first_review_is_omitted_for_unreviewed_prs() {
+        let pr = reviewed_pr(vec![review(
+            "author",
+            "COMMENTED",
+            Some(Utc.ymd(2021, 3, 1).and_hms(11, 0, 0)),
+        )]);
+
+        assert!(!has_metric(&pr.get_score().score(), ScoreType::TimeToFirstReview(0)));
+    }
+
+    #[test]
+    fn open_prs_omit_merge_and_close_dependent_metrics_only() {
+        let pr = PullRequestDataFixture {
+            merged_at: None,
+            closed_at: None,
+            ..PullRequestDataFixture::scorable()
+        }
+        .build();
+
+        let score = pr.get_score().score();
+
+        assert!(!score.iter().any(|score_type| matches!(
+            score_type,
+            ScoreType::TimeToMerge(_)
+                | ScoreType::PullRequestLeadTime(_)
+                | ScoreType::BusinessDaysLeadTime(_)
+        )));
+        assert!(score.contains(&ScoreType::PullRequestSize(1)));
+        assert!(score.contains(&ScoreType::NumberOfCommits(1)));
+    }
+
+    #[test]
+    fn deletion_ratio_is_the_share_of_changed_lines_that_were_removed() {
+        // a cleanup that removes more than it adds (much like PR #6830 did)
+        let cleanup_diff = "diff --git a/src/foo.rs b/src/foo.rs
+index 1111111..2222222 100644
+--- a/src/foo.rs
++++ b/src/foo.rs
+@@ -1,5 +1,2 @@
+ fn foo() {}
+-fn bar() {}
+-fn baz() {}
+-fn qux() {}
++fn quux() {}
+";
+        let pr = PullRequestDataFixture {
+            patch_set: patch_set(cleanup_diff),
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(pr.get_deletion_ratio(), 0.75);
+        assert!(pr.get_score().score().contains(&ScoreType::DeletionRatio(0.75)));
+
+        let empty_pr = PullRequestDataFixture::default().build();
+        assert_eq!(empty_pr.get_deletion_ratio(), 0.0);
+    }
+
+    #[test]
+    fn linked_issues_are_deduplicated_and_ignored_inside_code_blocks() {
+        let pr = PullRequestDataFixture {
+            main_message: Some(String::from(
+                "Closes #12 and resolves https://github.com/octocat/Hello-World/issues/34.\n\
+                ```\n\
+                git commit -m 'fixes #99'\n\
+                ```\n\
+                Also mentions #56, which it does not close.",
+            )),
+            comments: vec![
+                comment(
+                    "dummy-author",
+                    "Oh, and this fixes #12 too.",
+                    Utc.ymd(2021, 3, 1).and_hms(11, 0, 0),
+                ),
+                comment(
+                    "reviewer",
+                    "Fixes octocat/Hello-World#78?",
+                    Utc.ymd(2021, 3, 1).and_hms(12, 0, 0),
+                ),
+            ],
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(
+            pr.get_linked_issues(),
+            vec![linked_issue(None, 12), linked_issue(Some(("octocat", "hello-world")), 34)]
+        );
+    }
+
+    fn linked_issue(repository: Option<(&str, &str)>, number: u64) -> LinkedIssue {
+        LinkedIssue {
+            owner: repository.map(|(owner, _)| owner.to_string()),
+            repo: repository.map(|(_, repo)| repo.to_string()),
+            number,
+        }
+    }
+
+    #[test]
+    fn linked_issues_are_told_apart_by_repository_as_well_as_by_number() {
+        let pr = PullRequestDataFixture {
+            main_message: Some(String::from(
+                "Closes #12, fixes octocat/Hello-World#12 and fixes octocat/Spoon-Knife#12.\n\
+                Also resolves https://github.com/OctoCat/hello-world/issues/12.",
+            )),
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(
+            pr.get_linked_issues(),
+            vec![
+                linked_issue(None, 12),
+                linked_issue(Some(("octocat", "hello-world")), 12),
+                linked_issue(Some(("octocat", "spoon-knife")), 12),
+            ]
+        );
+    }
+
+    #[test]
+    fn checklist_completion_is_the_ratio_of_checked_items() {
+        let pr = PullRequestDataFixture {
+            main_message: Some(String::from(
+                "## Checklist\n\
+                - [x] Tests\n\
+                - [X] Docs\n\
+                * [x] Changelog\n\
+                - [ ] Screenshots",
+            )),
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(pr.get_checklist_completion(), 0.75);
+    }
+
+    #[test]
+    fn checklist_completion_is_zero_without_a_checklist() {
+        let pr = PullRequestDataFixture {
+            main_message: Some(String::from(
+                "Just a regular body, linking [an item](https://example.com).",
+            )),
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(pr.get_checklist_completion(), 0.0);
+    }
+
+    #[test]
+    fn commit_message_quality_is_the_ratio_of_descriptive_non_merge_commits() {
+        let committed_at = Utc.ymd(2021, 3, 1).and_hms(9, 0, 0);
+        let pr = PullRequestDataFixture {
+            commits: vec![
+                commit_root(
+                    "Add retries to the reviews fetch\n\nGitHub sends empty bodies under load.",
+                    committed_at,
+                    1,
+                ),
+                commit_root("fix", committed_at, 1),
+                // a long subject alone is not enough
+                commit_root("Rename the analyzer's settings struct", committed_at, 1),
+                commit_root("", committed_at, 1),
+                // merge commits are left out, be it by their parents or by their auto-generated message
+                commit_root("Merge branch 'main' into feature", committed_at, 2),
+                commit_root(
+                    "Merge pull request #42 from owner/feature\n\nFeature",
+                    committed_at,
+                    1,
+                ),
+            ],
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(pr.get_commit_message_quality(), Some(0.25));
+        assert!(pr.get_score().score().contains(&ScoreType::CommitMessageQuality(0.25)));
+    }
+
+    #[test]
+    fn commit_message_quality_does_not_apply_to_merge_commits_only() {
+        let pr = PullRequestDataFixture {
+            commits: vec![commit_root(
+                "Merge remote-tracking branch 'origin/develop' into QA",
+                Utc.ymd(2021, 3, 1).and_hms(9, 0, 0),
+                2,
+            )],
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(pr.get_commit_message_quality(), None);
+        assert!(!has_metric(&pr.get_score().score(), ScoreType::CommitMessageQuality(0.0)));
+    }
+
+    #[test]
+    fn after_hours_activity_ratio_counts_weekends_and_off_hours_in_the_team_timezone() {
+        let pr = PullRequestDataFixture {
+            commits: vec![
+                // monday 08:00 UTC is 10:00 in the team's timezone
+                commit_root("Add the reviews retrier", Utc.ymd(2021, 3, 1).and_hms(8, 0, 0), 1),
+                // monday 17:30 UTC is already 19:30 in the team's timezone
+                commit_root("Fix the retrier's backoff", Utc.ymd(2021, 3, 1).and_hms(17, 30, 0), 1),
+            ],
+            comments: vec![comment(
+                "reviewer",
+                "Looks good",
+                Utc.ymd(2021, 3, 2).and_hms(12, 0, 0),
+            )],
+            // saturday
+            reviews: vec![review(
+                "reviewer",
+                "APPROVED",
+                Some(Utc.ymd(2021, 3, 6).and_hms(12, 0, 0)),
+            )],
+            scoring_settings: ScoringSettings {
+                timezone: Timezone::UtcOffset(FixedOffset::east(2 * 3600)),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(pr.get_after_hours_activity_ratio(), Some(0.5));
+        assert!(pr.get_score().score().contains(&ScoreType::AfterHoursActivityRatio(0.5)));
+        assert_eq!(
+            PullRequestDataFixture::default().build().get_after_hours_activity_ratio(),
+            None
+        );
+    }
+
+    #[test]
+    fn business_days_lead_time_skips_weekends_and_holidays() {
+        let pr_closed_between = |created_at, closed_at, holidays: &str| {
+            PullRequestDataFixture {
+                created_at,
+                closed_at: Some(closed_at),
+                scoring_settings: ScoringSettings {
+                    holidays: Holidays::from_list(holidays).unwrap(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+            .build()
+            .get_score()
+            .score()
+        };
+
+        // opened on a Friday, closed on the following Tuesday
+        let across_weekend = pr_closed_between(
+            Utc.ymd(2021, 3, 5).and_hms(10, 0, 0),
+            Utc.ymd(2021, 3, 9).and_hms(10, 0, 0),
+            "",
+        );
+        assert!(across_weekend.contains(&ScoreType::PullRequestLeadTime(4)));
+        assert!(across_weekend.contains(&ScoreType::BusinessDaysLeadTime(2)));
+
+        // opened on a Wednesday, closed on Friday; Thursday being a holiday
+        let across_holiday = pr_closed_between(
+            Utc.ymd(2021, 3, 3).and_hms(10, 0, 0),
+            Utc.ymd(2021, 3, 5).and_hms(10, 0, 0),
+            "2021-03-04",
+        );
+        assert!(across_holiday.contains(&ScoreType::PullRequestLeadTime(2)));
+        assert!(across_holiday.contains(&ScoreType::BusinessDaysLeadTime(1)));
+    }
+
+    #[test]
+    fn signed_commit_ratio_is_the_ratio_of_verified_commits() {
+        let committed_at = Utc.ymd(2021, 3, 1).and_hms(9, 0, 0);
+        let mut signed_commit = commit_root("Sign the release tags", committed_at, 1);
+        signed_commit.commit.verification.verified = true;
+
+        let pr = PullRequestDataFixture {
+            commits: vec![
+                signed_commit,
+                commit_root("fix", committed_at, 1),
+                commit_root("fix again", committed_at, 1),
+            ],
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(pr.get_signed_commit_ratio(), Some(0.33));
+        assert!(pr.get_score().score().contains(&ScoreType::SignedCommitRatio(0.33)));
+        assert_eq!(pr_with_commits(0).get_signed_commit_ratio(), None);
+    }
+
+    #[test]
+    fn authorship_mismatch_counts_commits_by_someone_else_but_unattributed_ones() {
+        let committed_at = Utc.ymd(2021, 3, 1).and_hms(9, 0, 0);
+        let mut cherry_picked_commit = commit_root("Cherry-pick the fix", committed_at, 1);
+        cherry_picked_commit.author.as_mut().unwrap().login = String::from("someone-else");
+        let mut unattributed_commit = commit_root("Commit from an unknown e-mail", committed_at, 1);
+        unattributed_commit.author = None;
+        let mut own_commit = commit_root("Fix the fix", committed_at, 1);
+        own_commit.author.as_mut().unwrap().login = String::from("Dummy-Author");
+
+        let pr = PullRequestDataFixture {
+            commits: vec![cherry_picked_commit, unattributed_commit, own_commit],
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(pr.get_authorship_mismatch(), 1);
+        assert!(pr.get_score().score().contains(&ScoreType::AuthorshipMismatch(1)));
+    }
+
+    #[test]
+    fn description_length_counts_only_the_body_and_is_zero_without_one() {
+        let described_pr = PullRequestDataFixture {
+            pr_author: String::from("author"),
+            main_message: Some(String::from("Añade caché")),
+            comments: vec![comment(
+                "author",
+                "A much longer follow-up",
+                Utc.ymd(2021, 3, 1).and_hms(11, 0, 0),
+            )],
+            ..Default::default()
+        }
+        .build();
+        let bodiless_pr = PullRequestDataFixture::default().build();
+
+        assert_eq!(described_pr.get_description_length(), 11);
+        assert_eq!(bodiless_pr.get_description_length(), 0);
+    }
+
+    #[test]
+    fn contentious_comments_are_counted_only_with_contention_words() {
+        let discussion = || {
+            vec![
+                comment("reviewer", "Nice one, LGTM!", Utc.ymd(2021, 3, 1).and_hms(11, 0, 0)),
+                comment(
+                    "dummy-author",
+                    "```sql\nSELECT ID FROM USERS WHERE ACTIVE\n```\nIs this query fine?",
+                    Utc.ymd(2021, 3, 1).and_hms(12, 0, 0),
+                ),
+                comment(
+                    "reviewer",
+                    "Why is this still here???",
+                    Utc.ymd(2021, 3, 1).and_hms(13, 0, 0),
+                ),
+                comment(
+                    "dummy-author",
+                    "Because it is needed, obviously.",
+                    Utc.ymd(2021, 3, 1).and_hms(14, 0, 0),
+                ),
+                comment("reviewer", "I TOLD YOU ALREADY.", Utc.ymd(2021, 3, 1).and_hms(15, 0, 0)),
+            ]
+        };
+        let pr = PullRequestDataFixture {
+            comments: discussion(),
+            scoring_settings: ScoringSettings {
+                contention_words: Some(ContentionWords::from_list("obviously")),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+        .build();
+        let unsupplied_pr = PullRequestDataFixture {
+            comments: discussion(),
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(pr.get_contentious_discussion(), Some(3));
+        assert!(pr.get_score().score().contains(&ScoreType::ContentiousDiscussion(3)));
+        assert_eq!(unsupplied_pr.get_contentious_discussion(), None);
+        assert!(!has_metric(
+            &unsupplied_pr.get_score().score(),
+            ScoreType::ContentiousDiscussion(0)
+        ));
+    }
+
+    #[test]
+    fn missing_descriptions_are_told_apart_from_empty_ones() {
+        let pr_with_body = |main_message: Option<&str>| {
+            PullRequestDataFixture {
+                main_message: main_message.map(String::from),
+                ..Default::default()
+            }
+            .build()
+        };
+        let missing_description = pr_with_body(None);
+        let empty_description = pr_with_body(Some(""));
+        let real_description = pr_with_body(Some("Adds caching"));
+
+        assert!(missing_description.is_description_missing());
+        assert!(!empty_description.is_description_missing());
+        assert!(!real_description.is_description_missing());
+
+        assert_eq!(missing_description.get_description_length(), 0);
+        assert_eq!(empty_description.get_description_length(), 0);
+        assert_eq!(real_description.get_description_length(), 12);
+
+        // the empty body is still part of the author's commentary; the missing one, there is none of
+        assert_eq!(missing_description.get_author_commentary().len(), 0);
+        assert_eq!(empty_description.get_author_commentary(), vec![""]);
+        assert_eq!(missing_description.get_amount_of_author_commentary(), 0);
+
+        assert!(missing_description
+            .get_score()
+            .score()
+            .contains(&ScoreType::MissingDescription(1.0)));
+        assert!(empty_description
+            .get_score()
+            .score()
+            .contains(&ScoreType::MissingDescription(0.0)));
+        assert!(real_description.get_score().score().contains(&ScoreType::MissingDescription(0.0)));
+    }
+
+    #[test]
+    fn commitless_prs_are_scored_without_time_to_merge() {
+        let pr = pr_with_commits(0);
+
+        assert_eq!(pr.get_first_commit_date(), None);
+
+        let score = pr.get_score().score();
+        assert!(score.contains(&ScoreType::NumberOfCommits(0)));
+        assert!(!has_metric(&score, ScoreType::TimeToMerge(0)));
+    }
+
+    #[test]
+    fn number_of_commits_is_scored_for_single_commit_prs() {
+        assert!(pr_with_commits(1).get_score().score().contains(&ScoreType::NumberOfCommits(1)));
+    }
+
+    #[test]
+    fn number_of_commits_is_scored_for_multiple_commit_prs() {
+        assert!(pr_with_commits(10).get_score().score().contains(&ScoreType::NumberOfCommits(10)));
+    }
+
+    #[test]
+    fn reaction_count_tallies_every_reaction_on_the_body() {
+        let pr = PullRequestDataFixture {
+            commits: vec![commit_root("Dummy commit", Utc.ymd(2021, 3, 1).and_hms(9, 0, 0), 1)],
+            reactions: vec![
+                reaction("reviewer", "+1"),
+                reaction("reviewer", "hooray"),
+                reaction("bystander", "-1"),
+            ],
+            ..Default::default()
+        }
+        .build();
+
+        assert!(pr.get_score().score().contains(&ScoreType::ReactionCount(3)));
+    }
+
+    #[test]
+    fn reviewed_file_coverage_is_the_ratio_of_files_commented_on_by_others() {
+        let commented_at = Utc.ymd(2021, 3, 1).and_hms(10, 0, 0);
+        let five_file_diff = format!(
+            "{}{}",
+            THREE_FILE_DIFF,
+            ["src/qux.rs", "src/quux.rs"]
+                .iter()
+                .map(|path| format!(
+                    "diff --git a/{0} b/{0}\n\
+                    index 1111111..2222222 100644\n\
+                    --- a/{0}\n\
+                    +++ b/{0}\n\
+                    @@ -1,1 +1,2 @@\n \
+                    fn foo() {{}}\n\
+                    +fn bar() {{}}\n",
+                    path
+                ))
+                .collect::<String>()
+        );
+        let pr = PullRequestDataFixture {
+            pr_author: "octocat".to_string(),
+            patch_set: patch_set(&five_file_diff),
+            commit_comments: vec![
+                commit_comment("reviewer", "src/foo.rs", commented_at),
+                commit_comment("another-reviewer", "src/foo.rs", commented_at),
+                commit_comment("reviewer", "src/qux.rs", commented_at),
+                // the author's own remarks say nothing about the review's thoroughness
+                commit_comment("octocat", "src/bar.rs", commented_at),
+            ],
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(pr.patch_set().files().len(), 5);
+        assert_eq!(pr.get_reviewed_file_coverage(), 0.4);
+        assert!(pr.get_score().score().contains(&ScoreType::ReviewedFileCoverage(0.4)));
+
+        let empty_pr = PullRequestDataFixture::default().build();
+        assert_eq!(empty_pr.get_reviewed_file_coverage(), 0.0);
+    }
+
+    #[test]
+    fn files_changed_counts_every_file_in_the_diff() {
+        let single_file_pr = pr_with_commits(1);
+        let three_file_pr = PullRequestDataFixture {
+            commits: vec![commit_root("Dummy commit", Utc.ymd(2021, 3, 1).and_hms(9, 0, 0), 1)],
+            patch_set: patch_set(THREE_FILE_DIFF),
+            ..Default::default()
+        }
+        .build();
+
+        assert!(single_file_pr.get_score().score().contains(&ScoreType::FilesChanged(1)));
+        assert!(three_file_pr.get_score().score().contains(&ScoreType::FilesChanged(3)));
+    }
+
+    #[test]
+    fn excluded_files_do_not_count_towards_line_metrics() {
+        let lock_file_diff = format!(
+            "{}diff --git a/Cargo.lock b/Cargo.lock\n\
+            index 1111111..2222222 100644\n\
+            --- a/Cargo.lock\n\
+            +++ b/Cargo.lock\n\
+            @@ -1,1 +1,10001 @@\n \
+            # lock file\n{}",
+            SINGLE_LINE_DIFF,
+            "+dependency\n".repeat(10000)
+        );
+        let pr_with_diff = |diff: &str, excluded_paths: Vec<Pattern>| {
+            PullRequestDataFixture {
+                patch_set: patch_set(diff),
+                scoring_settings: ScoringSettings {
+                    excluded_paths,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+            .build()
+        };
+
+        let plain_pr = pr_with_diff(SINGLE_LINE_DIFF, ScoringSettings::default().excluded_paths);
+        let excluding_pr = pr_with_diff(&lock_file_diff, ScoringSettings::default().excluded_paths);
+        let including_pr = pr_with_diff(&lock_file_diff, Vec::new());
+
+        assert_eq!(excluding_pr.get_amount_of_changes(), plain_pr.get_amount_of_changes());
+        assert_eq!(
+            excluding_pr.get_amount_of_net_added_non_test_lines(),
+            plain_pr.get_amount_of_net_added_non_test_lines()
+        );
+        assert_eq!(including_pr.get_amount_of_changes(), plain_pr.get_amount_of_changes() + 10000);
+    }
+
+    #[test]
+    fn review_iterations_are_split_by_the_authors_activity() {
+        let day = |d: u32, h: u32| Utc.ymd(2021, 3, d).and_hms(h, 0, 0);
+
+        let pr = PullRequestDataFixture {
+            pr_author: String::from("author"),
+            created_at: day(1, 10),
+            commits: vec![
+                commit_root("Initial implementation", day(1, 9), 1),
+                commit_root("Address review comments", day(2, 9), 1),
+            ],
+            comments: vec![
+                comment("author", "Fixed, thanks!", day(2, 12)),
+                comment("reviewer-a", "Almost there", day(2, 14)), // reviewers' comments are not reviews
+            ],
+            reviews: vec![
+                // first round; two reviewers, no author activity in between
+                review("reviewer-a", "CHANGES_REQUESTED", Some(day(1, 12))),
+                review("reviewer-b", "COMMENTED", Some(day(1, 13))),
+                // second round; after the 'Address review comments' commit
+                review("reviewer-a", "CHANGES_REQUESTED", Some(day(2, 11))),
+                review("author", "COMMENTED", Some(day(2, 11))), // author's replies are not reviews
+                // third round; after the author's reply
+                review("reviewer-a", "APPROVED", Some(day(3, 10))),
+                review("reviewer-b", "PENDING", None),
+            ],
+            patch_set: patch_set(SINGLE_LINE_DIFF),
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(pr.get_review_iterations(), 3);
+        assert_eq!(pr_with_commits(1).get_review_iterations(), 0);
+    }
+
+    #[test]
+    fn participants_and_reviewers_are_listed_in_a_stable_sorted_order() {
+        let day = |h: u32| Utc.ymd(2021, 3, 1).and_hms(h, 0, 0);
+
+        let pr = PullRequestDataFixture {
+            pr_author: String::from("author"),
+            comments: vec![
+                comment("zoe", "Nice", day(11)),
+                comment("author", "Thanks", day(12)),
+                comment("mike", "+1", day(13)),
+            ],
+            reviews: vec![
+                review("yuri", "APPROVED", Some(day(14))),
+                review("adam", "CHANGES_REQUESTED", Some(day(15))),
+                review("yuri", "COMMENTED", Some(day(16))),
+            ],
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(pr.get_non_authoring_participants(), vec!["adam", "mike", "yuri", "zoe"]);
+        assert_eq!(pr.get_reviewers(), vec!["adam", "yuri"]);
+    }
+
+    #[test]
+    fn participants_differing_only_in_casing_are_counted_once() {
+        let day = |h: u32| Utc.ymd(2021, 3, 1).and_hms(h, 0, 0);
+
+        let pr = PullRequestDataFixture {
+            pr_author: String::from("Author"),
+            comments: vec![
+                comment("Octocat", "Nice", day(11)),
+                comment("author", "Thanks", day(12)),
+            ],
+            reviews: vec![
+                review("octocat", "APPROVED", Some(day(14))),
+                review("AUTHOR", "COMMENTED", Some(day(15))),
+            ],
+            commit_comments: vec![commit_comment("OctoCat", "src/main.rs", day(13))],
+            ..Default::default()
+        }
+        .build();
+
+        // the author is left out regardless of the casing they commented (or self-reviewed) under
+        assert_eq!(pr.get_non_authoring_participants(), vec!["octocat"]);
+        assert_eq!(pr.get_reviewers(), vec!["octocat"]);
+
+        let score = pr.get_score().score();
+        assert!(score.contains(&ScoreType::AmountOfParticipants(1)));
+        assert!(score.contains(&ScoreType::AmountOfReviewers(1)));
+    }
+
+    #[test]
+    fn authors_commenting_under_another_casing_do_not_count_as_first_reviewers() {
+        let day = |h: u32| Utc.ymd(2021, 3, 1).and_hms(h, 0, 0);
+
+        let pr = PullRequestDataFixture {
+            pr_author: String::from("Author"),
+            commit_comments: vec![
+                commit_comment("AUTHOR", "src/foo.rs", day(11)),
+                commit_comment("octocat", "src/foo.rs", day(13)),
+            ],
+            reviews: vec![review("author", "COMMENTED", Some(day(12)))],
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(pr.get_first_review_date(), Some(day(13)));
+    }
+
+    #[test]
+    fn authors_commenting_under_another_casing_interrupt_review_iterations() {
+        let day = |h: u32| Utc.ymd(2021, 3, 1).and_hms(h, 0, 0);
+
+        let pr = PullRequestDataFixture {
+            pr_author: String::from("Author"),
+            comments: vec![comment("AUTHOR", "Done", day(12))],
+            reviews: vec![
+                review("octocat", "CHANGES_REQUESTED", Some(day(11))),
+                review("author", "COMMENTED", Some(day(13))),
+                review("octocat", "APPROVED", Some(day(14))),
+            ],
+            ..Default::default()
+        }
+        .build();
+
+        // the author's self-review is no review round of its own; their comment still splits both rounds
+        assert_eq!(pr.get_review_iterations(), 2);
+    }
+
+    #[test]
+    fn author_commentary_spans_every_casing_of_the_authors_login() {
+        let day = |h: u32| Utc.ymd(2021, 3, 1).and_hms(h, 0, 0);
+        let mut self_review = review("AUTHOR", "COMMENTED", Some(day(13)));
+        self_review.body = Some(String::from("Self-review"));
+
+        let pr = PullRequestDataFixture {
+            pr_author: String::from("Author"),
+            main_message: Some(String::from("Body")),
+            comments: vec![
+                comment("author", "Comment", day(11)),
+                comment("octocat", "Not theirs", day(11)),
+            ],
+            commit_comments: vec![commit_comment("AuThOr", "src/foo.rs", day(12))],
+            reviews: vec![self_review],
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(pr.get_author_commentary().len(), 4);
+    }
+
+    #[test]
+    fn author_response_latency_recognizes_replies_under_another_casing() {
+        let day = |h: u32| Utc.ymd(2021, 3, 1).and_hms(h, 0, 0);
+
+        let pr = PullRequestDataFixture {
+            pr_author: String::from("Author"),
+            comments: vec![
+                comment("octocat", "Why?", day(10)),
+                comment("AUTHOR", "Because", day(12)),
+            ],
+            reviews: vec![review("author", "COMMENTED", Some(day(11)))],
+            ..Default::default()
+        }
+        .build();
+
+        // neither their reply is taken for a reviewer's, nor their self-review for something to reply to
+        assert_eq!(pr.get_author_response_latency(), Some(2));
+    }
+
+    #[test]
+    fn self_reviews_under_another_casing_do_not_keep_prs_from_being_self_merged() {
+        let created_at = Utc.ymd(2021, 3, 1).and_hms(10, 0, 0);
+
+        let pr = PullRequestDataFixture {
+            pr_author: String::from("Author"),
+            reviews: vec![review("AUTHOR", "COMMENTED", Some(created_at))],
+            created_at,
+            merged_at: Some(created_at + chrono::Duration::minutes(1)),
+            ..Default::default()
+        }
+        .build();
+
+        assert!(pr.is_likely_self_merged());
+    }
+
+    #[test]
+    fn outside_contributors_are_left_out_of_participants_and_reviewers_under_team_scoping() {
+        let day = |h: u32| Utc.ymd(2021, 3, 1).and_hms(h, 0, 0);
+
+        let pr = PullRequestDataFixture {
+            pr_author: String::from("author"),
+            comments: vec![
+                comment("Zoe", "Nice", day(11)),
+                comment("outsider", "Could this land in the next release?", day(12)),
+            ],
+            reviews: vec![
+                review("yuri", "APPROVED", Some(day(14))),
+                review("outsider", "APPROVED", Some(day(15))),
+            ],
+            scoring_settings: ScoringSettings {
+                team_members: Some(TeamMembers::from_list("author\nyuri\nzoe").unwrap()),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(pr.get_non_authoring_participants(), vec!["yuri", "zoe"]);
+        assert_eq!(pr.get_reviewers(), vec!["yuri"]);
+
+        let score = pr.get_score().score();
+        assert!(score.contains(&ScoreType::AmountOfParticipants(2)));
+        assert!(score.contains(&ScoreType::AmountOfReviewers(1)));
+    }
+
+    #[test]
+    fn unresolved_threads_are_only_scored_once_retrieved() {
+        let thread = |is_resolved: bool| ReviewThread {
+            is_resolved,
+        };
+
+        let pr_with_threads = |review_threads: Option<Vec<ReviewThread>>| {
+            PullRequestDataFixture {
+                review_threads,
+                ..PullRequestDataFixture::scorable()
+            }
+            .build()
+        };
+
+        let pr = pr_with_threads(Some(vec![thread(true), thread(false), thread(false)]));
+        assert!(pr.get_score().score().contains(&ScoreType::UnresolvedThreads(2)));
+
+        let pr = pr_with_threads(None);
+        assert!(!has_metric(&pr.get_score().score(), ScoreType::UnresolvedThreads(0)));
+    }
+
+    #[test]
+    fn time_in_draft_adds_up_every_draft_interval_until_ready() {
+        let pr_with_timeline = |draft: bool, timeline| {
+            PullRequestDataFixture {
+                draft,
+                timeline,
+                ..Default::default()
+            }
+            .build()
+        };
+
+        // not retrieved at all
+        assert_eq!(pr_with_timeline(false, None).get_time_in_draft(), None);
+
+        // never a draft
+        assert_eq!(pr_with_timeline(false, Some(Vec::new())).get_time_in_draft(), Some(0));
+
+        // opened as a draft at 10:00 and ready by 16:00; then back to draft from 20:00 until 08:00
+        let pr = pr_with_timeline(
+            false,
+            Some(vec![
+                timeline_event("convert_to_draft", Utc.ymd(2021, 3, 1).and_hms(20, 0, 0)),
+                timeline_event("ready_for_review", Utc.ymd(2021, 3, 1).and_hms(16, 0, 0)),
+                timeline_event("ready_for_review", Utc.ymd(2021, 3, 2).and_hms(8, 0, 0)),
+            ]),
+        );
+        assert_eq!(pr.get_time_in_draft(), Some(18));
+        assert!(pr.get_score().score().contains(&ScoreType::TimeInDraft(18)));
+
+        // closed while still a draft (as it was opened)
+        assert_eq!(pr_with_timeline(true, Some(Vec::new())).get_time_in_draft(), Some(24));
+    }
+
+    #[test]
+    fn squashed_prs_take_their_lead_time_as_time_to_merge_and_are_flagged() {
+        let day = |d: u32| Utc.ymd(2021, 3, d).and_hms(10, 0, 0);
+        let pr_committed_on = |commit_days: &[u32], merge_style: MergeStyle| {
+            PullRequestDataFixture {
+                commits: commit_days
+                    .iter()
+                    .map(|d| commit_root("Dummy commit", day(*d), 1))
+                    .collect(),
+                created_at: day(10),
+                merged_at: Some(day(20)),
+                closed_at: Some(day(20)),
+                scoring_settings: ScoringSettings {
+                    merge_style,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+            .build()
+        };
+        let time_to_merge_and_flags = |pr: PullRequestData| {
+            let score = pr.get_score();
+            let time_to_merge = score
+                .score_types()
+                .iter()
+                .find(|score_type| matches!(score_type, ScoreType::TimeToMerge(_)))
+                .cloned();
+
+            (time_to_merge, score.flags().to_vec())
+        };
+
+        // a long-lived branch, whose work started well before the PR was opened
+        assert_eq!(
+            time_to_merge_and_flags(pr_committed_on(&[1, 5], MergeStyle::Auto)),
+            (Some(ScoreType::TimeToMerge(19)), Vec::new())
+        );
+        // a single commit pushed before the PR was opened is still trusted
+        assert_eq!(
+            time_to_merge_and_flags(pr_committed_on(&[5], MergeStyle::Auto)),
+            (Some(ScoreType::TimeToMerge(15)), Vec::new())
+        );
+
+        // squashed (and force-pushed) after being opened; it would otherwise be 5 days
+        let squashed = pr_committed_on(&[15], MergeStyle::Auto);
+        assert!(squashed.is_squashed());
+        assert_eq!(
+            time_to_merge_and_flags(squashed),
+            (Some(ScoreType::TimeToMerge(10)), vec![ScoreFlag::SquashedTimeToMerge])
+        );
+
+        // the team's merge style overrides detection altogether
+        assert_eq!(
+            time_to_merge_and_flags(pr_committed_on(&[15], MergeStyle::Merge)),
+            (Some(ScoreType::TimeToMerge(5)), Vec::new())
+        );
+        assert_eq!(
+            time_to_merge_and_flags(pr_committed_on(&[1, 5], MergeStyle::Squash)),
+            (Some(ScoreType::TimeToMerge(10)), vec![ScoreFlag::SquashedTimeToMerge])
+        );
+    }
+
+    #[test]
+    fn green_merges_tell_missing_checks_apart_from_failing_ones() {
+        let pr_with_checks = |merged: bool, checks| {
+            let fixture = PullRequestDataFixture::default();
+            PullRequestDataFixture {
+                merged_at: if merged {
+                    fixture.merged_at
+                } else {
+                    None
+                },
+                checks,
+                ..fixture
+            }
+            .build()
+        };
+
+        assert_eq!(pr_with_checks(true, Some(ChecksOutcome::Passing)).get_green_merge(), Some(1.0));
+        assert_eq!(pr_with_checks(true, Some(ChecksOutcome::Failing)).get_green_merge(), Some(0.0));
+        assert_eq!(pr_with_checks(true, Some(ChecksOutcome::Pending)).get_green_merge(), Some(0.0));
+
+        // nothing to tell about checks that were not configured, not retrieved or not merged on
+        assert_eq!(pr_with_checks(true, Some(ChecksOutcome::NoChecks)).get_green_merge(), None);
+        assert_eq!(pr_with_checks(true, None).get_green_merge(), None);
+        assert_eq!(pr_with_checks(false, Some(ChecksOutcome::Passing)).get_green_merge(), None);
+        assert!(!has_metric(
+            &pr_with_checks(true, Some(ChecksOutcome::NoChecks)).get_score().score(),
+            ScoreType::GreenMergeRatio(0.0)
+        ));
+    }
+
+    #[test]
+    fn author_response_latency_averages_gaps_until_the_authors_next_reply() {
+        let day = |d: u32, h: u32| Utc.ymd(2021, 3, d).and_hms(h, 0, 0);
+
+        let pr = PullRequestDataFixture {
+            pr_author: String::from("author"),
+            comments: vec![
+                comment("reviewer-a", "Why not a map?", day(1, 12)),
+                comment("author", "Good point, done", day(1, 16)), // 4 hours after reviewer-a's comment
+                comment("author", "Also renamed it", day(1, 17)),
+                comment("reviewer-b", "LGTM", day(3, 10)), // never replied to
+            ],
+            reviews: vec![
+                review("reviewer-b", "CHANGES_REQUESTED", Some(day(1, 14))), // 2 hours after
+                review("author", "COMMENTED", Some(day(1, 15))), // author's reviews are no replies
+                review("reviewer-a", "PENDING", None),
+            ],
+            ..Default::default()
+        }
+        .build();
+
+        assert_eq!(pr.get_author_response_latency(), Some(3));
+        assert_eq!(reviewed_pr(Vec::new()).get_author_response_latency(), None);
+    }
+
+    #[test]
+    fn only_swiftly_merged_unreviewed_prs_are_deemed_self_merged() {
+        let opened_at = Utc.ymd(2021, 3, 1).and_hms(12, 0, 0);
+        let pr = |merged_after_minutes: Option<i64>, reviews: Vec<Review>| {
+            PullRequestDataFixture {
+                pr_author: String::from("author"),
+                reviews,
+                created_at: opened_at,
+                merged_at: merged_after_minutes
+                    .map(|minutes| opened_at + chrono::Duration::minutes(minutes)),
+                ..Default::default()
+            }
+            .build()
+        };
+
+        assert!(pr(Some(5), Vec::new()).is_likely_self_merged());
+        assert!(pr(Some(5), vec![review("author", "APPROVED", Some(opened_at))])
+            .is_likely_self_merged());
+        assert!(!pr(Some(5), vec![review("reviewer", "APPROVED", Some(opened_at))])
+            .is_likely_self_merged());
+        assert!(!pr(Some(3 * 60), Vec::new()).is_likely_self_merged()); // long enough to be looked at
+        assert!(!pr(None, Vec::new()).is_likely_self_merged());
+    }
+}
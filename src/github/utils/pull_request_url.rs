@@ -0,0 +1,109 @@
+//! A [`PullRequest`](octocrab::models::pulls::PullRequest)'s web URL (ie:
+//! `https://github.com/rust-lang/rust/pull/42`), decomposed into the owner, repository & PR number it
+//! points to.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// Host serving github.com's web pages; any other host is taken for a GitHub Enterprise instance.
+const GITHUB_HOST: &str = "github.com";
+
+lazy_static! {
+    /// Matches `{scheme}://{host}/{owner}/{repo}/pull/{n}`, optionally followed by any of the PR's tabs
+    /// (ie: `/files`), a query or a fragment.
+    static ref PULL_REQUEST_URL_REGEX: Regex = Regex::new(
+        r"^(?P<scheme>https?)://(?P<host>[^/\s]+)/(?P<owner>[^/\s]+)/(?P<repository>[^/\s]+)/pull/(?P<pr_number>\d+)(?:[/?#]\S*)?$"
+    )
+    .unwrap();
+}
+
+/// The components of a [`PullRequest`](octocrab::models::pulls::PullRequest)'s web URL.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PullRequestUrl {
+    pub scheme: String,
+    pub host: String,
+    pub owner: String,
+    pub repository: String,
+    pub pr_number: u64,
+}
+
+impl PullRequestUrl {
+    /// Parses a PR's web URL, as copied off either github.com or a GitHub Enterprise instance.
+    pub fn parse(url: &str) -> Result<Self, String> {
+        let captures = PULL_REQUEST_URL_REGEX.captures(url.trim()).ok_or_else(|| {
+            format!(
+                "Supplied value must be a PR's URL (ie: 'https://github.com/owner/repository/pull/42'), \
+                but was {}",
+                url
+            )
+        })?;
+
+        Ok(PullRequestUrl {
+            scheme: captures["scheme"].to_string(),
+            host: captures["host"].to_lowercase(),
+            owner: captures["owner"].to_string(),
+            repository: captures["repository"].to_string(),
+            pr_number: captures["pr_number"]
+                .parse()
+                .map_err(|_| format!("PR number is out of range in {}", url))?,
+        })
+    }
+
+    /// Returns the base URL of the GitHub Enterprise instance's REST API serving this PR; or [`None`] if
+    /// it is hosted on github.com.
+    pub fn api_base_url(&self) -> Option<String> {
+        if self.host == GITHUB_HOST || self.host == format!("www.{}", GITHUB_HOST) {
+            None
+        } else {
+            Some(format!("{}://{}/api/v3", self.scheme, self.host))
+        }
+    }
+}
+
+#[cfg(test)]
+mod pull_request_url_tests {
+    use crate::github::utils::pull_request_url::PullRequestUrl;
+
+    #[test]
+    fn github_urls_are_decomposed_into_their_components() {
+        let url = PullRequestUrl::parse("https://github.com/rust-lang/rust/pull/42").unwrap();
+
+        assert_eq!(url.owner, "rust-lang");
+        assert_eq!(url.repository, "rust");
+        assert_eq!(url.pr_number, 42);
+        assert_eq!(url.api_base_url(), None);
+
+        // URLs copied off any of the PR's tabs point to the very same PR
+        assert_eq!(
+            PullRequestUrl::parse("https://github.com/rust-lang/rust/pull/42/files#diff-1")
+                .unwrap(),
+            url
+        );
+    }
+
+    #[test]
+    fn enterprise_urls_point_to_their_instance_rest_api() {
+        let url = PullRequestUrl::parse("https://GitHub.MyCorp.com/backend/api/pull/7").unwrap();
+
+        assert_eq!(url.owner, "backend");
+        assert_eq!(url.repository, "api");
+        assert_eq!(url.pr_number, 7);
+        assert_eq!(url.api_base_url(), Some("https://github.mycorp.com/api/v3".to_string()));
+    }
+
+    #[test]
+    fn malformed_urls_are_rejected() {
+        for url in [
+            "github.com/rust-lang/rust/pull/42",
+            "https://github.com/rust-lang/rust/issues/42",
+            "https://github.com/rust-lang/rust/pull/",
+            "https://github.com/rust-lang/pull/42",
+            "ftp://github.com/rust-lang/rust/pull/42",
+            "https://github.com/rust-lang/rust/pull/99999999999999999999999",
+        ]
+        .iter()
+        {
+            assert!(PullRequestUrl::parse(url).is_err(), "{} should be rejected", url);
+        }
+    }
+}
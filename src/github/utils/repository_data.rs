@@ -1,203 +1,534 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::mem::discriminant;
 
-use chrono::{Date, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use itertools::Itertools;
 use log::trace;
-use num::integer;
 
-use crate::github::utils::pull_request_data::{PullRequestData, PullRequestDataResult};
+use crate::github::utils::pull_request_data::PullRequestData;
 use crate::scoring::scorable::Scorable;
-use crate::scoring::score::{Score, ScoreType};
+use crate::scoring::score::{Score, ScoreFlag, ScoreType};
+use crate::scoring::scoring_settings::ScoringSettings;
 
-pub type RepositoryData = Vec<PullRequestDataResult>;
+/// The instants at which a repository's PRs were opened and closed; the raw material of its Pull Request
+/// Flow Ratio. Open PRs contribute to `opened` alone.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PullRequestFlow {
+    pub opened: Vec<DateTime<Utc>>,
+    pub closed: Vec<DateTime<Utc>>,
+}
 
-impl Scorable for RepositoryData {
-    fn get_score(&self) -> Score {
-        // iterate over all individual PRs -
-        let individual_prs_data: Vec<&PullRequestData> = self
-            .into_iter()
-            .filter(|prdr| prdr.is_ok())
-            .map(|prdr| prdr.as_ref().unwrap())
+impl PullRequestFlow {
+    /// Gathers the [`PullRequestFlow`] of the provided array of [`PullRequestData`]s. Merge-PRs are left
+    /// out of it, unless their [`ScoringSettings`] explicitly include them; regardless of whether the
+    /// caller has filtered them out already.
+    pub fn of_sample(prs: &[&PullRequestData]) -> Self {
+        let prs: Vec<&&PullRequestData> = prs
+            .iter()
+            .filter(|prd| prd.scoring_settings().include_merge_prs || !prd.is_merge_pr())
             .collect();
 
-        individual_prs_data.get_score()
+        PullRequestFlow {
+            opened: prs.iter().map(|prd| prd.created_at()).collect(),
+            closed: prs.iter().filter_map(|prd| prd.closed_at()).collect(),
+        }
+    }
+
+    /// Merges several [`PullRequestFlow`]s (ie: those of every repository of an organization) into one.
+    pub fn merge<'a>(flows: impl IntoIterator<Item = &'a PullRequestFlow>) -> Self {
+        flows.into_iter().fold(PullRequestFlow::default(), |mut acc, flow| {
+            acc.opened.extend(&flow.opened);
+            acc.closed.extend(&flow.closed);
+            acc
+        })
     }
 }
 
-impl Scorable for Vec<&PullRequestData> {
-    fn get_score(&self) -> Score {
-        let total_amount_of_prs = self.iter().len() as u64;
-
-        // calculate their individual scores -
-        let scores: Vec<ScoreType> =
-            self.iter().map(|prd| prd.get_score()).flat_map(|score| score.score()).collect();
-
-        // subdivide their individual scores by type -
-        let mut total_amount_of_participants: u64 = 0;
-        let mut total_amount_of_reviewers: u64 = 0;
-        let mut total_attachments: u64 = 0;
-        let mut total_author_commentary_to_changes_ratio: f64 = 0.0;
-        let mut total_pull_requests_discussion_size: usize = 0;
-        let mut total_pull_request_lead_time: u64 = 0;
-        let mut total_pull_request_size: usize = 0;
-        let mut total_test_to_code_ratio: f64 = 0.0;
-        let mut total_time_to_merge: u64 = 0;
-
-        for score_type in scores.iter() {
+/// Produces the aggregated [`Score`] of the provided array of [`PullRequestData`]s, calculating its Pull
+/// Request Flow Ratio over `pull_request_flow` instead of over the PRs themselves (see [`FlowRatioSource`](
+/// crate::scoring::scoring_settings::FlowRatioSource)). The sample is aggregated according to the
+/// repository-level `scoring_settings`, regardless of its PRs' own; which makes no difference, unless the
+/// sample is empty or gathered across repositories. Any [`ScoreFlag`] raised by one of its PRs is raised by
+/// the aggregate as well, for their caveats apply to its metrics too.
+pub fn get_score_with_flow(
+    prs: &[&PullRequestData], pull_request_flow: &PullRequestFlow,
+    scoring_settings: &ScoringSettings,
+) -> Score {
+    trace!(
+        "Aggregating [{}] PRs' scores using their [{}].",
+        prs.len(),
+        scoring_settings.aggregation_strategy
+    );
+
+    // calculate their individual scores -
+    let scores: Vec<Score> = prs.iter().map(|prd| prd.get_score()).collect();
+    let flags: Vec<ScoreFlag> = scores.iter().flat_map(|score| score.flags().to_vec()).collect();
+
+    aggregate_scores(
+        scores.into_iter().map(|score| score.score()).collect(),
+        Some(calculate_pull_request_flow_ratio(pull_request_flow, scoring_settings)),
+        Some(calculate_reviewer_diversity(prs)),
+        calculate_review_participation_breadth(prs),
+        Some(calculate_self_merged_prs(prs)),
+        // unless closed-unmerged PRs are sampled, the rate would always be zero
+        if scoring_settings.include_closed_unmerged {
+            calculate_rejection_rate(prs)
+        } else {
+            None
+        },
+        scoring_settings,
+    )
+    .flagged_with(&flags)
+}
+
+/// Aggregates the individual PRs' `scores` (one set of [`ScoreType`]s per PR) into a single [`Score`],
+/// according to the [`ScoringSettings`]. Metrics that only apply to a whole sample cannot be derived
+/// from its PRs' [`ScoreType`]s, so they are supplied as-is - and left out if [`None`].
+pub fn aggregate_scores(
+    scores: Vec<Vec<ScoreType>>, pull_request_flow_ratio: Option<f64>,
+    reviewer_diversity: Option<u64>, review_participation_breadth: Option<f64>,
+    self_merged_prs: Option<u64>, rejection_rate: Option<f64>, scoring_settings: &ScoringSettings,
+) -> Score {
+    let aggregation_strategy = scoring_settings.aggregation_strategy;
+
+    // subdivide their individual scores by type -
+    // (PRs for which a particular metric does not apply simply do not contribute to its sample)
+    let mut after_hours_activity_ratio: Vec<f64> = Vec::new();
+    let mut amount_of_participants: Vec<f64> = Vec::new();
+    let mut amount_of_reviewers: Vec<f64> = Vec::new();
+    let mut attachments: Vec<f64> = Vec::new();
+    // (ratios relative to a PR's changes are kept alongside its size, in case they get weighted by it)
+    let mut author_commentary_to_changes_ratio: Vec<(f64, f64)> = Vec::new();
+    let mut author_response_latency: Vec<f64> = Vec::new();
+    let mut authorship_mismatch: Vec<f64> = Vec::new();
+    let mut binary_files_changed: Vec<f64> = Vec::new();
+    let mut business_days_lead_time: Vec<f64> = Vec::new();
+    let mut checklist_completion: Vec<f64> = Vec::new();
+    let mut commit_message_quality: Vec<f64> = Vec::new();
+    let mut contentious_discussion: Vec<f64> = Vec::new();
+    let mut deletion_ratio: Vec<f64> = Vec::new();
+    let mut description_length: Vec<f64> = Vec::new();
+    let mut files_changed: Vec<f64> = Vec::new();
+    let mut first_response_wait: Vec<f64> = Vec::new();
+    let mut green_merge_ratio: Vec<f64> = Vec::new();
+    let mut linked_issues: Vec<f64> = Vec::new();
+    let mut missing_description: Vec<f64> = Vec::new();
+    let mut number_of_commits: Vec<f64> = Vec::new();
+    let mut pull_requests_discussion_size: Vec<f64> = Vec::new();
+    let mut pull_request_lead_time: Vec<f64> = Vec::new();
+    let mut pull_request_size: Vec<f64> = Vec::new();
+    let mut reaction_count: Vec<f64> = Vec::new();
+    let mut review_iterations: Vec<f64> = Vec::new();
+    let mut reviewed_file_coverage: Vec<f64> = Vec::new();
+    let mut signed_commit_ratio: Vec<f64> = Vec::new();
+    let mut test_to_code_ratio: Vec<(f64, f64)> = Vec::new();
+    let mut time_in_draft: Vec<f64> = Vec::new();
+    let mut time_to_approval: Vec<f64> = Vec::new();
+    let mut time_to_first_review: Vec<f64> = Vec::new();
+    let mut time_to_merge: Vec<f64> = Vec::new();
+    let mut unresolved_threads: Vec<f64> = Vec::new();
+
+    for pr_scores in scores.iter() {
+        let pr_size = pr_scores
+            .iter()
+            .find_map(|score_type| match score_type {
+                ScoreType::PullRequestSize(prs) => Some(*prs as f64),
+                _ => None,
+            })
+            .unwrap_or(0.0);
+
+        for score_type in pr_scores.iter() {
             match score_type {
+                ScoreType::AfterHoursActivityRatio(ahar) => {
+                    after_hours_activity_ratio.push(*ahar);
+                    trace!("Adding {} after-hours-activity-ratio to sample.", ahar)
+                }
                 ScoreType::AmountOfParticipants(aop) => {
-                    total_amount_of_participants += aop;
-                    trace!(
-                        "Adding {} participants to count. Total count so far = {}",
-                        aop,
-                        total_amount_of_participants
-                    )
+                    amount_of_participants.push(*aop as f64);
+                    trace!("Adding {} participants to sample.", aop)
                 }
                 ScoreType::AmountOfReviewers(aor) => {
-                    total_amount_of_reviewers += aor;
-                    trace!(
-                        "Adding {} reviewers to count. Total count so far = {}",
-                        aor,
-                        total_amount_of_reviewers
-                    )
+                    amount_of_reviewers.push(*aor as f64);
+                    trace!("Adding {} reviewers to sample.", aor)
                 }
                 ScoreType::Attachments(a) => {
-                    total_attachments += a;
-                    trace!(
-                        "Adding {} attachments to count. Total count so far = {}",
-                        a,
-                        total_attachments
-                    )
+                    attachments.push(*a as f64);
+                    trace!("Adding {} attachments to sample.", a)
                 }
                 ScoreType::AuthorCommentaryToChangesRatio(actcr) => {
-                    total_author_commentary_to_changes_ratio += actcr;
-                    trace!(
-                        "Adding {} author-comments-to-changes-ratio to count. Total count so far = {}",
-                        actcr,
-                        total_author_commentary_to_changes_ratio
-                    )
+                    author_commentary_to_changes_ratio.push((*actcr, pr_size));
+                    trace!("Adding {} author-comments-to-changes-ratio to sample.", actcr)
+                }
+                ScoreType::AuthorResponseLatency(arl) => {
+                    author_response_latency.push(*arl as f64);
+                    trace!("Adding {} hours of author-response-latency to sample.", arl)
+                }
+                ScoreType::AuthorshipMismatch(am) => {
+                    authorship_mismatch.push(*am as f64);
+                    trace!("Adding {} mismatched commit authors to sample.", am)
+                }
+                ScoreType::BinaryFilesChanged(bfc) => {
+                    binary_files_changed.push(*bfc as f64);
+                    trace!("Adding {} changed binary files to sample.", bfc)
+                }
+                ScoreType::BusinessDaysLeadTime(bdlt) => {
+                    business_days_lead_time.push(*bdlt as f64);
+                    trace!("Adding {} business days of lead time to sample.", bdlt)
+                }
+                ScoreType::ChecklistCompletion(cc) => {
+                    checklist_completion.push(*cc);
+                    trace!("Adding {} checklist-completion to sample.", cc)
+                }
+                ScoreType::CommitMessageQuality(cmq) => {
+                    commit_message_quality.push(*cmq);
+                    trace!("Adding {} commit-message-quality to sample.", cmq)
+                }
+                ScoreType::ContentiousDiscussion(cd) => {
+                    contentious_discussion.push(*cd as f64);
+                    trace!("Adding {} contentious comments to sample.", cd)
+                }
+                ScoreType::DeletionRatio(dr) => {
+                    deletion_ratio.push(*dr);
+                    trace!("Adding {} deletion-ratio to sample.", dr)
+                }
+                ScoreType::DescriptionLength(dl) => {
+                    description_length.push(*dl as f64);
+                    trace!("Adding {} characters of description to sample.", dl)
+                }
+                ScoreType::FilesChanged(fc) => {
+                    files_changed.push(*fc as f64);
+                    trace!("Adding {} changed files to sample.", fc)
+                }
+                ScoreType::FirstResponseWait(frw) => {
+                    first_response_wait.push(*frw as f64);
+                    trace!("Adding {} hours of first-response wait to sample.", frw)
+                }
+                ScoreType::GreenMergeRatio(gmr) => {
+                    green_merge_ratio.push(*gmr);
+                    trace!("Adding {} green merge to sample.", gmr)
+                }
+                ScoreType::LinkedIssues(li) => {
+                    linked_issues.push(*li as f64);
+                    trace!("Adding {} linked issues to sample.", li)
+                }
+                ScoreType::MissingDescription(md) => {
+                    missing_description.push(*md);
+                    trace!("Adding {} missing description to sample.", md)
+                }
+                ScoreType::NumberOfCommits(noc) => {
+                    number_of_commits.push(*noc as f64);
+                    trace!("Adding {} commits to sample.", noc)
                 }
                 ScoreType::PullRequestsDiscussionSize(prds) => {
-                    total_pull_requests_discussion_size += prds;
-                    trace!(
-                        "Adding {} lines of discussion to count. Total count so far = {}",
-                        prds,
-                        total_pull_requests_discussion_size
-                    )
+                    pull_requests_discussion_size.push(*prds as f64);
+                    trace!("Adding {} lines of discussion to sample.", prds)
                 }
                 ScoreType::PullRequestFlowRatio(_) => {
-                    // PullRequestFlowRatio will be calculated below; there is nothing to sum here because it doesn't apply to individual PRs
+                    // PullRequestFlowRatio is supplied as a whole; there is nothing to sample here because it doesn't apply to individual PRs
                 }
                 ScoreType::PullRequestLeadTime(prlt) => {
-                    total_pull_request_lead_time += prlt;
-                    trace!(
-                        "Adding {} days of lead-time to count. Total count so far = {}",
-                        prlt,
-                        total_pull_request_lead_time
-                    )
+                    pull_request_lead_time.push(*prlt as f64);
+                    trace!("Adding {} days of lead-time to sample.", prlt)
                 }
                 ScoreType::PullRequestSize(prs) => {
-                    total_pull_request_size += prs;
-                    trace!(
-                        "Adding {} lines of code to count. Total count so far = {}",
-                        prs,
-                        total_pull_request_size
-                    )
+                    pull_request_size.push(*prs as f64);
+                    trace!("Adding {} lines of code to sample.", prs)
+                }
+                ScoreType::ReactionCount(rc) => {
+                    reaction_count.push(*rc as f64);
+                    trace!("Adding {} reactions to sample.", rc)
+                }
+                ScoreType::RejectionRate(_) => {
+                    // RejectionRate is supplied as a whole; there is nothing to sample here because it doesn't apply to individual PRs
+                }
+                ScoreType::ReviewIterations(ri) => {
+                    review_iterations.push(*ri as f64);
+                    trace!("Adding {} review iterations to sample.", ri)
+                }
+                ScoreType::ReviewParticipationBreadth(_) => {
+                    // ReviewParticipationBreadth is supplied as a whole; there is nothing to sample here because it doesn't apply to individual PRs
+                }
+                ScoreType::ReviewedFileCoverage(rfc) => {
+                    reviewed_file_coverage.push(*rfc);
+                    trace!("Adding {} reviewed-file-coverage to sample.", rfc)
+                }
+                ScoreType::ReviewerDiversity(_) => {
+                    // ReviewerDiversity is supplied as a whole; there is nothing to sample here because it doesn't apply to individual PRs
+                }
+                ScoreType::SelfMergedPrs(_) => {
+                    // SelfMergedPrs is supplied as a whole; there is nothing to sample here because it doesn't apply to individual PRs
+                }
+                ScoreType::SignedCommitRatio(scr) => {
+                    signed_commit_ratio.push(*scr);
+                    trace!("Adding {} signed-commit-ratio to sample.", scr)
                 }
                 ScoreType::TestToCodeRatio(ttcr) => {
-                    total_test_to_code_ratio += ttcr;
-                    trace!(
-                        "Adding {} test-to-code-ratio to count. Total count so far = {}",
-                        ttcr,
-                        total_test_to_code_ratio
-                    )
+                    test_to_code_ratio.push((*ttcr, pr_size));
+                    trace!("Adding {} test-to-code-ratio to sample.", ttcr)
+                }
+                ScoreType::TimeInDraft(tid) => {
+                    time_in_draft.push(*tid as f64);
+                    trace!("Adding {} hours of time-in-draft to sample.", tid)
+                }
+                ScoreType::TimeToApproval(tta) => {
+                    time_to_approval.push(*tta as f64);
+                    trace!("Adding {} hours of time-to-approval to sample.", tta)
+                }
+                ScoreType::TimeToFirstReview(ttfr) => {
+                    time_to_first_review.push(*ttfr as f64);
+                    trace!("Adding {} hours of time-to-first-review to sample.", ttfr)
                 }
                 ScoreType::TimeToMerge(ttm) => {
-                    total_time_to_merge += ttm;
-                    trace!(
-                        "Adding {} days of time-to-merge to count. Total count so far = {}",
-                        ttm,
-                        total_time_to_merge
-                    )
+                    time_to_merge.push(*ttm as f64);
+                    trace!("Adding {} days of time-to-merge to sample.", ttm)
+                }
+                ScoreType::UnresolvedThreads(ut) => {
+                    unresolved_threads.push(*ut as f64);
+                    trace!("Adding {} unresolved threads to sample.", ut)
                 }
             }
         }
+    }
 
-        // derive repository's global score by calculating the average of each type across all PRs -
-        let mut scorables: Vec<ScoreType> = Vec::new();
+    // derive repository's global score by aggregating each type across all PRs -
+    // (integer metrics are rounded up, so that any non-zero sample never collapses into a zero)
+    let aggregate = |sample: &[f64]| aggregation_strategy.aggregate(sample);
+    let aggregate_u64 = |sample: &[f64]| aggregate(sample).map(|value| value.ceil() as u64);
+    let aggregate_usize = |sample: &[f64]| aggregate(sample).map(|value| value.ceil() as usize);
+    let aggregate_ratio = |sample: &[(f64, f64)]| {
+        if scoring_settings.weight_by_size {
+            aggregation_strategy.aggregate_weighted(sample)
+        } else {
+            aggregate(&sample.iter().map(|(ratio, _)| *ratio).collect::<Vec<f64>>())
+        }
+    };
 
-        for score_type in ScoreType::get_iter() {
-            match score_type {
-                // having this iterator & match structure will guarantee that all possible ScoreType(s)
-                // are present and accounted for at compilation time; which means a developer doesn't
-                // have to worry about forgetting to include potential new ScoreType(s) into the scoring
-                // process
-                ScoreType::AmountOfParticipants(_) => {
-                    scorables.push(ScoreType::AmountOfParticipants(integer::div_ceil(
-                        total_amount_of_participants,
-                        total_amount_of_prs,
-                    )))
-                }
-                ScoreType::AmountOfReviewers(_) => scorables.push(ScoreType::AmountOfReviewers(
-                    integer::div_ceil(total_amount_of_reviewers, total_amount_of_prs),
-                )),
-                ScoreType::Attachments(_) => scorables.push(ScoreType::Attachments(
-                    integer::div_ceil(total_attachments, total_amount_of_prs),
-                )),
-                ScoreType::AuthorCommentaryToChangesRatio(_) => {
-                    scorables.push(ScoreType::AuthorCommentaryToChangesRatio(
-                        total_author_commentary_to_changes_ratio / (total_amount_of_prs as f64),
-                    ))
-                }
-                ScoreType::PullRequestsDiscussionSize(_) => {
-                    scorables.push(ScoreType::PullRequestsDiscussionSize(integer::div_ceil(
-                        total_pull_requests_discussion_size,
-                        total_amount_of_prs as usize,
-                    )))
-                }
-                ScoreType::PullRequestFlowRatio(_) => scorables.push(
-                    ScoreType::PullRequestFlowRatio(calculate_pull_request_flow_ratio(&self)),
-                ),
-                ScoreType::PullRequestLeadTime(_) => {
-                    scorables.push(ScoreType::PullRequestLeadTime(integer::div_ceil(
-                        total_pull_request_lead_time,
-                        total_amount_of_prs,
-                    )))
-                }
-                ScoreType::PullRequestSize(_) => scorables.push(ScoreType::PullRequestSize(
-                    integer::div_ceil(total_pull_request_size, total_amount_of_prs as usize),
-                )),
-                ScoreType::TestToCodeRatio(_) => scorables.push(ScoreType::TestToCodeRatio(
-                    total_test_to_code_ratio / (total_amount_of_prs as f64),
-                )),
-                ScoreType::TimeToMerge(_) => scorables.push(ScoreType::TimeToMerge(
-                    integer::div_ceil(total_time_to_merge, total_amount_of_prs),
-                )),
+    let mut scorables: Vec<ScoreType> = Vec::new();
+
+    for score_type in ScoreType::get_iter() {
+        match score_type {
+            // having this iterator & match structure will guarantee that all possible ScoreType(s)
+            // are present and accounted for at compilation time; which means a developer doesn't
+            // have to worry about forgetting to include potential new ScoreType(s) into the scoring
+            // process
+            ScoreType::AfterHoursActivityRatio(_) => {
+                // PRs without any activity don't carry this metric
+                scorables.extend(
+                    aggregate(&after_hours_activity_ratio).map(ScoreType::AfterHoursActivityRatio),
+                )
             }
+            ScoreType::AmountOfParticipants(_) => scorables.extend(
+                aggregate_u64(&amount_of_participants).map(ScoreType::AmountOfParticipants),
+            ),
+            ScoreType::AmountOfReviewers(_) => scorables
+                .extend(aggregate_u64(&amount_of_reviewers).map(ScoreType::AmountOfReviewers)),
+            ScoreType::Attachments(_) => {
+                scorables.extend(aggregate_u64(&attachments).map(ScoreType::Attachments))
+            }
+            ScoreType::AuthorCommentaryToChangesRatio(_) => scorables.extend(
+                aggregate_ratio(&author_commentary_to_changes_ratio)
+                    .map(ScoreType::AuthorCommentaryToChangesRatio),
+            ),
+            ScoreType::AuthorResponseLatency(_) => {
+                // PRs without any reviewer-to-author exchange don't carry this metric
+                scorables.extend(
+                    aggregate_u64(&author_response_latency).map(ScoreType::AuthorResponseLatency),
+                )
+            }
+            ScoreType::AuthorshipMismatch(_) => scorables
+                .extend(aggregate_u64(&authorship_mismatch).map(ScoreType::AuthorshipMismatch)),
+            ScoreType::BinaryFilesChanged(_) => scorables
+                .extend(aggregate_usize(&binary_files_changed).map(ScoreType::BinaryFilesChanged)),
+            ScoreType::BusinessDaysLeadTime(_) => scorables.extend(
+                aggregate_u64(&business_days_lead_time).map(ScoreType::BusinessDaysLeadTime),
+            ),
+            ScoreType::ChecklistCompletion(_) => scorables
+                .extend(aggregate(&checklist_completion).map(ScoreType::ChecklistCompletion)),
+            ScoreType::CommitMessageQuality(_) => {
+                // PRs made up of merge commits only don't carry this metric
+                scorables
+                    .extend(aggregate(&commit_message_quality).map(ScoreType::CommitMessageQuality))
+            }
+            ScoreType::ContentiousDiscussion(_) => scorables.extend(
+                aggregate_u64(&contentious_discussion).map(ScoreType::ContentiousDiscussion),
+            ),
+            ScoreType::DeletionRatio(_) => {
+                scorables.extend(aggregate(&deletion_ratio).map(ScoreType::DeletionRatio))
+            }
+            ScoreType::DescriptionLength(_) => scorables
+                .extend(aggregate_usize(&description_length).map(ScoreType::DescriptionLength)),
+            ScoreType::FilesChanged(_) => {
+                scorables.extend(aggregate_usize(&files_changed).map(ScoreType::FilesChanged))
+            }
+            ScoreType::FirstResponseWait(_) => {
+                // PRs without any activity don't carry this metric
+                scorables
+                    .extend(aggregate_u64(&first_response_wait).map(ScoreType::FirstResponseWait))
+            }
+            ScoreType::GreenMergeRatio(_) => {
+                // unmerged PRs, and those without checks, don't carry this metric
+                scorables.extend(aggregate(&green_merge_ratio).map(ScoreType::GreenMergeRatio))
+            }
+            ScoreType::LinkedIssues(_) => {
+                scorables.extend(aggregate_u64(&linked_issues).map(ScoreType::LinkedIssues))
+            }
+            ScoreType::MissingDescription(_) => {
+                scorables.extend(aggregate(&missing_description).map(ScoreType::MissingDescription))
+            }
+            ScoreType::NumberOfCommits(_) => {
+                scorables.extend(aggregate_u64(&number_of_commits).map(ScoreType::NumberOfCommits))
+            }
+            ScoreType::PullRequestsDiscussionSize(_) => scorables.extend(
+                aggregate_usize(&pull_requests_discussion_size)
+                    .map(ScoreType::PullRequestsDiscussionSize),
+            ),
+            ScoreType::PullRequestFlowRatio(_) => {
+                scorables.extend(pull_request_flow_ratio.map(ScoreType::PullRequestFlowRatio))
+            }
+            ScoreType::PullRequestLeadTime(_) => scorables
+                .extend(aggregate_u64(&pull_request_lead_time).map(ScoreType::PullRequestLeadTime)),
+            ScoreType::PullRequestSize(_) => scorables
+                .extend(aggregate_usize(&pull_request_size).map(ScoreType::PullRequestSize)),
+            ScoreType::ReactionCount(_) => {
+                scorables.extend(aggregate_u64(&reaction_count).map(ScoreType::ReactionCount))
+            }
+            ScoreType::RejectionRate(_) => {
+                scorables.extend(rejection_rate.map(ScoreType::RejectionRate))
+            }
+            ScoreType::ReviewIterations(_) => {
+                scorables.extend(aggregate_u64(&review_iterations).map(ScoreType::ReviewIterations))
+            }
+            ScoreType::ReviewParticipationBreadth(_) => scorables
+                .extend(review_participation_breadth.map(ScoreType::ReviewParticipationBreadth)),
+            ScoreType::ReviewedFileCoverage(_) => scorables
+                .extend(aggregate(&reviewed_file_coverage).map(ScoreType::ReviewedFileCoverage)),
+            ScoreType::ReviewerDiversity(_) => {
+                scorables.extend(reviewer_diversity.map(ScoreType::ReviewerDiversity))
+            }
+            ScoreType::SelfMergedPrs(_) => {
+                scorables.extend(self_merged_prs.map(ScoreType::SelfMergedPrs))
+            }
+            ScoreType::SignedCommitRatio(_) => {
+                // PRs without any commit don't carry this metric
+                scorables.extend(aggregate(&signed_commit_ratio).map(ScoreType::SignedCommitRatio))
+            }
+            ScoreType::TestToCodeRatio(_) => scorables
+                .extend(aggregate_ratio(&test_to_code_ratio).map(ScoreType::TestToCodeRatio)),
+            ScoreType::TimeInDraft(_) => {
+                scorables.extend(aggregate_u64(&time_in_draft).map(ScoreType::TimeInDraft))
+            }
+            ScoreType::TimeToApproval(_) => {
+                // unapproved PRs don't carry this metric, so they don't drag the aggregate down
+                scorables.extend(aggregate_u64(&time_to_approval).map(ScoreType::TimeToApproval))
+            }
+            ScoreType::TimeToFirstReview(_) => {
+                // unreviewed PRs don't carry this metric, so they don't drag the aggregate down
+                scorables
+                    .extend(aggregate_u64(&time_to_first_review).map(ScoreType::TimeToFirstReview))
+            }
+            ScoreType::TimeToMerge(_) => {
+                scorables.extend(aggregate_u64(&time_to_merge).map(ScoreType::TimeToMerge))
+            }
+            ScoreType::UnresolvedThreads(_) => scorables
+                .extend(aggregate_u64(&unresolved_threads).map(ScoreType::UnresolvedThreads)),
         }
+    }
 
-        Score::new(scorables)
+    let score = Score::new(scorables)
+        .graded(&scoring_settings.grade_weights, &scoring_settings.grading_config);
+
+    if scoring_settings.with_variance {
+        score.with_stddev(calculate_stddev(&scores.concat()))
+    } else {
+        score
     }
 }
 
-/// Calculates the PullRequestFlowRatio over the provided array of [`PullRequestData`]s.
-fn calculate_pull_request_flow_ratio(prs: &Vec<&PullRequestData>) -> f64 {
+/// Calculates the population standard deviation of every [`ScoreType`] among the provided individual PRs'
+/// `scores`, keyed by metric name (see [`ScoreType::get_csv_column`]). [`ScoreType`]s absent from every PR are left out.
+fn calculate_stddev(scores: &[ScoreType]) -> BTreeMap<String, f64> {
+    ScoreType::get_iter()
+        .filter_map(|metric| {
+            let sample: Vec<f64> = scores
+                .iter()
+                .filter(|score_type| discriminant(*score_type) == discriminant(&metric))
+                .map(|score_type| score_type.get_value())
+                .collect();
+
+            population_stddev(&sample).map(|stddev| (metric.get_csv_column().to_string(), stddev))
+        })
+        .collect()
+}
+
+/// Calculates the population standard deviation of a `sample`. Returns [`None`] if the `sample` is empty.
+fn population_stddev(sample: &[f64]) -> Option<f64> {
+    if sample.is_empty() {
+        return None;
+    }
+
+    let mean = sample.iter().sum::<f64>() / sample.len() as f64;
+    let variance =
+        sample.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / sample.len() as f64;
+
+    Some(variance.sqrt())
+}
+
+/// Calculates the ReviewerDiversity over the provided array of [`PullRequestData`]s; that is, the amount of
+/// distinct reviewers across all of them (see [`PullRequestData::get_reviewers`]).
+fn calculate_reviewer_diversity(prs: &[&PullRequestData]) -> u64 {
+    prs.iter().flat_map(|prd| prd.get_reviewers()).unique().count() as u64
+}
+
+/// Calculates the ReviewParticipationBreadth over the provided array of [`PullRequestData`]s; that is, the
+/// ratio of distinct reviewers (see [`PullRequestData::get_reviewers`]) to distinct authors across all of
+/// them. [`None`] if there are no PRs, and thus no authors, at all. Just like reviewers, authors are told
+/// apart regardless of their logins' case.
+fn calculate_review_participation_breadth(prs: &[&PullRequestData]) -> Option<f64> {
+    let authors = prs.iter().map(|prd| prd.pr_author().to_lowercase()).unique().count();
+
+    (authors > 0).then(|| calculate_reviewer_diversity(prs) as f64 / authors as f64)
+}
+
+/// Calculates the SelfMergedPrs over the provided array of [`PullRequestData`]s; that is, the amount of them
+/// that were most likely merged by their own authors (see [`PullRequestData::is_likely_self_merged`]).
+fn calculate_self_merged_prs(prs: &[&PullRequestData]) -> u64 {
+    prs.iter().filter(|prd| prd.is_likely_self_merged()).count() as u64
+}
+
+/// Calculates the RejectionRate over the provided array of [`PullRequestData`]s; that is, the ratio of their
+/// closed PRs that were closed without being merged (see [`PullRequestData::is_closed_unmerged`]). [`None`]
+/// if none of them has been closed.
+fn calculate_rejection_rate(prs: &[&PullRequestData]) -> Option<f64> {
+    let closed_prs = prs.iter().filter(|prd| prd.closed_at().is_some()).count();
+    let closed_unmerged_prs = prs.iter().filter(|prd| prd.is_closed_unmerged()).count();
+
+    (closed_prs > 0).then(|| closed_unmerged_prs as f64 / closed_prs as f64)
+}
+
+/// Calculates the PullRequestFlowRatio over the provided [`PullRequestFlow`]. Whether merge-PRs are part
+/// of it is up to whoever gathered the [`PullRequestFlow`] (see [`PullRequestFlow::of_sample`]). Days are
+/// told apart in the team's [`Timezone`](crate::scoring::scoring_settings::Timezone).
+fn calculate_pull_request_flow_ratio(
+    pull_request_flow: &PullRequestFlow, scoring_settings: &ScoringSettings,
+) -> f64 {
     // generate map with all PRs that were created in the same day -
-    let created_at_map: HashMap<Date<Utc>, u64> =
-        prs.iter().fold(HashMap::new(), |mut acc, prd| {
-            *acc.entry(prd.created_at().date()).or_insert(0) += 1;
+    let created_at_map: HashMap<NaiveDate, u64> =
+        pull_request_flow.opened.iter().fold(HashMap::new(), |mut acc, created_at| {
+            *acc.entry(scoring_settings.local_date(created_at)).or_insert(0) += 1;
             acc
         });
     trace!("pull-request-flow-ratio's created_at_map: {:?}", created_at_map);
 
-    // generate map with all PRs that were closed in the same day -
-    let closed_at_map: HashMap<Date<Utc>, u64> = prs.iter().fold(HashMap::new(), |mut acc, prd| {
-        *acc.entry(prd.closed_at().date()).or_insert(0) += 1;
-        acc
-    });
+    // generate map with all PRs that were closed in the same day (open PRs have not been closed yet) -
+    let closed_at_map: HashMap<NaiveDate, u64> =
+        pull_request_flow.closed.iter().fold(HashMap::new(), |mut acc, closed_at| {
+            *acc.entry(scoring_settings.local_date(closed_at)).or_insert(0) += 1;
+            acc
+        });
     trace!("pull-request-flow-ratio's closed_at_map: {:?}", closed_at_map);
 
     // generate map calculating the PullRequestFlowRatio of those entries that match between the two previous maps -
-    let pull_request_flow_ratio_map: HashMap<&Date<Utc>, f64> =
+    let pull_request_flow_ratio_map: HashMap<&NaiveDate, f64> =
         created_at_map.iter().fold(HashMap::new(), |mut acc, created_at_entry| {
             let closed_at_entry = closed_at_map.get(created_at_entry.0);
             if let Some(amount_of_closures_in_day) = closed_at_entry {
@@ -210,7 +541,467 @@ fn calculate_pull_request_flow_ratio(prs: &Vec<&PullRequestData>) -> f64 {
         });
     trace!("pull-request-flow-ratio's result map: {:?}", pull_request_flow_ratio_map);
 
+    // no creation day ever matched a closing day (common in small samples); avoid dividing by zero,
+    // which would produce a NaN that ends up poisoning the serialized JSON
+    if pull_request_flow_ratio_map.is_empty() {
+        return 0.0;
+    }
+
     // return average result -
     pull_request_flow_ratio_map.iter().map(|entry| entry.1).sum::<f64>()
         / (pull_request_flow_ratio_map.len() as f64)
 }
+
+#[cfg(test)]
+mod repository_data_tests {
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use crate::github::utils::fixtures::{
+        commit_root, has_metric, pr_with_commits, review, PullRequestDataFixture,
+    };
+    use crate::github::utils::pull_request_data::PullRequestData;
+    use crate::github::utils::repository_data::{
+        aggregate_scores, calculate_pull_request_flow_ratio,
+        calculate_review_participation_breadth, calculate_reviewer_diversity, get_score_with_flow,
+        population_stddev, PullRequestFlow,
+    };
+    use crate::scoring::scorable::Scorable;
+    use crate::scoring::score::{Score, ScoreFlag, ScoreType};
+    use crate::scoring::scoring_settings::{
+        AggregationStrategy, MergeStyle, ScoringSettings, Timezone,
+    };
+
+    /// Produces the aggregated [`Score`] of the provided array of [`PullRequestData`]s, calculating its
+    /// Pull Request Flow Ratio over the PRs themselves.
+    fn get_sample_score(prs: &[&PullRequestData], scoring_settings: &ScoringSettings) -> Score {
+        get_score_with_flow(prs, &PullRequestFlow::of_sample(prs), scoring_settings)
+    }
+
+    fn dummy_pr(
+        pr_number: u64, created_at: DateTime<Utc>, closed_at: DateTime<Utc>,
+    ) -> PullRequestData {
+        PullRequestDataFixture {
+            pr_number,
+            created_at,
+            merged_at: Some(closed_at),
+            closed_at: Some(closed_at),
+            ..Default::default()
+        }
+        .build()
+    }
+
+    #[test]
+    fn pull_request_flow_ratio_is_zero_when_creation_and_closure_days_never_coincide() {
+        let first_pr = dummy_pr(
+            1,
+            Utc.ymd(2021, 3, 1).and_hms(10, 0, 0),
+            Utc.ymd(2021, 3, 2).and_hms(10, 0, 0),
+        );
+        let second_pr = dummy_pr(
+            2,
+            Utc.ymd(2021, 3, 5).and_hms(10, 0, 0),
+            Utc.ymd(2021, 3, 8).and_hms(10, 0, 0),
+        );
+
+        let flow_ratio = calculate_pull_request_flow_ratio(
+            &PullRequestFlow::of_sample(&[&first_pr, &second_pr]),
+            &ScoringSettings::default(),
+        );
+
+        assert!(flow_ratio.is_finite());
+        assert_eq!(flow_ratio, 0.0);
+    }
+
+    fn flow_pr(
+        pr_title: &str, created_at: DateTime<Utc>, closed_at: DateTime<Utc>,
+        include_merge_prs: bool,
+    ) -> PullRequestData {
+        PullRequestDataFixture {
+            pr_title: pr_title.to_string(),
+            created_at,
+            merged_at: Some(closed_at),
+            closed_at: Some(closed_at),
+            scoring_settings: ScoringSettings {
+                include_merge_prs,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+        .build()
+    }
+
+    fn flow_ratio_of_sample(include_merge_prs: bool) -> f64 {
+        let first_day = Utc.ymd(2021, 3, 1).and_hms(10, 0, 0);
+        let second_day = Utc.ymd(2021, 3, 2).and_hms(10, 0, 0);
+
+        let prs = [
+            flow_pr("Add foo", first_day, first_day, include_merge_prs),
+            flow_pr("Add bar", first_day, second_day, include_merge_prs),
+            flow_pr("Add baz", second_day, second_day, include_merge_prs),
+            flow_pr("Merge develop into master", first_day, first_day, include_merge_prs),
+        ];
+
+        calculate_pull_request_flow_ratio(
+            &PullRequestFlow::of_sample(&prs.iter().collect::<Vec<&PullRequestData>>()),
+            &ScoringSettings::default(),
+        )
+    }
+
+    #[test]
+    fn pull_request_flow_ratio_averages_the_daily_ratios_of_coinciding_days() {
+        // 1st day: 2 opened / 1 closed = 2.0; 2nd day: 1 opened / 2 closed = 0.5 -
+        // (the merge-PR is left out of the sample's flow)
+        assert_eq!(flow_ratio_of_sample(false), 1.25);
+    }
+
+    #[test]
+    fn pull_request_flow_ratio_counts_merge_prs_only_when_included() {
+        // 1st day: 3 opened / 2 closed = 1.5; 2nd day: 1 opened / 2 closed = 0.5
+        assert_eq!(flow_ratio_of_sample(true), 1.0);
+    }
+
+    #[test]
+    fn merged_pull_request_flows_hold_every_opening_and_closure() {
+        let first_day = Utc.ymd(2021, 3, 1).and_hms(10, 0, 0);
+        let first_flow = PullRequestFlow {
+            opened: vec![first_day],
+            closed: vec![first_day],
+        };
+        let second_flow = PullRequestFlow {
+            opened: vec![first_day, first_day],
+            closed: Vec::new(),
+        };
+
+        let merged_flow = PullRequestFlow::merge(vec![&first_flow, &second_flow]);
+
+        assert_eq!(merged_flow.opened.len(), 3);
+        assert_eq!(merged_flow.closed.len(), 1);
+        assert_eq!(
+            calculate_pull_request_flow_ratio(&merged_flow, &ScoringSettings::default()),
+            3.0
+        );
+    }
+
+    #[test]
+    fn pull_request_flow_days_are_told_apart_in_the_team_timezone() {
+        // opened at 23:00 UTC, which is already the next day in Tokyo (UTC+9) - the very same day it gets
+        // closed in
+        let late_flow = PullRequestFlow {
+            opened: vec![Utc.ymd(2021, 3, 1).and_hms(23, 0, 0)],
+            closed: vec![Utc.ymd(2021, 3, 2).and_hms(5, 0, 0)],
+        };
+        let flow_ratio_in = |timezone: &str| {
+            calculate_pull_request_flow_ratio(
+                &late_flow,
+                &ScoringSettings {
+                    timezone: Timezone::parse(timezone).unwrap(),
+                    ..Default::default()
+                },
+            )
+        };
+
+        assert_eq!(flow_ratio_in("UTC"), 0.0);
+        assert_eq!(flow_ratio_in("Asia/Tokyo"), 1.0);
+        assert_eq!(flow_ratio_in("+09:00"), 1.0);
+    }
+
+    fn reviewed_pr(pr_author: &str, reviewers: &[&str]) -> PullRequestData {
+        PullRequestDataFixture {
+            pr_author: pr_author.to_string(),
+            reviews: reviewers
+                .iter()
+                .map(|reviewer| {
+                    review(reviewer, "COMMENTED", Some(Utc.ymd(2021, 3, 1).and_hms(10, 0, 0)))
+                })
+                .collect(),
+            ..Default::default()
+        }
+        .build()
+    }
+
+    #[test]
+    fn reviewer_diversity_counts_distinct_non_authoring_reviewers_across_prs() {
+        let first_pr = reviewed_pr("alice", &["bob", "carol", "alice"]);
+        let second_pr = reviewed_pr("bob", &["carol", "dave", "carol"]);
+        let unreviewed_pr = reviewed_pr("erin", &[]);
+
+        // bob, carol & dave; alice only ever reviewed her own PR
+        assert_eq!(calculate_reviewer_diversity(&[&first_pr, &second_pr, &unreviewed_pr]), 3);
+        assert_eq!(calculate_reviewer_diversity(&[&unreviewed_pr]), 0);
+    }
+
+    #[test]
+    fn review_participation_breadth_relates_distinct_reviewers_to_distinct_authors() {
+        let first_pr = reviewed_pr("alice", &["bob", "carol"]);
+        let second_pr = reviewed_pr("bob", &["alice"]);
+        let third_pr = reviewed_pr("alice", &["bob", "alice"]);
+
+        // alice, bob & carol reviewed the PRs authored by alice & bob
+        let sample = vec![&first_pr, &second_pr, &third_pr];
+        assert_eq!(calculate_review_participation_breadth(&sample), Some(1.5));
+        assert!(get_sample_score(&sample, &ScoringSettings::default())
+            .score()
+            .contains(&ScoreType::ReviewParticipationBreadth(1.5)));
+
+        // the authors only ever review each other's PRs
+        assert_eq!(calculate_review_participation_breadth(&[&second_pr, &third_pr]), Some(1.0));
+        assert_eq!(calculate_review_participation_breadth(&[]), None);
+    }
+
+    #[test]
+    fn review_participation_breadth_tells_authors_apart_regardless_of_case() {
+        let first_pr = reviewed_pr("Alice", &["bob", "Carol"]);
+        let second_pr = reviewed_pr("alice", &["BOB", "carol"]);
+
+        // bob & carol reviewed the PRs authored by a single alice
+        assert_eq!(calculate_review_participation_breadth(&[&first_pr, &second_pr]), Some(2.0));
+    }
+
+    #[test]
+    fn self_merged_prs_are_counted_across_the_sample() {
+        let opened_at = Utc.ymd(2021, 3, 1).and_hms(10, 0, 0);
+        let swiftly_merged_pr = dummy_pr(1, opened_at, opened_at + chrono::Duration::minutes(10));
+        let slowly_merged_pr = dummy_pr(2, opened_at, opened_at + chrono::Duration::days(2));
+        let reviewed_pr = reviewed_pr("alice", &["bob"]); // never merged either
+
+        let score = get_sample_score(
+            &[&swiftly_merged_pr, &slowly_merged_pr, &reviewed_pr],
+            &ScoringSettings::default(),
+        )
+        .score();
+
+        assert!(score.contains(&ScoreType::SelfMergedPrs(1)));
+    }
+
+    fn closed_pr(merged: bool, include_closed_unmerged: bool) -> PullRequestData {
+        let closed_at = Utc.ymd(2021, 3, 2).and_hms(10, 0, 0);
+
+        PullRequestDataFixture {
+            commits: vec![commit_root("Dummy commit", Utc.ymd(2021, 3, 1).and_hms(9, 0, 0), 1)],
+            merged_at: if merged {
+                Some(closed_at)
+            } else {
+                None
+            },
+            closed_at: Some(closed_at),
+            scoring_settings: ScoringSettings {
+                include_closed_unmerged,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+        .build()
+    }
+
+    #[test]
+    fn rejection_rate_is_the_share_of_closed_prs_that_were_never_merged() {
+        let merged_prs = [closed_pr(true, true), closed_pr(true, true), closed_pr(true, true)];
+        let abandoned_pr = closed_pr(false, true);
+        let open_pr = PullRequestDataFixture {
+            merged_at: None,
+            closed_at: None,
+            ..Default::default()
+        }
+        .build();
+
+        let mut sample: Vec<&PullRequestData> = merged_prs.iter().collect();
+        sample.push(&abandoned_pr);
+        sample.push(&open_pr);
+        let score = get_sample_score(
+            &sample,
+            &ScoringSettings {
+                include_closed_unmerged: true,
+                ..Default::default()
+            },
+        )
+        .score();
+
+        // open PRs are neither accepted nor rejected yet
+        assert!(score.contains(&ScoreType::RejectionRate(0.25)));
+        // abandoned PRs still take part in every metric that does not require a merge
+        assert!(abandoned_pr.get_score().score().contains(&ScoreType::PullRequestLeadTime(1)));
+        assert!(!has_metric(&abandoned_pr.get_score().score(), ScoreType::TimeToMerge(0)));
+    }
+
+    #[test]
+    fn rejection_rate_is_omitted_unless_closed_unmerged_prs_are_included() {
+        let merged_pr = closed_pr(true, false);
+        let abandoned_pr = closed_pr(false, false);
+
+        assert!(!has_metric(
+            &get_sample_score(&[&merged_pr, &abandoned_pr], &ScoringSettings::default()).score(),
+            ScoreType::RejectionRate(0.0)
+        ));
+    }
+
+    fn aggregated_number_of_commits(aggregation_strategy: AggregationStrategy) -> Vec<ScoreType> {
+        let prs: Vec<PullRequestData> = [1, 1, 2, 2, 20]
+            .iter()
+            .map(|amount_of_commits| pr_with_commits(*amount_of_commits))
+            .collect();
+
+        get_sample_score(
+            &prs.iter().collect::<Vec<&PullRequestData>>(),
+            &ScoringSettings {
+                aggregation_strategy,
+                ..Default::default()
+            },
+        )
+        .score()
+        .into_iter()
+        .filter(|score_type| matches!(score_type, ScoreType::NumberOfCommits(_)))
+        .collect()
+    }
+
+    #[test]
+    fn median_aggregation_is_not_dragged_by_outliers_unlike_mean() {
+        assert_eq!(
+            aggregated_number_of_commits(AggregationStrategy::Mean),
+            vec![ScoreType::NumberOfCommits(6)] // 26 / 5 = 5.2, rounded up
+        );
+        assert_eq!(
+            aggregated_number_of_commits(AggregationStrategy::Median),
+            vec![ScoreType::NumberOfCommits(2)]
+        );
+        assert_eq!(
+            aggregated_number_of_commits(AggregationStrategy::P90),
+            vec![ScoreType::NumberOfCommits(20)]
+        );
+    }
+
+    #[test]
+    fn samples_are_aggregated_according_to_the_repository_settings_instead_of_their_first_pr() {
+        let prs: Vec<PullRequestData> = [1, 1, 2, 2, 20]
+            .iter()
+            .map(|amount_of_commits| {
+                PullRequestDataFixture {
+                    commits: (0..*amount_of_commits)
+                        .map(|i| {
+                            commit_root("Dummy commit", Utc.ymd(2021, 3, 1).and_hms(i, 0, 0), 1)
+                        })
+                        .collect(),
+                    scoring_settings: ScoringSettings {
+                        aggregation_strategy: AggregationStrategy::Mean,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }
+                .build()
+            })
+            .collect();
+        let repository_settings = ScoringSettings {
+            aggregation_strategy: AggregationStrategy::Median,
+            ..Default::default()
+        };
+
+        let score =
+            get_sample_score(&prs.iter().collect::<Vec<&PullRequestData>>(), &repository_settings)
+                .score();
+
+        assert!(score.contains(&ScoreType::NumberOfCommits(2)));
+    }
+
+    #[test]
+    fn aggregates_raise_the_flags_of_any_of_their_prs_but_only_once() {
+        let pr = |merge_style: MergeStyle| {
+            PullRequestDataFixture {
+                scoring_settings: ScoringSettings {
+                    merge_style,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+            .build()
+        };
+        let sample_flags = |prs: &[PullRequestData]| {
+            get_sample_score(
+                &prs.iter().collect::<Vec<&PullRequestData>>(),
+                &ScoringSettings::default(),
+            )
+            .flags()
+            .to_vec()
+        };
+
+        assert!(sample_flags(&[pr(MergeStyle::Merge), pr(MergeStyle::Merge)]).is_empty());
+        assert_eq!(
+            sample_flags(&[pr(MergeStyle::Merge), pr(MergeStyle::Squash), pr(MergeStyle::Squash)]),
+            vec![ScoreFlag::SquashedTimeToMerge]
+        );
+    }
+
+    #[test]
+    fn population_stddev_of_a_known_distribution() {
+        assert_eq!(population_stddev(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]), Some(2.0));
+        assert_eq!(population_stddev(&[3.0]), Some(0.0));
+        assert_eq!(population_stddev(&[]), None);
+    }
+
+    #[test]
+    fn stddev_is_reported_alongside_the_aggregate_only_if_requested() {
+        let score_json = |with_variance: bool| {
+            let prs: Vec<PullRequestData> = [1, 3]
+                .iter()
+                .map(|amount_of_commits| {
+                    PullRequestDataFixture {
+                        commits: (0..*amount_of_commits)
+                            .map(|i| {
+                                commit_root("Dummy commit", Utc.ymd(2021, 3, 1).and_hms(i, 0, 0), 1)
+                            })
+                            .collect(),
+                        ..Default::default()
+                    }
+                    .build()
+                })
+                .collect();
+
+            get_sample_score(
+                &prs.iter().collect::<Vec<&PullRequestData>>(),
+                &ScoringSettings {
+                    with_variance,
+                    ..Default::default()
+                },
+            )
+            .to_json()
+        };
+
+        let json_with_variance: serde_json::Value =
+            serde_json::from_str(&score_json(true)).unwrap();
+        let json_without_variance: serde_json::Value =
+            serde_json::from_str(&score_json(false)).unwrap();
+
+        assert_eq!(json_with_variance["stddev"]["number_of_commits"], 1.0);
+        assert!(json_with_variance["score"].is_array());
+        assert!(json_without_variance.get("stddev").is_none());
+    }
+
+    #[test]
+    fn size_weighted_aggregation_differs_from_flat_aggregation_on_a_mixed_sample() {
+        let aggregated_ratio = |weight_by_size: bool| {
+            // a tiny PR full of commentary, and a huge one with barely any
+            let scores = vec![
+                vec![ScoreType::PullRequestSize(5), ScoreType::AuthorCommentaryToChangesRatio(1.0)],
+                vec![
+                    ScoreType::PullRequestSize(2000),
+                    ScoreType::AuthorCommentaryToChangesRatio(0.01),
+                ],
+            ];
+
+            let scoring_settings = ScoringSettings {
+                weight_by_size,
+                ..Default::default()
+            };
+
+            aggregate_scores(scores, None, None, None, None, None, &scoring_settings)
+                .score()
+                .into_iter()
+                .find_map(|score_type| match score_type {
+                    ScoreType::AuthorCommentaryToChangesRatio(ratio) => Some(ratio),
+                    _ => None,
+                })
+                .unwrap()
+        };
+
+        assert!((aggregated_ratio(false) - 0.505).abs() < 1e-9);
+        assert!((aggregated_ratio(true) - 25.0 / 2005.0).abs() < 1e-9);
+    }
+}
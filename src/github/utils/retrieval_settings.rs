@@ -0,0 +1,439 @@
+//! Settings that narrow down which [`PullRequest`](octocrab::models::pulls::PullRequest)s get retrieved
+//! as part of a [`Repository`](octocrab::models::Repository)'s sample.
+
+use chrono::{DateTime, Utc};
+use log::info;
+use octocrab::params;
+use rand::rngs::StdRng;
+use rand::{seq, SeedableRng};
+use strum_macros::{Display, EnumString, EnumVariantNames};
+
+/// User-tunable settings consumed by an [`Analyzer`](crate::github::utils::analyzer::Analyzer) while
+/// sampling a [`Repository`](octocrab::models::Repository)'s [`PullRequest`](octocrab::models::pulls::PullRequest)s.
+#[derive(Debug, Clone)]
+pub struct RetrievalSettings {
+    /// Only PRs created at or after this instant are sampled.
+    pub since: Option<DateTime<Utc>>,
+    /// Only PRs created at or before this instant are sampled.
+    pub until: Option<DateTime<Utc>>,
+    /// Only PRs in this state are sampled. See [`PullRequestState`].
+    pub state: PullRequestState,
+    /// Only PRs opened by one of these users (by login) are sampled. Empty means anyone's.
+    pub authors: Vec<String>,
+    /// Only PRs carrying these labels (by name) are sampled. Empty means any PR, labeled or not.
+    pub labels: Vec<String>,
+    /// Whether PRs must carry any or all of the `labels`. See [`LabelMode`].
+    pub label_mode: LabelMode,
+    /// The order in which PRs are listed (and thus sampled). See [`PullRequestSort`].
+    pub sort: PullRequestSort,
+    /// Whether PRs are listed in ascending or descending `sort` order. See [`SortDirection`].
+    pub direction: SortDirection,
+    /// How the sample is picked among the listed PRs. See [`SampleStrategy`].
+    pub sample_strategy: SampleStrategy,
+    /// Seeds the [`SampleStrategy::Random`] selection, so that the very same sample can be picked again
+    /// (as long as the listing itself does not change). A random seed is used when missing.
+    pub seed: Option<u64>,
+    /// Whether each PR's review threads are retrieved too; at the cost of an extra (GraphQL) request per
+    /// PR. See [`ScoreType::UnresolvedThreads`](crate::scoring::score::ScoreType::UnresolvedThreads).
+    pub with_threads: bool,
+    /// Whether each PR's timeline is retrieved too; at the cost of an extra request per PR. See
+    /// [`ScoreType::TimeInDraft`](crate::scoring::score::ScoreType::TimeInDraft).
+    pub with_timeline: bool,
+    /// Whether the checks (both statuses and check runs) reported on each PR's head commit are retrieved
+    /// too; at the cost of two extra requests per PR. See
+    /// [`ScoreType::GreenMergeRatio`](crate::scoring::score::ScoreType::GreenMergeRatio).
+    pub with_checks: bool,
+    /// Only PRs with at least this amount of changed lines take part in the sample. See
+    /// [`RetrievalSettings::is_within_changes_range`].
+    pub min_changes: Option<usize>,
+    /// Only PRs with at most this amount of changed lines take part in the sample. See
+    /// [`RetrievalSettings::is_within_changes_range`].
+    pub max_changes: Option<usize>,
+}
+
+impl Default for RetrievalSettings {
+    fn default() -> Self {
+        RetrievalSettings {
+            since: None,
+            until: None,
+            state: PullRequestState::Closed,
+            authors: Vec::new(),
+            labels: Vec::new(),
+            label_mode: LabelMode::Any,
+            sort: PullRequestSort::Created,
+            direction: SortDirection::Desc,
+            sample_strategy: SampleStrategy::Recent,
+            seed: None,
+            with_threads: false,
+            with_timeline: false,
+            with_checks: false,
+            min_changes: None,
+            max_changes: None,
+        }
+    }
+}
+
+impl RetrievalSettings {
+    /// Determines whether a PR created at `created_at` falls inside the `since`/`until` window. Open-ended
+    /// windows (either bound missing) are unbounded on that side.
+    pub fn is_within_date_range(&self, created_at: DateTime<Utc>) -> bool {
+        self.is_not_before_since(created_at)
+            && self.until.into_iter().all(|until| created_at <= until)
+    }
+
+    /// Determines whether a PR created at `created_at` is not older than the `since` bound (if any).
+    pub fn is_not_before_since(&self, created_at: DateTime<Utc>) -> bool {
+        self.since.into_iter().all(|since| created_at >= since)
+    }
+
+    /// Determines whether a PR with `changes` changed lines (see
+    /// [`PullRequestData::get_amount_of_changes`](crate::github::utils::pull_request_data::PullRequestData::get_amount_of_changes))
+    /// falls inside the `min_changes`/`max_changes` range; both bounds inclusive. Unlike every other
+    /// filter, it can only be told once a PR's diff has been fetched; so PRs left out by it still cost
+    /// their share of requests.
+    pub fn is_within_changes_range(&self, changes: usize) -> bool {
+        self.min_changes.into_iter().all(|min_changes| changes >= min_changes)
+            && self.max_changes.into_iter().all(|max_changes| changes <= max_changes)
+    }
+
+    /// Determines whether a page of PRs whose last one was created at `created_at` reaches past the
+    /// `since`/`until` window; in which case no further page can hold PRs inside of it. Only PRs listed by
+    /// creation date can tell - in any other order, PRs inside the window may always turn up later on.
+    pub fn is_past_date_window(&self, created_at: DateTime<Utc>) -> bool {
+        match (self.sort, self.direction) {
+            (PullRequestSort::Created, SortDirection::Desc) => {
+                !self.is_not_before_since(created_at)
+            }
+            (PullRequestSort::Created, SortDirection::Asc) => {
+                self.until.into_iter().any(|until| created_at > until)
+            }
+            _ => false,
+        }
+    }
+
+    /// Picks up to `sample_size` out of the listed `candidates` according to the `sample_strategy`;
+    /// keeping them in their listing order.
+    pub fn select_sample<T>(&self, candidates: Vec<T>, sample_size: usize) -> Vec<T> {
+        if candidates.len() <= sample_size {
+            return candidates;
+        }
+
+        let selected_positions: Vec<usize> = match self.sample_strategy {
+            SampleStrategy::Recent => (0..sample_size).collect(),
+            SampleStrategy::Random => {
+                let seed = self.seed.unwrap_or_else(rand::random);
+                // logged, so that a randomly seeded sample can be picked again
+                info!("Picking a random sample using seed [{}]...", seed);
+
+                let mut positions = seq::index::sample(
+                    &mut StdRng::seed_from_u64(seed),
+                    candidates.len(),
+                    sample_size,
+                )
+                .into_vec();
+                positions.sort_unstable();
+                positions
+            }
+            // evenly spaced positions, always including the first candidate; each one is bigger than
+            // the previous one, since there are more candidates than positions
+            SampleStrategy::Spread => {
+                (0..sample_size).map(|i| i * candidates.len() / sample_size).collect()
+            }
+        };
+
+        candidates
+            .into_iter()
+            .enumerate()
+            .filter(|(position, _)| selected_positions.binary_search(position).is_ok())
+            .map(|(_, candidate)| candidate)
+            .collect()
+    }
+
+    /// Determines whether a PR opened by `login` is among the selected `authors` (if any). GitHub logins
+    /// are case-insensitive, and so is this comparison.
+    pub fn is_selected_author(&self, login: &str) -> bool {
+        self.authors.is_empty()
+            || self.authors.iter().any(|author| author.eq_ignore_ascii_case(login))
+    }
+
+    /// Determines whether a PR carrying `pr_labels` (by name) matches the selected `labels` (if any),
+    /// according to the `label_mode`. Unlabeled PRs never match an active label filter. GitHub label
+    /// names are case-insensitive, and so is this comparison.
+    pub fn is_selected_label_set(&self, pr_labels: &[&str]) -> bool {
+        let is_carried =
+            |label: &String| pr_labels.iter().any(|pr_label| pr_label.eq_ignore_ascii_case(label));
+
+        match self.label_mode {
+            _ if self.labels.is_empty() => true,
+            LabelMode::Any => self.labels.iter().any(is_carried),
+            LabelMode::All => self.labels.iter().all(is_carried),
+        }
+    }
+}
+
+/// The states a [`PullRequest`](octocrab::models::pulls::PullRequest) can be sampled in.
+#[derive(Display, EnumString, EnumVariantNames, Debug, Copy, Clone, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum PullRequestState {
+    /// Still under way. Metrics that depend on a PR being merged or closed are left out of their scores.
+    Open,
+    /// Done with. Only merged ones can be analyzed, though; discarded PRs end up in error.
+    Closed,
+    /// Both of the above.
+    All,
+}
+
+impl PullRequestState {
+    /// Maps this state into its GitHub API's query parameter.
+    pub fn as_param(&self) -> params::State {
+        match self {
+            PullRequestState::Open => params::State::Open,
+            PullRequestState::Closed => params::State::Closed,
+            PullRequestState::All => params::State::All,
+        }
+    }
+}
+
+/// The orders in which [`PullRequest`](octocrab::models::pulls::PullRequest)s can be listed (and thus
+/// sampled).
+#[derive(Display, EnumString, EnumVariantNames, Debug, Copy, Clone, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum PullRequestSort {
+    /// By creation date.
+    Created,
+    /// By last update date; useful for catching active work.
+    Updated,
+    /// By amount of comments.
+    Popularity,
+}
+
+impl PullRequestSort {
+    /// Maps this sort into its GitHub API's query parameter.
+    pub fn as_param(&self) -> params::pulls::Sort {
+        match self {
+            PullRequestSort::Created => params::pulls::Sort::Created,
+            PullRequestSort::Updated => params::pulls::Sort::Updated,
+            PullRequestSort::Popularity => params::pulls::Sort::Popularity,
+        }
+    }
+}
+
+/// The directions in which [`PullRequest`](octocrab::models::pulls::PullRequest)s can be listed.
+#[derive(Display, EnumString, EnumVariantNames, Debug, Copy, Clone, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+impl SortDirection {
+    /// Maps this direction into its GitHub API's query parameter.
+    pub fn as_param(&self) -> params::Direction {
+        match self {
+            SortDirection::Asc => params::Direction::Ascending,
+            SortDirection::Desc => params::Direction::Descending,
+        }
+    }
+}
+
+/// How a sample is picked among a [`Repository`](octocrab::models::Repository)'s listed
+/// [`PullRequest`](octocrab::models::pulls::PullRequest)s.
+#[derive(Display, EnumString, EnumVariantNames, Debug, Copy, Clone, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum SampleStrategy {
+    /// The first ones listed; ie: the most recent ones, in the default sort order.
+    Recent,
+    /// A random selection out of a larger set of listed candidates. See [`RetrievalSettings::seed`].
+    Random,
+    /// An evenly spaced selection out of a larger set of listed candidates; which, when listed by
+    /// creation date, spans their whole time range.
+    Spread,
+}
+
+impl SampleStrategy {
+    /// How many times bigger than the sample the set of listed candidates is, for every strategy other
+    /// than [`SampleStrategy::Recent`].
+    pub const CANDIDATE_POOL_FACTOR: usize = 5;
+
+    /// The amount of [`PullRequest`](octocrab::models::pulls::PullRequest)s to list in order to pick a
+    /// sample of `sample_size` out of them. Only listing gets pricier, though: every candidate left out
+    /// of the sample is never analyzed.
+    pub fn candidate_pool_size(&self, sample_size: u16) -> usize {
+        match self {
+            SampleStrategy::Recent => sample_size as usize,
+            SampleStrategy::Random | SampleStrategy::Spread => {
+                sample_size as usize * SampleStrategy::CANDIDATE_POOL_FACTOR
+            }
+        }
+    }
+}
+
+/// How a [`PullRequest`](octocrab::models::pulls::PullRequest)'s labels are matched against the selected
+/// ones.
+#[derive(Display, EnumString, EnumVariantNames, Debug, Copy, Clone, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum LabelMode {
+    /// The PR carries at least one of the selected labels.
+    Any,
+    /// The PR carries every single one of the selected labels.
+    All,
+}
+
+#[cfg(test)]
+mod retrieval_settings_tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::github::utils::fixtures::{patch_set, PullRequestDataFixture, SINGLE_LINE_DIFF};
+    use crate::github::utils::retrieval_settings::{
+        LabelMode, PullRequestSort, RetrievalSettings, SampleStrategy, SortDirection,
+    };
+
+    #[test]
+    fn prs_are_selected_by_their_amount_of_changes() {
+        let one_line_pr = PullRequestDataFixture {
+            patch_set: patch_set(SINGLE_LINE_DIFF),
+            ..Default::default()
+        }
+        .build();
+        let changes = one_line_pr.get_amount_of_changes();
+        assert_eq!(changes, 1);
+
+        let at_least = |min_changes| RetrievalSettings {
+            min_changes: Some(min_changes),
+            ..Default::default()
+        };
+        let at_most = |max_changes| RetrievalSettings {
+            max_changes: Some(max_changes),
+            ..Default::default()
+        };
+
+        assert!(!at_least(10).is_within_changes_range(changes));
+        assert!(at_least(1).is_within_changes_range(changes));
+        assert!(!at_most(0).is_within_changes_range(changes));
+        assert!(at_most(1).is_within_changes_range(changes));
+        assert!(RetrievalSettings::default().is_within_changes_range(changes));
+    }
+
+    #[test]
+    fn authors_are_matched_case_insensitively_and_empty_means_anyone() {
+        let retrieval_settings = RetrievalSettings {
+            authors: vec![String::from("Alice"), String::from("bob")],
+            ..Default::default()
+        };
+
+        assert!(retrieval_settings.is_selected_author("alice"));
+        assert!(retrieval_settings.is_selected_author("BOB"));
+        assert!(!retrieval_settings.is_selected_author("carol"));
+        assert!(RetrievalSettings::default().is_selected_author("carol"));
+    }
+
+    #[test]
+    fn labels_are_matched_by_any_or_all_and_unlabeled_prs_are_excluded() {
+        let any_label = RetrievalSettings {
+            labels: vec![String::from("hotfix"), String::from("Bug")],
+            ..Default::default()
+        };
+        let all_labels = RetrievalSettings {
+            label_mode: LabelMode::All,
+            ..any_label.clone()
+        };
+
+        assert!(any_label.is_selected_label_set(&["bug"]));
+        assert!(!all_labels.is_selected_label_set(&["bug"]));
+        assert!(all_labels.is_selected_label_set(&["bug", "HOTFIX", "backend"]));
+        assert!(!any_label.is_selected_label_set(&[]));
+        assert!(!all_labels.is_selected_label_set(&[]));
+        assert!(RetrievalSettings::default().is_selected_label_set(&[]));
+    }
+
+    #[test]
+    fn prs_are_selected_by_their_creation_date_with_both_bounds_inclusive() {
+        let since = Utc.ymd(2021, 3, 1).and_hms(0, 0, 0);
+        let until = Utc.ymd(2021, 3, 31).and_hms(23, 59, 59);
+        let window = RetrievalSettings {
+            since: Some(since),
+            until: Some(until),
+            ..Default::default()
+        };
+
+        assert!(window.is_within_date_range(since));
+        assert!(window.is_within_date_range(until));
+        assert!(!window.is_within_date_range(since - chrono::Duration::seconds(1)));
+        assert!(!window.is_within_date_range(until + chrono::Duration::seconds(1)));
+
+        // open-ended windows are unbounded on their missing side
+        let since_only = RetrievalSettings {
+            until: None,
+            ..window.clone()
+        };
+        let until_only = RetrievalSettings {
+            since: None,
+            ..window
+        };
+        assert!(since_only.is_within_date_range(Utc.ymd(2030, 1, 1).and_hms(0, 0, 0)));
+        assert!(until_only.is_within_date_range(Utc.ymd(2010, 1, 1).and_hms(0, 0, 0)));
+        assert!(!since_only.is_not_before_since(Utc.ymd(2010, 1, 1).and_hms(0, 0, 0)));
+    }
+
+    #[test]
+    fn only_creation_ordered_listings_can_reach_past_the_date_window() {
+        let window = RetrievalSettings {
+            since: Some(Utc.ymd(2021, 3, 1).and_hms(0, 0, 0)),
+            until: Some(Utc.ymd(2021, 3, 31).and_hms(23, 59, 59)),
+            ..Default::default()
+        };
+        let before_window = Utc.ymd(2021, 2, 15).and_hms(0, 0, 0);
+        let after_window = Utc.ymd(2021, 4, 15).and_hms(0, 0, 0);
+
+        assert!(window.is_past_date_window(before_window));
+        assert!(!window.is_past_date_window(after_window));
+
+        let ascending_window = RetrievalSettings {
+            direction: SortDirection::Asc,
+            ..window.clone()
+        };
+        assert!(!ascending_window.is_past_date_window(before_window));
+        assert!(ascending_window.is_past_date_window(after_window));
+
+        let updated_window = RetrievalSettings {
+            sort: PullRequestSort::Updated,
+            ..window
+        };
+        assert!(!updated_window.is_past_date_window(before_window));
+        assert!(!updated_window.is_past_date_window(after_window));
+    }
+
+    #[test]
+    fn samples_are_picked_in_listing_order_according_to_their_strategy() {
+        let candidates: Vec<usize> = (0..20).collect();
+        let with_strategy = |sample_strategy| RetrievalSettings {
+            sample_strategy,
+            seed: Some(42),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            with_strategy(SampleStrategy::Recent).select_sample(candidates.clone(), 4),
+            vec![0, 1, 2, 3]
+        );
+        assert_eq!(
+            with_strategy(SampleStrategy::Spread).select_sample(candidates.clone(), 4),
+            vec![0, 5, 10, 15]
+        );
+
+        let random_sample =
+            with_strategy(SampleStrategy::Random).select_sample(candidates.clone(), 4);
+        assert_eq!(random_sample.len(), 4);
+        assert!(random_sample.windows(2).all(|pair| pair[0] < pair[1]));
+        // the very same seed picks the very same sample
+        assert_eq!(
+            with_strategy(SampleStrategy::Random).select_sample(candidates.clone(), 4),
+            random_sample
+        );
+
+        // short listings are taken whole, whatever the strategy
+        assert_eq!(with_strategy(SampleStrategy::Spread).select_sample(vec![7, 8], 4), vec![7, 8]);
+        assert_eq!(SampleStrategy::Recent.candidate_pool_size(10), 10);
+        assert_eq!(SampleStrategy::Random.candidate_pool_size(10), 50);
+    }
+}
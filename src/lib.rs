@@ -0,0 +1,143 @@
+//! PRolice's analyzer, usable as a library: it scraps a GitHub repository's pull requests and measures them
+//! (see [`ScoreType`](scoring::score::ScoreType)), just like the `prolice` CLI does.
+//! <br/><br/>
+//!
+//! ### Usage example:
+//!
+//! ```no_run
+//! use prolice::{analyze_repo, AnalyzeOptions};
+//!
+//! # async fn example() -> Result<(), prolice::AnalyzeError> {
+//! let score = analyze_repo("rust-lang", "rust", AnalyzeOptions {
+//!     sample_size: 50,
+//!     ..AnalyzeOptions::new("MY_AWESOME_PERSONAL_ACCESS_TOKEN")
+//! })
+//! .await?;
+//!
+//! println!("{}", score);
+//! # Ok(())
+//! # }
+//! ```
+
+use tokio::sync::mpsc;
+
+use crate::github::client::pool::{GitHubConnectionPool, GitHubConnectionPoolManager};
+use crate::github::utils::analyzer::AnalyzerBuilder;
+use crate::github::utils::pull_request_data::PullRequestData;
+use crate::github::utils::repository_data::get_score_with_flow;
+
+// shared with the CLI, which compiles them on its own (see main.rs); whatever only it needs is dead code
+// as far as the library's entry point is concerned
+#[allow(dead_code)]
+mod error;
+
+#[allow(dead_code)]
+mod metadata;
+
+#[allow(dead_code)]
+pub(crate) mod github;
+
+#[allow(dead_code)]
+pub(crate) mod report;
+
+#[allow(dead_code)]
+pub(crate) mod scoring;
+
+pub use crate::error::AnalyzeError;
+pub use crate::github::client::retry::{AbusePolicy, RetryPolicy};
+pub use crate::github::utils::retrieval_settings::RetrievalSettings;
+pub use crate::scoring::score::{Score, ScoreType};
+pub use crate::scoring::scoring_settings::ScoringSettings;
+
+/// The amount of PRs that make up a sample, unless told otherwise.
+pub const DEFAULT_SAMPLE_SIZE: u16 = 100;
+
+/// Everything [`analyze_repo`] needs to know besides which repository to analyze.
+#[derive(Debug, Clone)]
+pub struct AnalyzeOptions {
+    /// GitHub's `Personal Access Token`.
+    pub github_token: String,
+    /// The base URL of a GitHub Enterprise instance's REST API (ie: `https://github.mycorp.com/api/v3`);
+    /// github.com's is used if [`None`].
+    pub github_base_url: Option<String>,
+    /// The amount of PRs that make up the sample (see [`Analyzer::stream_repo_data`]).
+    pub sample_size: u16,
+    /// The maximum amount of concurrent connections against GitHub.
+    pub connection_pool_size: usize,
+    pub retrieval_settings: RetrievalSettings,
+    pub scoring_settings: ScoringSettings,
+    pub retry_policy: RetryPolicy,
+}
+
+impl AnalyzeOptions {
+    /// Instantiates the default [`AnalyzeOptions`] (the very same ones the CLI uses) for the given
+    /// `github_token`.
+    pub fn new(github_token: &str) -> Self {
+        AnalyzeOptions {
+            github_token: github_token.to_string(),
+            github_base_url: None,
+            sample_size: DEFAULT_SAMPLE_SIZE,
+            connection_pool_size: DEFAULT_SAMPLE_SIZE as usize,
+            retrieval_settings: RetrievalSettings::default(),
+            scoring_settings: ScoringSettings::default(),
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+}
+
+/// Analyzes a sample of the `owner`'s `repo`sitory's PRs, and produces its aggregated [`Score`].
+/// <br/><br/>
+/// The connection pool that every request goes through is created (and disposed of) along the way. PRs
+/// whose data could not be retrieved are left out of the sample, just like merge-PRs (unless the
+/// [`ScoringSettings`] include them) and PRs outside the [`RetrievalSettings`]' range of changes are;
+/// unless the [`RetryPolicy`] says to abort on rate-limiting (see [`AbusePolicy::Fail`]).
+pub async fn analyze_repo(
+    owner: &str, repo: &str, opts: AnalyzeOptions,
+) -> Result<Score, AnalyzeError> {
+    let connection_pool = GitHubConnectionPool::new(
+        GitHubConnectionPoolManager::new(&opts.github_token, opts.github_base_url),
+        opts.connection_pool_size,
+    );
+
+    let analyzer = AnalyzerBuilder::new(owner, repo, &opts.github_token, &connection_pool)
+        .retrieval_settings(opts.retrieval_settings)
+        .scoring_settings(opts.scoring_settings)
+        .retry_policy(opts.retry_policy)
+        .init()
+        .await?;
+
+    let (result_sender, mut result_receiver) = mpsc::unbounded_channel();
+    analyzer.stream_repo_data(opts.sample_size, false, result_sender).await;
+
+    let mut positioned_pull_requests_data: Vec<(usize, PullRequestData)> = Vec::new();
+    while let Some((position, pull_request_data_result)) = result_receiver.recv().await {
+        // PRs that errored were already reported while being retrieved
+        match pull_request_data_result {
+            Ok(pull_request_data) => {
+                positioned_pull_requests_data.push((position, pull_request_data))
+            }
+            Err(e) if analyzer.retry_policy().aborts_on(&e) => return Err(e),
+            Err(_) => {}
+        }
+    }
+
+    // PRs arrive in the order their retrieval completed; restore the listing's
+    positioned_pull_requests_data.sort_by_key(|(position, _)| *position);
+    let pull_requests_data: Vec<PullRequestData> = positioned_pull_requests_data
+        .into_iter()
+        .map(|(_, pull_request_data)| pull_request_data)
+        .filter(|pull_request_data| {
+            analyzer.scoring_settings().include_merge_prs || !pull_request_data.is_merge_pr()
+        })
+        .filter(|pull_request_data| {
+            analyzer
+                .retrieval_settings()
+                .is_within_changes_range(pull_request_data.get_amount_of_changes())
+        })
+        .collect();
+
+    let pull_request_flow = analyzer.retrieve_sample_flow(&pull_requests_data).await;
+    let sample: Vec<&PullRequestData> = pull_requests_data.iter().collect();
+
+    Ok(get_score_with_flow(&sample, &pull_request_flow, analyzer.scoring_settings()))
+}
@@ -0,0 +1,113 @@
+//! Formats in which the application's logs can be emitted, alongside a minimal logger for those that
+//! `simplelog` does not cover.
+//! <br/><br/>
+//! The `json` format prints one compact JSON object per log record (ie:
+//! `{"timestamp":"...","level":"INFO","target":"prolice::github","message":"..."}`), ready to be
+//! ingested by log aggregators.
+
+use std::io::Write;
+
+use chrono::{Local, SecondsFormat};
+use log::{LevelFilter, Log, Metadata, Record, SetLoggerError};
+use serde_json::json;
+use strum_macros::{Display, EnumString, EnumVariantNames};
+
+/// Formats in which log records can be emitted.
+#[derive(Display, EnumString, EnumVariantNames, Debug, Copy, Clone, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum LogFormat {
+    /// Colored, human-readable lines. The default.
+    Human,
+    /// One compact JSON object per log record, printed to stderr.
+    Json,
+}
+
+/// Logger that prints every record as a single line of JSON into stderr; so that logs never get mixed
+/// up with the analysis results printed into stdout.
+pub struct JsonLogger {
+    level: LevelFilter,
+    allowed_target: &'static str,
+}
+
+impl JsonLogger {
+    /// Globally initializes the [`JsonLogger`] as the one and only logging facility, emitting records up
+    /// to `level` whose target starts with `allowed_target` (ie: the package's name, leaving out the
+    /// noise from dependencies).
+    pub fn init(level: LevelFilter, allowed_target: &'static str) -> Result<(), SetLoggerError> {
+        log::set_max_level(level);
+        log::set_boxed_logger(Box::new(JsonLogger {
+            level,
+            allowed_target,
+        }))
+    }
+
+    /// Renders a `record` as a single line of JSON.
+    fn format(record: &Record) -> String {
+        json!({
+            "timestamp": Local::now().to_rfc3339_opts(SecondsFormat::Millis, false),
+            "level": record.level().as_str(),
+            "target": record.target(),
+            "message": record.args().to_string(),
+        })
+        .to_string()
+    }
+}
+
+impl Log for JsonLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level && metadata.target().starts_with(self.allowed_target)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            // a failure to write a log record is no reason to abort (nor is there anywhere left to report it)
+            let _ = writeln!(std::io::stderr().lock(), "{}", JsonLogger::format(record));
+        }
+    }
+
+    fn flush(&self) {
+        let _ = std::io::stderr().flush();
+    }
+}
+
+#[cfg(test)]
+mod logging_tests {
+    use log::{Level, LevelFilter, Log, Record};
+
+    use crate::prolice_logging::JsonLogger;
+
+    #[test]
+    fn records_are_rendered_as_single_line_json_objects() {
+        let line = JsonLogger::format(
+            &Record::builder()
+                .level(Level::Warn)
+                .target("prolice::github::client")
+                .args(format_args!("Rate limit reached;\nretrying in {} secs", 60))
+                .build(),
+        );
+
+        assert!(!line.contains('\n'));
+
+        let object: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(object["level"], "WARN");
+        assert_eq!(object["target"], "prolice::github::client");
+        assert_eq!(object["message"], "Rate limit reached;\nretrying in 60 secs");
+        assert!(chrono::DateTime::parse_from_rfc3339(object["timestamp"].as_str().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn only_records_within_level_and_from_the_allowed_target_are_enabled() {
+        let logger = JsonLogger {
+            level: LevelFilter::Info,
+            allowed_target: "prolice",
+        };
+        let enabled = |level: Level, target: &str| {
+            logger.enabled(&log::Metadata::builder().level(level).target(target).build())
+        };
+
+        assert!(enabled(Level::Info, "prolice::github"));
+        assert!(enabled(Level::Error, "prolice"));
+        assert!(!enabled(Level::Debug, "prolice::github"));
+        assert!(!enabled(Level::Info, "reqwest::connect"));
+    }
+}
@@ -1,56 +1,167 @@
 extern crate time;
 
+use std::iter::once;
+use std::path::Path;
 use std::process;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
+use anyhow::anyhow;
+use chrono::{DateTime, Local, NaiveDate, Utc};
 use clap::{App, Arg, ArgMatches};
 use console::{Emoji, Term};
-use log::{debug, error, LevelFilter};
-use once_cell::sync::OnceCell;
+use futures::future::{join_all, try_join_all};
+use futures::try_join;
+use glob::Pattern;
+use itertools::Itertools;
+use log::{debug, error, warn, LevelFilter};
+use octocrab::models::pulls::PullRequest;
+use regex::Regex;
 use simplelog::{ConfigBuilder, TerminalMode};
+use strum::VariantNames;
+use tokio::signal;
+use tokio::sync::{mpsc, Semaphore};
 
-use scoring::scorable::Scorable;
+use prolice::DEFAULT_SAMPLE_SIZE;
 
+use crate::error::AnalyzeError;
+use crate::github::client::connector::{get_rate_limit_budget, GitHubConnector};
 use crate::github::client::pool::{GitHubConnectionPool, GitHubConnectionPoolManager};
-use crate::github::utils::analyzer::AnalyzerBuilder;
+use crate::github::client::retry::{
+    AbusePolicy, RetryPolicy, DEFAULT_EMPTY_RESPONSE_RETRIES, DEFAULT_MAX_RETRIES,
+};
+use crate::github::utils::analyzer::{
+    Analyzer, AnalyzerBuilder, Interruption, DEFAULT_PR_TIMEOUT_SECS,
+};
+use crate::github::utils::failure_dump::FailureDump;
+use crate::github::utils::pull_request_cache::PullRequestCache;
 use crate::github::utils::pull_request_data::PullRequestData;
-use crate::scoring::score::{Score, ScoreType};
+use crate::github::utils::pull_request_url::PullRequestUrl;
+use crate::github::utils::repository_data::{get_score_with_flow, PullRequestFlow};
+use crate::github::utils::retrieval_settings::{
+    LabelMode, PullRequestSort, PullRequestState, RetrievalSettings, SampleStrategy, SortDirection,
+};
+use crate::prolice_config::{Config, ConfigurableArg};
+use crate::prolice_logging::{JsonLogger, LogFormat};
+use crate::report::opener;
+use crate::report::output_format::OutputFormat;
+use crate::report::result_output::ResultOutput;
+use crate::report::sqlite::{PullRequestRow, SqliteReport};
+use crate::scoring::baseline::Baseline;
+use crate::scoring::grade::{GradeWeights, GradingConfig};
+use crate::scoring::rescoring::{rescore, RescoredRepository, StoredScoreRecord};
+use crate::scoring::scorable::Scorable;
+use crate::scoring::score::{
+    MultiRepoScore, OrganizationScore, SampleStats, Score, ScoreRecord, ScoreType,
+};
+use crate::scoring::scoring_settings::{
+    AggregationStrategy, ContentionWords, FlowRatioSource, Holidays, MergePrHeuristic, MergeStyle,
+    ScoringSettings, TeamMembers, Timezone, WorkingHours,
+};
+use crate::scoring::threshold::Threshold;
 
-#[path = "error.rs"]
-mod prolice_error;
+#[path = "config.rs"]
+mod prolice_config;
 
-#[path = "metadata.rs"]
-mod prolice_metadata;
+#[path = "logging.rs"]
+mod prolice_logging;
+
+// the library's modules, compiled into the binary as well; the library only exposes what its own entry
+// point needs (see lib.rs)
+mod error;
 
 mod github;
 
+mod metadata;
+
+mod report;
+
 mod scoring;
 
 // CLI params ---
+const ABUSE_POLICY_PARAM: &str = "abuse-policy";
+const AGGREGATE_PARAM: &str = "aggregate";
+const AUTHOR_PARAM: &str = "author";
+const BASELINE_PARAM: &str = "baseline";
+const CACHE_DIR_PARAM: &str = "cache-dir";
+const CONFIG_PARAM: &str = "config";
+const CONNECTIONS_PARAM: &str = "connections";
+const CONTENTION_WORDS_PARAM: &str = "contention-words";
+const DIRECTION_PARAM: &str = "direction";
+const DISCUSSION_IDEAL_RANGE_PARAM: &str = "discussion-ideal-range";
+const DUMP_FAILURES_PARAM: &str = "dump-failures";
+const EMPTY_RESPONSE_RETRIES_PARAM: &str = "empty-response-retries";
+const EXCLUDE_PATH_PARAM: &str = "exclude-path";
+const EXPLAIN_PARAM: &str = "explain";
+const FAIL_IF_PARAM: &str = "fail-if";
+const FLOW_RATIO_IDEAL_PARAM: &str = "flow-ratio-ideal";
+const FLOW_RATIO_SOURCE_PARAM: &str = "flow-ratio-source";
+const FROM_JSON_PARAM: &str = "from-json";
+const GITHUB_BASE_URL_PARAM: &str = "github-base-url";
 const GITHUB_TOKEN_PARAM: &str = "github-token";
+const HOLIDAYS_PARAM: &str = "holidays";
+const LABEL_PARAM: &str = "label";
+const LABEL_MODE_PARAM: &str = "label-mode";
+const LOG_FORMAT_PARAM: &str = "log-format";
 const LOG_LEVEL_PARAM: &str = "log-level";
+const MAX_CHANGES_PARAM: &str = "max-changes";
+const MAX_INFLIGHT_PARAM: &str = "max-inflight";
+const MAX_RETRIES_PARAM: &str = "max-retries";
+const MERGE_PR_HEURISTIC_PARAM: &str = "merge-pr-heuristic";
+const MERGE_STYLE_PARAM: &str = "merge-style";
+const MIN_CHANGES_PARAM: &str = "min-changes";
+const OUTPUT_FORMAT_PARAM: &str = "format";
+const OUTPUT_PARAM: &str = "output";
 const OWNER_PARAM: &str = "owner";
 const PR_NUMBER_PARAM: &str = "pr-number";
+const PR_TIMEOUT_SECS_PARAM: &str = "pr-timeout-secs";
+const PR_URL_PARAM: &str = "pr-url";
 const REPOSITORY_PARAM: &str = "repository";
 const SAMPLE_SIZE_PARAM: &str = "sample-size";
+const SAMPLE_STRATEGY_PARAM: &str = "sample-strategy";
+const SEED_PARAM: &str = "seed";
+const SINCE_PARAM: &str = "since";
+const SORT_PARAM: &str = "sort";
+const STATE_PARAM: &str = "state";
+const TEAM_MEMBERS_PARAM: &str = "team-members";
+const TEST_PATTERN_PARAM: &str = "test-pattern";
+const TEST_RATIO_TARGET_PARAM: &str = "test-ratio-target";
+const TIMEZONE_PARAM: &str = "timezone";
+const UNTIL_PARAM: &str = "until";
+const WEIGHTS_PARAM: &str = "weights";
+const WORK_HOURS_PARAM: &str = "work-hours";
 
 // CLI flags ---
+const ALL_REPOS_FLAG: &str = "all-repos";
+const COUNT_SELF_REVIEWS_FLAG: &str = "count-self-reviews";
+const DRY_RUN_FLAG: &str = "dry-run";
 const INCLUDE_MERGE_PRS_FLAG: &str = "include-merge-prs";
+const INCLUDE_CLOSED_UNMERGED_FLAG: &str = "include-closed-unmerged";
+const NO_COLOR_FLAG: &str = "no-color";
+const OPEN_REPORT_FLAG: &str = "open-report";
 const PRINT_LEGENDS_FLAG: &str = "print-legends";
+const REFRESH_CACHE_FLAG: &str = "refresh-cache";
 const SILENT_MODE_FLAG: &str = "silent-mode";
+const STRICT_BUDGET_FLAG: &str = "strict-budget";
+const WEIGHT_BY_SIZE_FLAG: &str = "weight-by-size";
+const WITH_THREADS_FLAG: &str = "with-threads";
+const WITH_TIMELINE_FLAG: &str = "with-timeline";
+const WITH_CHECKS_FLAG: &str = "with-checks";
+const WITH_VARIANCE_FLAG: &str = "with-variance";
 
 // Default values ---
-const DEFAULT_SAMPLE_SIZE: u8 = 100;
-const MAX_SAMPLE_SIZE: u8 = DEFAULT_SAMPLE_SIZE;
-const MIN_SAMPLE_SIZE: u8 = 1;
+const MAX_SAMPLE_SIZE: u16 = 1000; // samples bigger than a single GitHub page (100 items) are fetched through pagination
+const MIN_SAMPLE_SIZE: u16 = 1;
 
-const DEFAULT_CONNECTION_POOL_SIZE: u8 = DEFAULT_SAMPLE_SIZE;
+const DEFAULT_CONNECTION_POOL_SIZE: u16 = DEFAULT_SAMPLE_SIZE;
 /* Using bigger pools than this default usually triggers *more* API abuse detection mechanisms from GitHub
 * ('more' because GitHub's definition of 'abuse' is arbitrary; sometimes a pool of 300+ concurrent connections
 * may trigger an abuse alarm in some requests, other times all of them will pass without hiccups).
 *
-* We usually skip blocked requests if GitHub gets too trigger happy with its abuse heuristics, but an
-* incomplete PR, even partially incomplete, is completely discarded; which ultimately shrinks our analysis pool
-* (which we don't want).
+* Blocked requests are retried (see --max-retries) if GitHub gets too trigger happy with its abuse heuristics,
+* but an incomplete PR, even partially incomplete, is completely discarded by default; which ultimately shrinks
+* our analysis pool (which we don't want). --abuse-policy retry re-queues such PRs for a later pass instead.
 *
 * So it's overall better to use rational defaults and try that as many concurrent connections as possible
 * get completed successfully, than have a massive pool where half of the requests fail (it may get 'faster'
@@ -59,18 +170,7 @@ const DEFAULT_CONNECTION_POOL_SIZE: u8 = DEFAULT_SAMPLE_SIZE;
 *
 * https://docs.github.com/en/rest/guides/best-practices-for-integrators#dealing-with-abuse-rate-limits
 */
-
-/// Global connection pool for GitHub.
-/// <br><br>
-/// We need the connection pool to be static (and thus have a static lifetime) because all GitHub calls
-/// are asynchronous. This allows us to perform hundreds of calls simultaneously, but with one caveat:
-/// spawned async tasks require complete ownership of the involved data for indefinite duration, and thus
-/// borrowed data (and this involves the borrowed shared-amongst-all connection pool) must have a 'static
-/// lifetime (so as to guarantee it does not get dereferenced along the way).
-/// We cannot set this 'static lifetime inside the main function (which is technically a tokio wrapper
-/// itself) either, so this is the most elegant way to go around the problem.
-/// https://stackoverflow.com/a/27826181
-static GITHUB_CONNECTION_POOL: OnceCell<GitHubConnectionPool> = OnceCell::new();
+const MIN_CONNECTION_POOL_SIZE: u16 = 1;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -80,6 +180,25 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // determine if console is user attended or not (ie: output is being piped into a file) ---
     let console_is_user_attended = console::user_attended();
 
+    // strip colors (and emojis) off the output, if asked to ---
+    let plain_output = colors_are_disabled(&args);
+    if plain_output {
+        console::set_colors_enabled(false);
+        console::set_colors_enabled_stderr(false);
+    }
+
+    // explain a single metric, without running any analysis at all ---
+    if let Some(metric) = args.value_of(EXPLAIN_PARAM) {
+        let score_type = ScoreType::from_name(metric).unwrap(); // already validated by the CLI
+        Term::stdout().write_line(&score_type.get_titled_legend())?;
+        return Ok(());
+    }
+
+    // re-score a previously exported report, without hitting GitHub at all ---
+    if let Some(report_path) = args.value_of(FROM_JSON_PARAM) {
+        return rescore_report(&args, Path::new(report_path), console_is_user_attended);
+    }
+
     // parse obligatory params ---
 
     // since the clamp crate is in charge of making sure these obligatory params are fulfilled as requirements,
@@ -91,26 +210,223 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         process::exit(1)
     });
 
-    let owner = args.value_of(OWNER_PARAM).unwrap_or_else(|| {
-        eprintln!("{} is an obligatory param! Aborting operation.", OWNER_PARAM);
-        process::exit(1)
-    });
+    // a PR's URL stands in for the owner, repository & PR number altogether
+    let pull_request_url: Option<PullRequestUrl> =
+        args.value_of(PR_URL_PARAM).map(|url| PullRequestUrl::parse(url).unwrap()); // already validated by the CLI
 
-    let repository = args.value_of(REPOSITORY_PARAM).unwrap_or_else(|| {
-        eprintln!("{} is an obligatory param! Aborting operation.", REPOSITORY_PARAM);
-        process::exit(1)
-    });
+    let owner = match &pull_request_url {
+        Some(pull_request_url) => pull_request_url.owner.as_str(),
+        None => args.value_of(OWNER_PARAM).unwrap_or_else(|| {
+            eprintln!("{} is an obligatory param! Aborting operation.", OWNER_PARAM);
+            process::exit(1)
+        }),
+    };
 
-    let sample_size: u8 = args.value_of_t_or_exit(SAMPLE_SIZE_PARAM);
+    let all_repos: bool = args.is_present(ALL_REPOS_FLAG);
+
+    // repositories are discovered on the fly when analyzing the whole organization
+    let repositories: Vec<&str> = if let Some(pull_request_url) = &pull_request_url {
+        vec![pull_request_url.repository.as_str()]
+    } else if all_repos {
+        Vec::new()
+    } else {
+        args.values_of(REPOSITORY_PARAM)
+            .unwrap_or_else(|| {
+                eprintln!("{} is an obligatory param! Aborting operation.", REPOSITORY_PARAM);
+                process::exit(1)
+            })
+            .map(str::trim)
+            .filter(|repository| !repository.is_empty())
+            .unique()
+            .collect()
+    };
+
+    let sample_size: u16 = args.value_of_t_or_exit(SAMPLE_SIZE_PARAM);
 
     // parse optional params & flags ---
+    // PRs hosted on a GitHub Enterprise instance are retrieved from its own API, unless told otherwise
+    let github_base_url: Option<String> = args
+        .value_of(GITHUB_BASE_URL_PARAM)
+        .map(String::from)
+        .or_else(|| pull_request_url.as_ref().and_then(PullRequestUrl::api_base_url));
+
+    let pull_request_cache: Option<PullRequestCache> =
+        args.value_of(CACHE_DIR_PARAM).map(|cache_dir| {
+            PullRequestCache::new(Path::new(cache_dir), args.is_present(REFRESH_CACHE_FLAG))
+        });
+
+    let failure_dump: Option<FailureDump> =
+        args.value_of(DUMP_FAILURES_PARAM).map(|dump_dir| FailureDump::new(Path::new(dump_dir)));
+
     let silent_mode: bool = !console_is_user_attended || args.is_present(SILENT_MODE_FLAG);
 
     let include_merge_prs: bool = args.is_present(INCLUDE_MERGE_PRS_FLAG);
 
+    let include_closed_unmerged: bool = args.is_present(INCLUDE_CLOSED_UNMERGED_FLAG);
+
+    let with_variance: bool = args.is_present(WITH_VARIANCE_FLAG);
+
+    let weight_by_size: bool = args.is_present(WEIGHT_BY_SIZE_FLAG);
+
+    let count_self_reviews: bool = args.is_present(COUNT_SELF_REVIEWS_FLAG);
+
+    // both were already validated by the CLI, so their unwrap(s) are safe
+    let (work_start, work_end) =
+        WorkingHours::parse_hours(args.value_of(WORK_HOURS_PARAM).unwrap()).unwrap();
+    let working_hours = WorkingHours {
+        start: work_start,
+        end: work_end,
+    };
+    let timezone = Timezone::parse(args.value_of(TIMEZONE_PARAM).unwrap()).unwrap();
+
+    let team_members: Option<TeamMembers> = args
+        .value_of(TEAM_MEMBERS_PARAM)
+        .map(|path| TeamMembers::from_file(Path::new(path)).unwrap()); // already validated by the CLI
+
+    let holidays: Holidays = args
+        .value_of(HOLIDAYS_PARAM)
+        .map(|path| Holidays::from_file(Path::new(path)).unwrap()) // already validated by the CLI
+        .unwrap_or_default();
+
+    let contention_words: Option<ContentionWords> = args
+        .value_of(CONTENTION_WORDS_PARAM)
+        .map(|path| ContentionWords::from_file(Path::new(path)).unwrap()); // already validated by the CLI
+
+    let with_threads: bool = args.is_present(WITH_THREADS_FLAG);
+
+    let with_timeline: bool = args.is_present(WITH_TIMELINE_FLAG);
+    let with_checks: bool = args.is_present(WITH_CHECKS_FLAG);
+
+    let merge_pr_heuristic: MergePrHeuristic = value_of_enum(&args, MERGE_PR_HEURISTIC_PARAM);
+    let merge_style: MergeStyle = value_of_enum(&args, MERGE_STYLE_PARAM);
+
+    let state: PullRequestState = value_of_enum(&args, STATE_PARAM);
+
+    let authors: Vec<String> = args
+        .values_of(AUTHOR_PARAM)
+        .map(|authors| {
+            authors
+                .map(str::trim)
+                .filter(|author| !author.is_empty())
+                .unique()
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let labels: Vec<String> = args
+        .values_of(LABEL_PARAM)
+        .map(|labels| {
+            labels
+                .map(str::trim)
+                .filter(|label| !label.is_empty())
+                .unique()
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let label_mode: LabelMode = value_of_enum(&args, LABEL_MODE_PARAM);
+
+    let sort: PullRequestSort = value_of_enum(&args, SORT_PARAM);
+
+    let direction: SortDirection = value_of_enum(&args, DIRECTION_PARAM);
+
+    let sample_strategy: SampleStrategy = value_of_enum(&args, SAMPLE_STRATEGY_PARAM);
+
+    let seed: Option<u64> = args.value_of_t(SEED_PARAM).ok();
+
+    let max_retries: u32 = args.value_of_t_or_exit(MAX_RETRIES_PARAM);
+
+    let abuse_policy: AbusePolicy = value_of_enum(&args, ABUSE_POLICY_PARAM);
+
+    let empty_response_retries: u32 = args.value_of_t_or_exit(EMPTY_RESPONSE_RETRIES_PARAM);
+
+    let pr_timeout_secs: u64 = args.value_of_t_or_exit(PR_TIMEOUT_SECS_PARAM);
+
+    let strict_budget: bool = args.is_present(STRICT_BUDGET_FLAG);
+
+    let dry_run: bool = args.is_present(DRY_RUN_FLAG);
+
+    let aggregation_strategy: AggregationStrategy = value_of_enum(&args, AGGREGATE_PARAM);
+
+    let flow_ratio_source: FlowRatioSource = value_of_enum(&args, FLOW_RATIO_SOURCE_PARAM);
+
+    let output_format: OutputFormat = get_output_format(&args, console_is_user_attended);
+
+    let connection_pool_size: u16 = args.value_of_t_or_exit(CONNECTIONS_PARAM);
+
+    // shared by every analyzer, so that the bound holds across all repositories analyzed at once
+    let inflight_limit = Arc::new(Semaphore::new(
+        args.value_of_t(MAX_INFLIGHT_PARAM).unwrap_or(connection_pool_size as usize),
+    ));
+
     let print_metric_legends: bool = !silent_mode && args.is_present(PRINT_LEGENDS_FLAG);
 
-    let selected_pr_number: Result<u64, _> = args.value_of_t(PR_NUMBER_PARAM);
+    let selected_pr_number: Result<u64, clap::Error> = match &pull_request_url {
+        Some(pull_request_url) => Ok(pull_request_url.pr_number),
+        None => args.value_of_t(PR_NUMBER_PARAM),
+    };
+
+    // dates were already validated by the CLI, so parsing them again cannot fail
+    let since: Option<DateTime<Utc>> =
+        args.value_of(SINCE_PARAM).map(|since| parse_date(since, false).unwrap());
+    let until: Option<DateTime<Utc>> =
+        args.value_of(UNTIL_PARAM).map(|until| parse_date(until, true).unwrap());
+
+    let min_changes: Option<usize> = args.value_of_t(MIN_CHANGES_PARAM).ok();
+    let max_changes: Option<usize> = args.value_of_t(MAX_CHANGES_PARAM).ok();
+
+    // pattern was already validated by the CLI, so compiling it again cannot fail
+    let test_file_pattern: Option<Regex> =
+        args.value_of(TEST_PATTERN_PARAM).map(|pattern| Regex::new(pattern).unwrap());
+
+    let excluded_paths: Vec<Pattern> = args
+        .values_of(EXCLUDE_PATH_PARAM)
+        .map(|excluded_paths| {
+            excluded_paths.map(|excluded_path| Pattern::new(excluded_path).unwrap()).collect()
+        })
+        .unwrap_or_else(|| ScoringSettings::default().excluded_paths);
+
+    let grade_weights: GradeWeights = get_grade_weights(&args);
+
+    let grading_config: GradingConfig = get_grading_config(&args);
+
+    let thresholds: Vec<Threshold> = get_thresholds(&args);
+
+    let baseline: Option<Baseline> =
+        args.value_of(BASELINE_PARAM).map(|path| Baseline::from_file(Path::new(path)).unwrap()); // already validated by the CLI
+
+    if repositories.len() > 1 && selected_pr_number.is_ok() {
+        eprintln!(
+            "{} can only be used alongside a single {}! Aborting operation.",
+            PR_NUMBER_PARAM, REPOSITORY_PARAM
+        );
+        process::exit(1)
+    }
+
+    if repositories.len() > 1 && baseline.is_some() {
+        eprintln!(
+            "{} can only be used alongside a single {}! Aborting operation.",
+            BASELINE_PARAM, REPOSITORY_PARAM
+        );
+        process::exit(1)
+    }
+
+    if let Err(e) = check_date_window(since, until) {
+        eprintln!("{}! Aborting operation.", e);
+        process::exit(1)
+    }
+
+    if let (Some(min_changes), Some(max_changes)) = (min_changes, max_changes) {
+        if max_changes < min_changes {
+            eprintln!(
+                "{} ({}) cannot be lower than {} ({})! Aborting operation.",
+                MAX_CHANGES_PARAM, max_changes, MIN_CHANGES_PARAM, min_changes
+            );
+            process::exit(1)
+        }
+    }
 
     // initialize logging facade ---
     let log_level = if !silent_mode {
@@ -122,20 +438,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         LevelFilter::Off
     };
 
-    init_logging(log_level);
+    init_logging(log_level, value_of_enum(&args, LOG_FORMAT_PARAM), !plain_output);
 
     // initialize GitHub's connection pool ---
-    GITHUB_CONNECTION_POOL.set(
-        GitHubConnectionPool::new(
-            GitHubConnectionPoolManager::new(github_token),
-            DEFAULT_CONNECTION_POOL_SIZE as usize // (must be a good API citizen and use a rational number of concurrent connections, or risk rejection by remote endpoint)
-        )
-    ).unwrap_or_else(|e| {
-        error!("Could not initialize GitHub's connection pool. This is a mandatory requirement for operation. Aborting immediately.");
-        panic!(e) // this is a fatal error that involves delving into the codebase; ungracefully panic
-    });
-
-    let github_connection_pool = GITHUB_CONNECTION_POOL.get().unwrap(); // we just initialized it above, no need to error check (again)
+    // (shared by every analyzer; must be a good API citizen and use a rational number of concurrent connections, or risk rejection by remote endpoint)
+    let github_connection_pool = GitHubConnectionPool::new(
+        GitHubConnectionPoolManager::new(github_token, github_base_url),
+        connection_pool_size as usize,
+    );
 
     // initialize app ---
     let stdout: Option<Term> = if !silent_mode {
@@ -147,186 +457,1651 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     if let Some(stdout) = &stdout {
         stdout.write_line(get_logo())?;
 
-        let paper_emoji = Emoji("📃", "*");
-        let looking_glass_emoji = Emoji("🔍", "*");
-        let number_emoji = Emoji("🔢", "*");
-        let ruler_emoji = Emoji("📏", "*");
+        let paper_emoji = emoji("📃", "*", plain_output);
+        let looking_glass_emoji = emoji("🔍", "*", plain_output);
+        let number_emoji = emoji("🔢", "*", plain_output);
+        let ruler_emoji = emoji("📏", "*", plain_output);
+        let calendar_emoji = emoji("📅", "*", plain_output);
+        let author_emoji = emoji("👤", "*", plain_output);
+        let label_emoji = emoji("🏷️", "*", plain_output);
 
         stdout.write_line(&format!("{} Initializing analysis for [{}].", paper_emoji, owner))?;
-        stdout.write_line(&format!("{} Target is [{}].", looking_glass_emoji, repository))?;
+        if all_repos {
+            stdout.write_line(&format!(
+                "{} Target is every repository under [{}].",
+                looking_glass_emoji, owner
+            ))?;
+        } else {
+            stdout.write_line(&format!(
+                "{} Target is [{}].",
+                looking_glass_emoji,
+                repositories.join("], [")
+            ))?;
+        }
 
         if let Ok(pr_number) = selected_pr_number {
             stdout
                 .write_line(&format!("{} Selected PR number is [{}].", number_emoji, pr_number))?;
         } else {
             stdout.write_line(&format!(
-                "{} Using a sample size of [{}] PRs per repository.",
-                ruler_emoji, sample_size
+                "{} Using a sample size of [{}] [{}] PRs per repository.",
+                ruler_emoji, sample_size, state
             ))?;
+
+            if since.is_some() || until.is_some() {
+                stdout.write_line(&format!(
+                    "{} Sampling PRs created between [{}] and [{}].",
+                    calendar_emoji,
+                    since.map_or_else(|| String::from("the beginning"), |since| since.to_string()),
+                    until.map_or_else(|| String::from("now"), |until| until.to_string())
+                ))?;
+            }
+
+            if !labels.is_empty() {
+                stdout.write_line(&format!(
+                    "{} Sampling PRs labeled with [{}] of [{}].",
+                    label_emoji,
+                    label_mode,
+                    labels.join("], [")
+                ))?;
+            }
+
+            if !authors.is_empty() {
+                stdout.write_line(&format!(
+                    "{} Sampling PRs opened by [{}].",
+                    author_emoji,
+                    authors.join("], [")
+                ))?;
+            }
         }
 
         stdout.write_line(&"=".repeat(stdout.size().1 as usize))?; // print separator for whole length of stdout
     }
 
-    // initialize repo/pr analyzer ---
-    let analyzer = AnalyzerBuilder::new(owner, repository, github_token, github_connection_pool)
-        .init()
-        .await
-        .unwrap_or_else(|e| {
+    // initialize repo/pr analyzer(s) ---
+    let scoring_settings = ScoringSettings {
+        test_file_pattern,
+        excluded_paths,
+        merge_pr_heuristic,
+        merge_style,
+        include_merge_prs,
+        include_closed_unmerged,
+        flow_ratio_source,
+        aggregation_strategy,
+        grade_weights,
+        grading_config,
+        with_variance,
+        weight_by_size,
+        count_self_reviews,
+        working_hours,
+        timezone,
+        team_members,
+        holidays,
+        contention_words,
+    };
+
+    // shared by every analyzer, so that a single interrupt winds all of them down
+    let interruption = Interruption::default();
+
+    let analyzer_builder_for = |repository: &str| {
+        let analyzer_builder =
+            AnalyzerBuilder::new(owner, repository, github_token, &github_connection_pool)
+                .retrieval_settings(RetrievalSettings {
+                    since,
+                    until,
+                    state,
+                    authors: authors.clone(),
+                    labels: labels.clone(),
+                    label_mode,
+                    sort,
+                    direction,
+                    sample_strategy,
+                    seed,
+                    with_threads,
+                    with_timeline,
+                    with_checks,
+                    min_changes,
+                    max_changes,
+                })
+                .scoring_settings(scoring_settings.clone())
+                .retry_policy(RetryPolicy {
+                    max_retries,
+                    empty_response_retries,
+                    abuse_policy,
+                    ..Default::default()
+                })
+                .pr_timeout(Duration::from_secs(pr_timeout_secs))
+                .inflight_limit(inflight_limit.clone())
+                .interruption(interruption.clone());
+
+        let analyzer_builder = match &pull_request_cache {
+            Some(pull_request_cache) => {
+                analyzer_builder.pull_request_cache(pull_request_cache.clone())
+            }
+            None => analyzer_builder,
+        };
+
+        match &failure_dump {
+            Some(failure_dump) => analyzer_builder.failure_dump(failure_dump.clone()),
+            None => analyzer_builder,
+        }
+    };
+
+    let analyzers: Vec<Analyzer> = if all_repos {
+        // the repository name is irrelevant when initializing every repository of an organization
+        analyzer_builder_for("").init_all().await.unwrap_or_else(|e| {
             error!(
-                "There was an error initializing Analyzer for [{}]/[{}]. Aborting operation.",
-                owner, repository
+                "There was an error initializing Analyzers for organization [{}]. Aborting operation.",
+                owner
             );
-            error!("{}", e);
-            // we don't to panic in this potentially expected scenario (owner or repo name(s) may be misspelled in passed args)
-            // exit gracefully, but with an error
-            process::exit(1)
-        });
+            exit_on_error(&e, &args, output_format)
+        })
+    } else {
+        join_all(repositories.iter().map(|repository| {
+            let analyzer_builder = analyzer_builder_for(repository);
+
+            async move { analyzer_builder.init().await }
+        }))
+        .await
+        .into_iter()
+        .zip(repositories.iter())
+        .map(|(analyzer, repository)| {
+            analyzer.unwrap_or_else(|e| {
+                error!(
+                    "There was an error initializing Analyzer for [{}]/[{}]. Aborting operation.",
+                    owner, repository
+                );
+                // we don't to panic in this potentially expected scenario (owner or repo name(s) may be misspelled in passed args)
+                // exit gracefully, but with an error
+                exit_on_error(&e, &args, output_format)
+            })
+        })
+        .collect()
+    };
+
+    let result_out = get_result_output(&args, output_format); // result always ignores 'silent' flag
+
+    // list the would-be sample(s), without analyzing anything ---
+    if dry_run {
+        let samples: Vec<Vec<PullRequest>> =
+            join_all(analyzers.iter().map(|analyzer| analyzer.list_repo_sample(sample_size))).await;
+
+        for (analyzer, sample) in analyzers.iter().zip(samples.iter()) {
+            for pr in sample {
+                result_out.write_line(&format!(
+                    "{}#{}\t{}\t{}",
+                    analyzer.repository().name,
+                    pr.number,
+                    pr.user.login,
+                    pr.title
+                ))?;
+            }
+        }
+
+        result_out.finish()?;
+        return Ok(());
+    }
+
+    // check whether the analysis fits in the remaining rate-limit budget ---
+    let retrieval_settings = analyzers[0].retrieval_settings();
+    let retry_policy = analyzers[0].retry_policy();
+    let planned_requests = if selected_pr_number.is_ok() {
+        Analyzer::requests_per_pr(retrieval_settings, retry_policy)
+    } else {
+        analyzers.len() as u64
+            * Analyzer::planned_requests(
+                sample_size,
+                retrieval_settings,
+                analyzers[0].scoring_settings(),
+                retry_policy,
+            )
+    };
+
+    if !check_rate_limit_budget(&analyzers[0], planned_requests).await && strict_budget {
+        error!(
+            "Aborting operation, as the remaining rate-limit budget is not enough (see --{}).",
+            STRICT_BUDGET_FLAG
+        );
+        process::exit(1)
+    }
 
     // execute analysis for selected target(s) ---
-    let result_out = Term::stdout(); // result always ignores 'silent' flag
+    let mut threshold_breaches: Vec<String> = Vec::new();
 
     if let Ok(pr_number) = selected_pr_number {
+        // a PR number can only be selected alongside a single repository, so there is exactly one analyzer
+        let analyzer = &analyzers[0];
+
         // https://github.com/warnerbrostv/Project-Brainiac-Java/pull/5486
         let pr_score: Score = analyzer
             .retrieve_pr_data(pr_number) // 6909/6913 for attachments; 5486 for extensive commentary; 6854 for a REALLY LONG wip PR; 6830 for more deletions than additions
             .await
-            .unwrap_or_else(|e| {
-                error!("{}", e);
-                process::exit(1);
-            })
+            .unwrap_or_else(|e| exit_on_error(&e, &args, output_format))
             .get_score();
 
-        print_metrics_legends(print_metric_legends, &result_out); // print metrics' legends, if flag allows for it
-        result_out.write_line(&format!("{}", pr_score))?;
+        print_metrics_legends(print_metric_legends, &Term::stdout()); // print metrics' legends, if flag allows for it
+
+        match output_format {
+            OutputFormat::Json => result_out.write_line(&format!("{}", pr_score))?,
+            OutputFormat::Csv => {
+                result_out.write_line(&Score::to_csv_header())?;
+                result_out.write_line(&pr_score.to_csv(&pr_number.to_string()))?;
+            }
+            OutputFormat::Prometheus => result_out.write_str(&Score::to_prometheus(&[(
+                vec![
+                    ("owner", owner),
+                    ("repo", &analyzer.repository().name),
+                    ("pr_number", &pr_number.to_string()),
+                ],
+                &pr_score,
+            )]))?,
+            OutputFormat::Jsonl => result_out.write_line(
+                &ScoreRecord {
+                    owner,
+                    repository: Some(&analyzer.repository().name),
+                    pr_number: Some(pr_number),
+                    score: &pr_score,
+                }
+                .to_json_line(),
+            )?,
+            OutputFormat::Html => result_out.write_str(&Score::to_html(
+                &[(format!("{}/{}#{}", owner, analyzer.repository().name, pr_number), &pr_score)],
+                &Local::now(),
+            ))?,
+            OutputFormat::Sqlite => write_sqlite_report(
+                &args,
+                &result_out,
+                &[PullRequestRow {
+                    owner,
+                    repository: &analyzer.repository().name,
+                    pr_number,
+                    score: &pr_score,
+                }],
+            )?,
+            OutputFormat::Pretty => result_out.write_line(&pr_score.to_pretty(&format!(
+                "{}/{}#{}",
+                owner,
+                analyzer.repository().name,
+                pr_number
+            )))?,
+        }
+
+        threshold_breaches.extend(find_threshold_breaches(
+            &thresholds,
+            &format!("{}/{}#{}", owner, analyzer.repository().name, pr_number),
+            &pr_score,
+        ));
     } else {
-        let repo_score: Score = analyzer
-            .retrieve_repo_data(sample_size)
+        // JSON-lines output streams every PR's record as soon as it gets retrieved, so legends must go first
+        let is_streamed = output_format == OutputFormat::Jsonl;
+        let streamed_output = if is_streamed {
+            Some(&result_out)
+        } else {
+            None
+        };
+
+        if is_streamed {
+            print_metrics_legends(print_metric_legends, &Term::stdout()); // print metrics' legends, if flag allows for it
+        }
+
+        // from now on, Ctrl+C winds the analysis down rather than killing it; a second one still does
+        tokio::spawn(handle_interrupts(interruption.clone()));
+
+        // all repositories are analyzed concurrently, sharing the same connection pool
+        let repository_analyses: Vec<RepositoryAnalysis> =
+            try_join_all(analyzers.iter().map(|analyzer| {
+                analyze_repository(analyzer, sample_size, !silent_mode, streamed_output)
+            }))
             .await
+            .unwrap_or_else(|e| exit_on_error(&e, &args, output_format));
+
+        if repository_analyses
             .iter()
-            .filter_map(|pull_request_data_result| pull_request_data_result.as_ref().ok())
-            .filter(|pull_request_data| {
-                let passes_filter = include_merge_prs || !pull_request_data.is_merge_pr();
-
-                if !passes_filter {
-                    debug!(
-                        "[{}]/[{}] filtered out for being a merge PR.",
-                        repository,
-                        pull_request_data.pr_number()
-                    )
+            .any(|repository_analysis| repository_analysis.sample_stats.was_interrupted())
+        {
+            warn!(
+                "The analysis was interrupted; its results cover only the PRs that had been fetched by then."
+            );
+        }
+
+        if !is_streamed {
+            print_metrics_legends(print_metric_legends, &Term::stdout()); // print metrics' legends, if flag allows for it
+        }
+
+        if all_repos {
+            // every PR weighs the same on the organization's aggregate, regardless of its repository
+            let org_score: Score = get_score_with_flow(
+                &repository_analyses
+                    .iter()
+                    .flat_map(|repository_analysis| repository_analysis.pull_requests_data.iter())
+                    .collect::<Vec<&PullRequestData>>(),
+                &PullRequestFlow::merge(
+                    repository_analyses
+                        .iter()
+                        .map(|repository_analysis| &repository_analysis.pull_request_flow),
+                ),
+                &scoring_settings,
+            )
+            .with_sample_stats(SampleStats::sum(
+                repository_analyses
+                    .iter()
+                    .map(|repository_analysis| &repository_analysis.sample_stats),
+            ));
+
+            // the organization-wide aggregate may get consumed while being output
+            threshold_breaches.extend(find_threshold_breaches(
+                &thresholds,
+                &format!("{} (organization-wide)", owner),
+                &org_score,
+            ));
+
+            match output_format {
+                OutputFormat::Json => {
+                    let repo_scores: MultiRepoScore = repository_analyses
+                        .iter()
+                        .map(|repository_analysis| {
+                            (
+                                repository_analysis.repository.clone(),
+                                repository_analysis.repo_score(),
+                            )
+                        })
+                        .collect();
+
+                    result_out.write_line(&format!(
+                        "{}",
+                        OrganizationScore::new(org_score, repo_scores)
+                    ))?
+                }
+                OutputFormat::Csv => {
+                    result_out.write_line(&format!("repository,{}", Score::to_csv_header()))?;
+                    for repository_analysis in &repository_analyses {
+                        for csv_row in repository_analysis.to_csv_rows() {
+                            result_out.write_line(&format!(
+                                "{},{}",
+                                repository_analysis.repository, csv_row
+                            ))?;
+                        }
+                    }
+                    result_out.write_line(&format!(
+                        "{}/*,{}",
+                        owner,
+                        org_score.to_csv("aggregate")
+                    ))?;
                 }
+                OutputFormat::Prometheus => {
+                    // the organization's aggregate is told apart from its repositories' by its lack of 'repo' label
+                    let repo_scores: Vec<(String, Score)> = repository_analyses
+                        .iter()
+                        .map(|repository_analysis| {
+                            (
+                                repository_analysis.repository.clone(),
+                                repository_analysis.repo_score(),
+                            )
+                        })
+                        .collect();
 
-                passes_filter
-            })
-            .collect::<Vec<&PullRequestData>>()
-            .get_score();
+                    result_out.write_str(&Score::to_prometheus(
+                        &once((vec![("owner", owner)], &org_score))
+                            .chain(repo_scores.iter().map(|(repository, repo_score)| {
+                                (vec![("owner", owner), ("repo", repository.as_str())], repo_score)
+                            }))
+                            .collect::<Vec<(Vec<(&str, &str)>, &Score)>>(),
+                    ))?
+                }
+                OutputFormat::Jsonl => {
+                    // every PR's record was streamed already; only the aggregates are left
+                    for repository_analysis in &repository_analyses {
+                        result_out.write_line(&repository_analysis.to_json_line(owner))?;
+                    }
+                    result_out.write_line(
+                        &ScoreRecord {
+                            owner,
+                            repository: None,
+                            pr_number: None,
+                            score: &org_score,
+                        }
+                        .to_json_line(),
+                    )?;
+                }
+                OutputFormat::Html => {
+                    let repo_scores: Vec<(String, Score)> = repository_analyses
+                        .iter()
+                        .map(|repository_analysis| {
+                            (
+                                format!("{}/{}", owner, repository_analysis.repository),
+                                repository_analysis.repo_score(),
+                            )
+                        })
+                        .collect();
+
+                    result_out.write_str(&Score::to_html(
+                        &repo_scores
+                            .iter()
+                            .map(|(title, repo_score)| (title.clone(), repo_score))
+                            .chain(once((format!("{} (organization-wide)", owner), &org_score)))
+                            .collect::<Vec<(String, &Score)>>(),
+                        &Local::now(),
+                    ))?
+                }
+                OutputFormat::Sqlite => write_repository_analyses_into_sqlite(
+                    &args,
+                    &result_out,
+                    owner,
+                    &repository_analyses,
+                )?,
+                OutputFormat::Pretty => {
+                    for repository_analysis in &repository_analyses {
+                        result_out.write_line(&repository_analysis.to_pretty(owner))?;
+                        result_out.write_line("")?;
+                    }
+                    result_out.write_line(
+                        &org_score.to_pretty(&format!("{} (organization-wide)", owner)),
+                    )?;
+                }
+            }
+        } else if let [repository_analysis] = repository_analyses.as_slice() {
+            // a single repository keeps the original, map-less, output; compared against the baseline, if any
+            let repo_score = match &baseline {
+                Some(baseline) => {
+                    let repo_score = repository_analysis.repo_score();
+                    let delta = baseline.diff(&repo_score);
+                    repo_score.with_delta(delta)
+                }
+                None => repository_analysis.repo_score(),
+            };
+
+            match output_format {
+                OutputFormat::Json => result_out.write_line(&format!("{}", repo_score))?,
+                OutputFormat::Csv => {
+                    result_out.write_line(&Score::to_csv_header())?;
+                    for csv_row in repository_analysis.to_csv_rows() {
+                        result_out.write_line(&csv_row)?;
+                    }
+                }
+                OutputFormat::Prometheus => result_out.write_str(&Score::to_prometheus(&[(
+                    vec![("owner", owner), ("repo", &repository_analysis.repository)],
+                    &repo_score,
+                )]))?,
+                OutputFormat::Jsonl => result_out.write_line(
+                    &ScoreRecord {
+                        owner,
+                        repository: Some(&repository_analysis.repository),
+                        pr_number: None,
+                        score: &repo_score,
+                    }
+                    .to_json_line(),
+                )?,
+                OutputFormat::Html => result_out.write_str(&Score::to_html(
+                    &[(format!("{}/{}", owner, repository_analysis.repository), &repo_score)],
+                    &Local::now(),
+                ))?,
+                OutputFormat::Sqlite => write_repository_analyses_into_sqlite(
+                    &args,
+                    &result_out,
+                    owner,
+                    &repository_analyses,
+                )?,
+                OutputFormat::Pretty => result_out.write_line(
+                    &repo_score.to_pretty(&format!("{}/{}", owner, repository_analysis.repository)),
+                )?,
+            }
+
+            threshold_breaches.extend(find_threshold_breaches(
+                &thresholds,
+                &format!("{}/{}", owner, repository_analysis.repository),
+                &repo_score,
+            ));
+        } else {
+            match output_format {
+                OutputFormat::Json => {
+                    let multi_repo_score: MultiRepoScore = repository_analyses
+                        .iter()
+                        .map(|repository_analysis| {
+                            (
+                                repository_analysis.repository.clone(),
+                                repository_analysis.repo_score(),
+                            )
+                        })
+                        .collect();
+
+                    result_out.write_line(&format!("{}", multi_repo_score))?
+                }
+                OutputFormat::Csv => {
+                    result_out.write_line(&format!("repository,{}", Score::to_csv_header()))?;
+                    for repository_analysis in &repository_analyses {
+                        for csv_row in repository_analysis.to_csv_rows() {
+                            result_out.write_line(&format!(
+                                "{},{}",
+                                repository_analysis.repository, csv_row
+                            ))?;
+                        }
+                    }
+                }
+                OutputFormat::Prometheus => {
+                    let repo_scores: Vec<(String, Score)> = repository_analyses
+                        .iter()
+                        .map(|repository_analysis| {
+                            (
+                                repository_analysis.repository.clone(),
+                                repository_analysis.repo_score(),
+                            )
+                        })
+                        .collect();
+
+                    result_out.write_str(&Score::to_prometheus(
+                        &repo_scores
+                            .iter()
+                            .map(|(repository, repo_score)| {
+                                (vec![("owner", owner), ("repo", repository.as_str())], repo_score)
+                            })
+                            .collect::<Vec<(Vec<(&str, &str)>, &Score)>>(),
+                    ))?
+                }
+                OutputFormat::Jsonl => {
+                    for repository_analysis in &repository_analyses {
+                        result_out.write_line(&repository_analysis.to_json_line(owner))?;
+                    }
+                }
+                OutputFormat::Html => {
+                    let repo_scores: Vec<(String, Score)> = repository_analyses
+                        .iter()
+                        .map(|repository_analysis| {
+                            (
+                                format!("{}/{}", owner, repository_analysis.repository),
+                                repository_analysis.repo_score(),
+                            )
+                        })
+                        .collect();
+
+                    result_out.write_str(&Score::to_html(
+                        &repo_scores
+                            .iter()
+                            .map(|(title, repo_score)| (title.clone(), repo_score))
+                            .collect::<Vec<(String, &Score)>>(),
+                        &Local::now(),
+                    ))?
+                }
+                OutputFormat::Sqlite => write_repository_analyses_into_sqlite(
+                    &args,
+                    &result_out,
+                    owner,
+                    &repository_analyses,
+                )?,
+                OutputFormat::Pretty => {
+                    let pretty_scores: Vec<String> = repository_analyses
+                        .iter()
+                        .map(|repository_analysis| repository_analysis.to_pretty(owner))
+                        .collect();
+
+                    result_out.write_line(&pretty_scores.join("\n\n"))?
+                }
+            }
+
+            for repository_analysis in &repository_analyses {
+                threshold_breaches.extend(find_threshold_breaches(
+                    &thresholds,
+                    &format!("{}/{}", owner, repository_analysis.repository),
+                    &repository_analysis.repo_score(),
+                ));
+            }
+        }
+    }
 
-        print_metrics_legends(print_metric_legends, &result_out); // print metrics' legends, if flag allows for it
-        result_out.write_line(&format!("{}", repo_score))?;
+    result_out.finish()?;
+    open_report(&args, output_format, silent_mode);
+
+    // partial results are output just as usual; but automation should not mistake them for whole ones -
+    // nor mistake their threshold breaches for those of the whole sample
+    if interruption.is_raised() {
+        process::exit(130) // 128 + SIGINT, as shells do
     }
 
+    exit_on_threshold_breaches(&threshold_breaches);
+
     Ok(())
 }
 
-/// Retrieves the application's ASCII-art logo.
-fn get_logo() -> &'static str {
-    r#"
-        ooooooooo.   ooooooooo.             oooo   o8o
-        `888   `Y88. `888   `Y88.           `888   `"'
-         888   .d88'  888   .d88'  .ooooo.   888  oooo   .ooooo.   .ooooo.
-         888ooo88P'   888ooo88P'  d88' `88b  888  `888  d88' `"Y8 d88' `88b
-         888          888`88b.    888   888  888   888  888       888ooo888
-         888          888  `88b.  888   888  888   888  888   .o8 888    .o
-        o888o        o888o  o888o `Y8bod8P' o888o o888o `Y8bod8P' `Y8bod8P'
-        ------------ What you gonna do when they come for you -------------
-    "#
+/// The outcome of analyzing a sample of a single repository's PRs.
+struct RepositoryAnalysis {
+    repository: String,
+    pull_requests_data: Vec<PullRequestData>,
+    /// The flow over which the repository's Pull Request Flow Ratio is calculated; see [`FlowRatioSource`].
+    pull_request_flow: PullRequestFlow,
+    /// The repository-level settings its sample gets aggregated with.
+    scoring_settings: ScoringSettings,
+    sample_stats: SampleStats,
 }
 
-/// Prints analyzed metrics' legends into target [`Term`], if `toggle` is `true`;
-fn print_metrics_legends(toggle: bool, term: &Term) {
-    if !toggle {
-        return;
+impl RepositoryAnalysis {
+    /// Returns the aggregated [`Score`] of every analyzed PR.
+    fn repo_score(&self) -> Score {
+        get_score_with_flow(
+            &self.pull_requests_data.iter().collect::<Vec<&PullRequestData>>(),
+            &self.pull_request_flow,
+            &self.scoring_settings,
+        )
+        .with_sample_stats(self.sample_stats)
     }
 
-    term.write_line(&ScoreType::get_legends()).unwrap_or_else(|e| {
-        error!("An error has occurred while printing metrics' legends to term! Error = {}", e);
-    });
-    term.write_line(&"=".repeat(term.size().1 as usize)).unwrap_or_else(|e| {
-        error!("An error has occurred while printing line separator term! Error = {}", e);
-    });
-}
+    /// Returns one CSV row per analyzed PR, followed by a final aggregate row.
+    fn to_csv_rows(&self) -> Vec<String> {
+        self.pull_requests_data
+            .iter()
+            .map(|pull_request_data| {
+                pull_request_data.get_score().to_csv(&pull_request_data.pr_number().to_string())
+            })
+            .chain(once(self.repo_score().to_csv("aggregate")))
+            .collect()
+    }
 
-/// Initializes the `Log` crate's logging facade.
-fn init_logging(log_level: LevelFilter) {
-    simplelog::TermLogger::init(
-        log_level,
-        ConfigBuilder::new()
-            .add_filter_allow_str(prolice_metadata::package_name())
-            .set_time_to_local(true)
-            .build(),
-        TerminalMode::Mixed,
-    )
-    .unwrap() // we want to panic if the logger couldn't be initialized, so the unwrap() is adequate
+    /// Returns the aggregated [`Score`] as a single JSON-lines record.
+    fn to_json_line(&self, owner: &str) -> String {
+        ScoreRecord {
+            owner,
+            repository: Some(&self.repository),
+            pr_number: None,
+            score: &self.repo_score(),
+        }
+        .to_json_line()
+    }
+
+    /// Returns the aggregated [`Score`] in a human-readable form, headed by the repository's full name.
+    fn to_pretty(&self, owner: &str) -> String {
+        self.repo_score().to_pretty(&format!("{}/{}", owner, self.repository))
+    }
 }
 
-/// Sets up the CLI for the whole application.
-fn setup_cli() -> ArgMatches {
-    return App::new(prolice_metadata::package_name())
-        .version(prolice_metadata::full_version())
-        .author(prolice_metadata::authors())
-        .about(prolice_metadata::description())
-        // params start here ---
-        .arg(
-            Arg::new(OWNER_PARAM)
-                .long(OWNER_PARAM)
-                .short('O')
-                .about("The owner of the repository under scrutiny")
-                .required(true)
-                .takes_value(true)
-                .case_insensitive(false),
-        )
-        .arg(
-            Arg::new(REPOSITORY_PARAM)
-                .long(REPOSITORY_PARAM)
-                .short('R')
-                .about("The repository under scrutiny")
-                .required(true)
-                .takes_value(true)
-                .case_insensitive(false),
-        )
-        .arg(
-            Arg::new(SAMPLE_SIZE_PARAM)
-                .long(SAMPLE_SIZE_PARAM)
-                .short('S')
-                .about(
-                    "The amount of PRs that will be fetched as sample for the analysis (unless a specific \
-                    PR number is selected as individual target)"
-                )
-                .required(true)
-                .takes_value(true)
-                .validator(|value| {
-                    let value = value.parse::<usize>();
+/// Analyzes a sample of `sample_size` PRs from `analyzer`'s repository; merge-PRs are left out of it
+/// unless its [`ScoringSettings`] include them, and so are PRs outside of its [`RetrievalSettings`]'
+/// changes range. Sampling progress is displayed only if `show_progress` is `true`.
+/// <br/><br/>
+/// PRs are drained from the [`Analyzer`] as soon as each one of them gets retrieved; if a
+/// `streamed_output` is given, every sampled PR's JSON-lines record is written into it right away.
+/// Should the analysis get interrupted (see [`Interruption`]), the sample is made of whatever PRs were
+/// retrieved by then; and its [`SampleStats`] tell as much.
+/// <br/><br/>
+/// Under [`AbusePolicy::Fail`], the analysis is aborted with the first PR that GitHub's rate-limiting (or
+/// abuse detection) mechanisms kept rejecting.
+async fn analyze_repository(
+    analyzer: &Analyzer, sample_size: u16, show_progress: bool,
+    streamed_output: Option<&ResultOutput>,
+) -> Result<RepositoryAnalysis, AnalyzeError> {
+    let repository = &analyzer.repository().name;
+    let include_merge_prs = analyzer.scoring_settings().include_merge_prs;
+    let retry_policy = analyzer.retry_policy();
+    let retrieval_settings = analyzer.retrieval_settings();
 
-                    if value.is_err() {
-                        return Err(format!(
-                            "Supplied value must be an integer number between {} and {}",
-                            MIN_SAMPLE_SIZE, MAX_SAMPLE_SIZE
-                        ));
-                    }
+    let (result_sender, mut result_receiver) = mpsc::unbounded_channel();
 
-                    let value = value.unwrap();
+    let streaming = async {
+        analyzer.stream_repo_data(sample_size, show_progress, result_sender).await;
+        Ok(())
+    };
+    let draining = async {
+        let mut fetched: usize = 0;
+        let mut errored: usize = 0;
+        let mut skipped_on_interruption: usize = 0;
+        let mut filtered_as_merge: usize = 0;
+        let mut filtered_by_size: usize = 0;
+        let mut pull_requests_data: Vec<(usize, PullRequestData)> = Vec::new();
 
-                    if !(1..=100).contains(&value) {
-                        return Err(format!(
-                            "Supplied value must be an integer number between {} and {}, but was {}",
-                            MIN_SAMPLE_SIZE, MAX_SAMPLE_SIZE, value
-                        ));
-                    }
+        while let Some((position, pull_request_data_result)) = result_receiver.recv().await {
+            // PRs that errored were already reported while being retrieved; but they still count towards
+            // the sample's stats, lest its aggregates look more authoritative than they are
+            let pull_request_data = match pull_request_data_result {
+                Ok(pull_request_data) => pull_request_data,
+                Err(AnalyzeError::PullRequestSkippedOnInterruption {
+                    ..
+                }) => {
+                    skipped_on_interruption += 1;
+                    continue;
+                }
+                Err(e) if retry_policy.aborts_on(&e) => return Err(e),
+                Err(_) => {
+                    errored += 1;
+                    continue;
+                }
+            };
+            fetched += 1;
 
-                    Ok(())
+            if !include_merge_prs && pull_request_data.is_merge_pr() {
+                debug!(
+                    "[{}]/[{}] filtered out for being a merge PR.",
+                    repository,
+                    pull_request_data.pr_number()
+                );
+                filtered_as_merge += 1;
+                continue;
+            }
+
+            let changes = pull_request_data.get_amount_of_changes();
+            if !retrieval_settings.is_within_changes_range(changes) {
+                debug!(
+                    "[{}]/[{}] filtered out for its size ([{}] changes).",
+                    repository,
+                    pull_request_data.pr_number(),
+                    changes
+                );
+                filtered_by_size += 1;
+                continue;
+            }
+
+            if let Some(streamed_output) = streamed_output {
+                // records are written whole, from a single task; so they never interleave
+                let record = ScoreRecord {
+                    owner: analyzer.owner(),
+                    repository: Some(repository),
+                    pr_number: Some(pull_request_data.pr_number()),
+                    score: &pull_request_data.get_score(),
+                }
+                .to_json_line();
+
+                streamed_output.write_line(&record).unwrap_or_else(|e| {
+                    error!("An error has occurred while streaming PR record! Error = {}", e);
+                });
+            }
+
+            pull_requests_data.push((position, pull_request_data));
+        }
+
+        Ok((
+            fetched,
+            errored,
+            skipped_on_interruption,
+            filtered_as_merge,
+            filtered_by_size,
+            pull_requests_data,
+        ))
+    };
+
+    let (
+        _,
+        (
+            fetched,
+            errored,
+            skipped_on_interruption,
+            filtered_as_merge,
+            filtered_by_size,
+            mut positioned_pull_requests_data,
+        ),
+    ) = try_join!(streaming, draining)?;
+
+    // PRs arrive in the order their retrieval completed; restore the listing's
+    positioned_pull_requests_data.sort_by_key(|(position, _)| *position);
+    let pull_requests_data: Vec<PullRequestData> = positioned_pull_requests_data
+        .into_iter()
+        .map(|(_, pull_request_data)| pull_request_data)
+        .collect();
+
+    // every listed PR ends up either fetched, errored or skipped upon interruption
+    let sample_stats = SampleStats {
+        requested: sample_size as u64,
+        actual_sample_size: (fetched + errored + skipped_on_interruption) as u64,
+        fetched: fetched as u64,
+        filtered_as_merge: filtered_as_merge as u64,
+        filtered_by_size: filtered_by_size as u64,
+        errored: errored as u64,
+        skipped_on_interruption: skipped_on_interruption as u64,
+    };
+
+    let pull_request_flow = analyzer.retrieve_sample_flow(&pull_requests_data).await;
+
+    Ok(RepositoryAnalysis {
+        repository: repository.to_string(),
+        pull_requests_data,
+        pull_request_flow,
+        scoring_settings: analyzer.scoring_settings().clone(),
+        sample_stats,
+    })
+}
+
+/// Re-grades and re-aggregates the per-PR scores of the JSON-lines report at `report_path` (see
+/// [`rescore`]) according to the scoring params in `args`, and outputs the results just like a fresh
+/// analysis of the same repositories would.
+fn rescore_report(
+    args: &ArgMatches, report_path: &Path, console_is_user_attended: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output_format: OutputFormat = get_output_format(args, console_is_user_attended);
+    let exit_on_invalid_report = |msg: String| -> ! {
+        exit_on_error(&AnalyzeError::Other(anyhow!(msg)), args, output_format)
+    };
+
+    let raw_report = std::fs::read_to_string(report_path).unwrap_or_else(|e| {
+        exit_on_invalid_report(format!("Could not read report [{}]: {}", report_path.display(), e))
+    });
+
+    let records = StoredScoreRecord::from_json_lines(&raw_report).unwrap_or_else(|e| {
+        exit_on_invalid_report(format!("Invalid report [{}]: {}", report_path.display(), e))
+    });
+
+    let scoring_settings = ScoringSettings {
+        aggregation_strategy: value_of_enum(args, AGGREGATE_PARAM),
+        grade_weights: get_grade_weights(args),
+        grading_config: get_grading_config(args),
+        with_variance: args.is_present(WITH_VARIANCE_FLAG),
+        weight_by_size: args.is_present(WEIGHT_BY_SIZE_FLAG),
+        ..Default::default()
+    };
+
+    let rescored_repositories = rescore(records, &scoring_settings);
+
+    if rescored_repositories.is_empty() {
+        exit_on_invalid_report(format!(
+            "Report [{}] holds no repository records.",
+            report_path.display()
+        ));
+    }
+
+    let thresholds: Vec<Threshold> = get_thresholds(args);
+    let threshold_breaches: Vec<String> = rescored_repositories
+        .iter()
+        .flat_map(|rescored| {
+            find_threshold_breaches(
+                &thresholds,
+                &format!("{}/{}", rescored.owner, rescored.repository),
+                &rescored.repo_score,
+            )
+        })
+        .collect();
+
+    let silent_mode = !console_is_user_attended || args.is_present(SILENT_MODE_FLAG);
+    print_metrics_legends(!silent_mode && args.is_present(PRINT_LEGENDS_FLAG), &Term::stdout());
+
+    let result_out = get_result_output(args, output_format);
+    let is_single_repository = rescored_repositories.len() == 1;
+
+    match output_format {
+        OutputFormat::Json => {
+            if is_single_repository {
+                result_out.write_line(&format!("{}", rescored_repositories[0].repo_score))?
+            } else {
+                let multi_repo_score: MultiRepoScore = rescored_repositories
+                    .into_iter()
+                    .map(|rescored| (rescored.repository, rescored.repo_score))
+                    .collect();
+
+                result_out.write_line(&format!("{}", multi_repo_score))?
+            }
+        }
+        OutputFormat::Csv => {
+            let repository_column = |rescored: &RescoredRepository| {
+                if is_single_repository {
+                    String::new()
+                } else {
+                    format!("{},", rescored.repository)
+                }
+            };
+
+            result_out.write_line(&format!(
+                "{}{}",
+                if is_single_repository {
+                    ""
+                } else {
+                    "repository,"
+                },
+                Score::to_csv_header()
+            ))?;
+            for rescored in &rescored_repositories {
+                for (pr_number, pr_score) in &rescored.pull_requests {
+                    result_out.write_line(&format!(
+                        "{}{}",
+                        repository_column(rescored),
+                        pr_score.to_csv(&pr_number.to_string())
+                    ))?;
+                }
+                result_out.write_line(&format!(
+                    "{}{}",
+                    repository_column(rescored),
+                    rescored.repo_score.to_csv("aggregate")
+                ))?;
+            }
+        }
+        OutputFormat::Prometheus => result_out.write_str(&Score::to_prometheus(
+            &rescored_repositories
+                .iter()
+                .map(|rescored| {
+                    (
+                        vec![
+                            ("owner", rescored.owner.as_str()),
+                            ("repo", rescored.repository.as_str()),
+                        ],
+                        &rescored.repo_score,
+                    )
+                })
+                .collect::<Vec<(Vec<(&str, &str)>, &Score)>>(),
+        ))?,
+        OutputFormat::Jsonl => {
+            for rescored in &rescored_repositories {
+                for (pr_number, pr_score) in &rescored.pull_requests {
+                    result_out.write_line(
+                        &ScoreRecord {
+                            owner: &rescored.owner,
+                            repository: Some(&rescored.repository),
+                            pr_number: Some(*pr_number),
+                            score: pr_score,
+                        }
+                        .to_json_line(),
+                    )?;
+                }
+                result_out.write_line(
+                    &ScoreRecord {
+                        owner: &rescored.owner,
+                        repository: Some(&rescored.repository),
+                        pr_number: None,
+                        score: &rescored.repo_score,
+                    }
+                    .to_json_line(),
+                )?;
+            }
+        }
+        OutputFormat::Html => result_out.write_str(&Score::to_html(
+            &rescored_repositories
+                .iter()
+                .map(|rescored| {
+                    (format!("{}/{}", rescored.owner, rescored.repository), &rescored.repo_score)
+                })
+                .collect::<Vec<(String, &Score)>>(),
+            &Local::now(),
+        ))?,
+        OutputFormat::Sqlite => write_sqlite_report(
+            args,
+            &result_out,
+            &rescored_repositories
+                .iter()
+                .flat_map(|rescored| {
+                    rescored.pull_requests.iter().map(move |(pr_number, pr_score)| PullRequestRow {
+                        owner: &rescored.owner,
+                        repository: &rescored.repository,
+                        pr_number: *pr_number,
+                        score: pr_score,
+                    })
+                })
+                .collect::<Vec<PullRequestRow>>(),
+        )?,
+        OutputFormat::Pretty => {
+            let pretty_scores: Vec<String> = rescored_repositories
+                .iter()
+                .map(|rescored| {
+                    rescored
+                        .repo_score
+                        .to_pretty(&format!("{}/{}", rescored.owner, rescored.repository))
+                })
+                .collect();
+
+            result_out.write_line(&pretty_scores.join("\n\n"))?
+        }
+    }
+
+    result_out.finish()?;
+    open_report(args, output_format, silent_mode);
+
+    exit_on_threshold_breaches(&threshold_breaches);
+
+    Ok(())
+}
+
+/// Raises the `interruption` as soon as the user hits Ctrl+C (ie: SIGINT), so that the analysis gets wound
+/// down and whatever was fetched by then still gets output. Hitting Ctrl+C again exits right away, without
+/// any output.
+async fn handle_interrupts(interruption: Interruption) {
+    if signal::ctrl_c().await.is_err() {
+        warn!("Could not listen for Ctrl+C; interrupting the analysis will not output partial results.");
+        return;
+    }
+
+    warn!(
+        "Interrupted! Waiting for the PRs being fetched to finish, so that partial results can be output; hit Ctrl+C again to exit right away."
+    );
+    interruption.raise();
+
+    if signal::ctrl_c().await.is_ok() {
+        process::exit(130)
+    }
+}
+
+/// Checks whether GitHub's remaining rate-limit budget (shared by every [`Analyzer`], since they all use
+/// the same token) covers the `planned_requests`; warning about it - and about when the budget gets reset -
+/// if it does not. Returns `false` only if the budget is known to fall short; a budget that could not be
+/// checked at all is given the benefit of the doubt.
+async fn check_rate_limit_budget(analyzer: &Analyzer, planned_requests: u64) -> bool {
+    let budget = match get_rate_limit_budget(&*analyzer.get_github_client().await).await {
+        Ok(budget) => budget,
+        Err(e) => {
+            warn!("Could not check the remaining rate-limit budget; carrying on regardless.");
+            debug!("{}", e);
+            return true;
+        }
+    };
+
+    if budget.remaining < planned_requests {
+        warn!(
+            "The analysis is estimated to take [{}] requests, but only [{}] (out of [{}]) remain in the current rate-limit window. It resets at [{}].",
+            planned_requests,
+            budget.remaining,
+            budget.limit,
+            budget.reset_at().with_timezone(&Local)
+        );
+        return false;
+    }
+
+    debug!(
+        "The analysis is estimated to take [{}] requests; [{}] (out of [{}]) remain in the current rate-limit window.",
+        planned_requests, budget.remaining, budget.limit
+    );
+    true
+}
+
+/// Whether colors (and emojis) must be left out of the output: either because `--no-color` was passed, or
+/// because the `NO_COLOR` environment variable is set to a non-empty value (see:
+/// [https://no-color.org](https://no-color.org)).
+fn colors_are_disabled(args: &ArgMatches) -> bool {
+    args.is_present(NO_COLOR_FLAG)
+        || matches!(std::env::var_os("NO_COLOR"), Some(no_color) if !no_color.is_empty())
+}
+
+/// Builds an [`Emoji`] that always falls back to its ASCII alternate for `plain_output`. (Otherwise, it
+/// only does so for terminals that cannot render it.)
+fn emoji<'a>(emoji: &'a str, fallback: &'a str, plain_output: bool) -> Emoji<'a, 'a> {
+    if plain_output {
+        Emoji(fallback, fallback)
+    } else {
+        Emoji(emoji, fallback)
+    }
+}
+
+/// Retrieves the application's ASCII-art logo.
+fn get_logo() -> &'static str {
+    r#"
+        ooooooooo.   ooooooooo.             oooo   o8o
+        `888   `Y88. `888   `Y88.           `888   `"'
+         888   .d88'  888   .d88'  .ooooo.   888  oooo   .ooooo.   .ooooo.
+         888ooo88P'   888ooo88P'  d88' `88b  888  `888  d88' `"Y8 d88' `88b
+         888          888`88b.    888   888  888   888  888       888ooo888
+         888          888  `88b.  888   888  888   888  888   .o8 888    .o
+        o888o        o888o  o888o `Y8bod8P' o888o o888o `Y8bod8P' `Y8bod8P'
+        ------------ What you gonna do when they come for you -------------
+    "#
+}
+
+/// Prints analyzed metrics' legends into target [`Term`], if `toggle` is `true`;
+fn print_metrics_legends(toggle: bool, term: &Term) {
+    if !toggle {
+        return;
+    }
+
+    term.write_line(&ScoreType::get_legends()).unwrap_or_else(|e| {
+        error!("An error has occurred while printing metrics' legends to term! Error = {}", e);
+    });
+    term.write_line(&"=".repeat(term.size().1 as usize)).unwrap_or_else(|e| {
+        error!("An error has occurred while printing line separator term! Error = {}", e);
+    });
+}
+
+/// Reads the [`GradeWeights`] out of the `--weights` file, if any; aborting the operation if it cannot be
+/// read, or holds invalid weights.
+fn get_grade_weights(args: &ArgMatches) -> GradeWeights {
+    args.value_of(WEIGHTS_PARAM)
+        .map(|path| {
+            GradeWeights::from_file(Path::new(path)).unwrap_or_else(|e| {
+                eprintln!("{}! Aborting operation.", e);
+                process::exit(1)
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// Gathers the [`GradingConfig`] out of `args` (their validators already made sure its bands are valid,
+/// and every one of them has a default).
+fn get_grading_config(args: &ArgMatches) -> GradingConfig {
+    GradingConfig {
+        flow_ratio_ideal: GradingConfig::parse_ideal(
+            args.value_of(FLOW_RATIO_IDEAL_PARAM).unwrap(),
+        )
+        .unwrap(),
+        test_ratio_target: GradingConfig::parse_ideal(
+            args.value_of(TEST_RATIO_TARGET_PARAM).unwrap(),
+        )
+        .unwrap(),
+        discussion_ideal_range: GradingConfig::parse_range(
+            args.value_of(DISCUSSION_IDEAL_RANGE_PARAM).unwrap(),
+        )
+        .unwrap(),
+    }
+}
+
+/// Parses every `--fail-if` threshold in `args` (their validator already made sure they are valid).
+fn get_thresholds(args: &ArgMatches) -> Vec<Threshold> {
+    args.values_of(FAIL_IF_PARAM)
+        .map(|expressions| expressions.map(|expression| expression.parse().unwrap()).collect())
+        .unwrap_or_default()
+}
+
+/// Describes every one of the `thresholds` breached by a `score`, which is named after its `label` (ie:
+/// `rust-lang/rust`).
+fn find_threshold_breaches(thresholds: &[Threshold], label: &str, score: &Score) -> Vec<String> {
+    thresholds
+        .iter()
+        .filter_map(|threshold| {
+            threshold.breached_by(score).map(|value| {
+                format!("[{}] breached threshold [{}] (was {})", label, threshold, value)
+            })
+        })
+        .collect()
+}
+
+/// Reports the `error` that aborted the analysis and exits with a non-zero status code. Under
+/// `--format json`, the error is written as a JSON object too (see [`AnalyzeError::to_json`]), so that
+/// whatever consumes the results can tell what went wrong. It goes into the `--output` file if selected,
+/// or into stderr otherwise.
+fn exit_on_error(error: &AnalyzeError, args: &ArgMatches, output_format: OutputFormat) -> ! {
+    error!("{}", error.message());
+
+    if output_format == OutputFormat::Json {
+        match args.value_of(OUTPUT_PARAM) {
+            Some(output) => {
+                let result_out = ResultOutput::new(Some(Path::new(output)));
+
+                if let Err(e) =
+                    result_out.write_line(&error.to_json()).and_then(|_| result_out.finish())
+                {
+                    warn!("Could not write the error into [{}]: {}", output, e);
+                    eprintln!("{}", error.to_json());
+                }
+            }
+            None => eprintln!("{}", error.to_json()),
+        }
+    }
+
+    process::exit(1)
+}
+
+/// Reports the `threshold_breaches` (if any) and exits with a non-zero status code, turning the analysis
+/// into a failing quality gate. Results must have been output beforehand.
+fn exit_on_threshold_breaches(threshold_breaches: &[String]) {
+    if threshold_breaches.is_empty() {
+        return;
+    }
+
+    for threshold_breach in threshold_breaches {
+        eprintln!("{}", threshold_breach);
+    }
+
+    process::exit(2)
+}
+
+/// Checks that the `since`/`until` window is not reversed; open-ended windows always hold.
+fn check_date_window(
+    since: Option<DateTime<Utc>>, until: Option<DateTime<Utc>>,
+) -> Result<(), String> {
+    match (since, until) {
+        (Some(since), Some(until)) if until < since => {
+            Err(format!("{} ({}) cannot precede {} ({})", UNTIL_PARAM, until, SINCE_PARAM, since))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Parses an ISO-8601 date (ie: `2021-03-31`) or date-time (ie: `2021-03-31T18:00:00Z`) into a
+/// [`DateTime<Utc>`]. Bare dates are expanded to the start of the day, or to its very last second if
+/// `end_of_day` is `true` (so that they may be used as inclusive upper bounds).
+fn parse_date(value: &str, end_of_day: bool) -> Result<DateTime<Utc>, String> {
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(date_time.with_timezone(&Utc));
+    }
+
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        format!(
+            "Supplied value must be an ISO-8601 date (YYYY-MM-DD) or date-time (YYYY-MM-DDTHH:MM:SSZ), but was {}",
+            value
+        )
+    })?;
+
+    let date_time = if end_of_day {
+        date.and_hms(23, 59, 59)
+    } else {
+        date.and_hms(0, 0, 0)
+    };
+
+    Ok(DateTime::from_utc(date_time, Utc))
+}
+
+/// Determines where results end up (see [`ResultOutput`]). An SQLite database is not written as text, so
+/// its results always go through [`write_sqlite_report`] instead; only the database's path gets printed.
+/// Parses the value of the `name` enum argument, regardless of its case: its possible values are
+/// validated ignoring case (see `case_insensitive`), but enums only parse their own kebab-case names.
+fn value_of_enum<T: FromStr>(args: &ArgMatches, name: &str) -> T {
+    args.value_of(name).and_then(|value| value.to_ascii_lowercase().parse().ok()).unwrap()
+    // already validated (and defaulted) by the CLI
+}
+
+fn get_result_output(args: &ArgMatches, output_format: OutputFormat) -> ResultOutput {
+    ResultOutput::new(
+        args.value_of(OUTPUT_PARAM)
+            .filter(|_| output_format != OutputFormat::Sqlite)
+            .map(Path::new),
+    )
+}
+
+/// Opens the HTML report written into the `--output` file in the platform's browser, if asked to (see
+/// [`report_to_open`]). Failing to do so is only warned about, as the report itself has been written
+/// already.
+fn open_report(args: &ArgMatches, output_format: OutputFormat, silent_mode: bool) {
+    if let Some(report) = report_to_open(args, output_format, silent_mode) {
+        if let Err(e) = opener::open(report) {
+            warn!("Could not open [{}]: {}", report.display(), e);
+        }
+    }
+}
+
+/// Determines the report to be opened in the platform's browser, if any: the HTML one written into the
+/// `--output` file, if asked to; unless in `silent_mode`, since nobody would be there to look at it.
+fn report_to_open(
+    args: &ArgMatches, output_format: OutputFormat, silent_mode: bool,
+) -> Option<&Path> {
+    if !args.is_present(OPEN_REPORT_FLAG) || silent_mode {
+        return None;
+    }
+
+    if output_format != OutputFormat::Html {
+        warn!("Only HTML reports get opened; the [{}] one was left as is.", output_format);
+        return None;
+    }
+
+    args.value_of(OUTPUT_PARAM).map(Path::new) // required by the flag
+}
+
+/// Upserts every one of the `pull_request_rows` into the SQLite database at the `--output` file (see
+/// [`OutputFormat::Sqlite`]), and prints its path.
+fn write_sqlite_report(
+    args: &ArgMatches, result_out: &ResultOutput, pull_request_rows: &[PullRequestRow],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let database = Path::new(args.value_of(OUTPUT_PARAM).unwrap()); // required by the SQLite format
+
+    SqliteReport::open(database)?.upsert(pull_request_rows, &Utc::now())?;
+    result_out.write_line(&database.display().to_string())?;
+
+    Ok(())
+}
+
+/// Upserts every PR analyzed across the `repository_analyses` into the SQLite database (see
+/// [`write_sqlite_report`]).
+fn write_repository_analyses_into_sqlite(
+    args: &ArgMatches, result_out: &ResultOutput, owner: &str,
+    repository_analyses: &[RepositoryAnalysis],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let pr_scores: Vec<(&str, u64, Score)> = repository_analyses
+        .iter()
+        .flat_map(|repository_analysis| {
+            repository_analysis.pull_requests_data.iter().map(move |pull_request_data| {
+                (
+                    repository_analysis.repository.as_str(),
+                    pull_request_data.pr_number(),
+                    pull_request_data.get_score(),
+                )
+            })
+        })
+        .collect();
+
+    write_sqlite_report(
+        args,
+        result_out,
+        &pr_scores
+            .iter()
+            .map(|(repository, pr_number, pr_score)| PullRequestRow {
+                owner,
+                repository,
+                pr_number: *pr_number,
+                score: pr_score,
+            })
+            .collect::<Vec<PullRequestRow>>(),
+    )
+}
+
+/// Determines the [`OutputFormat`] of the results: the selected one, if any. Otherwise, results are
+/// pretty-printed for attended consoles, and printed as JSON for anything else (ie: when piped, or
+/// written into an output file). Pretty-printed results written into a file are never colored.
+fn get_output_format(args: &ArgMatches, console_is_user_attended: bool) -> OutputFormat {
+    let output_format = if args.is_present(OUTPUT_FORMAT_PARAM) {
+        value_of_enum(args, OUTPUT_FORMAT_PARAM)
+    } else if console_is_user_attended && !args.is_present(OUTPUT_PARAM) {
+        OutputFormat::Pretty
+    } else {
+        OutputFormat::Json
+    };
+
+    if output_format == OutputFormat::Pretty && args.is_present(OUTPUT_PARAM) {
+        console::set_colors_enabled(false);
+    }
+
+    if output_format == OutputFormat::Sqlite && !args.is_present(OUTPUT_PARAM) {
+        eprintln!(
+            "The {} format can only be written into an --{} file! Aborting operation.",
+            output_format, OUTPUT_PARAM
+        );
+        process::exit(1)
+    }
+
+    output_format
+}
+
+/// Initializes the `Log` crate's logging facade, emitting records in the given [`LogFormat`]. Human-readable
+/// records get their level `colored` after its severity.
+fn init_logging(log_level: LevelFilter, log_format: LogFormat, colored: bool) {
+    match log_format {
+        LogFormat::Human if !colored => simplelog::SimpleLogger::init(
+            log_level,
+            ConfigBuilder::new()
+                .add_filter_allow_str(metadata::package_name())
+                .set_time_to_local(true)
+                .build(),
+        ),
+        LogFormat::Human => simplelog::TermLogger::init(
+            log_level,
+            ConfigBuilder::new()
+                .add_filter_allow_str(metadata::package_name())
+                .set_time_to_local(true)
+                .build(),
+            TerminalMode::Mixed,
+        ),
+        LogFormat::Json => JsonLogger::init(log_level, metadata::package_name()),
+    }
+    .unwrap() // we want to panic if the logger couldn't be initialized, so the unwrap() is adequate
+}
+
+/// Sets up the CLI for the whole application.
+fn setup_cli() -> ArgMatches {
+    with_cli(get_matches_with_config)
+}
+
+/// Builds the CLI for the whole application, and hands it over to `parse` (ie: to get its matches).
+fn with_cli<T>(parse: impl FnOnce(App) -> T) -> T {
+    return parse(App::new(metadata::package_name())
+        .version(metadata::full_version())
+        .author(metadata::authors())
+        .about(metadata::description())
+        // params start here ---
+        .arg(
+            Arg::new(OWNER_PARAM)
+                .long(OWNER_PARAM)
+                .short('O')
+                .about("The owner of the repository under scrutiny")
+                .required_unless_present_any([EXPLAIN_PARAM, FROM_JSON_PARAM, PR_URL_PARAM])
+                .takes_value(true)
+                .case_insensitive(false),
+        )
+        .arg(
+            Arg::new(REPOSITORY_PARAM)
+                .long(REPOSITORY_PARAM)
+                .short('R')
+                .about(
+                    "The repository under scrutiny. Several repositories (under the same owner) can be \
+                    analyzed at once by either repeating this param or supplying a comma-separated list; \
+                    results are then reported per repository"
+                )
+                .required_unless_present_any([
+                    ALL_REPOS_FLAG,
+                    EXPLAIN_PARAM,
+                    FROM_JSON_PARAM,
+                    PR_URL_PARAM,
+                ])
+                .takes_value(true)
+                .multiple_occurrences(true)
+                .use_delimiter(true)
+                .case_insensitive(false),
+        )
+        .arg(
+            Arg::new(SAMPLE_SIZE_PARAM)
+                .long(SAMPLE_SIZE_PARAM)
+                .short('S')
+                .about(
+                    "The amount of PRs that will be fetched as sample for the analysis (unless a specific \
+                    PR number is selected as individual target). Samples bigger than 100 PRs are fetched \
+                    across multiple pages"
+                )
+                .required(true)
+                .takes_value(true)
+                .validator(|value| {
+                    let value = value.parse::<usize>();
+
+                    if value.is_err() {
+                        return Err(format!(
+                            "Supplied value must be an integer number between {} and {}",
+                            MIN_SAMPLE_SIZE, MAX_SAMPLE_SIZE
+                        ));
+                    }
+
+                    let value = value.unwrap();
+
+                    if !(MIN_SAMPLE_SIZE as usize..=MAX_SAMPLE_SIZE as usize).contains(&value) {
+                        return Err(format!(
+                            "Supplied value must be an integer number between {} and {}, but was {}",
+                            MIN_SAMPLE_SIZE, MAX_SAMPLE_SIZE, value
+                        ));
+                    }
+
+                    Ok(())
                 })
                 .default_value(&DEFAULT_SAMPLE_SIZE.to_string())
-                .conflicts_with(PR_NUMBER_PARAM) // user must either select sample size or a specific PR; not both
+                .conflicts_with_all(&[PR_NUMBER_PARAM, PR_URL_PARAM]) // user must either select sample size or a specific PR; not both
+        )
+        .arg(
+            Arg::new(AGGREGATE_PARAM)
+                .long(AGGREGATE_PARAM)
+                .about(
+                    "How individual PRs' metrics are collapsed into the repository's: their mean, their \
+                    median (immune to outliers) or their 90th percentile. Valid only for whole Repository \
+                    analysis"
+                )
+                .required(false)
+                .takes_value(true)
+                .possible_values(AggregationStrategy::VARIANTS)
+                .case_insensitive(true)
+                .default_value(&ScoringSettings::default().aggregation_strategy.to_string())
+                .conflicts_with_all(&[PR_NUMBER_PARAM, PR_URL_PARAM]),
+        )
+        .arg(
+            Arg::new(BASELINE_PARAM)
+                .long(BASELINE_PARAM)
+                .about(
+                    "A repository's score previously exported with '--format json', against which the current \
+                    one is compared: every metric's delta (and percent change) is reported alongside it, \
+                    while metrics present in just one of them are reported as added or removed. Valid only \
+                    for a single Repository's whole analysis"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| Baseline::from_file(Path::new(value)).map(|_| ()))
+                .conflicts_with_all(&[
+                    PR_NUMBER_PARAM,
+                    PR_URL_PARAM,
+                    ALL_REPOS_FLAG,
+                    DRY_RUN_FLAG,
+                    FROM_JSON_PARAM,
+                ]),
+        )
+        .arg(
+            Arg::new(FROM_JSON_PARAM)
+                .long(FROM_JSON_PARAM)
+                .about(
+                    "A JSON-lines report previously exported with '--format jsonl', whose PRs' scores are \
+                    re-graded and re-aggregated (according to --aggregate, --weight-by-size, --weights and \
+                    --with-variance) without hitting GitHub at all. Sample-wide metrics are carried over as \
+                    they were"
+                )
+                .required(false)
+                .takes_value(true)
+                .conflicts_with_all(&[PR_NUMBER_PARAM, PR_URL_PARAM, ALL_REPOS_FLAG, DRY_RUN_FLAG]),
+        )
+        .arg(
+            Arg::new(FLOW_RATIO_SOURCE_PARAM)
+                .long(FLOW_RATIO_SOURCE_PARAM)
+                .about(
+                    "The PRs over which the Pull Request Flow Ratio is calculated: the sampled ones alone \
+                    (sample), or every PR opened or closed throughout the sample's time span, as reported \
+                    by GitHub (repository). The latter is not biased by small samples, at the cost of \
+                    listing every such PR: up to 10 pages (of 100 PRs each) for openings and as many for \
+                    closures, beyond which the sample is used after all. Either way, merge-PRs are left \
+                    out unless included. Valid only for whole Repository analysis"
+                )
+                .required(false)
+                .takes_value(true)
+                .possible_values(FlowRatioSource::VARIANTS)
+                .case_insensitive(true)
+                .default_value(&ScoringSettings::default().flow_ratio_source.to_string())
+                .conflicts_with_all(&[PR_NUMBER_PARAM, PR_URL_PARAM]),
+        )
+        .arg(
+            Arg::new(WORK_HOURS_PARAM)
+                .long(WORK_HOURS_PARAM)
+                .about(
+                    "The team's working hours on weekdays (ie: 9-18, with 18:00 itself excluded). Commits, \
+                    comments and reviews outside of them (or on weekends) count towards the \
+                    AfterHoursActivityRatio metric"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| WorkingHours::parse_hours(value).map(|_| ()))
+                .default_value("9-18"),
+        )
+        .arg(
+            Arg::new(TIMEZONE_PARAM)
+                .long(TIMEZONE_PARAM)
+                .alias("tz")
+                .about(
+                    "The team's timezone, either as an IANA name (ie: Asia/Tokyo) or as an offset from UTC \
+                    (ie: +02:00, -0530), in which --work-hours are evaluated and days are told apart (ie: \
+                    when bucketing PRs by day for the PullRequestFlowRatio, or when counting business days)"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| Timezone::parse(value).map(|_| ()))
+                .default_value("UTC"),
+        )
+        .arg(
+            Arg::new(TEAM_MEMBERS_PARAM)
+                .long(TEAM_MEMBERS_PARAM)
+                .about(
+                    "A plain-text file listing the team's members, one login per line (lines starting \
+                    with '#' are ignored). Only they count as participants and reviewers, so that outside \
+                    contributors (and bots) do not inflate AmountOfParticipants, AmountOfReviewers nor \
+                    ReviewerDiversity"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| TeamMembers::from_file(Path::new(value)).map(|_| ())),
+        )
+        .arg(
+            Arg::new(HOLIDAYS_PARAM)
+                .long(HOLIDAYS_PARAM)
+                .about(
+                    "A plain-text file listing the team's holidays, one date (ie: 2021-12-25) per line \
+                    (lines starting with '#' are ignored). Just like weekends, they do not count towards \
+                    BusinessDaysLeadTime"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| Holidays::from_file(Path::new(value)).map(|_| ())),
+        )
+        .arg(
+            Arg::new(CONTENTION_WORDS_PARAM)
+                .long(CONTENTION_WORDS_PARAM)
+                .about(
+                    "A plain-text file listing words (or phrases) that mark a comment as heated, one per line \
+                    (lines starting with '#' are ignored). Enables the ContentiousDiscussion metric, which \
+                    counts comments carrying any of them - or runs of question marks ('???'), or all-caps \
+                    sentences. A rough, keyword-based signal rather than a judgment"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| ContentionWords::from_file(Path::new(value)).map(|_| ())),
+        )
+        .arg(
+            Arg::new(WEIGHTS_PARAM)
+                .long(WEIGHTS_PARAM)
+                .about(
+                    "A JSON file overriding how much each metric weighs on the overall grade, keyed by \
+                    its CSV column name (ie: '{ \"time_to_merge\": 3, \"attachments\": 1 }'). A weight of 0 \
+                    leaves a metric out of the grade altogether"
+                )
+                .required(false)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new(FLOW_RATIO_IDEAL_PARAM)
+                .long(FLOW_RATIO_IDEAL_PARAM)
+                .about(
+                    "The ideal PullRequestFlowRatio when grading (ie: 1.2 for a team whose backlog is \
+                    expected to grow); ratios from 20% below it up to 25% above it are deemed ideal too"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| GradingConfig::parse_ideal(value).map(|_| ()))
+                .default_value("1.0"),
+        )
+        .arg(
+            Arg::new(TEST_RATIO_TARGET_PARAM)
+                .long(TEST_RATIO_TARGET_PARAM)
+                .about(
+                    "The TestToCodeRatio from which on a PR is deemed thoroughly tested when grading \
+                    (ie: 1.0 to expect a line of tests for every line of code)"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| GradingConfig::parse_ideal(value).map(|_| ()))
+                .default_value("0.5"),
+        )
+        .arg(
+            Arg::new(DISCUSSION_IDEAL_RANGE_PARAM)
+                .long(DISCUSSION_IDEAL_RANGE_PARAM)
+                .about(
+                    "The band of PullRequestsDiscussionSize deemed ideal when grading, as <low>..<high> \
+                    (ie: 5..30). Bigger teams tend to discuss more"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| GradingConfig::parse_range(value).map(|_| ()))
+                .default_value("2..20"),
+        )
+        .arg(
+            Arg::new(CONNECTIONS_PARAM)
+                .long(CONNECTIONS_PARAM)
+                .short('C')
+                .about(
+                    "The maximum amount of concurrent connections against GitHub. Bigger values speed up \
+                    the analysis, but risk triggering GitHub's abuse detection mechanisms (rejected requests \
+                    may cause PRs to be discarded from the sample)"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| match value.parse::<u16>() {
+                    Ok(value) if value >= MIN_CONNECTION_POOL_SIZE => Ok(()),
+                    _ => Err(format!(
+                        "Supplied value must be an integer number between {} and {}, but was {}",
+                        MIN_CONNECTION_POOL_SIZE,
+                        u16::MAX,
+                        value
+                    )),
+                })
+                .default_value(&DEFAULT_CONNECTION_POOL_SIZE.to_string()),
+        )
+        .arg(
+            Arg::new(MAX_INFLIGHT_PARAM)
+                .long(MAX_INFLIGHT_PARAM)
+                .about(
+                    "The maximum amount of PRs whose data is retrieved at once, across all repositories. \
+                    The rest wait for their turn instead of racing for connections (and timing out). \
+                    Defaults to the amount of connections"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| match value.parse::<usize>() {
+                    Ok(value) if value >= 1 => Ok(()),
+                    _ => Err(format!(
+                        "Supplied value must be a positive integer number, but was {}",
+                        value
+                    )),
+                })
+                .conflicts_with_all(&[PR_NUMBER_PARAM, PR_URL_PARAM]),
         )
         .arg(
             Arg::new(PR_NUMBER_PARAM)
@@ -335,25 +2110,274 @@ fn setup_cli() -> ArgMatches {
                 .about("A specific pull-request to be selected as target for the analysis.")
                 .required(false)
                 .takes_value(true)
-                .validator(|value| {
-                    let value = value.parse::<u64>();
-
-                    if value.is_err() {
-                        return Err("Supplied value must be an integer number");
-                    }
-
-                    Ok(())
-                })
-                .conflicts_with(SAMPLE_SIZE_PARAM) // user must either select sample size or a specific PR; not both
+                .validator(|value| {
+                    let value = value.parse::<u64>();
+
+                    if value.is_err() {
+                        return Err("Supplied value must be an integer number");
+                    }
+
+                    Ok(())
+                })
+                .conflicts_with(SAMPLE_SIZE_PARAM) // user must either select sample size or a specific PR; not both
+        )
+        .arg(
+            Arg::new(PR_URL_PARAM)
+                .long(PR_URL_PARAM)
+                .about(
+                    "A specific pull-request to be selected as target for the analysis, by its URL (ie: \
+                    'https://github.com/owner/repository/pull/42'); in lieu of the owner, repository and \
+                    PR number. PRs hosted on a GitHub Enterprise instance are retrieved from its REST API \
+                    (at '/api/v3'), unless a base URL is explicitly supplied"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| PullRequestUrl::parse(value).map(|_| ()))
+                .conflicts_with_all(&[
+                    OWNER_PARAM,
+                    REPOSITORY_PARAM,
+                    PR_NUMBER_PARAM,
+                    SAMPLE_SIZE_PARAM,
+                    ALL_REPOS_FLAG,
+                ]),
+        )
+        .arg(
+            Arg::new(PR_TIMEOUT_SECS_PARAM)
+                .long(PR_TIMEOUT_SECS_PARAM)
+                .about(
+                    "How many seconds a single PR's data is waited for before giving up on it. PRs that \
+                    take any longer (ie: because of thousands of comments) are discarded from the sample, \
+                    instead of stalling the whole analysis"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| match value.parse::<u64>() {
+                    Ok(value) if value > 0 => Ok(()),
+                    _ => Err(format!("Supplied value must be a positive integer number, but was {}", value)),
+                })
+                .default_value(&DEFAULT_PR_TIMEOUT_SECS.to_string()),
+        )
+        .arg(
+            Arg::new(SINCE_PARAM)
+                .long(SINCE_PARAM)
+                .about(
+                    "Restricts the sample to PRs created at or after this ISO-8601 date (ie: 2021-01-01). \
+                    Valid only for whole Repository analysis"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| parse_date(value, false).map(|_| ()))
+                .conflicts_with_all(&[PR_NUMBER_PARAM, PR_URL_PARAM])
+        )
+        .arg(
+            Arg::new(UNTIL_PARAM)
+                .long(UNTIL_PARAM)
+                .about(
+                    "Restricts the sample to PRs created at or before this ISO-8601 date (ie: 2021-03-31). \
+                    Valid only for whole Repository analysis"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| parse_date(value, true).map(|_| ()))
+                .conflicts_with_all(&[PR_NUMBER_PARAM, PR_URL_PARAM])
+        )
+        .arg(
+            Arg::new(MIN_CHANGES_PARAM)
+                .long(MIN_CHANGES_PARAM)
+                .about(
+                    "Leaves PRs with fewer changed lines than this (ie: trivial one-line fixes) out of the \
+                    sample. PRs' sizes are only known once their diffs are fetched, so those left out still \
+                    consume API requests. Valid only for whole Repository analysis"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| {
+                    value.parse::<usize>().map(|_| ()).map_err(|_| {
+                        format!("Supplied value must be a non-negative integer number, but was {}", value)
+                    })
+                })
+                .conflicts_with_all(&[PR_NUMBER_PARAM, PR_URL_PARAM])
+        )
+        .arg(
+            Arg::new(MAX_CHANGES_PARAM)
+                .long(MAX_CHANGES_PARAM)
+                .about(
+                    "Leaves PRs with more changed lines than this (ie: mega-PRs) out of the sample. PRs' \
+                    sizes are only known once their diffs are fetched, so those left out still consume API \
+                    requests. Valid only for whole Repository analysis"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| {
+                    value.parse::<usize>().map(|_| ()).map_err(|_| {
+                        format!("Supplied value must be a non-negative integer number, but was {}", value)
+                    })
+                })
+                .conflicts_with_all(&[PR_NUMBER_PARAM, PR_URL_PARAM])
+        )
+        .arg(
+            Arg::new(TEST_PATTERN_PARAM)
+                .long(TEST_PATTERN_PARAM)
+                .short('T')
+                .about(
+                    "A regular expression that identifies test files by their path (ie: '(^|/)tests?/|_test\\.go$'). \
+                    By default, any file containing the word 'test' in its path is considered a test file"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| Regex::new(value).map(|_| ())),
+        )
+        .arg(
+            Arg::new(EXCLUDE_PATH_PARAM)
+                .long(EXCLUDE_PATH_PARAM)
+                .about(
+                    "A glob pattern (ie: '*.generated.ts') identifying files whose changes are left out of \
+                    line-count metrics (PR size, test-to-code ratio). Patterns without any '/' match file \
+                    names anywhere in the repository. Several patterns can be supplied by repeating this \
+                    param, and they replace the defaults: lock files (Cargo.lock, package-lock.json, \
+                    yarn.lock...), minified assets (*.min.js, *.min.css) and vendored directories \
+                    (vendor, node_modules)"
+                )
+                .required(false)
+                .takes_value(true)
+                .multiple_occurrences(true)
+                .validator(|value| Pattern::new(value).map(|_| ()).map_err(|e| e.to_string())),
+        )
+        .arg(
+            Arg::new(EXPLAIN_PARAM)
+                .long(EXPLAIN_PARAM)
+                .about(
+                    "Prints the legend of a single metric and exits, without running any analysis. Metrics \
+                    are named as in --print-legends (ie: 'PullRequestLeadTime') or after their CSV column \
+                    (ie: 'pull_request_lead_time'), case-insensitively"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| ScoreType::from_name(value).map(|_| ())),
+        )
+        .arg(
+            Arg::new(FAIL_IF_PARAM)
+                .long(FAIL_IF_PARAM)
+                .about(
+                    "A condition on a metric (named after its CSV column) that fails the analysis when met, \
+                    exiting with status code 2 once the results have been output - ie: \
+                    'pull_request_size>800' or 'test_to_code_ratio<0.2'. Supported operators are >, >=, <, \
+                    <=, == and !=. It is checked against the PR's score, the repository's aggregate (every \
+                    repository's, if several) or the organization-wide aggregate (for --all-repos); metrics \
+                    missing from them are not checked. Several conditions can be supplied by repeating this \
+                    param"
+                )
+                .required(false)
+                .takes_value(true)
+                .multiple_occurrences(true)
+                .validator(|value| value.parse::<Threshold>().map(|_| ())),
         )
         .arg(
             Arg::new(GITHUB_TOKEN_PARAM)
                 .long(GITHUB_TOKEN_PARAM)
                 .short('G')
                 .about("Sets the personal access token under which to perform the PR analysis")
-                .required(true)
+                .required_unless_present_any([EXPLAIN_PARAM, FROM_JSON_PARAM])
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new(OUTPUT_FORMAT_PARAM)
+                .long(OUTPUT_FORMAT_PARAM)
+                .short('F')
+                .about(
+                    "The format in which results are printed. 'csv' prints one row per analyzed PR, \
+                    followed by a final aggregate row. 'prometheus' prints the aggregated metrics as \
+                    gauges in Prometheus' text exposition format (ie: for node_exporter's textfile collector). \
+                    'jsonl' streams one compact JSON record per analyzed PR as soon as it gets retrieved, \
+                    followed by the aggregate(s). 'html' prints a standalone HTML document with one table \
+                    per (aggregated) score, ready to be published. 'sqlite' upserts one row per analyzed PR \
+                    (keyed by owner, repository, PR number and day) into the --output database, for \
+                    trends to be drawn across runs. 'pretty' prints every metric alongside a summary of its \
+                    legend, colored after how healthy it is. By default, results are pretty-printed when \
+                    the console is attended, and printed as JSON otherwise (ie: when piped, or written into \
+                    an --output file)"
+                )
+                .required(false)
+                .takes_value(true)
+                .possible_values(OutputFormat::VARIANTS)
+                .case_insensitive(true),
+        )
+        .arg(
+            Arg::new(OUTPUT_PARAM)
+                .long(OUTPUT_PARAM)
+                .short('o')
+                .about(
+                    "A file into which results are written (creating its parent directories, if needed), \
+                    instead of printing them. Only the file's path gets printed then"
+                )
+                .required(false)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new(OPEN_REPORT_FLAG)
+                .long(OPEN_REPORT_FLAG)
+                .about(
+                    "Opens the HTML report (see --format) in the platform's browser once it has been \
+                    written into the --output file. Nothing gets opened in silent-mode"
+                )
+                .takes_value(false)
+                .requires(OUTPUT_PARAM),
+        )
+        .arg(
+            Arg::new(CACHE_DIR_PARAM)
+                .long(CACHE_DIR_PARAM)
+                .about(
+                    "A directory where every PR's fetched data gets cached, keyed by owner/repository/PR \
+                    number. Cached PRs are not fetched from GitHub again, which speeds up repeated analyses \
+                    of the same PRs (and spares the API's rate limit). Only closed (or merged) PRs get \
+                    cached, as open ones are still bound to change. By default, nothing gets cached"
+                )
+                .required(false)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new(CONFIG_PARAM)
+                .long(CONFIG_PARAM)
+                .about(
+                    "A TOML configuration file whose entries (keyed after any other param or flag, in \
+                    snake_case) stand in for those not explicitly passed through the CLI (ie: \
+                    'sample_size = 50'). By default, a '.prolice.toml' in the current directory is used, \
+                    if there is one"
+                )
+                .required(false)
                 .takes_value(true),
         )
+        .arg(
+            Arg::new(GITHUB_BASE_URL_PARAM)
+                .long(GITHUB_BASE_URL_PARAM)
+                .about(
+                    "The base URL of a GitHub Enterprise instance's REST API (ie: \
+                    'https://github.mycorp.com/api/v3'). By default, github.com's REST API is targeted"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| match reqwest::Url::parse(value) {
+                    Ok(url) if url.scheme() == "http" || url.scheme() == "https" => Ok(()),
+                    _ => Err(format!(
+                        "Supplied value must be an absolute HTTP(S) URL, but was {}",
+                        value
+                    )),
+                }),
+        )
+        .arg(
+            Arg::new(LOG_FORMAT_PARAM)
+                .long(LOG_FORMAT_PARAM)
+                .about(
+                    "The format in which logs are emitted. 'json' prints one JSON object per log record \
+                    (with its timestamp, level, target and message) into stderr, ready to be ingested by \
+                    log aggregators. Silent-mode still turns off all logging, regardless of the format"
+                )
+                .required(false)
+                .takes_value(true)
+                .possible_values(LogFormat::VARIANTS)
+                .case_insensitive(true)
+                .default_value(&LogFormat::Human.to_string()),
+        )
         .arg(
             Arg::new(LOG_LEVEL_PARAM)
                 .long(LOG_LEVEL_PARAM)
@@ -370,10 +2394,231 @@ fn setup_cli() -> ArgMatches {
                     LevelFilter::Off.as_str(),
                 ])
                 .case_insensitive(true)
-                .default_value(prolice_metadata::default_log_level().as_str())
+                .default_value(metadata::default_log_level().as_str())
                 .conflicts_with(SILENT_MODE_FLAG),
         )
+        .arg(
+            Arg::new(MAX_RETRIES_PARAM)
+                .long(MAX_RETRIES_PARAM)
+                .about(
+                    "How many times a request rejected by GitHub's rate-limiting (or abuse detection) \
+                    mechanisms is retried, with exponential backoff, before giving up on it. Diffs that \
+                    GitHub fails to serve on its own side (ie: with a 502) are retried just as many times. \
+                    Zero disables retrying altogether"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| {
+                    value.parse::<u32>().map(|_| ()).map_err(|_| {
+                        format!("Supplied value must be a non-negative integer number, but was {}", value)
+                    })
+                })
+                .default_value(&DEFAULT_MAX_RETRIES.to_string()),
+        )
+        .arg(
+            Arg::new(ABUSE_POLICY_PARAM)
+                .long(ABUSE_POLICY_PARAM)
+                .about(
+                    "What becomes of a PR whose requests are still rejected by GitHub's rate-limiting (or \
+                    abuse detection) mechanisms once retries are exhausted: re-queued, and retrieved once \
+                    more after the rest of the sample is done (retry); discarded, shrinking the sample \
+                    (skip); or the whole analysis is aborted (fail). Valid only for whole Repository \
+                    analysis"
+                )
+                .required(false)
+                .takes_value(true)
+                .possible_values(AbusePolicy::VARIANTS)
+                .case_insensitive(true)
+                .default_value(&RetryPolicy::default().abuse_policy.to_string()),
+        )
+        .arg(
+            Arg::new(DUMP_FAILURES_PARAM)
+                .long(DUMP_FAILURES_PARAM)
+                .about(
+                    "A directory where every raw response from GitHub that could not be parsed gets written \
+                    (one file per request), for inspection or bug reports. By default, such responses are \
+                    only logged at trace level"
+                )
+                .required(false)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new(EMPTY_RESPONSE_RETRIES_PARAM)
+                .long(EMPTY_RESPONSE_RETRIES_PARAM)
+                .about(
+                    "How many times a PR's reviews, commit comments or commits are re-requested when GitHub \
+                    answers with an empty body (which it occasionally does, transiently, while under load) \
+                    before accepting them as empty. Zero disables retrying altogether"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| {
+                    value.parse::<u32>().map(|_| ()).map_err(|_| {
+                        format!("Supplied value must be a non-negative integer number, but was {}", value)
+                    })
+                })
+                .default_value(&DEFAULT_EMPTY_RESPONSE_RETRIES.to_string()),
+        )
+        .arg(
+            Arg::new(MERGE_PR_HEURISTIC_PARAM)
+                .long(MERGE_PR_HEURISTIC_PARAM)
+                .about(
+                    "How merge-PRs are told apart from regular ones: by their title starting with 'Merge' \
+                    (title-prefix), by their head commit being a merge commit or carrying no changes at \
+                    all (multiple-parents), or by requiring both to hold (both)"
+                )
+                .required(false)
+                .takes_value(true)
+                .possible_values(MergePrHeuristic::VARIANTS)
+                .case_insensitive(true)
+                .default_value(&ScoringSettings::default().merge_pr_heuristic.to_string()),
+        )
+        .arg(
+            Arg::new(MERGE_STYLE_PARAM)
+                .long(MERGE_STYLE_PARAM)
+                .about(
+                    "How the team merges its PRs, which tells whether their first commit dates back to when \
+                    work on them started: 'auto' takes PRs carrying a single commit authored after they were \
+                    opened as squashed, while 'squash' (or 'merge') takes every PR as squashed (or not). \
+                    Squashed PRs' TimeToMerge is their lead time, flagged as such in the output"
+                )
+                .required(false)
+                .takes_value(true)
+                .possible_values(MergeStyle::VARIANTS)
+                .case_insensitive(true)
+                .default_value(&ScoringSettings::default().merge_style.to_string()),
+        )
+        .arg(
+            Arg::new(AUTHOR_PARAM)
+                .long(AUTHOR_PARAM)
+                .about(
+                    "Restricts the sample to PRs opened by this user (by login). Several authors can be \
+                    selected by either repeating this param or supplying a comma-separated list. Valid \
+                    only for whole Repository analysis"
+                )
+                .required(false)
+                .takes_value(true)
+                .multiple_occurrences(true)
+                .use_delimiter(true)
+                .conflicts_with_all(&[PR_NUMBER_PARAM, PR_URL_PARAM]),
+        )
+        .arg(
+            Arg::new(LABEL_PARAM)
+                .long(LABEL_PARAM)
+                .about(
+                    "Restricts the sample to PRs carrying this label (ie: 'hotfix'). Several labels can be \
+                    selected by either repeating this param or supplying a comma-separated list; see \
+                    label-mode. Unlabeled PRs are left out. Valid only for whole Repository analysis"
+                )
+                .required(false)
+                .takes_value(true)
+                .multiple_occurrences(true)
+                .use_delimiter(true)
+                .conflicts_with_all(&[PR_NUMBER_PARAM, PR_URL_PARAM]),
+        )
+        .arg(
+            Arg::new(LABEL_MODE_PARAM)
+                .long(LABEL_MODE_PARAM)
+                .about(
+                    "Whether sampled PRs must carry any of the selected labels (any), or every single one \
+                    of them (all)"
+                )
+                .required(false)
+                .takes_value(true)
+                .possible_values(LabelMode::VARIANTS)
+                .case_insensitive(true)
+                .default_value(&RetrievalSettings::default().label_mode.to_string()),
+        )
+        .arg(
+            Arg::new(STATE_PARAM)
+                .long(STATE_PARAM)
+                .about(
+                    "The state of the PRs that will be fetched as sample for the analysis. Metrics that \
+                    depend on a PR being merged or closed (time-to-merge, lead time) are left out for open \
+                    PRs. Valid only for whole Repository analysis"
+                )
+                .required(false)
+                .takes_value(true)
+                .possible_values(PullRequestState::VARIANTS)
+                .case_insensitive(true)
+                .default_value(&RetrievalSettings::default().state.to_string()),
+        )
+        .arg(
+            Arg::new(SORT_PARAM)
+                .long(SORT_PARAM)
+                .about(
+                    "The order in which PRs are listed, and thus sampled: by creation date (created), by \
+                    last update date (updated) or by amount of comments (popularity). Only creation \
+                    order allows the since/until window to cut the listing short. Valid only for whole \
+                    Repository analysis"
+                )
+                .required(false)
+                .takes_value(true)
+                .possible_values(PullRequestSort::VARIANTS)
+                .case_insensitive(true)
+                .default_value(&RetrievalSettings::default().sort.to_string())
+                .conflicts_with_all(&[PR_NUMBER_PARAM, PR_URL_PARAM]),
+        )
+        .arg(
+            Arg::new(DIRECTION_PARAM)
+                .long(DIRECTION_PARAM)
+                .about(
+                    "Whether PRs are listed in ascending (asc; ie: oldest first) or descending (desc; ie: \
+                    newest first) sort order. Valid only for whole Repository analysis"
+                )
+                .required(false)
+                .takes_value(true)
+                .possible_values(SortDirection::VARIANTS)
+                .case_insensitive(true)
+                .default_value(&RetrievalSettings::default().direction.to_string())
+                .conflicts_with_all(&[PR_NUMBER_PARAM, PR_URL_PARAM]),
+        )
+        .arg(
+            Arg::new(SAMPLE_STRATEGY_PARAM)
+                .long(SAMPLE_STRATEGY_PARAM)
+                .about(
+                    "How the sample is picked among the listed PRs: the first ones listed (recent), a random \
+                    selection (random) or an evenly spaced selection across the listing's time range \
+                    (spread). Non-recent strategies list 5 times as many PRs as the sample size to pick from, \
+                    which costs about 1 extra API request per 25 sampled PRs. Valid only for whole Repository \
+                    analysis"
+                )
+                .required(false)
+                .takes_value(true)
+                .possible_values(SampleStrategy::VARIANTS)
+                .case_insensitive(true)
+                .default_value(&RetrievalSettings::default().sample_strategy.to_string())
+                .conflicts_with_all(&[PR_NUMBER_PARAM, PR_URL_PARAM]),
+        )
+        .arg(
+            Arg::new(SEED_PARAM)
+                .long(SEED_PARAM)
+                .about(
+                    "Seeds the random sample strategy, so that the very same sample can be picked again \
+                    (as long as the repository's PRs do not change in the meantime). When missing, a random \
+                    seed is used - and logged. Ignored by every other sample strategy"
+                )
+                .required(false)
+                .takes_value(true)
+                .validator(|value| {
+                    value.parse::<u64>().map(|_| ()).map_err(|_| {
+                        format!("Supplied value must be a non-negative integer number, but was {}", value)
+                    })
+                })
+                .conflicts_with_all(&[PR_NUMBER_PARAM, PR_URL_PARAM]),
+        )
         // optional flags start here ---
+        .arg(
+            Arg::new(ALL_REPOS_FLAG)
+                .long(ALL_REPOS_FLAG)
+                .about(
+                    "Analyzes every repository under the owner, which must be an organization (archived and \
+                    empty repositories are skipped). The sample size applies per repository; results are \
+                    reported per repository, plus an organization-wide aggregate"
+                )
+                .takes_value(false)
+                .conflicts_with_all(&[REPOSITORY_PARAM, PR_NUMBER_PARAM, PR_URL_PARAM]),
+        )
         .arg(
             Arg::new(INCLUDE_MERGE_PRS_FLAG)
                 .long(INCLUDE_MERGE_PRS_FLAG)
@@ -385,6 +2630,50 @@ fn setup_cli() -> ArgMatches {
                 )
                 .takes_value(false),
         )
+        .arg(
+            Arg::new(INCLUDE_CLOSED_UNMERGED_FLAG)
+                .long(INCLUDE_CLOSED_UNMERGED_FLAG)
+                .about(
+                    "Marks PRs that were closed without being merged (ie: abandoned ones) as valid targets \
+                    for analysis (by default these are excluded). They are scored for every metric that \
+                    does not require a merge, and the repository's RejectionRate is calculated. Valid only \
+                    for whole Repository analysis; for individual PR analysis this flag is ignored"
+                )
+                .takes_value(false),
+        )
+        .arg(
+            Arg::new(COUNT_SELF_REVIEWS_FLAG)
+                .long(COUNT_SELF_REVIEWS_FLAG)
+                .about(
+                    "Counts the reviews PR authors leave on their own PRs as reviews in reviewer-centric \
+                    metrics (AmountOfReviewers, ReviewerDiversity and TimeToFirstReview). By default they \
+                    only count towards commentary volume (PullRequestsDiscussionSize and \
+                    AuthorCommentaryToChangesRatio)"
+                )
+                .takes_value(false),
+        )
+        .arg(
+            Arg::new(DRY_RUN_FLAG)
+                .long(DRY_RUN_FLAG)
+                .about(
+                    "Lists the PRs that would make up the sample (one per line: repository#number, \
+                    author and title) without analyzing any of them, so that filters can be validated \
+                    cheaply. Merge-PRs are told apart by their title alone. Valid only for whole \
+                    Repository analysis"
+                )
+                .takes_value(false)
+                .conflicts_with_all(&[PR_NUMBER_PARAM, PR_URL_PARAM]),
+        )
+        .arg(
+            Arg::new(STRICT_BUDGET_FLAG)
+                .long(STRICT_BUDGET_FLAG)
+                .about(
+                    "Aborts the operation before analyzing anything if GitHub's remaining rate-limit \
+                    budget cannot cover the estimated amount of requests (by default, it is only warned \
+                    about)"
+                )
+                .takes_value(false),
+        )
         .arg(
             Arg::new(SILENT_MODE_FLAG)
                 .long(SILENT_MODE_FLAG)
@@ -399,6 +2688,16 @@ fn setup_cli() -> ArgMatches {
                 .conflicts_with(LOG_LEVEL_PARAM)
                 .conflicts_with(PRINT_LEGENDS_FLAG),
         )
+        .arg(
+            Arg::new(NO_COLOR_FLAG)
+                .long(NO_COLOR_FLAG)
+                .about(
+                    "Strips colors off the results and logs, and replaces emojis with their ASCII \
+                    alternates; ie: for CI logs, or dumb terminals. Setting the NO_COLOR environment \
+                    variable has the same effect"
+                )
+                .takes_value(false),
+        )
         .arg(
             Arg::new(PRINT_LEGENDS_FLAG)
                 .long(PRINT_LEGENDS_FLAG)
@@ -409,5 +2708,303 @@ fn setup_cli() -> ArgMatches {
                 .takes_value(false)
                 .conflicts_with(SILENT_MODE_FLAG),
         )
-        .get_matches();
+        .arg(
+            Arg::new(REFRESH_CACHE_FLAG)
+                .long(REFRESH_CACHE_FLAG)
+                .about(
+                    "Ignores the data already cached in the cache directory, fetching every PR from GitHub \
+                    again (and overwriting their cached data)"
+                )
+                .takes_value(false)
+                .requires(CACHE_DIR_PARAM),
+        )
+        .arg(
+            Arg::new(WEIGHT_BY_SIZE_FLAG)
+                .long(WEIGHT_BY_SIZE_FLAG)
+                .about(
+                    "Weights each PR's contribution to the repository's ratio metrics \
+                    (AuthorCommentaryToChangesRatio and TestToCodeRatio) by its PullRequestSize, so that \
+                    a 5-line PR does not count as much as a 2000-line one. Valid only for whole \
+                    Repository analysis"
+                )
+                .takes_value(false)
+                .conflicts_with_all(&[PR_NUMBER_PARAM, PR_URL_PARAM]),
+        )
+        .arg(
+            Arg::new(WITH_THREADS_FLAG)
+                .long(WITH_THREADS_FLAG)
+                .about(
+                    "Retrieves each PR's review threads too, in order to measure how many of them were left \
+                    unresolved (UnresolvedThreads). It costs an extra request per PR against GitHub's GraphQL \
+                    API, so it is disabled by default"
+                )
+                .takes_value(false),
+        )
+        .arg(
+            Arg::new(WITH_TIMELINE_FLAG)
+                .long(WITH_TIMELINE_FLAG)
+                .about(
+                    "Retrieves each PR's timeline too, in order to measure how long it spent as a draft before \
+                    being marked as ready for review (TimeInDraft). It costs an extra request per PR, so it is \
+                    disabled by default"
+                )
+                .takes_value(false),
+        )
+        .arg(
+            Arg::new(WITH_CHECKS_FLAG)
+                .long(WITH_CHECKS_FLAG)
+                .about(
+                    "Retrieves the statuses and check runs reported on each PR's head commit too, in order to \
+                    measure how many PRs were merged with their checks passing (GreenMergeRatio). It costs two \
+                    extra requests per PR, so it is disabled by default"
+                )
+                .takes_value(false),
+        )
+        .arg(
+            Arg::new(WITH_VARIANCE_FLAG)
+                .long(WITH_VARIANCE_FLAG)
+                .about(
+                    "Reports each metric's standard deviation across the sample alongside the repository's \
+                    score (as a sibling 'stddev' field of the JSON output), which tells consistent teams \
+                    apart from erratic ones. Valid only for whole Repository analysis"
+                )
+                .takes_value(false)
+                .conflicts_with_all(&[PR_NUMBER_PARAM, PR_URL_PARAM]),
+        ));
+}
+
+/// Parses the CLI args against the `app`, standing in for any of them that was not explicitly passed with
+/// the configuration file's entries (see [`Config`]).
+fn get_matches_with_config(app: App) -> ArgMatches {
+    let cli_args: Vec<String> = std::env::args().collect();
+
+    let config = Config::load(&cli_args, CONFIG_PARAM).unwrap_or_else(|e| {
+        eprintln!("{}! Aborting operation.", e);
+        process::exit(1)
+    });
+
+    let cli_args = match config {
+        Some(config) => {
+            if config.contains(GITHUB_TOKEN_PARAM) {
+                // logging is not initialized yet, and this deserves to be seen even in silent-mode
+                eprintln!(
+                    "WARNING: the configuration file holds a {}; consider passing it through the CLI \
+                    instead, so that it does not get shared (or committed) alongside the file.",
+                    GITHUB_TOKEN_PARAM
+                );
+            }
+
+            config.merge_into_args(cli_args, &ConfigurableArg::from_app(&app)).unwrap_or_else(|e| {
+                eprintln!("Invalid configuration file: {}! Aborting operation.", e);
+                process::exit(1)
+            })
+        }
+        None => cli_args,
+    };
+
+    app.get_matches_from(cli_args)
+}
+
+#[cfg(test)]
+mod main_tests {
+    use std::path::Path;
+
+    use chrono::{TimeZone, Utc};
+    use clap::ArgMatches;
+    use serde_json::json;
+
+    use crate::github::client::pool::{GitHubConnectionPool, GitHubConnectionPoolManager};
+    use crate::github::client::retry::{AbusePolicy, RetryPolicy};
+    use crate::github::utils::analyzer::AnalyzerBuilder;
+    use crate::github::utils::fixtures::{pull_request_json, repository_json, CannedGitHubApi};
+    use crate::github::utils::retrieval_settings::{
+        LabelMode, PullRequestSort, PullRequestState, SampleStrategy, SortDirection,
+    };
+    use crate::prolice_logging::LogFormat;
+    use crate::report::output_format::OutputFormat;
+    use crate::scoring::scoring_settings::{
+        AggregationStrategy, FlowRatioSource, MergePrHeuristic, MergeStyle,
+    };
+    use crate::{
+        analyze_repository, check_date_window, parse_date, report_to_open, value_of_enum, with_cli,
+        ABUSE_POLICY_PARAM, AGGREGATE_PARAM, DIRECTION_PARAM, FLOW_RATIO_SOURCE_PARAM,
+        LABEL_MODE_PARAM, LOG_FORMAT_PARAM, MERGE_PR_HEURISTIC_PARAM, MERGE_STYLE_PARAM,
+        OUTPUT_FORMAT_PARAM, SAMPLE_STRATEGY_PARAM, SORT_PARAM, STATE_PARAM,
+    };
+
+    fn parse_args(args: &[&str]) -> ArgMatches {
+        let required_args =
+            ["prolice", "--owner", "owner", "--repository", "repo", "--github-token", "t"];
+
+        with_cli(|app| app.get_matches_from(required_args.iter().chain(args)))
+    }
+
+    #[tokio::test]
+    async fn sample_stats_report_the_requested_sample_size_even_if_fewer_prs_are_listed() {
+        // GitHub lists two PRs, whose details cannot be retrieved
+        let base_url = CannedGitHubApi::default()
+            .json("/orgs/owner/repos", json!([repository_json("owner", "repository")]))
+            .json(
+                "/repos/owner/repository/pulls",
+                json!([
+                    pull_request_json("owner", "repository", 1, "author"),
+                    pull_request_json("owner", "repository", 2, "author"),
+                ]),
+            )
+            .serve()
+            .await;
+        // a PR's data is fetched through several connections at once
+        let connection_pool = GitHubConnectionPool::new(
+            GitHubConnectionPoolManager::new("token", Some(base_url)),
+            16,
+        );
+        let analyzer = AnalyzerBuilder::new("owner", "repository", "token", &connection_pool)
+            .retry_policy(RetryPolicy {
+                max_retries: 0,
+                ..Default::default()
+            })
+            .init()
+            .await
+            .unwrap();
+
+        let sample_stats =
+            analyze_repository(&analyzer, 5, false, None).await.unwrap().sample_stats;
+
+        assert_eq!(sample_stats.requested, 5);
+        assert_eq!(sample_stats.actual_sample_size, 2);
+        assert_eq!(sample_stats.errored, 2);
+    }
+
+    #[test]
+    fn only_html_reports_written_into_an_output_file_get_opened() {
+        let html_report = parse_args(&["--open-report", "--output", "report.html"]);
+
+        assert_eq!(
+            report_to_open(&html_report, OutputFormat::Html, false),
+            Some(Path::new("report.html"))
+        );
+        assert_eq!(report_to_open(&html_report, OutputFormat::Html, true), None);
+        assert_eq!(report_to_open(&html_report, OutputFormat::Csv, false), None);
+        assert_eq!(
+            report_to_open(&parse_args(&["--output", "report.html"]), OutputFormat::Html, false),
+            None
+        );
+
+        // there is nothing to open unless the report gets written into a file
+        let required_args =
+            ["prolice", "--owner", "owner", "--repository", "repo", "--github-token", "t"];
+        assert!(with_cli(|app| {
+            app.try_get_matches_from(required_args.iter().chain(&["--open-report"]))
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn enum_values_are_parsed_regardless_of_their_case() {
+        assert_eq!(
+            value_of_enum::<MergePrHeuristic>(
+                &parse_args(&["--merge-pr-heuristic", "TITLE-PREFIX"]),
+                MERGE_PR_HEURISTIC_PARAM
+            ),
+            MergePrHeuristic::TitlePrefix
+        );
+        assert_eq!(
+            value_of_enum::<AggregationStrategy>(
+                &parse_args(&["--aggregate", "MEDIAN"]),
+                AGGREGATE_PARAM
+            ),
+            AggregationStrategy::Median
+        );
+        assert_eq!(
+            value_of_enum::<MergeStyle>(
+                &parse_args(&["--merge-style", "SQUASH"]),
+                MERGE_STYLE_PARAM
+            ),
+            MergeStyle::Squash
+        );
+        assert_eq!(
+            value_of_enum::<AbusePolicy>(
+                &parse_args(&["--abuse-policy", "RETRY"]),
+                ABUSE_POLICY_PARAM
+            ),
+            AbusePolicy::Retry
+        );
+        assert_eq!(
+            value_of_enum::<PullRequestState>(&parse_args(&["--state", "CLOSED"]), STATE_PARAM),
+            PullRequestState::Closed
+        );
+        assert_eq!(
+            value_of_enum::<OutputFormat>(&parse_args(&["--format", "CSV"]), OUTPUT_FORMAT_PARAM),
+            OutputFormat::Csv
+        );
+        assert_eq!(
+            value_of_enum::<LabelMode>(&parse_args(&["--label-mode", "ALL"]), LABEL_MODE_PARAM),
+            LabelMode::All
+        );
+        assert_eq!(
+            value_of_enum::<PullRequestSort>(&parse_args(&["--sort", "UPDATED"]), SORT_PARAM),
+            PullRequestSort::Updated
+        );
+        assert_eq!(
+            value_of_enum::<SortDirection>(&parse_args(&["--direction", "ASC"]), DIRECTION_PARAM),
+            SortDirection::Asc
+        );
+        assert_eq!(
+            value_of_enum::<LogFormat>(&parse_args(&["--log-format", "JSON"]), LOG_FORMAT_PARAM),
+            LogFormat::Json
+        );
+        assert_eq!(
+            value_of_enum::<SampleStrategy>(
+                &parse_args(&["--sample-strategy", "SPREAD"]),
+                SAMPLE_STRATEGY_PARAM
+            ),
+            SampleStrategy::Spread
+        );
+        assert_eq!(
+            value_of_enum::<FlowRatioSource>(
+                &parse_args(&["--flow-ratio-source", "REPOSITORY"]),
+                FLOW_RATIO_SOURCE_PARAM
+            ),
+            FlowRatioSource::Repository
+        );
+    }
+
+    #[test]
+    fn bare_dates_are_expanded_to_either_end_of_their_day() {
+        assert_eq!(parse_date("2021-03-31", false), Ok(Utc.ymd(2021, 3, 31).and_hms(0, 0, 0)));
+        assert_eq!(parse_date("2021-03-31", true), Ok(Utc.ymd(2021, 3, 31).and_hms(23, 59, 59)));
+    }
+
+    #[test]
+    fn date_times_are_taken_as_is_regardless_of_the_end_of_day() {
+        assert_eq!(
+            parse_date("2021-03-31T18:00:00Z", true),
+            Ok(Utc.ymd(2021, 3, 31).and_hms(18, 0, 0))
+        );
+        // offsets are converted into UTC
+        assert_eq!(
+            parse_date("2021-03-31T23:30:00-03:00", false),
+            Ok(Utc.ymd(2021, 4, 1).and_hms(2, 30, 0))
+        );
+    }
+
+    #[test]
+    fn anything_but_iso_8601_dates_is_rejected() {
+        assert!(parse_date("31/03/2021", false).is_err());
+        assert!(parse_date("2021-02-30", false).is_err());
+        assert!(parse_date("yesterday", true).is_err());
+    }
+
+    #[test]
+    fn until_cannot_precede_since() {
+        let march_first = parse_date("2021-03-01", false).unwrap();
+        let end_of_march_first = parse_date("2021-03-01", true).unwrap();
+        let end_of_february = parse_date("2021-02-28", true).unwrap();
+
+        assert!(check_date_window(Some(march_first), Some(end_of_february)).is_err());
+        // a single-day window is a perfectly valid one
+        assert!(check_date_window(Some(march_first), Some(end_of_march_first)).is_ok());
+        assert!(check_date_window(Some(march_first), None).is_ok());
+        assert!(check_date_window(None, Some(end_of_february)).is_ok());
+    }
 }
@@ -0,0 +1,12 @@
+//! Utilities for presenting a [`Score`](crate::scoring::score::Score) (and the progress towards it) to
+//! the outside world.
+
+pub mod opener;
+
+pub mod output_format;
+
+pub mod progress_bar;
+
+pub mod result_output;
+
+pub mod sqlite;
@@ -0,0 +1,37 @@
+//! Launches reports in whatever application the platform associates them with (ie: a browser, for HTML).
+
+use std::path::Path;
+use std::process::Command;
+
+/// Opens the file at `path` through the platform's opener: `open` on macOS, `start` on Windows and
+/// `xdg-open` anywhere else. Fails if the opener could not be launched, or if it reported a failure.
+pub fn open(path: &Path) -> anyhow::Result<()> {
+    let status = opener_command(path).status()?;
+
+    if !status.success() {
+        anyhow::bail!("the platform's opener exited with [{}]", status);
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn opener_command(path: &Path) -> Command {
+    let mut command = Command::new("open");
+    command.arg(path);
+    command
+}
+
+#[cfg(target_os = "windows")]
+fn opener_command(path: &Path) -> Command {
+    let mut command = Command::new("cmd");
+    command.args(&["/C", "start", ""]).arg(path); // the empty title keeps quoted paths from becoming one
+    command
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn opener_command(path: &Path) -> Command {
+    let mut command = Command::new("xdg-open");
+    command.arg(path);
+    command
+}
@@ -0,0 +1,31 @@
+//! Formats in which analysis results can be printed.
+
+use strum_macros::{Display, EnumString, EnumVariantNames};
+
+/// Formats in which a [`Score`](crate::scoring::score::Score) can be printed to stdout.
+#[derive(Display, EnumString, EnumVariantNames, Debug, Copy, Clone, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum OutputFormat {
+    /// Pretty-printed JSON of the (aggregated) score. The default whenever results are piped (or written
+    /// into a file).
+    Json,
+    /// A header row, followed by one row per analyzed PR and a final aggregate row. Ready to be
+    /// dropped into a spreadsheet.
+    Csv,
+    /// Prometheus' text exposition format: one gauge per (aggregated) metric, labeled by owner and
+    /// repository. Ready to be picked up by `node_exporter`'s textfile collector.
+    Prometheus,
+    /// JSON-lines: one compact JSON record per analyzed PR, printed as soon as it gets retrieved,
+    /// followed by the aggregate(s). Ready to be piped into line-oriented tooling.
+    Jsonl,
+    /// A standalone HTML document: one table per (aggregated) score, holding every metric's value and a
+    /// summary of its legend. Ready to be published as is.
+    Html,
+    /// An SQLite database (the `--output` file, which it requires) into which one row per analyzed PR
+    /// gets upserted; aggregates are left out, since they can be derived from the rows themselves. Ready
+    /// for long-term trend analysis across runs.
+    Sqlite,
+    /// Colored, human-readable text: every metric's name, value and a summary of its legend. The
+    /// default whenever the console is attended (and results are not written into a file).
+    Pretty,
+}
@@ -0,0 +1,97 @@
+//! A minimalistic terminal progress bar, drawn on top of the [`console`] crate.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use console::Term;
+
+/// Width (in characters) of the bar itself, brackets excluded.
+const BAR_WIDTH: usize = 30;
+
+/// Progress of a set of `total` tasks, redrawn on `stderr` every time one of them completes; so that
+/// `stdout` stays clean for the analysis results.
+/// <br/><br/>
+/// Nothing gets drawn if `stderr` is not a terminal (ie: it is being redirected into a file).
+pub struct ProgressBar {
+    term: Term,
+    label: String,
+    total: usize,
+    completed: AtomicUsize,
+    failed: AtomicUsize,
+}
+
+impl ProgressBar {
+    pub fn new(label: &str, total: usize) -> Self {
+        let progress_bar = ProgressBar {
+            term: Term::stderr(),
+            label: label.to_string(),
+            total,
+            completed: AtomicUsize::new(0),
+            failed: AtomicUsize::new(0),
+        };
+
+        progress_bar.draw(0, 0);
+        progress_bar
+    }
+
+    /// Marks one more task as completed; `failed` ones are tallied separately as well.
+    pub fn advance(&self, failed: bool) {
+        let completed = self.completed.fetch_add(1, Ordering::SeqCst) + 1;
+        let failed = if failed {
+            self.failed.fetch_add(1, Ordering::SeqCst) + 1
+        } else {
+            self.failed.load(Ordering::SeqCst)
+        };
+
+        self.draw(completed, failed);
+    }
+
+    /// Wipes the progress bar off the terminal.
+    pub fn finish(&self) {
+        if self.term.is_term() {
+            let _ = self.term.clear_line();
+        }
+    }
+
+    fn draw(&self, completed: usize, failed: usize) {
+        if self.term.is_term() {
+            // a progress bar failing to draw is no reason to disrupt the analysis; just ignore it
+            let _ = self.term.clear_line();
+            let _ = self.term.write_str(&self.render(completed, failed));
+        }
+    }
+
+    fn render(&self, completed: usize, failed: usize) -> String {
+        // an empty sample has nothing left to do, so it counts as complete
+        let filled =
+            (BAR_WIDTH * completed.min(self.total)).checked_div(self.total).unwrap_or(BAR_WIDTH);
+
+        format!(
+            "[{}] [{}{}] {}/{} PRs ({} failed)",
+            self.label,
+            "#".repeat(filled),
+            "-".repeat(BAR_WIDTH - filled),
+            completed,
+            self.total,
+            failed
+        )
+    }
+}
+
+#[cfg(test)]
+mod progress_bar_tests {
+    use crate::report::progress_bar::ProgressBar;
+
+    #[test]
+    fn render_shows_completed_total_and_failed() {
+        let progress_bar = ProgressBar::new("rust", 100);
+
+        assert_eq!(
+            progress_bar.render(50, 2),
+            "[rust] [###############---------------] 50/100 PRs (2 failed)"
+        );
+        assert_eq!(
+            progress_bar.render(0, 0),
+            "[rust] [------------------------------] 0/100 PRs (0 failed)"
+        );
+    }
+}
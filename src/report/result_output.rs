@@ -0,0 +1,94 @@
+//! The destination of an analysis' results: either `stdout` or a file.
+
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use console::Term;
+
+/// Where analysis results end up. Results are written straight into `stdout`, unless an output file
+/// was selected; in which case they are buffered until [`ResultOutput::finish`] writes them all into it
+/// at once, and only the file's path gets printed instead.
+/// <br/><br/>
+/// Either way, results always ignore the 'silent' flag.
+pub struct ResultOutput {
+    stdout: Term,
+    output_file: Option<PathBuf>,
+    buffer: RefCell<String>,
+}
+
+impl ResultOutput {
+    pub fn new(output_file: Option<&Path>) -> Self {
+        ResultOutput {
+            stdout: Term::stdout(),
+            output_file: output_file.map(Path::to_path_buf),
+            buffer: RefCell::new(String::new()),
+        }
+    }
+
+    /// Writes a string, followed by a line break.
+    pub fn write_line(&self, s: &str) -> io::Result<()> {
+        match self.output_file {
+            Some(_) => {
+                let mut buffer = self.buffer.borrow_mut();
+                buffer.push_str(s);
+                buffer.push('\n');
+                Ok(())
+            }
+            None => self.stdout.write_line(s),
+        }
+    }
+
+    /// Writes a string as is.
+    pub fn write_str(&self, s: &str) -> io::Result<()> {
+        match self.output_file {
+            Some(_) => {
+                self.buffer.borrow_mut().push_str(s);
+                Ok(())
+            }
+            None => self.stdout.write_str(s),
+        }
+    }
+
+    /// Flushes buffered results into the output file (if any), creating its parent directories along
+    /// the way, and prints its path.
+    pub fn finish(self) -> io::Result<()> {
+        if let Some(output_file) = &self.output_file {
+            if let Some(parent) =
+                output_file.parent().filter(|parent| !parent.as_os_str().is_empty())
+            {
+                fs::create_dir_all(parent)?;
+            }
+
+            fs::write(output_file, self.buffer.borrow().as_bytes())?;
+            self.stdout.write_line(&output_file.display().to_string())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod result_output_tests {
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    use crate::report::result_output::ResultOutput;
+
+    #[test]
+    fn results_are_written_into_the_output_file_creating_its_parent_directories() {
+        let output_dir = env::temp_dir().join(format!("prolice-result-output-{}", process::id()));
+        let output_file = output_dir.join("nested").join("score.json");
+
+        let result_output = ResultOutput::new(Some(&output_file));
+        result_output.write_line("{").unwrap();
+        result_output.write_str("}").unwrap();
+        result_output.finish().unwrap();
+
+        assert_eq!(fs::read_to_string(&output_file).unwrap(), "{\n}");
+
+        fs::remove_dir_all(output_dir).unwrap();
+    }
+}
@@ -0,0 +1,260 @@
+//! An SQLite database that keeps every analyzed PR's [`Score`] across runs (ie: for long-term trend
+//! analysis); one row per PR and day it was analyzed on, with a column per [`ScoreType`].
+//! <br/><br/>
+//! The table's schema is built out of [`ScoreType::get_iter`], so metrics introduced after a database was
+//! created get their column added the next time it is written into. Integral metrics are stored as
+//! integers, and every other one as a real.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use rusqlite::types::Value;
+use rusqlite::{Connection, NO_PARAMS};
+use thiserror::Error;
+
+use crate::scoring::score::{MetricRecord, Score, ScoreType};
+
+/// The table every PR's row is written into.
+pub const PULL_REQUESTS_TABLE: &str = "pull_requests";
+
+#[derive(Error, Debug)]
+pub enum SqliteReportError {
+    #[error("could not create the database's parent directories: {0}")]
+    Io(#[from] io::Error),
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+}
+
+/// A single PR's [`Score`], as written into a [`SqliteReport`].
+pub struct PullRequestRow<'a> {
+    pub owner: &'a str,
+    pub repository: &'a str,
+    pub pr_number: u64,
+    pub score: &'a Score,
+}
+
+/// A connection to an SQLite database holding PRs' [`Score`]s, keyed by `(owner, repository, pr_number,
+/// analyzed_at)`. `analyzed_at` is the (UTC) day a PR was analyzed on; so re-running an analysis on the
+/// same day replaces that day's rows instead of duplicating them.
+pub struct SqliteReport {
+    connection: Connection,
+}
+
+impl SqliteReport {
+    /// Opens (or creates, along with its parent directories) the database at `path`, making sure its
+    /// table holds a column for every [`ScoreType`].
+    pub fn open(path: &Path) -> Result<Self, SqliteReportError> {
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+
+        SqliteReport::with_connection(Connection::open(path)?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Self, SqliteReportError> {
+        let sqlite_report = SqliteReport {
+            connection,
+        };
+        sqlite_report.migrate()?;
+
+        Ok(sqlite_report)
+    }
+
+    /// Creates the table, if missing; and adds a column for every [`ScoreType`] it does not know of yet.
+    fn migrate(&self) -> Result<(), SqliteReportError> {
+        self.connection.execute_batch(&format!(
+            "CREATE TABLE IF NOT EXISTS {} (\
+                owner TEXT NOT NULL, \
+                repository TEXT NOT NULL, \
+                pr_number INTEGER NOT NULL, \
+                analyzed_at TEXT NOT NULL, \
+                grade REAL, \
+                PRIMARY KEY (owner, repository, pr_number, analyzed_at)\
+            )",
+            PULL_REQUESTS_TABLE
+        ))?;
+
+        let existing_columns: Vec<String> = self
+            .connection
+            .prepare(&format!("PRAGMA table_info({})", PULL_REQUESTS_TABLE))?
+            .query_map(NO_PARAMS, |row| row.get(1))?
+            .collect::<Result<Vec<String>, rusqlite::Error>>()?;
+
+        for score_type in ScoreType::get_iter() {
+            let column = score_type.get_csv_column();
+
+            if !existing_columns.iter().any(|existing_column| existing_column == column) {
+                self.connection.execute_batch(&format!(
+                    "ALTER TABLE {} ADD COLUMN {} {}",
+                    PULL_REQUESTS_TABLE,
+                    column,
+                    column_type_of(&score_type)
+                ))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes every one of the `rows` as analyzed at `analyzed_at`, all at once; replacing any row that
+    /// was written for the same PR on the same day. Metrics a PR's [`Score`] does not carry are left
+    /// empty (`NULL`).
+    pub fn upsert(
+        &mut self, rows: &[PullRequestRow], analyzed_at: &DateTime<Utc>,
+    ) -> Result<(), SqliteReportError> {
+        let metric_columns: Vec<&str> =
+            ScoreType::get_iter().map(|score_type| score_type.get_csv_column()).collect();
+        let sql = format!(
+            "INSERT OR REPLACE INTO {} (owner, repository, pr_number, analyzed_at, grade, {}) VALUES ({})",
+            PULL_REQUESTS_TABLE,
+            metric_columns.join(", "),
+            vec!["?"; metric_columns.len() + 5].join(", ")
+        );
+        let analyzed_at = analyzed_at.format("%Y-%m-%d").to_string();
+
+        let transaction = self.connection.transaction()?;
+        {
+            let mut statement = transaction.prepare(&sql)?;
+
+            for row in rows {
+                let mut values: Vec<Value> = vec![
+                    Value::Text(row.owner.to_string()),
+                    Value::Text(row.repository.to_string()),
+                    Value::Integer(row.pr_number as i64),
+                    Value::Text(analyzed_at.clone()),
+                    row.score.grade().map_or(Value::Null, |grade| Value::Real(grade.value)),
+                ];
+                values.extend(metric_columns.iter().map(|column| {
+                    row.score
+                        .score_types()
+                        .iter()
+                        .find(|score_type| score_type.get_csv_column() == *column)
+                        .map_or(Value::Null, value_of)
+                }));
+
+                statement.execute(values)?;
+            }
+        }
+        transaction.commit()?;
+
+        Ok(())
+    }
+}
+
+/// Returns the SQLite column type a [`ScoreType`]'s values are stored as; integral values are kept
+/// integral, just like in JSON (see [`MetricRecord`]).
+fn column_type_of(score_type: &ScoreType) -> &'static str {
+    if MetricRecord::from(score_type.clone()).value.is_u64() {
+        "INTEGER"
+    } else {
+        "REAL"
+    }
+}
+
+/// Returns a [`ScoreType`]'s value, as stored in its column.
+fn value_of(score_type: &ScoreType) -> Value {
+    let record = MetricRecord::from(score_type.clone());
+
+    match record.value.as_u64() {
+        Some(value) => Value::Integer(value as i64),
+        None => Value::Real(score_type.get_value()),
+    }
+}
+
+#[cfg(test)]
+mod sqlite_tests {
+    use chrono::{TimeZone, Utc};
+    use rusqlite::{Connection, NO_PARAMS};
+
+    use crate::report::sqlite::{PullRequestRow, SqliteReport, PULL_REQUESTS_TABLE};
+    use crate::scoring::score::{Score, ScoreType};
+
+    fn count_rows(sqlite_report: &SqliteReport) -> i64 {
+        sqlite_report
+            .connection
+            .query_row(&format!("SELECT COUNT(*) FROM {}", PULL_REQUESTS_TABLE), NO_PARAMS, |row| {
+                row.get(0)
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn rows_are_upserted_per_pr_and_day_with_a_column_per_metric() {
+        let mut sqlite_report =
+            SqliteReport::with_connection(Connection::open_in_memory().unwrap()).unwrap();
+
+        let first_score =
+            Score::new(vec![ScoreType::TimeToMerge(3), ScoreType::TestToCodeRatio(0.5)]);
+        let rescored = Score::new(vec![ScoreType::TimeToMerge(4)]);
+        let row = |pr_number: u64, score| PullRequestRow {
+            owner: "acme",
+            repository: "service-a",
+            pr_number,
+            score,
+        };
+
+        let morning = Utc.ymd(2021, 3, 1).and_hms(9, 0, 0);
+        let evening = Utc.ymd(2021, 3, 1).and_hms(18, 0, 0);
+        sqlite_report.upsert(&[row(1, &first_score), row(2, &first_score)], &morning).unwrap();
+        // re-running on the same day replaces the PR's row, rather than duplicating it
+        sqlite_report.upsert(&[row(1, &rescored)], &evening).unwrap();
+        assert_eq!(count_rows(&sqlite_report), 2);
+
+        let (time_to_merge, test_to_code_ratio): (i64, Option<f64>) = sqlite_report
+            .connection
+            .query_row(
+                &format!(
+                    "SELECT time_to_merge, test_to_code_ratio FROM {} WHERE pr_number = 1",
+                    PULL_REQUESTS_TABLE
+                ),
+                NO_PARAMS,
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!((time_to_merge, test_to_code_ratio), (4, None));
+
+        // whereas another day's analysis is kept alongside, for trends to be drawn
+        sqlite_report
+            .upsert(&[row(1, &first_score)], &Utc.ymd(2021, 3, 2).and_hms(9, 0, 0))
+            .unwrap();
+        assert_eq!(count_rows(&sqlite_report), 3);
+    }
+
+    #[test]
+    fn metrics_missing_from_an_older_database_get_their_column_added() {
+        let connection = Connection::open_in_memory().unwrap();
+        connection
+            .execute_batch(&format!(
+                "CREATE TABLE {} (owner TEXT NOT NULL, repository TEXT NOT NULL, pr_number INTEGER NOT NULL, \
+                analyzed_at TEXT NOT NULL, grade REAL, time_to_merge INTEGER, \
+                PRIMARY KEY (owner, repository, pr_number, analyzed_at))",
+                PULL_REQUESTS_TABLE
+            ))
+            .unwrap();
+
+        let mut sqlite_report = SqliteReport::with_connection(connection).unwrap();
+        sqlite_report
+            .upsert(
+                &[PullRequestRow {
+                    owner: "acme",
+                    repository: "service-a",
+                    pr_number: 1,
+                    score: &Score::new(vec![ScoreType::ReviewedFileCoverage(0.25)]),
+                }],
+                &Utc.ymd(2021, 3, 1).and_hms(9, 0, 0),
+            )
+            .unwrap();
+
+        let reviewed_file_coverage: f64 = sqlite_report
+            .connection
+            .query_row(
+                &format!("SELECT reviewed_file_coverage FROM {}", PULL_REQUESTS_TABLE),
+                NO_PARAMS,
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(reviewed_file_coverage, 0.25);
+    }
+}
@@ -0,0 +1,250 @@
+//! A previously stored [`Score`], against which a freshly calculated one gets compared metric by metric;
+//! so that trends can be tracked from one run to the next.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::scoring::score::Score;
+
+/// How a single metric changed between a [`Baseline`] and a current [`Score`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum MetricDelta {
+    /// Present in both; `delta` is the current value minus the baseline's, and `percent_change` is that
+    /// same delta relative to the baseline's value ([`None`] if the baseline's value was zero).
+    Changed {
+        baseline: f64,
+        current: f64,
+        delta: f64,
+        percent_change: Option<f64>,
+    },
+    /// Only present in the current [`Score`] (ie: a metric the baseline's version did not know of yet).
+    Added {
+        current: f64,
+    },
+    /// Only present in the [`Baseline`] (ie: a metric that has been dropped, or that was not applicable
+    /// to the current sample).
+    Removed {
+        baseline: f64,
+    },
+}
+
+/// The metrics of a previously stored [`Score`], keyed by their metric name (see
+/// [`ScoreType::get_csv_column`](crate::scoring::score::ScoreType::get_csv_column)).
+/// <br/><br/>
+/// They are read as plain name/value pairs rather than as [`ScoreType`](crate::scoring::score::ScoreType)s,
+/// so that a baseline stored by an older (or newer) version - carrying metrics unknown to this one - can
+/// still be compared against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Baseline {
+    metrics: BTreeMap<String, f64>,
+}
+
+impl Baseline {
+    /// Reads a [`Baseline`] from a JSON file holding a single repository's [`Score`], as output by
+    /// `--format json`.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let raw_baseline = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read baseline file [{}]: {}", path.display(), e))?;
+
+        Baseline::from_json(&raw_baseline)
+            .map_err(|e| format!("Invalid baseline file [{}]: {}", path.display(), e))
+    }
+
+    fn from_json(raw_baseline: &str) -> Result<Self, String> {
+        let baseline: Value = serde_json::from_str(raw_baseline).map_err(|e| e.to_string())?;
+
+        let score = baseline.get("score").and_then(Value::as_array).ok_or(
+            "expected an object with a 'score' list (ie: a single repository's JSON output)",
+        )?;
+
+        let mut metrics = BTreeMap::new();
+
+        for metric in score {
+            let (name, value) = match metric.as_object() {
+                Some(record) if record.contains_key("metric") => {
+                    let name = record
+                        .get("metric")
+                        .and_then(Value::as_str)
+                        .ok_or_else(|| format!("expected a metric name, but found {}", metric))?;
+
+                    (name.to_string(), record.get("value"))
+                }
+                // stored by a version that predates SCORE_SCHEMA_VERSION 2, named after the ScoreType
+                Some(entries) if entries.len() == 1 => {
+                    let (name, value) = entries.iter().next().unwrap();
+
+                    (to_metric_name(name), Some(value))
+                }
+                _ => return Err(format!("expected a single-metric object, but found {}", metric)),
+            };
+
+            let value = value
+                .and_then(Value::as_f64)
+                .ok_or_else(|| format!("metric '{}' does not hold a number", name))?;
+
+            metrics.insert(name, value);
+        }
+
+        Ok(Baseline {
+            metrics,
+        })
+    }
+
+    /// Compares the `current` [`Score`] against this [`Baseline`]; matching metrics by kind, regardless
+    /// of their order. Deltas are rounded to 2 decimals.
+    pub fn diff(&self, current: &Score) -> BTreeMap<String, MetricDelta> {
+        let mut deltas: BTreeMap<String, MetricDelta> = current
+            .score_types()
+            .iter()
+            .map(|score_type| {
+                let name = score_type.get_csv_column().to_string();
+                let current = score_type.get_value();
+
+                let delta = match self.metrics.get(&name) {
+                    Some(&baseline) => MetricDelta::Changed {
+                        baseline,
+                        current,
+                        delta: round_2(current - baseline),
+                        percent_change: if baseline == 0.0 {
+                            None
+                        } else {
+                            Some(round_2((current - baseline) / baseline.abs() * 100.0))
+                        },
+                    },
+                    None => MetricDelta::Added {
+                        current,
+                    },
+                };
+
+                (name, delta)
+            })
+            .collect();
+
+        for (name, &baseline) in &self.metrics {
+            deltas.entry(name.clone()).or_insert(MetricDelta::Removed {
+                baseline,
+            });
+        }
+
+        deltas
+    }
+}
+
+fn round_2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Turns a [`ScoreType`](crate::scoring::score::ScoreType)'s name (ie: `PullRequestLeadTime`) into its
+/// metric name (ie: `pull_request_lead_time`).
+pub(crate) fn to_metric_name(score_type_name: &str) -> String {
+    let mut metric_name = String::new();
+
+    for (index, character) in score_type_name.chars().enumerate() {
+        if index > 0 && character.is_uppercase() {
+            metric_name.push('_');
+        }
+        metric_name.push(character.to_ascii_lowercase());
+    }
+
+    metric_name
+}
+
+#[cfg(test)]
+mod baseline_tests {
+    use crate::scoring::baseline::{Baseline, MetricDelta};
+    use crate::scoring::score::{Score, ScoreType};
+
+    #[test]
+    fn metrics_are_matched_by_kind_and_unknown_ones_reported_as_removed() {
+        let baseline = Baseline::from_json(
+            r#"{ "schema_version": 2, "score": [
+                { "metric": "time_to_merge", "value": 4, "unit": "days" },
+                { "metric": "test_to_code_ratio", "value": 0.5, "unit": "ratio" },
+                { "metric": "amount_of_reviewers", "value": 0, "unit": "count" },
+                { "metric": "some_retired_metric", "value": 12, "unit": "count" }
+            ], "grade": { "value": 72.4, "letter": "C" } }"#,
+        )
+        .unwrap();
+        let current = Score::new(vec![
+            ScoreType::TestToCodeRatio(0.4),
+            ScoreType::TimeToMerge(5),
+            ScoreType::AmountOfReviewers(2),
+            ScoreType::ReactionCount(3),
+        ]);
+
+        let deltas = baseline.diff(&current);
+
+        assert_eq!(
+            deltas["time_to_merge"],
+            MetricDelta::Changed {
+                baseline: 4.0,
+                current: 5.0,
+                delta: 1.0,
+                percent_change: Some(25.0),
+            }
+        );
+        assert_eq!(
+            deltas["test_to_code_ratio"],
+            MetricDelta::Changed {
+                baseline: 0.5,
+                current: 0.4,
+                delta: -0.1,
+                percent_change: Some(-20.0),
+            }
+        );
+        assert!(matches!(
+            deltas["amount_of_reviewers"],
+            MetricDelta::Changed {
+                percent_change: None,
+                ..
+            }
+        ));
+        assert_eq!(
+            deltas["reaction_count"],
+            MetricDelta::Added {
+                current: 3.0
+            }
+        );
+        assert_eq!(
+            deltas["some_retired_metric"],
+            MetricDelta::Removed {
+                baseline: 12.0
+            }
+        );
+    }
+
+    #[test]
+    fn baselines_stored_before_metric_records_are_still_compared_against() {
+        let baseline = Baseline::from_json(
+            r#"{ "score": [{ "PullRequestsDiscussionSize": 40 }, { "SelfMergedPrs": 2 }] }"#,
+        )
+        .unwrap();
+
+        let deltas = baseline.diff(&Score::new(vec![
+            ScoreType::PullRequestsDiscussionSize(50),
+            ScoreType::SelfMergedPrs(2),
+        ]));
+
+        assert!(matches!(
+            deltas["pull_requests_discussion_size"],
+            MetricDelta::Changed {
+                delta, ..
+            } if (delta - 10.0).abs() < f64::EPSILON
+        ));
+        assert!(matches!(deltas["self_merged_prs"], MetricDelta::Changed { .. }));
+        assert_eq!(deltas.len(), 2);
+    }
+
+    #[test]
+    fn anything_but_a_single_score_is_rejected() {
+        assert!(Baseline::from_json(r#"{ "rust": { "score": [] } }"#).is_err());
+        assert!(Baseline::from_json(r#"{ "score": [{ "TimeToMerge": "4" }] }"#).is_err());
+        assert!(Baseline::from_json(r#"{ "score": [{ "metric": "time_to_merge" }] }"#).is_err());
+        assert!(Baseline::from_json("not even JSON").is_err());
+    }
+}
@@ -0,0 +1,390 @@
+//! An overall grade that sums a whole [`Score`](crate::scoring::score::Score) up into a single headline
+//! number (and letter).
+//! <br/><br/>
+//! Every [`ScoreType`] is first normalized into a `0..=1` sub-score - how close it is to its ideal - and
+//! then all sub-scores are averaged according to their [`GradeWeights`]. Where that ideal lies is mostly
+//! built in, save for the bands teams most often calibrate to their own size and habits (see
+//! [`GradingConfig`]). Metrics absent from a
+//! [`Score`](crate::scoring::score::Score) (ie: [`ScoreType::TimeToFirstReview`] for unreviewed PRs) are
+//! left out of the average altogether, instead of counting as a zero.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::scoring::score::ScoreType;
+
+/// An overall, normalized, grade.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Grade {
+    /// From 0 (worst) to 100 (best), rounded to a single decimal.
+    pub value: f64,
+    /// From 'A' (90 and above) to 'F' (below 60).
+    pub letter: String,
+}
+
+impl Grade {
+    /// Grades `score_types` according to `weights`, deeming them ideal within the bands of the
+    /// `grading_config`. Returns [`None`] if none of them carries any weight.
+    pub fn of(
+        score_types: &[ScoreType], weights: &GradeWeights, grading_config: &GradingConfig,
+    ) -> Option<Grade> {
+        let (weighted_sum, total_weight) = score_types
+            .iter()
+            .map(|score_type| {
+                (sub_score(score_type, grading_config), weights.weight_of(score_type))
+            })
+            .filter(|(_, weight)| *weight > 0.0)
+            .fold((0.0, 0.0), |(weighted_sum, total_weight), (sub_score, weight)| {
+                (weighted_sum + sub_score * weight, total_weight + weight)
+            });
+
+        if total_weight == 0.0 {
+            return None;
+        }
+
+        let value = (weighted_sum / total_weight * 1000.0).round() / 10.0;
+
+        Some(Grade {
+            value,
+            letter: letter_of(value).to_string(),
+        })
+    }
+}
+
+/// How much each [`ScoreType`] weighs on a [`Grade`], keyed by the [`ScoreType`]'s CSV column name (see
+/// [`ScoreType::get_csv_column`]). Zero-weighted metrics do not take part in the grade at all.
+#[derive(Debug, Clone)]
+pub struct GradeWeights {
+    weights: BTreeMap<String, f64>,
+}
+
+impl Default for GradeWeights {
+    fn default() -> Self {
+        let weights = ScoreType::get_iter()
+            .map(|score_type| {
+                let weight = match score_type {
+                    // the core flow metrics; the ones that most directly translate into delivery speed
+                    // and review quality
+                    ScoreType::PullRequestLeadTime(_)
+                    | ScoreType::PullRequestSize(_)
+                    | ScoreType::TestToCodeRatio(_)
+                    | ScoreType::TimeToFirstReview(_)
+                    | ScoreType::TimeToMerge(_) => 2.0,
+                    // only meaningful for PRs with a visual component; their absence is no shortcoming
+                    ScoreType::Attachments(_) | ScoreType::BinaryFilesChanged(_) => 0.0,
+                    // only meaningful for teams that require signed commits
+                    ScoreType::SignedCommitRatio(_) => 0.0,
+                    // an alternative take on PullRequestLeadTime; weighing both would count it twice
+                    ScoreType::BusinessDaysLeadTime(_) => 0.0,
+                    // describes how a PR came to be rather than how well it was reviewed
+                    ScoreType::TimeInDraft(_) => 0.0,
+                    // proxy-authored work is sometimes legitimate (ie: cherry-picks, bots' PRs)
+                    ScoreType::AuthorshipMismatch(_) => 0.0,
+                    // tells what kind of change a PR is, rather than how well it was made
+                    ScoreType::DeletionRatio(_) => 0.0,
+                    // a rough, keyword-based signal; worth a look, but not worth grading on
+                    ScoreType::ContentiousDiscussion(_) => 0.0,
+                    // some rejection is healthy; how much of it is too much depends on the team
+                    ScoreType::RejectionRate(_) => 0.0,
+                    _ => 1.0,
+                };
+
+                (score_type.get_csv_column().to_string(), weight)
+            })
+            .collect();
+
+        GradeWeights {
+            weights,
+        }
+    }
+}
+
+impl GradeWeights {
+    /// Reads custom weights from a JSON file holding an object of (non-negative) weights, keyed by CSV
+    /// column name - ie: `{ "time_to_merge": 3, "attachments": 1 }`. Metrics missing from the file keep
+    /// their default weight.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let raw_weights = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read weights file [{}]: {}", path.display(), e))?;
+
+        GradeWeights::from_json(&raw_weights)
+            .map_err(|e| format!("Invalid weights file [{}]: {}", path.display(), e))
+    }
+
+    fn from_json(raw_weights: &str) -> Result<Self, String> {
+        let custom_weights: BTreeMap<String, f64> =
+            serde_json::from_str(raw_weights).map_err(|e| e.to_string())?;
+
+        let mut grade_weights = GradeWeights::default();
+
+        for (metric, weight) in custom_weights {
+            if !grade_weights.weights.contains_key(&metric) {
+                return Err(format!(
+                    "unknown metric [{}]; valid ones are [{}]",
+                    metric,
+                    grade_weights.weights.keys().cloned().collect::<Vec<String>>().join("], [")
+                ));
+            }
+
+            if weight < 0.0 || !weight.is_finite() {
+                return Err(format!("weight for [{}] must be a non-negative number", metric));
+            }
+
+            grade_weights.weights.insert(metric, weight);
+        }
+
+        Ok(grade_weights)
+    }
+
+    /// Returns the weight of a particular [`ScoreType`].
+    pub fn weight_of(&self, score_type: &ScoreType) -> f64 {
+        self.weights.get(score_type.get_csv_column()).copied().unwrap_or_default()
+    }
+}
+
+/// The ideal bands of those [`ScoreType`]s whose ideal depends the most on a team's size and habits. Any
+/// other [`ScoreType`]'s ideal is built into [`sub_score`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradingConfig {
+    /// The ideal `PullRequestFlowRatio`; ratios from 20% below it up to 25% above it are deemed ideal
+    /// too (ie: `0.8..=1.25` for the default 1:1).
+    pub flow_ratio_ideal: f64,
+    /// The `TestToCodeRatio` from which on a PR is deemed thoroughly tested.
+    pub test_ratio_target: f64,
+    /// The band of `PullRequestsDiscussionSize` deemed ideal; bigger teams tend to discuss more.
+    pub discussion_ideal_range: (f64, f64),
+}
+
+impl Default for GradingConfig {
+    fn default() -> Self {
+        GradingConfig {
+            flow_ratio_ideal: 1.0,
+            // see ScoreType::TestToCodeRatio's legend
+            test_ratio_target: 0.5,
+            // discussions can be neither too short nor too long
+            discussion_ideal_range: (2.0, 20.0),
+        }
+    }
+}
+
+impl GradingConfig {
+    /// Parses a single ideal value (ie: `flow_ratio_ideal`), which must be a positive number.
+    pub fn parse_ideal(raw_ideal: &str) -> Result<f64, String> {
+        let ideal: f64 =
+            raw_ideal.trim().parse().map_err(|_| format!("[{}] is not a number", raw_ideal))?;
+
+        if ideal <= 0.0 || !ideal.is_finite() {
+            return Err(format!("[{}] must be a positive number", raw_ideal));
+        }
+
+        Ok(ideal)
+    }
+
+    /// Parses an ideal band, given as `<low>..<high>` (ie: `5..30`); both ends being non-negative, and
+    /// the high one greater than the low one.
+    pub fn parse_range(raw_range: &str) -> Result<(f64, f64), String> {
+        let invalid = || format!("[{}] is not a range (ie: 5..30)", raw_range);
+
+        let separator = raw_range.find("..").ok_or_else(invalid)?;
+        let low: f64 = raw_range[..separator].trim().parse().map_err(|_| invalid())?;
+        let high: f64 = raw_range[separator + 2..].trim().parse().map_err(|_| invalid())?;
+
+        if low < 0.0 || !high.is_finite() || high <= low {
+            return Err(format!(
+                "[{}] must go from a non-negative number up to a greater one",
+                raw_range
+            ));
+        }
+
+        Ok((low, high))
+    }
+}
+
+/// Normalizes a [`ScoreType`] into a sub-score from 0 (worst) to 1 (ideal), within the bands of the
+/// `grading_config`.
+pub fn sub_score(score_type: &ScoreType, grading_config: &GradingConfig) -> f64 {
+    match score_type {
+        // the odd late-night hotfix is forgivable
+        ScoreType::AfterHoursActivityRatio(value) => at_most(*value, 0.1, 0.5),
+        ScoreType::AmountOfParticipants(value) => at_least(*value as f64, 3.0),
+        ScoreType::AmountOfReviewers(value) => at_least(*value as f64, 2.0),
+        ScoreType::Attachments(value) => at_least(*value as f64, 1.0),
+        ScoreType::AuthorCommentaryToChangesRatio(value) => within(*value, 0.01, 0.1),
+        ScoreType::AuthorResponseLatency(value) => at_most(*value as f64, 4.0, 48.0),
+        ScoreType::AuthorshipMismatch(value) => at_most(*value as f64, 0.0, 5.0),
+        ScoreType::BinaryFilesChanged(value) => at_most(*value as f64, 10.0, 50.0),
+        ScoreType::BusinessDaysLeadTime(value) => at_most(*value as f64, 2.0, 10.0),
+        ScoreType::ChecklistCompletion(value) => at_least(*value, 1.0),
+        // the odd quick fix is forgivable
+        ScoreType::CommitMessageQuality(value) => at_least(*value, 0.8),
+        ScoreType::ContentiousDiscussion(value) => at_most(*value as f64, 0.0, 5.0),
+        ScoreType::DeletionRatio(value) => at_least(*value, 0.5),
+        // a few sentences' worth of what, how and why
+        ScoreType::DescriptionLength(value) => at_least(*value as f64, 200.0),
+        ScoreType::FilesChanged(value) => at_most(*value as f64, 10.0, 50.0),
+        // acknowledging a PR takes way less than reviewing it
+        ScoreType::FirstResponseWait(value) => at_most(*value as f64, 2.0, 24.0),
+        ScoreType::GreenMergeRatio(value) => at_least(*value, 1.0),
+        ScoreType::LinkedIssues(value) => at_least(*value as f64, 1.0),
+        ScoreType::MissingDescription(value) => at_most(*value, 0.0, 1.0),
+        ScoreType::NumberOfCommits(value) => at_most(*value as f64, 5.0, 20.0),
+        ScoreType::PullRequestsDiscussionSize(value) => {
+            let (low, high) = grading_config.discussion_ideal_range;
+            within(*value as f64, low, high)
+        }
+        // the closer to the ideal, the better
+        ScoreType::PullRequestFlowRatio(value) => within(
+            *value,
+            grading_config.flow_ratio_ideal * 0.8,
+            grading_config.flow_ratio_ideal * 1.25,
+        ),
+        ScoreType::PullRequestLeadTime(value) => at_most(*value as f64, 2.0, 14.0),
+        ScoreType::PullRequestSize(value) => at_most(*value as f64, 200.0, 1000.0),
+        ScoreType::ReactionCount(value) => at_least(*value as f64, 2.0),
+        ScoreType::RejectionRate(value) => at_most(*value, 0.1, 0.5),
+        ScoreType::ReviewIterations(value) => at_most(*value as f64, 1.0, 5.0),
+        // everyone reviewing someone else's work on top of a few outside reviewers
+        ScoreType::ReviewParticipationBreadth(value) => at_least(*value, 1.5),
+        ScoreType::ReviewedFileCoverage(value) => at_least(*value, 0.5),
+        ScoreType::ReviewerDiversity(value) => at_least(*value as f64, 3.0),
+        ScoreType::SelfMergedPrs(value) => at_most(*value as f64, 0.0, 5.0),
+        ScoreType::SignedCommitRatio(value) => at_least(*value, 1.0),
+        ScoreType::TestToCodeRatio(value) => at_least(*value, grading_config.test_ratio_target),
+        // a day's worth of work in progress is par for the course
+        ScoreType::TimeInDraft(value) => at_most(*value as f64, 24.0, 168.0),
+        // a day for a PR to be approved is par for the course
+        ScoreType::TimeToApproval(value) => at_most(*value as f64, 24.0, 120.0),
+        ScoreType::TimeToFirstReview(value) => at_most(*value as f64, 4.0, 48.0),
+        ScoreType::TimeToMerge(value) => at_most(*value as f64, 3.0, 30.0),
+        ScoreType::UnresolvedThreads(value) => at_most(*value as f64, 0.0, 5.0),
+    }
+}
+
+/// Ideal once `value` reaches `ideal`; proportionally worse below it.
+fn at_least(value: f64, ideal: f64) -> f64 {
+    (value / ideal).clamp(0.0, 1.0)
+}
+
+/// Ideal up to `ideal`; linearly worse from there on, down to zero at `worst`.
+fn at_most(value: f64, ideal: f64, worst: f64) -> f64 {
+    ((worst - value) / (worst - ideal)).clamp(0.0, 1.0)
+}
+
+/// Ideal inside the `low..=high` band; proportionally worse the farther away from it.
+fn within(value: f64, low: f64, high: f64) -> f64 {
+    if value < low {
+        at_least(value, low)
+    } else if value > high {
+        (high / value).min(1.0)
+    } else {
+        1.0
+    }
+}
+
+fn letter_of(value: f64) -> char {
+    match value {
+        value if value >= 90.0 => 'A',
+        value if value >= 80.0 => 'B',
+        value if value >= 70.0 => 'C',
+        value if value >= 60.0 => 'D',
+        _ => 'F',
+    }
+}
+
+#[cfg(test)]
+mod grade_tests {
+    use crate::scoring::grade::{Grade, GradeWeights, GradingConfig};
+    use crate::scoring::score::{Score, ScoreType};
+
+    #[test]
+    fn grade_is_the_weighted_average_of_present_metrics() {
+        let weights =
+            GradeWeights::from_json(r#"{ "time_to_merge": 3, "review_iterations": 1 }"#).unwrap();
+
+        // an ideal time-to-merge (3 days) and a review-iterations right halfway to the worst (3 of 5)
+        let grade = Grade::of(
+            &[ScoreType::TimeToMerge(3), ScoreType::ReviewIterations(3)],
+            &weights,
+            &GradingConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            grade,
+            Grade {
+                value: 87.5,
+                letter: String::from("B"),
+            }
+        );
+    }
+
+    #[test]
+    fn ratios_are_clamped_around_their_ideal_band() {
+        let weights = GradeWeights::default();
+
+        let grade_of = |score_type: ScoreType| {
+            Grade::of(&[score_type], &weights, &GradingConfig::default()).unwrap().value
+        };
+
+        assert_eq!(grade_of(ScoreType::PullRequestFlowRatio(1.0)), 100.0);
+        assert_eq!(grade_of(ScoreType::PullRequestFlowRatio(2.5)), 50.0);
+        assert_eq!(grade_of(ScoreType::PullRequestsDiscussionSize(1)), 50.0);
+        assert_eq!(grade_of(ScoreType::PullRequestsDiscussionSize(40)), 50.0);
+        assert_eq!(grade_of(ScoreType::TestToCodeRatio(3.0)), 100.0);
+    }
+
+    #[test]
+    fn zero_weighted_metrics_do_not_take_part() {
+        assert_eq!(
+            Grade::of(
+                &[ScoreType::Attachments(0)],
+                &GradeWeights::default(),
+                &GradingConfig::default()
+            ),
+            None
+        );
+        assert!(GradeWeights::from_json(r#"{ "unknown_metric": 1 }"#).is_err());
+        assert!(GradeWeights::from_json(r#"{ "time_to_merge": -1 }"#).is_err());
+    }
+
+    #[test]
+    fn the_same_score_grades_differently_under_other_ideal_bands() {
+        let score = Score::new(vec![
+            ScoreType::PullRequestFlowRatio(2.0),
+            ScoreType::PullRequestsDiscussionSize(30),
+            ScoreType::TestToCodeRatio(0.5),
+        ]);
+        let weights = GradeWeights::default();
+
+        let default_grade = score.overall_grade(&weights, &GradingConfig::default()).unwrap();
+        let big_team_grade = score
+            .overall_grade(
+                &weights,
+                &GradingConfig {
+                    flow_ratio_ideal: 2.0,
+                    test_ratio_target: 1.0,
+                    discussion_ideal_range: (5.0, 30.0),
+                },
+            )
+            .unwrap();
+
+        // the flow ratio (0.625 -> 1) and discussion (0.67 -> 1) become ideal, while tests fall short (1 -> 0.5)
+        assert_eq!(default_grade.value, 82.3);
+        assert_eq!(big_team_grade.value, 75.0);
+    }
+
+    #[test]
+    fn ideal_bands_are_parsed_from_their_cli_form() {
+        assert_eq!(GradingConfig::parse_ideal("1.5"), Ok(1.5));
+        assert!(GradingConfig::parse_ideal("0").is_err());
+        assert!(GradingConfig::parse_ideal("fast").is_err());
+
+        assert_eq!(GradingConfig::parse_range("5..30"), Ok((5.0, 30.0)));
+        assert_eq!(GradingConfig::parse_range(" 0.5 .. 2 "), Ok((0.5, 2.0)));
+        assert!(GradingConfig::parse_range("30..5").is_err());
+        assert!(GradingConfig::parse_range("5-30").is_err());
+    }
+}
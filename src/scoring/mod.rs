@@ -1,3 +1,13 @@
+pub mod baseline;
+
+pub mod grade;
+
+pub mod rescoring;
+
 pub mod score;
 
 pub mod scorable;
+
+pub mod scoring_settings;
+
+pub mod threshold;
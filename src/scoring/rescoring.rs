@@ -0,0 +1,262 @@
+//! Offline re-scoring of a previously exported JSON-lines report (see
+//! [`OutputFormat::Jsonl`](crate::report::output_format::OutputFormat::Jsonl)): its PRs' [`Score`]s are
+//! re-graded and re-aggregated according to the current [`ScoringSettings`], without hitting GitHub at all.
+//! <br/><br/>
+//! Metrics that only apply to a whole sample ([`ScoreType::PullRequestFlowRatio`],
+//! [`ScoreType::RejectionRate`], [`ScoreType::ReviewParticipationBreadth`], [`ScoreType::ReviewerDiversity`]
+//! and [`ScoreType::SelfMergedPrs`]) cannot be derived from its PRs' [`Score`]s, so they are carried over from the report's repository records as
+//! they are. Organization records are ignored altogether. Any [`ScoreFlag`](crate::scoring::score::ScoreFlag)
+//! of a PR's is kept on its re-graded [`Score`], and raised by its repository's aggregate as well.
+
+use serde::Deserialize;
+
+use crate::github::utils::repository_data::aggregate_scores;
+use crate::scoring::score::{SampleStats, Score, ScoreType};
+use crate::scoring::scoring_settings::ScoringSettings;
+
+/// A [`ScoreRecord`](crate::scoring::score::ScoreRecord), as read back from a JSON-lines report.
+#[derive(Debug, Deserialize)]
+pub struct StoredScoreRecord {
+    pub owner: String,
+    #[serde(default)]
+    pub repository: Option<String>,
+    #[serde(default)]
+    pub pr_number: Option<u64>,
+    #[serde(flatten)]
+    pub score: Score,
+}
+
+impl StoredScoreRecord {
+    /// Reads every [`StoredScoreRecord`] out of a `raw_report` holding one of them per line. Blank lines
+    /// are skipped; any other unreadable line fails the whole report.
+    pub fn from_json_lines(raw_report: &str) -> Result<Vec<Self>, String> {
+        raw_report
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line)
+                    .map_err(|e| format!("unreadable record at line [{}]: {}", index + 1, e))
+            })
+            .collect()
+    }
+}
+
+/// A single repository's re-graded [`Score`]s.
+#[derive(Debug)]
+pub struct RescoredRepository {
+    pub owner: String,
+    pub repository: String,
+    /// Every PR's re-graded [`Score`], keyed by its number; in the report's order.
+    pub pull_requests: Vec<(u64, Score)>,
+    /// The re-aggregated [`Score`] of all of the above.
+    pub repo_score: Score,
+}
+
+/// Re-grades and re-aggregates the `records` of every repository in a report, according to the
+/// `scoring_settings`. Repositories are returned in the order they first turn up in the report.
+pub fn rescore(
+    records: Vec<StoredScoreRecord>, scoring_settings: &ScoringSettings,
+) -> Vec<RescoredRepository> {
+    let mut repositories: Vec<(String, String)> = Vec::new();
+
+    for record in &records {
+        if let Some(repository) = &record.repository {
+            let key = (record.owner.clone(), repository.clone());
+
+            if !repositories.contains(&key) {
+                repositories.push(key);
+            }
+        }
+    }
+
+    let mut records: Vec<Option<StoredScoreRecord>> = records.into_iter().map(Some).collect();
+
+    repositories
+        .into_iter()
+        .map(|(owner, repository)| {
+            let mut pull_requests: Vec<(u64, Score)> = Vec::new();
+            let mut pull_request_flow_ratio: Option<f64> = None;
+            let mut reviewer_diversity: Option<u64> = None;
+            let mut review_participation_breadth: Option<f64> = None;
+            let mut self_merged_prs: Option<u64> = None;
+            let mut rejection_rate: Option<f64> = None;
+            let mut sample_stats: Option<SampleStats> = None;
+
+            for record in records.iter_mut().filter(|record| {
+                matches!(record, Some(record)
+                    if record.owner == owner && record.repository.as_ref() == Some(&repository))
+            }) {
+                let record = record.take().unwrap(); // filtered for presence above
+
+                match record.pr_number {
+                    Some(pr_number) => {
+                        let flags = record.score.flags().to_vec();
+
+                        pull_requests.push((
+                            pr_number,
+                            Score::new(record.score.score())
+                                .graded(
+                                    &scoring_settings.grade_weights,
+                                    &scoring_settings.grading_config,
+                                )
+                                .flagged_with(&flags),
+                        ))
+                    }
+                    None => {
+                        sample_stats = record.score.sample_stats();
+
+                        for score_type in record.score.score() {
+                            match score_type {
+                                ScoreType::PullRequestFlowRatio(value) => {
+                                    pull_request_flow_ratio = Some(value)
+                                }
+                                ScoreType::RejectionRate(value) => rejection_rate = Some(value),
+                                ScoreType::ReviewParticipationBreadth(value) => {
+                                    review_participation_breadth = Some(value)
+                                }
+                                ScoreType::ReviewerDiversity(value) => {
+                                    reviewer_diversity = Some(value)
+                                }
+                                ScoreType::SelfMergedPrs(value) => self_merged_prs = Some(value),
+                                _ => {} // every other metric gets re-aggregated from the PRs' scores
+                            }
+                        }
+                    }
+                }
+            }
+
+            let repo_score = aggregate_scores(
+                pull_requests.iter().map(|(_, score)| score.score_types().to_vec()).collect(),
+                pull_request_flow_ratio,
+                reviewer_diversity,
+                review_participation_breadth,
+                self_merged_prs,
+                rejection_rate,
+                scoring_settings,
+            )
+            .flagged_with(pull_requests.iter().flat_map(|(_, score)| score.flags()));
+
+            RescoredRepository {
+                owner,
+                repository,
+                pull_requests,
+                repo_score: match sample_stats {
+                    Some(sample_stats) => repo_score.with_sample_stats(sample_stats),
+                    None => repo_score,
+                },
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod rescoring_tests {
+    use crate::github::utils::fixtures::has_metric;
+    use crate::scoring::rescoring::{rescore, StoredScoreRecord};
+    use crate::scoring::score::{Score, ScoreFlag, ScoreRecord, ScoreType};
+    use crate::scoring::scoring_settings::ScoringSettings;
+
+    #[test]
+    fn exported_records_are_regraded_and_reaggregated_per_repository() {
+        let pr_score = |time_to_merge: u64| {
+            Score::new(vec![ScoreType::TimeToMerge(time_to_merge), ScoreType::NumberOfCommits(2)])
+        };
+        let repo_score =
+            Score::new(vec![ScoreType::TimeToMerge(99), ScoreType::ReviewerDiversity(4)]);
+
+        let record = |repository: &str, pr_number: Option<u64>, score: &Score| {
+            ScoreRecord {
+                owner: "owner",
+                repository: Some(repository),
+                pr_number,
+                score,
+            }
+            .to_json_line()
+        };
+
+        let report = [
+            record("repo-a", Some(1), &pr_score(3)),
+            record("repo-b", Some(7), &pr_score(30)),
+            String::new(),
+            record("repo-a", Some(2), &pr_score(5)),
+            record("repo-a", None, &repo_score),
+        ]
+        .join("\n");
+
+        let rescored = rescore(
+            StoredScoreRecord::from_json_lines(&report).unwrap(),
+            &ScoringSettings::default(),
+        );
+
+        assert_eq!(rescored.len(), 2);
+        assert_eq!(rescored[0].repository, "repo-a");
+        assert_eq!(
+            rescored[0].pull_requests.iter().map(|(pr_number, _)| *pr_number).collect::<Vec<u64>>(),
+            vec![1, 2]
+        );
+        // exported without a grade, but graded once re-scored
+        assert!(rescored[0].pull_requests[0].1.to_json().contains("\"grade\""));
+
+        // stale aggregates are recalculated; sample-wide metrics are carried over
+        let repo_a_score = rescored[0].repo_score.score_types();
+        assert!(repo_a_score.contains(&ScoreType::TimeToMerge(4)));
+        assert!(repo_a_score.contains(&ScoreType::ReviewerDiversity(4)));
+        assert!(!has_metric(rescored[1].repo_score.score_types(), ScoreType::ReviewerDiversity(0)));
+
+        assert!(StoredScoreRecord::from_json_lines("{ not json }").is_err());
+    }
+
+    #[test]
+    fn flags_survive_rescoring_and_are_raised_by_the_repository_aggregate() {
+        let record = |pr_number: Option<u64>, score: &Score| {
+            ScoreRecord {
+                owner: "owner",
+                repository: Some("repo"),
+                pr_number,
+                score,
+            }
+            .to_json_line()
+        };
+
+        let report = [
+            record(Some(1), &Score::new(vec![ScoreType::TimeToMerge(3)])),
+            record(
+                Some(2),
+                &Score::new(vec![ScoreType::TimeToMerge(5)])
+                    .flagged(ScoreFlag::SquashedTimeToMerge),
+            ),
+            // a stale aggregate, exported before its PR got flagged
+            record(None, &Score::new(vec![ScoreType::TimeToMerge(4)])),
+        ]
+        .join("\n");
+
+        let rescored = rescore(
+            StoredScoreRecord::from_json_lines(&report).unwrap(),
+            &ScoringSettings::default(),
+        );
+
+        assert!(rescored[0].pull_requests[0].1.flags().is_empty());
+        assert_eq!(rescored[0].pull_requests[1].1.flags(), &[ScoreFlag::SquashedTimeToMerge]);
+        assert_eq!(rescored[0].repo_score.flags(), &[ScoreFlag::SquashedTimeToMerge]);
+    }
+
+    #[test]
+    fn unversioned_reports_are_rescored_skipping_unknown_metrics() {
+        // as exported before SCORE_SCHEMA_VERSION 2; the last metric is no longer (or not yet) known
+        let report = [
+            r#"{"owner":"owner","repository":"repo","pr_number":1,"score":[{"TimeToMerge":3},{"NumberOfCommits":2}]}"#,
+            r#"{"owner":"owner","repository":"repo","pr_number":2,"score":[{"TimeToMerge":5},{"SomeRetiredMetric":1}]}"#,
+        ]
+        .join("\n");
+
+        let records = StoredScoreRecord::from_json_lines(&report).unwrap();
+        assert!(records[0].score.to_json().contains("\"schema_version\": 1"));
+
+        let rescored = rescore(records, &ScoringSettings::default());
+
+        assert_eq!(rescored.len(), 1);
+        assert_eq!(rescored[0].pull_requests[1].1.score_types(), &[ScoreType::TimeToMerge(5)][..]);
+        assert!(rescored[0].repo_score.score_types().contains(&ScoreType::TimeToMerge(4)));
+    }
+}
@@ -1,24 +1,74 @@
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
 use std::fmt::{Display, Formatter};
+use std::iter::once;
+use std::iter::FromIterator;
+use std::mem::discriminant;
 
-use log::error;
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Local};
+use console::style;
+use log::{error, warn};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
 use strum::IntoEnumIterator;
 use strum_macros::{Display, EnumIter};
 
+use crate::metadata;
+use crate::scoring::baseline::{to_metric_name, MetricDelta};
+use crate::scoring::grade::{sub_score, Grade, GradeWeights, GradingConfig};
+
+/// The version of the JSON schema that [`Score`]s (and the [`ScoreType`]s within) are serialized with;
+/// bumped whenever their shape changes in a way that could break consumers. New metrics do not bump it, so
+/// consumers must be ready to skip metrics they do not know of. The unversioned shape that preceded it
+/// (every [`ScoreType`] as a single `{ "<ScoreType>": <value> }` object) counts as version 1.
+pub const SCORE_SCHEMA_VERSION: u32 = 2;
+
 /// Enumeration of important qualities from either a [`PullRequest`](octocrab::models::pulls::PullRequest)
 /// or a [`Repository`](octocrab::models::Repository) that are worth analyzing and measuring.
-#[derive(Display, Serialize, Deserialize, EnumIter, Debug, PartialEq)]
+/// <br/><br/>
+/// Every [`ScoreType`] is (de)serialized as a flat [`MetricRecord`], regardless of its underlying numeric
+/// type.
+#[derive(Display, Serialize, Deserialize, EnumIter, Debug, Clone, PartialEq)]
+#[serde(into = "MetricRecord", try_from = "MetricRecord")]
 pub enum ScoreType {
+    AfterHoursActivityRatio(f64),
     AmountOfParticipants(u64),
     AmountOfReviewers(u64),
     Attachments(u64),
     AuthorCommentaryToChangesRatio(f64),
+    AuthorResponseLatency(u64),
+    AuthorshipMismatch(u64),
+    BinaryFilesChanged(usize),
+    BusinessDaysLeadTime(u64),
+    ChecklistCompletion(f64),
+    CommitMessageQuality(f64),
+    ContentiousDiscussion(u64),
+    DeletionRatio(f64),
+    DescriptionLength(usize),
+    FilesChanged(usize),
+    FirstResponseWait(u64),
+    GreenMergeRatio(f64),
+    LinkedIssues(u64),
+    MissingDescription(f64),
+    NumberOfCommits(u64),
     PullRequestsDiscussionSize(usize),
     PullRequestFlowRatio(f64),
     PullRequestLeadTime(u64),
     PullRequestSize(usize),
+    ReactionCount(u64),
+    RejectionRate(f64),
+    ReviewIterations(u64),
+    ReviewParticipationBreadth(f64),
+    ReviewedFileCoverage(f64),
+    ReviewerDiversity(u64),
+    SelfMergedPrs(u64),
+    SignedCommitRatio(f64),
     TestToCodeRatio(f64),
+    TimeInDraft(u64),
+    TimeToApproval(u64),
+    TimeToFirstReview(u64),
     TimeToMerge(u64),
+    UnresolvedThreads(u64),
 }
 
 impl ScoreType {
@@ -32,6 +82,11 @@ impl ScoreType {
     // https://sourcelevel.io/blog/5-metrics-engineering-managers-can-extract-from-pull-requests
     pub fn get_legend(&self) -> &'static str {
         match &self {
+            ScoreType::AfterHoursActivityRatio(_) =>
+                "The ratio of a PR's activity (commits, comments and reviews alike) that took place on \
+                weekends, or outside of the team's working hours. A steady trickle of after-hours work \
+                hints at looming deadlines and overworked people, which sooner or later takes its toll on \
+                both the team's health and the code's quality.",
             ScoreType::AmountOfParticipants(_) =>
                 "The amount of non-authoring people participating in a PR's discussion. Bigger participation \
                 may enrich discussion and produce higher quality code.",
@@ -49,6 +104,88 @@ impl ScoreType {
                 A slim commentary may make for an ambiguous PR, shifting the burden of understanding \
                 onto the reviewer and consuming extra time from it. On the other hand, too many comments \
                 may pollute a PR with unneeded noise, to the same effect.",
+            ScoreType::AuthorResponseLatency(_) =>
+                "The average amount of hours a PR's author takes to reply after a reviewer weighs in; \
+                that is, between each review (or comment) from someone other than the author and the \
+                author's next comment. Where Time To First Review measures reviewers' responsiveness, \
+                this one measures authors': feedback left waiting for an answer stalls a PR just the \
+                same. PRs without any reviewer-to-author exchange are left out of the repository's \
+                average.",
+            ScoreType::AuthorshipMismatch(_) =>
+                "The amount of a PR's commits authored by someone other than whoever opened the PR. It \
+                flags proxy-authored work (ie: cherry-picks, or PRs opened by bots on someone else's \
+                behalf), whose actual authors may never be around to answer for it during review. Commits \
+                GitHub could not attribute to any of its users are left out.",
+            ScoreType::BinaryFilesChanged(_) =>
+                "The amount of binary files (ie: images, fonts, archives) touched by a PR. Their changes \
+                carry no lines, so they slip through line-based metrics such as Pull Request Size: a PR \
+                that mostly swaps a 2MB image would look like it changed nothing at all. It pairs well \
+                with Attachments for PRs with a visual component.",
+            ScoreType::BusinessDaysLeadTime(_) =>
+                "Just like the Pull Request Lead Time, how many days pull requests take to be merged or \
+                closed; but counting business days only. Weekends (and the team's holidays, if supplied) \
+                are left out, so that a PR opened on a Friday and merged on Monday does not look like it \
+                sat idle for three days. Better suited for SLA reporting.",
+            ScoreType::ChecklistCompletion(_) =>
+                "The ratio of checked-off items among the markdown task lists ('- [x]' / '- [ ]') in a \
+                PR's body and its author's comments; 0 if there are none. PR templates usually carry a \
+                checklist of chores (tests, docs, changelog...) that are easy to forget; a PR that leaves \
+                them unchecked may well be leaving them undone.",
+            ScoreType::CommitMessageQuality(_) =>
+                "The ratio of a PR's commits whose message carries both a descriptive subject line (at \
+                least 15 characters long) and a body explaining it. Terse messages such as 'fix' or 'wip' \
+                turn the repository's history (and tools like blame or bisect) into a dead end for whoever \
+                comes next. Merge commits (and their auto-generated messages) are left out; PRs made up \
+                of merge commits only are left out of the repository's average.",
+            ScoreType::ContentiousDiscussion(_) =>
+                "The amount of a PR's comments (its body included) carrying escalation markers: runs of \
+                question marks ('???'), all-caps sentences or any of the supplied contention words. A rough, \
+                keyword-based signal rather than a judgment; it cannot tell banter from a heated exchange, \
+                but it can point at discussions worth a second look. Only scored when contention words are \
+                supplied.",
+            ScoreType::DeletionRatio(_) =>
+                "The ratio of a PR's changed lines that were deletions; 0 if it changed none. Cleanups and \
+                pure refactors remove about as much as (or more than) they add, and come with different \
+                review dynamics: high-deletion PRs are often low-risk, since code that is gone can no \
+                longer break.",
+            ScoreType::DescriptionLength(_) =>
+                "The amount of characters in a PR's description (its body), leaving any later comments \
+                aside. A one-liner (or an empty body) shifts the burden of figuring out what a PR is \
+                about - and why - onto its reviewers; a rich description lets them start off with the \
+                author's context instead of having to reverse-engineer it from the code.",
+            ScoreType::FilesChanged(_) =>
+                "The amount of files touched by a PR. It complements Pull Request Size by capturing how \
+                scattered a change is across the codebase: a 50-line change spread across 40 files is way \
+                harder to review (and to reason about) than those same 50 lines in a single file.",
+            ScoreType::FirstResponseWait(_) =>
+                "The amount of hours between a PR being opened and its first activity of any kind (a \
+                comment, a review or a commit comment) by anyone but its author. Unlike Time To \
+                First Review, which waits for a reviewer, it captures how long PRs sit in the review \
+                queue without anyone so much as acknowledging them: a long wait means neglected PRs, and \
+                authors left wondering whether anybody is going to look at their work at all. PRs without \
+                any activity are left out of the repository's average.",
+            ScoreType::GreenMergeRatio(_) =>
+                "Whether a PR got merged with all of its checks (CI statuses and check runs alike) passing: \
+                1 if it did, 0 if any of them had failed or not finished yet by then (CI statuses are only \
+                known as they stand now, though). Merging on red (or before CI \
+                is done) ships changes nobody has verified; for repositories, the mean tells the share of \
+                merged PRs that were green. PRs without any check configured are left out rather than \
+                counted as failures. It takes two extra requests per PR to retrieve each PR's checks, so it \
+                is only scored when requested (see `--with-checks`).",
+            ScoreType::LinkedIssues(_) =>
+                "The amount of issues a PR's author declared it closes (ie: 'closes #123', 'fixes #456'). \
+                A traceability signal: PRs linked to the issues they address carry their own 'why' along \
+                with them, and keep the issue tracker in sync with the codebase for free.",
+            ScoreType::MissingDescription(_) =>
+                "Whether a PR lacks a description (body) altogether: 1 if it does, 0 otherwise. Unlike a \
+                short (or deliberately emptied) one, a missing description means not even a template was \
+                filled in; so it is told apart from Description Length, as a stronger warning sign. For \
+                repositories, the mean tells the share of PRs that lack one.",
+            ScoreType::NumberOfCommits(_) =>
+                "Every commit in a PR is a step the reviewer may have to retrace. Fewer, well-scoped commits \
+                (each one a self-contained, meaningful change) tend to be easier to review than a long trail \
+                of 'fix', 'wip' and 'address comments' commits; which may also hint at work that was not \
+                thought through before being submitted.",
             ScoreType::PullRequestsDiscussionSize(_) =>
                 "Similar to Author Commentary to Changes Ratio, it measures the total amount of comments \
                 in a PR, but irrespective of who they come from. On the contrary to social media posts, \
@@ -84,8 +221,72 @@ impl ScoreType {
                 longer pull requests faster than shorter ones, for it is more difficult to perform thorough \
                 reviews when there are too many things going on. Regardless of how thorough the reviews \
                 are, big PRs lead to the Time To Merge going up, and the quality going down.",
+            ScoreType::ReactionCount(_) =>
+                "The amount of reactions (👍, 👎, 🎉...) left on a PR's body. A lightweight form of \
+                engagement that never shows up as a textual comment: people who read a PR and agreed \
+                with it (or celebrated it) without having anything to add.",
+            ScoreType::RejectionRate(_) =>
+                "The ratio of the sample's closed PRs that were closed without being merged. Some rejection \
+                is healthy (ie: experiments, or changes superseded by better ones); but a high rate means \
+                effort being thrown away, which may point to unclear requirements or to work that was not \
+                agreed upon before being started. Only scored when closed-unmerged PRs are part of the \
+                sample (see `--include-closed-unmerged`). Being a property of the whole sample, it does not \
+                apply to individual PRs.",
+            ScoreType::ReviewIterations(_) =>
+                "The amount of review rounds a PR went through; that is, how many times reviewers had to come \
+                back to it after its author addressed their feedback (by either pushing new commits or \
+                replying). A single round means the PR was good to go (or rejected) at first sight. Many \
+                rounds may point to unclear requirements, to PRs that were submitted before they were \
+                ready, or to reviewers that drip-feed their feedback instead of doing thorough passes.",
+            ScoreType::ReviewParticipationBreadth(_) =>
+                "The ratio of distinct reviewers to distinct authors across the sample; that is, how \
+                broadly reviewing is shared among the people who write PRs. Values near 1 suggest siloed \
+                review, where the same few people review each other's work (or below 1, where a handful \
+                of gatekeepers review everyone's); higher values suggest a cross-review culture. Being a \
+                property of the whole sample, it does not apply to individual PRs.",
+            ScoreType::ReviewedFileCoverage(_) =>
+                "The ratio of a PR's changed files that received at least one inline comment from someone \
+                other than its author. Approving a PR without a single remark on most of its files hints \
+                at a skim rather than a thorough review; the more of a PR's files its reviewers engage \
+                with, the more likely its issues get caught before being merged. Not every file deserves \
+                a remark, though: an occasional low coverage is to be expected.",
+            ScoreType::ReviewerDiversity(_) =>
+                "The amount of distinct people (other than the PRs' own authors) that reviewed any PR in \
+                the sample. A small pool of reviewers means knowledge of the codebase is concentrated in \
+                few heads, and that those few become a bottleneck for everyone else's work. Being a \
+                property of the whole sample, it does not apply to individual PRs.",
+            ScoreType::SelfMergedPrs(_) =>
+                "The amount of PRs in the sample that were most likely merged by their own authors, \
+                without anyone else's review: a governance red flag, for changes reach the target branch \
+                without a second pair of eyes. GitHub's PR listings do not tell who merged a PR, so it is \
+                derived conservatively: PRs merged within an hour of being opened, without a single review \
+                from someone other than their author. Being a property of the whole sample, it does not \
+                apply to individual PRs.",
+            ScoreType::SignedCommitRatio(_) =>
+                "The ratio of a PR's commits whose signature GitHub could verify. Signed commits prove \
+                that they were authored by whom they claim to be, which security-conscious teams (and \
+                compliance frameworks) require of every change. PRs without any commit are left out of \
+                the repository's average.",
             ScoreType::TestToCodeRatio(_) =>
                 "As a rule of thumb, at least half of a PR should be comprised of tests whenever possible.",
+            ScoreType::TimeInDraft(_) =>
+                "The amount of hours a PR spent as a draft (ie: work in progress, not up for review yet) \
+                before being marked as ready for review. It tells WIP time apart from genuine review time, \
+                which would otherwise get mixed up in the Pull Request Lead Time. PRs that were never drafts \
+                spent zero hours as one. It takes an extra request per PR to retrieve each PR's timeline, so \
+                it is only measured on demand.",
+            ScoreType::TimeToApproval(_) =>
+                "The amount of hours between a PR being opened and its first approval from someone other \
+                than its author. Unlike the Time to First Review, which any review (or commit comment) \
+                satisfies, it measures how long it takes for a PR to be deemed good enough to go in. PRs \
+                that never got approved (be it because they are still under review, or because they \
+                were merged without one) are left out of the repository's average.",
+            ScoreType::TimeToFirstReview(_) =>
+                "The amount of hours between a PR being opened and the first review (or commit comment) \
+                from someone other than its author. It measures reviewers' responsiveness: PRs that sit \
+                unattended for long grow stale, force their authors to context-switch back into them, and \
+                end up inflating the Pull Request Lead Time. PRs that never got reviewed are left out of \
+                the repository's average.",
             ScoreType::TimeToMerge(_) =>
                 "In general, pull requests are open with some work in progress, which means that measuring \
                 Pull Request Lead Time does not tell the whole story. Time to Merge is how much time \
@@ -105,48 +306,517 @@ impl ScoreType {
     
                 NOTE: \n\
                 This metric is rendered somewhat obsolete if developers work on WIP branches before squashing \
-                all the changes into a single commit that is later used as base for the PR (this would \
-                make the Time To Merge effectively equal to the Pull Request Lead Time). However, the metric \
+                all the changes into a single commit that is later used as base for the PR, since the first \
+                commit then tells nothing about when work started. Squashed PRs (see `--merge-style`) take \
+                their Pull Request Lead Time as their Time To Merge instead, flagged as such. However, the metric \
                 still remains incredibly useful for merge PRs (for example, merge develop into master): \
                 said PRs would have a very short Pull Request Lead Time (they don't get thorough re-reviews), \
                 but measuring against the first commit's date (Time to Merge) will tell how long it takes \
                 for features to get accumulated into a milestone worthy enough of merging into one of \
                 the 'big' branches.",
+            ScoreType::UnresolvedThreads(_) =>
+                "The amount of review threads (conversations started on a portion of a PR's diff) that \
+                were never marked as resolved. A dangling thread is feedback that may have been silently \
+                ignored rather than addressed - or a discussion that was simply abandoned. It takes an \
+                extra request per PR to retrieve them, so it is only measured on demand; PRs whose threads \
+                could not be retrieved count as having none.",
+        }
+    }
+
+    /// Returns the name of the CSV column under which a particular [`ScoreType`]'s value is exported.
+    pub fn get_csv_column(&self) -> &'static str {
+        match &self {
+            ScoreType::AfterHoursActivityRatio(_) => "after_hours_activity_ratio",
+            ScoreType::AmountOfParticipants(_) => "amount_of_participants",
+            ScoreType::AmountOfReviewers(_) => "amount_of_reviewers",
+            ScoreType::Attachments(_) => "attachments",
+            ScoreType::AuthorCommentaryToChangesRatio(_) => "author_commentary_to_changes_ratio",
+            ScoreType::AuthorResponseLatency(_) => "author_response_latency",
+            ScoreType::AuthorshipMismatch(_) => "authorship_mismatch",
+            ScoreType::BinaryFilesChanged(_) => "binary_files_changed",
+            ScoreType::BusinessDaysLeadTime(_) => "business_days_lead_time",
+            ScoreType::ChecklistCompletion(_) => "checklist_completion",
+            ScoreType::CommitMessageQuality(_) => "commit_message_quality",
+            ScoreType::ContentiousDiscussion(_) => "contentious_discussion",
+            ScoreType::DeletionRatio(_) => "deletion_ratio",
+            ScoreType::DescriptionLength(_) => "description_length",
+            ScoreType::FilesChanged(_) => "files_changed",
+            ScoreType::FirstResponseWait(_) => "first_response_wait",
+            ScoreType::GreenMergeRatio(_) => "green_merge_ratio",
+            ScoreType::LinkedIssues(_) => "linked_issues",
+            ScoreType::MissingDescription(_) => "missing_description",
+            ScoreType::NumberOfCommits(_) => "number_of_commits",
+            ScoreType::PullRequestsDiscussionSize(_) => "pull_requests_discussion_size",
+            ScoreType::PullRequestFlowRatio(_) => "pull_request_flow_ratio",
+            ScoreType::PullRequestLeadTime(_) => "pull_request_lead_time",
+            ScoreType::PullRequestSize(_) => "pull_request_size",
+            ScoreType::ReactionCount(_) => "reaction_count",
+            ScoreType::RejectionRate(_) => "rejection_rate",
+            ScoreType::ReviewIterations(_) => "review_iterations",
+            ScoreType::ReviewParticipationBreadth(_) => "review_participation_breadth",
+            ScoreType::ReviewedFileCoverage(_) => "reviewed_file_coverage",
+            ScoreType::ReviewerDiversity(_) => "reviewer_diversity",
+            ScoreType::SelfMergedPrs(_) => "self_merged_prs",
+            ScoreType::SignedCommitRatio(_) => "signed_commit_ratio",
+            ScoreType::TestToCodeRatio(_) => "test_to_code_ratio",
+            ScoreType::TimeInDraft(_) => "time_in_draft",
+            ScoreType::TimeToApproval(_) => "time_to_approval",
+            ScoreType::TimeToFirstReview(_) => "time_to_first_review",
+            ScoreType::TimeToMerge(_) => "time_to_merge",
+            ScoreType::UnresolvedThreads(_) => "unresolved_threads",
+        }
+    }
+
+    /// Returns the unit a particular [`ScoreType`]'s value is measured in; as reported alongside it in
+    /// JSON output.
+    pub fn get_unit(&self) -> &'static str {
+        match &self {
+            ScoreType::BusinessDaysLeadTime(_)
+            | ScoreType::PullRequestLeadTime(_)
+            | ScoreType::TimeToMerge(_) => "days",
+            ScoreType::AuthorResponseLatency(_)
+            | ScoreType::FirstResponseWait(_)
+            | ScoreType::TimeInDraft(_)
+            | ScoreType::TimeToApproval(_)
+            | ScoreType::TimeToFirstReview(_) => "hours",
+            ScoreType::DescriptionLength(_) => "characters",
+            ScoreType::PullRequestSize(_) => "lines",
+            ScoreType::AfterHoursActivityRatio(_)
+            | ScoreType::AuthorCommentaryToChangesRatio(_)
+            | ScoreType::ChecklistCompletion(_)
+            | ScoreType::CommitMessageQuality(_)
+            | ScoreType::DeletionRatio(_)
+            | ScoreType::GreenMergeRatio(_)
+            | ScoreType::MissingDescription(_)
+            | ScoreType::PullRequestFlowRatio(_)
+            | ScoreType::RejectionRate(_)
+            | ScoreType::ReviewParticipationBreadth(_)
+            | ScoreType::ReviewedFileCoverage(_)
+            | ScoreType::SignedCommitRatio(_)
+            | ScoreType::TestToCodeRatio(_) => "ratio",
+            ScoreType::AmountOfParticipants(_)
+            | ScoreType::AmountOfReviewers(_)
+            | ScoreType::Attachments(_)
+            | ScoreType::AuthorshipMismatch(_)
+            | ScoreType::BinaryFilesChanged(_)
+            | ScoreType::ContentiousDiscussion(_)
+            | ScoreType::FilesChanged(_)
+            | ScoreType::LinkedIssues(_)
+            | ScoreType::NumberOfCommits(_)
+            | ScoreType::PullRequestsDiscussionSize(_)
+            | ScoreType::ReactionCount(_)
+            | ScoreType::ReviewIterations(_)
+            | ScoreType::ReviewerDiversity(_)
+            | ScoreType::SelfMergedPrs(_)
+            | ScoreType::UnresolvedThreads(_) => "count",
+        }
+    }
+
+    /// Returns a particular [`ScoreType`]'s value, regardless of its underlying numeric type.
+    pub fn get_value(&self) -> f64 {
+        match &self {
+            ScoreType::AmountOfParticipants(value)
+            | ScoreType::AmountOfReviewers(value)
+            | ScoreType::Attachments(value)
+            | ScoreType::AuthorResponseLatency(value)
+            | ScoreType::AuthorshipMismatch(value)
+            | ScoreType::BusinessDaysLeadTime(value)
+            | ScoreType::ContentiousDiscussion(value)
+            | ScoreType::FirstResponseWait(value)
+            | ScoreType::LinkedIssues(value)
+            | ScoreType::NumberOfCommits(value)
+            | ScoreType::PullRequestLeadTime(value)
+            | ScoreType::ReactionCount(value)
+            | ScoreType::ReviewIterations(value)
+            | ScoreType::ReviewerDiversity(value)
+            | ScoreType::SelfMergedPrs(value)
+            | ScoreType::TimeInDraft(value)
+            | ScoreType::TimeToApproval(value)
+            | ScoreType::TimeToFirstReview(value)
+            | ScoreType::TimeToMerge(value)
+            | ScoreType::UnresolvedThreads(value) => *value as f64,
+            ScoreType::AfterHoursActivityRatio(value)
+            | ScoreType::AuthorCommentaryToChangesRatio(value)
+            | ScoreType::ChecklistCompletion(value)
+            | ScoreType::CommitMessageQuality(value)
+            | ScoreType::DeletionRatio(value)
+            | ScoreType::GreenMergeRatio(value)
+            | ScoreType::MissingDescription(value)
+            | ScoreType::PullRequestFlowRatio(value)
+            | ScoreType::RejectionRate(value)
+            | ScoreType::ReviewParticipationBreadth(value)
+            | ScoreType::ReviewedFileCoverage(value)
+            | ScoreType::SignedCommitRatio(value)
+            | ScoreType::TestToCodeRatio(value) => *value,
+            ScoreType::BinaryFilesChanged(value)
+            | ScoreType::DescriptionLength(value)
+            | ScoreType::FilesChanged(value)
+            | ScoreType::PullRequestsDiscussionSize(value)
+            | ScoreType::PullRequestSize(value) => *value as f64,
+        }
+    }
+
+    /// Returns a particular [`ScoreType`]'s value, formatted as a CSV cell.
+    fn get_csv_value(&self) -> String {
+        match &self {
+            ScoreType::AmountOfParticipants(value)
+            | ScoreType::AmountOfReviewers(value)
+            | ScoreType::Attachments(value)
+            | ScoreType::AuthorResponseLatency(value)
+            | ScoreType::AuthorshipMismatch(value)
+            | ScoreType::BusinessDaysLeadTime(value)
+            | ScoreType::ContentiousDiscussion(value)
+            | ScoreType::FirstResponseWait(value)
+            | ScoreType::LinkedIssues(value)
+            | ScoreType::NumberOfCommits(value)
+            | ScoreType::PullRequestLeadTime(value)
+            | ScoreType::ReactionCount(value)
+            | ScoreType::ReviewIterations(value)
+            | ScoreType::ReviewerDiversity(value)
+            | ScoreType::SelfMergedPrs(value)
+            | ScoreType::TimeInDraft(value)
+            | ScoreType::TimeToApproval(value)
+            | ScoreType::TimeToFirstReview(value)
+            | ScoreType::TimeToMerge(value)
+            | ScoreType::UnresolvedThreads(value) => value.to_string(),
+            ScoreType::AfterHoursActivityRatio(value)
+            | ScoreType::AuthorCommentaryToChangesRatio(value)
+            | ScoreType::ChecklistCompletion(value)
+            | ScoreType::CommitMessageQuality(value)
+            | ScoreType::DeletionRatio(value)
+            | ScoreType::GreenMergeRatio(value)
+            | ScoreType::MissingDescription(value)
+            | ScoreType::PullRequestFlowRatio(value)
+            | ScoreType::RejectionRate(value)
+            | ScoreType::ReviewParticipationBreadth(value)
+            | ScoreType::ReviewedFileCoverage(value)
+            | ScoreType::SignedCommitRatio(value)
+            | ScoreType::TestToCodeRatio(value) => value.to_string(),
+            ScoreType::BinaryFilesChanged(value)
+            | ScoreType::DescriptionLength(value)
+            | ScoreType::FilesChanged(value)
+            | ScoreType::PullRequestsDiscussionSize(value)
+            | ScoreType::PullRequestSize(value) => value.to_string(),
         }
     }
 
+    /// Returns the first sentence of a particular [`ScoreType`]'s legend; a one-line summary of it.
+    pub fn get_summary(&self) -> &'static str {
+        let legend = self.get_legend();
+
+        legend.find(". ").map(|index| &legend[..=index]).unwrap_or(legend)
+    }
+
+    /// Returns a particular [`ScoreType`]'s legend, titled after its name.
+    pub fn get_titled_legend(&self) -> String {
+        let score_type_name: String = self.to_string();
+        let mut result = String::new();
+
+        result.push('\n');
+        result.push_str(&"-".repeat(score_type_name.len()));
+        result.push('\n');
+        result.push_str(&score_type_name);
+        result.push('\n');
+        result.push_str(&"-".repeat(score_type_name.len()));
+        result.push_str("\n\n");
+        result.push_str(self.get_legend());
+        result.push('\n');
+
+        result
+    }
+
     /// Returns a verbose explanation of all possible [`ScoreType`]s.
     pub fn get_legends() -> String {
-        let mut result = String::new();
+        ScoreType::get_iter().map(|score_type| score_type.get_titled_legend()).collect()
+    }
 
-        for score_type in ScoreType::get_iter() {
-            let score_type_name: String = score_type.to_string();
+    /// Finds the [`ScoreType`] named `name`, case-insensitively; either after itself (ie:
+    /// `PullRequestLeadTime`) or after its CSV column (ie: `pull_request_lead_time`). Unknown names are
+    /// rejected, suggesting the closest known one (if any is close enough to be a likely typo).
+    pub fn from_name(name: &str) -> Result<ScoreType, String> {
+        let normalize = |name: &str| name.trim().replace('_', "").to_lowercase();
+        let normalized_name = normalize(name);
 
-            result.push('\n');
-            result.push_str(&"-".repeat(score_type_name.len()));
-            result.push('\n');
-            result.push_str(&score_type_name);
-            result.push('\n');
-            result.push_str(&"-".repeat(score_type_name.len()));
-            result.push_str("\n\n");
-            result.push_str(score_type.get_legend());
-            result.push('\n');
+        if let Some(score_type) = ScoreType::get_iter()
+            .find(|score_type| normalize(&score_type.to_string()) == normalized_name)
+        {
+            return Ok(score_type);
         }
 
-        result
+        // typos are told apart from entirely different names by being at most a third of the name off
+        let closest = ScoreType::get_iter()
+            .map(|score_type| {
+                (edit_distance(&normalize(&score_type.to_string()), &normalized_name), score_type)
+            })
+            .min_by_key(|(distance, _)| *distance)
+            .filter(|(distance, _)| *distance <= normalized_name.len().max(3) / 3);
+
+        Err(match closest {
+            Some((_, score_type)) => {
+                format!("unknown metric [{}]; did you mean [{}]?", name, score_type)
+            }
+            None => format!(
+                "unknown metric [{}]; valid ones are [{}]",
+                name,
+                ScoreType::get_iter()
+                    .map(|score_type| score_type.to_string())
+                    .collect::<Vec<String>>()
+                    .join("], [")
+            ),
+        })
     }
 }
 
+/// A single [`ScoreType`], as (de)serialized in JSON; ie:
+/// `{ "metric": "pull_request_lead_time", "value": 3, "unit": "days" }`. Metrics are named after their
+/// [CSV column](ScoreType::get_csv_column), and integral values are kept integral.
+/// <br/><br/>
+/// The unversioned shape that preceded [`SCORE_SCHEMA_VERSION`] 2 (ie: `{ "PullRequestLeadTime": 3 }`) is
+/// still read, so that older reports can be read back.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "serde_json::Value")]
+pub struct MetricRecord {
+    pub metric: String,
+    pub value: serde_json::Value,
+    /// See [`ScoreType::get_unit`]. Informative only; it is not checked when reading a [`MetricRecord`].
+    #[serde(default)]
+    pub unit: String,
+}
+
+impl From<ScoreType> for MetricRecord {
+    fn from(score_type: ScoreType) -> Self {
+        let value = match &score_type {
+            ScoreType::AmountOfParticipants(value)
+            | ScoreType::AmountOfReviewers(value)
+            | ScoreType::Attachments(value)
+            | ScoreType::AuthorResponseLatency(value)
+            | ScoreType::AuthorshipMismatch(value)
+            | ScoreType::BusinessDaysLeadTime(value)
+            | ScoreType::ContentiousDiscussion(value)
+            | ScoreType::FirstResponseWait(value)
+            | ScoreType::LinkedIssues(value)
+            | ScoreType::NumberOfCommits(value)
+            | ScoreType::PullRequestLeadTime(value)
+            | ScoreType::ReactionCount(value)
+            | ScoreType::ReviewIterations(value)
+            | ScoreType::ReviewerDiversity(value)
+            | ScoreType::SelfMergedPrs(value)
+            | ScoreType::TimeInDraft(value)
+            | ScoreType::TimeToApproval(value)
+            | ScoreType::TimeToFirstReview(value)
+            | ScoreType::TimeToMerge(value)
+            | ScoreType::UnresolvedThreads(value) => serde_json::Value::from(*value),
+            ScoreType::AfterHoursActivityRatio(value)
+            | ScoreType::AuthorCommentaryToChangesRatio(value)
+            | ScoreType::ChecklistCompletion(value)
+            | ScoreType::CommitMessageQuality(value)
+            | ScoreType::DeletionRatio(value)
+            | ScoreType::GreenMergeRatio(value)
+            | ScoreType::MissingDescription(value)
+            | ScoreType::PullRequestFlowRatio(value)
+            | ScoreType::RejectionRate(value)
+            | ScoreType::ReviewParticipationBreadth(value)
+            | ScoreType::ReviewedFileCoverage(value)
+            | ScoreType::SignedCommitRatio(value)
+            | ScoreType::TestToCodeRatio(value) => serde_json::Value::from(*value),
+            ScoreType::BinaryFilesChanged(value)
+            | ScoreType::DescriptionLength(value)
+            | ScoreType::FilesChanged(value)
+            | ScoreType::PullRequestsDiscussionSize(value)
+            | ScoreType::PullRequestSize(value) => serde_json::Value::from(*value),
+        };
+
+        MetricRecord {
+            metric: score_type.get_csv_column().to_string(),
+            value,
+            unit: score_type.get_unit().to_string(),
+        }
+    }
+}
+
+impl TryFrom<serde_json::Value> for MetricRecord {
+    type Error = String;
+
+    fn try_from(record: serde_json::Value) -> Result<Self, Self::Error> {
+        let entries = record
+            .as_object()
+            .ok_or_else(|| format!("expected a single-metric object, but found {}", record))?;
+
+        match entries.get("metric") {
+            Some(metric) => Ok(MetricRecord {
+                metric: metric
+                    .as_str()
+                    .ok_or_else(|| format!("expected a metric name, but found {}", metric))?
+                    .to_string(),
+                value: entries
+                    .get("value")
+                    .cloned()
+                    .ok_or_else(|| format!("metric {} holds no value", metric))?,
+                unit: entries
+                    .get("unit")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+            }),
+            // stored by a version that predates SCORE_SCHEMA_VERSION 2, named after the ScoreType
+            None if entries.len() == 1 => {
+                let (name, value) = entries.iter().next().unwrap();
+
+                Ok(MetricRecord {
+                    metric: to_metric_name(name),
+                    value: value.clone(),
+                    unit: String::new(),
+                })
+            }
+            None => Err(format!("expected a single-metric object, but found {}", record)),
+        }
+    }
+}
+
+impl TryFrom<MetricRecord> for ScoreType {
+    type Error = String;
+
+    fn try_from(record: MetricRecord) -> Result<Self, Self::Error> {
+        let score_type = ScoreType::get_iter()
+            .find(|score_type| score_type.get_csv_column() == record.metric)
+            .ok_or_else(|| format!("unknown metric '{}'", record.metric))?;
+
+        let integral = || {
+            record.value.as_u64().ok_or_else(|| {
+                format!("metric '{}' does not hold a non-negative integer", record.metric)
+            })
+        };
+        let fractional = || {
+            record
+                .value
+                .as_f64()
+                .ok_or_else(|| format!("metric '{}' does not hold a number", record.metric))
+        };
+
+        Ok(match score_type {
+            ScoreType::AfterHoursActivityRatio(_) => {
+                ScoreType::AfterHoursActivityRatio(fractional()?)
+            }
+            ScoreType::AmountOfParticipants(_) => ScoreType::AmountOfParticipants(integral()?),
+            ScoreType::AmountOfReviewers(_) => ScoreType::AmountOfReviewers(integral()?),
+            ScoreType::Attachments(_) => ScoreType::Attachments(integral()?),
+            ScoreType::AuthorCommentaryToChangesRatio(_) => {
+                ScoreType::AuthorCommentaryToChangesRatio(fractional()?)
+            }
+            ScoreType::AuthorResponseLatency(_) => ScoreType::AuthorResponseLatency(integral()?),
+            ScoreType::AuthorshipMismatch(_) => ScoreType::AuthorshipMismatch(integral()?),
+            ScoreType::BinaryFilesChanged(_) => ScoreType::BinaryFilesChanged(integral()? as usize),
+            ScoreType::BusinessDaysLeadTime(_) => ScoreType::BusinessDaysLeadTime(integral()?),
+            ScoreType::ChecklistCompletion(_) => ScoreType::ChecklistCompletion(fractional()?),
+            ScoreType::CommitMessageQuality(_) => ScoreType::CommitMessageQuality(fractional()?),
+            ScoreType::ContentiousDiscussion(_) => ScoreType::ContentiousDiscussion(integral()?),
+            ScoreType::DeletionRatio(_) => ScoreType::DeletionRatio(fractional()?),
+            ScoreType::DescriptionLength(_) => ScoreType::DescriptionLength(integral()? as usize),
+            ScoreType::FilesChanged(_) => ScoreType::FilesChanged(integral()? as usize),
+            ScoreType::FirstResponseWait(_) => ScoreType::FirstResponseWait(integral()?),
+            ScoreType::GreenMergeRatio(_) => ScoreType::GreenMergeRatio(fractional()?),
+            ScoreType::LinkedIssues(_) => ScoreType::LinkedIssues(integral()?),
+            ScoreType::MissingDescription(_) => ScoreType::MissingDescription(fractional()?),
+            ScoreType::NumberOfCommits(_) => ScoreType::NumberOfCommits(integral()?),
+            ScoreType::PullRequestsDiscussionSize(_) => {
+                ScoreType::PullRequestsDiscussionSize(integral()? as usize)
+            }
+            ScoreType::PullRequestFlowRatio(_) => ScoreType::PullRequestFlowRatio(fractional()?),
+            ScoreType::PullRequestLeadTime(_) => ScoreType::PullRequestLeadTime(integral()?),
+            ScoreType::PullRequestSize(_) => ScoreType::PullRequestSize(integral()? as usize),
+            ScoreType::ReactionCount(_) => ScoreType::ReactionCount(integral()?),
+            ScoreType::RejectionRate(_) => ScoreType::RejectionRate(fractional()?),
+            ScoreType::ReviewIterations(_) => ScoreType::ReviewIterations(integral()?),
+            ScoreType::ReviewParticipationBreadth(_) => {
+                ScoreType::ReviewParticipationBreadth(fractional()?)
+            }
+            ScoreType::ReviewedFileCoverage(_) => ScoreType::ReviewedFileCoverage(fractional()?),
+            ScoreType::ReviewerDiversity(_) => ScoreType::ReviewerDiversity(integral()?),
+            ScoreType::SelfMergedPrs(_) => ScoreType::SelfMergedPrs(integral()?),
+            ScoreType::SignedCommitRatio(_) => ScoreType::SignedCommitRatio(fractional()?),
+            ScoreType::TestToCodeRatio(_) => ScoreType::TestToCodeRatio(fractional()?),
+            ScoreType::TimeInDraft(_) => ScoreType::TimeInDraft(integral()?),
+            ScoreType::TimeToApproval(_) => ScoreType::TimeToApproval(integral()?),
+            ScoreType::TimeToFirstReview(_) => ScoreType::TimeToFirstReview(integral()?),
+            ScoreType::TimeToMerge(_) => ScoreType::TimeToMerge(integral()?),
+            ScoreType::UnresolvedThreads(_) => ScoreType::UnresolvedThreads(integral()?),
+        })
+    }
+}
+
+/// The [`SCORE_SCHEMA_VERSION`] that unversioned [`Score`]s count as.
+fn unversioned_schema_version() -> u32 {
+    1
+}
+
+/// Reads a [`Score`]'s [`ScoreType`]s back; skipping (with a warning) those metrics this version does not
+/// know of, as [`SCORE_SCHEMA_VERSION`] requires. Known metrics holding the wrong kind of value still fail.
+fn deserialize_known_score_types<'de, D>(deserializer: D) -> Result<Vec<ScoreType>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let records: Vec<MetricRecord> = Vec::deserialize(deserializer)?;
+
+    records
+        .into_iter()
+        .filter(|record| {
+            let is_known = ScoreType::get_iter()
+                .any(|score_type| score_type.get_csv_column() == record.metric);
+
+            if !is_known {
+                warn!("Skipping unknown metric '{}'.", record.metric);
+            }
+
+            is_known
+        })
+        .map(|record| ScoreType::try_from(record).map_err(D::Error::custom))
+        .collect()
+}
+
 /// A collection of [`ScoreType`]s, the "end-product" of an analysis.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Score {
+    /// See [`SCORE_SCHEMA_VERSION`]. Missing from [`Score`]s stored before it was introduced.
+    #[serde(default = "unversioned_schema_version")]
+    schema_version: u32,
+    #[serde(deserialize_with = "deserialize_known_score_types")]
     score: Vec<ScoreType>,
+    /// The [`Score`]'s [`Grade`], if it was graded at all. See [`Score::graded`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    grade: Option<Grade>,
+    /// The bands the [`Score`] was graded within, so that its [`ScoreType`]s are told healthy or not by
+    /// the same standard when displayed. Not part of its output.
+    #[serde(skip)]
+    grading_config: GradingConfig,
+    /// Caveats on how some of the [`Score`]'s [`ScoreType`]s were measured, if any. See [`Score::flagged`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    flags: Vec<ScoreFlag>,
+    /// The population standard deviation of each [`ScoreType`] across the sample this [`Score`] was
+    /// aggregated from (keyed by metric name; see [`ScoreType::get_csv_column`]), if requested at all.
+    /// See [`Score::with_stddev`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    stddev: Option<BTreeMap<String, f64>>,
+    /// How the sample this [`Score`] was aggregated from came to be, if it was aggregated from one at all.
+    /// See [`Score::with_sample_stats`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sample_stats: Option<SampleStats>,
+    /// How each [`ScoreType`] changed against a previously stored [`Score`] (keyed by metric name), if
+    /// compared against one at all. See [`Score::with_delta`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    delta: Option<BTreeMap<String, MetricDelta>>,
 }
 
 impl Score {
     pub fn new(score: Vec<ScoreType>) -> Self {
         Score {
+            schema_version: SCORE_SCHEMA_VERSION,
             score,
+            grade: None,
+            grading_config: GradingConfig::default(),
+            flags: Vec::new(),
+            stddev: None,
+            sample_stats: None,
+            delta: None,
         }
     }
 
@@ -154,6 +824,346 @@ impl Score {
         self.score
     }
 
+    pub fn score_types(&self) -> &[ScoreType] {
+        &self.score
+    }
+
+    pub fn grade(&self) -> Option<&Grade> {
+        self.grade.as_ref()
+    }
+
+    pub fn sample_stats(&self) -> Option<SampleStats> {
+        self.sample_stats
+    }
+
+    /// Sums this [`Score`] up into a single [`Grade`], according to the given `weights` and within the
+    /// bands of the `grading_config`. Returns [`None`] if none of its [`ScoreType`]s carries any weight.
+    pub fn overall_grade(
+        &self, weights: &GradeWeights, grading_config: &GradingConfig,
+    ) -> Option<Grade> {
+        Grade::of(&self.score, weights, grading_config)
+    }
+
+    /// Attaches this [`Score`]'s [`overall_grade`](Score::overall_grade) to it, so that it gets reported
+    /// alongside the individual [`ScoreType`]s.
+    pub fn graded(mut self, weights: &GradeWeights, grading_config: &GradingConfig) -> Self {
+        self.grade = self.overall_grade(weights, grading_config);
+        self.grading_config = grading_config.clone();
+        self
+    }
+
+    /// Attaches the standard deviation of each of this [`Score`]'s [`ScoreType`]s across the sample it
+    /// was aggregated from; so that it gets reported alongside them.
+    pub fn with_stddev(mut self, stddev: BTreeMap<String, f64>) -> Self {
+        self.stddev = Some(stddev);
+        self
+    }
+
+    /// Attaches the [`SampleStats`] of the sample this [`Score`] was aggregated from; so that consumers
+    /// can judge how much confidence it deserves.
+    pub fn with_sample_stats(mut self, sample_stats: SampleStats) -> Self {
+        self.sample_stats = Some(sample_stats);
+        self
+    }
+
+    /// Attaches how each of this [`Score`]'s [`ScoreType`]s changed against a previously stored one (see
+    /// [`Baseline::diff`](crate::scoring::baseline::Baseline::diff)); so that it gets reported alongside them.
+    pub fn with_delta(mut self, delta: BTreeMap<String, MetricDelta>) -> Self {
+        self.delta = Some(delta);
+        self
+    }
+
+    /// Attaches a [`ScoreFlag`] to this [`Score`], so that it gets reported alongside the [`ScoreType`]s
+    /// it concerns. Flagging it twice over the same caveat makes no difference.
+    pub fn flagged(mut self, flag: ScoreFlag) -> Self {
+        if !self.flags.contains(&flag) {
+            self.flags.push(flag);
+        }
+        self
+    }
+
+    /// Attaches every one of the `flags` to this [`Score`]. See [`Score::flagged`].
+    pub fn flagged_with<'a>(self, flags: impl IntoIterator<Item = &'a ScoreFlag>) -> Self {
+        flags.into_iter().fold(self, |score, flag| score.flagged(*flag))
+    }
+
+    pub fn flags(&self) -> &[ScoreFlag] {
+        &self.flags
+    }
+
+    /// Returns the CSV header matching [`Score::to_csv`]'s rows: a leading `pr_number` column, followed
+    /// by one column per [`ScoreType`] (in [`ScoreType::get_iter`]'s order).
+    pub fn to_csv_header() -> String {
+        once("pr_number")
+            .chain(ScoreType::get_iter().map(|score_type| score_type.get_csv_column()))
+            .collect::<Vec<&str>>()
+            .join(",")
+    }
+
+    /// Returns this [`Score`] as a CSV row, led by `row_label` (ie: the PR number it belongs to) under
+    /// the `pr_number` column. [`ScoreType`]s absent from this [`Score`] are left as empty cells.
+    pub fn to_csv(&self, row_label: &str) -> String {
+        once(row_label.to_string())
+            .chain(ScoreType::get_iter().map(|column| {
+                self.score
+                    .iter()
+                    .find(|score_type| discriminant(*score_type) == discriminant(&column))
+                    .map(|score_type| score_type.get_csv_value())
+                    .unwrap_or_default()
+            }))
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+
+    /// Returns the given `labeled_scores` in Prometheus' text exposition format: one gauge per
+    /// [`ScoreType`] (named after its CSV column, prefixed by `prolice_`) described by its legend, holding
+    /// one sample per [`Score`] under its labels (ie: `[("owner", "x"), ("repo", "y")]`). [`ScoreType`]s
+    /// absent from every [`Score`] are left out altogether.
+    pub fn to_prometheus(labeled_scores: &[(Vec<(&str, &str)>, &Score)]) -> String {
+        let mut result = String::new();
+
+        for metric in ScoreType::get_iter() {
+            let samples: Vec<(&Vec<(&str, &str)>, &ScoreType)> = labeled_scores
+                .iter()
+                .filter_map(|(labels, score)| {
+                    score
+                        .score
+                        .iter()
+                        .find(|score_type| discriminant(*score_type) == discriminant(&metric))
+                        .map(|score_type| (labels, score_type))
+                })
+                .collect();
+
+            if samples.is_empty() {
+                continue;
+            }
+
+            let metric_name = format!("prolice_{}", metric.get_csv_column());
+
+            result.push_str(&format!(
+                "# HELP {} {}\n",
+                metric_name,
+                metric.get_legend().replace('\\', "\\\\").replace('\n', "\\n")
+            ));
+            result.push_str(&format!("# TYPE {} gauge\n", metric_name));
+
+            for (labels, score_type) in samples {
+                let labels = labels
+                    .iter()
+                    .map(|(name, value)| {
+                        format!(
+                            "{}=\"{}\"",
+                            name,
+                            value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+                        )
+                    })
+                    .collect::<Vec<String>>()
+                    .join(",");
+
+                result.push_str(&format!(
+                    "{}{{{}}} {}\n",
+                    metric_name,
+                    labels,
+                    score_type.get_csv_value()
+                ));
+            }
+        }
+
+        result
+    }
+
+    /// Returns this [`Score`] in a human-readable form, headed by `title` (ie: the repository it belongs
+    /// to): its grade and sample stats (if any), followed by one line per [`ScoreType`] holding its name,
+    /// its value and a summary of its legend. Each line is colored after how healthy its value is (see
+    /// [`sub_score`]); colors are left out wherever the terminal does not support them.
+    pub fn to_pretty(&self, title: &str) -> String {
+        let mut result = format!("{}\n", style(title).bold().underlined());
+
+        if let Some(grade) = &self.grade {
+            result.push_str(&format!("Grade: {} ({})\n", grade.letter, grade.value));
+        }
+
+        if let Some(sample_stats) = &self.sample_stats {
+            result.push_str(&format!("Sample: {}\n", sample_stats));
+        }
+
+        let name_width =
+            ScoreType::get_iter().map(|metric| metric.to_string().len()).max().unwrap_or(0);
+
+        for score_type in &self.score {
+            let name = score_type.to_string();
+            let value = self.get_display_value(score_type);
+
+            // padded before being styled, since escape codes would throw the padding off
+            let line = format!("{:<width$} {:>12}", name, value, width = name_width);
+            let health = sub_score(score_type, &self.grading_config);
+            let line = if health >= 0.8 {
+                style(line).green()
+            } else if health >= 0.5 {
+                style(line).yellow()
+            } else {
+                style(line).red()
+            };
+
+            result.push_str(&format!("\n  {}  {}", line, style(score_type.get_summary()).dim()));
+        }
+
+        let removed_metrics: Vec<&str> = self
+            .delta
+            .iter()
+            .flatten()
+            .filter(|(_, delta)| matches!(delta, MetricDelta::Removed { .. }))
+            .map(|(name, _)| name.as_str())
+            .collect();
+
+        if !removed_metrics.is_empty() {
+            result.push_str(&format!("\n\nMissing since baseline: {}", removed_metrics.join(", ")));
+        }
+
+        result
+    }
+
+    /// Returns the given `titled_scores` as a standalone HTML document: one section per [`Score`], headed
+    /// by its title (ie: the repository it belongs to) and holding the same details as
+    /// [`Score::to_pretty`] does, laid out in a table. Rows are classed (`healthy`, `fair` or `poor`)
+    /// after how healthy their value is (see [`sub_score`]).
+    /// <br/><br/>
+    /// The document closes with the full legend of every reported [`ScoreType`] and the moment it was
+    /// `generated_at`, so that it explains itself wherever it gets published.
+    pub fn to_html(titled_scores: &[(String, &Score)], generated_at: &DateTime<Local>) -> String {
+        let mut result = String::from(
+            "<!DOCTYPE html>\n\
+            <html lang=\"en\">\n\
+            <head>\n\
+            <meta charset=\"utf-8\">\n\
+            <title>PRolice report</title>\n\
+            <style>\n\
+            body { font-family: sans-serif; margin: 2em; }\n\
+            table { border-collapse: collapse; }\n\
+            th, td { padding: 0.3em 0.8em; border-bottom: 1px solid #ddd; text-align: left; }\n\
+            td.value { text-align: right; font-family: monospace; }\n\
+            dt { font-weight: bold; margin-top: 1em; }\n\
+            footer { margin-top: 2em; color: #57606a; }\n\
+            tr.healthy td.value { color: #1a7f37; }\n\
+            tr.fair td.value { color: #9a6700; }\n\
+            tr.poor td.value { color: #cf222e; }\n\
+            </style>\n\
+            </head>\n\
+            <body>\n",
+        );
+
+        for (title, score) in titled_scores {
+            result.push_str(&format!("<section>\n<h2>{}</h2>\n", escape_html(title)));
+
+            if let Some(grade) = &score.grade {
+                result.push_str(&format!("<p>Grade: {} ({})</p>\n", grade.letter, grade.value));
+            }
+
+            if let Some(sample_stats) = &score.sample_stats {
+                result.push_str(&format!("<p>Sample: {}</p>\n", sample_stats));
+            }
+
+            result.push_str(
+                "<table>\n<thead><tr><th>Metric</th><th>Value</th><th>Meaning</th></tr></thead>\n<tbody>\n",
+            );
+
+            for score_type in &score.score {
+                let health = sub_score(score_type, &score.grading_config);
+                let class = if health >= 0.8 {
+                    "healthy"
+                } else if health >= 0.5 {
+                    "fair"
+                } else {
+                    "poor"
+                };
+
+                result.push_str(&format!(
+                    "<tr class=\"{}\"><td>{}</td><td class=\"value\">{}</td><td>{}</td></tr>\n",
+                    class,
+                    score_type,
+                    escape_html(&score.get_display_value(score_type)),
+                    escape_html(score_type.get_summary())
+                ));
+            }
+
+            result.push_str("</tbody>\n</table>\n</section>\n");
+        }
+
+        let reported_metrics: Vec<ScoreType> = ScoreType::get_iter()
+            .filter(|metric| {
+                titled_scores.iter().any(|(_, score)| {
+                    score
+                        .score
+                        .iter()
+                        .any(|score_type| discriminant(score_type) == discriminant(metric))
+                })
+            })
+            .collect();
+
+        if !reported_metrics.is_empty() {
+            result.push_str("<section>\n<h2>Legends</h2>\n<dl>\n");
+
+            for metric in reported_metrics {
+                result.push_str(&format!("<dt>{}</dt>\n<dd>", metric));
+                for paragraph in metric.get_legend().split("\n\n") {
+                    result.push_str(&format!("<p>{}</p>", escape_html(paragraph)));
+                }
+                result.push_str("</dd>\n");
+            }
+
+            result.push_str("</dl>\n</section>\n");
+        }
+
+        result.push_str(&format!(
+            "<footer>Generated by {} {} on <time datetime=\"{}\">{}</time>.</footer>\n</body>\n</html>\n",
+            metadata::package_name(),
+            metadata::full_version(),
+            generated_at.to_rfc3339(),
+            generated_at.format("%Y-%m-%d %H:%M:%S %:z")
+        ));
+        result
+    }
+
+    /// Returns a [`ScoreType`]'s value as displayed to humans: whole numbers as such, and any other one
+    /// with two decimals; followed by its standard deviation, if this [`Score`] carries it.
+    fn get_display_value(&self, score_type: &ScoreType) -> String {
+        let value = score_type.get_value();
+        let mut display_value = if value.fract() == 0.0 {
+            value.to_string()
+        } else {
+            format!("{:.2}", value)
+        };
+
+        if let Some(stddev) =
+            self.stddev.as_ref().and_then(|stddev| stddev.get(score_type.get_csv_column()))
+        {
+            display_value.push_str(&format!(" ±{:.2}", stddev));
+        }
+
+        match self.delta.as_ref().and_then(|delta| delta.get(score_type.get_csv_column())) {
+            Some(MetricDelta::Changed {
+                delta,
+                percent_change: Some(percent_change),
+                ..
+            }) => display_value.push_str(&format!(" ({:+} / {:+.2}%)", delta, percent_change)),
+            Some(MetricDelta::Changed {
+                delta,
+                percent_change: None,
+                ..
+            }) => display_value.push_str(&format!(" ({:+})", delta)),
+            Some(MetricDelta::Added {
+                ..
+            }) => display_value.push_str(" (new)"),
+            _ => {}
+        }
+
+        for flag in self.flags.iter().filter(|flag| flag.concerns(score_type)) {
+            display_value.push_str(&format!(" ({})", flag.get_label()));
+        }
+
+        display_value
+    }
+
     pub fn to_json(&self) -> String {
         serde_json::to_string_pretty(&self).unwrap_or_else(|e| {
             error!("Could not construct JSON for Score [{:#?}].", &self);
@@ -167,3 +1177,627 @@ impl Display for Score {
         write!(f, "{}", &self.to_json())
     }
 }
+
+/// A caveat on how some of a [`Score`]'s [`ScoreType`]s were measured; which consumers may want to
+/// take into account before comparing them against other [`Score`]s'.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreFlag {
+    /// The PR's commits were squashed (see
+    /// [`MergeStyle`](crate::scoring::scoring_settings::MergeStyle)), so its first commit tells nothing
+    /// about when work on it started; its `TimeToMerge` is its lead time instead.
+    SquashedTimeToMerge,
+}
+
+impl ScoreFlag {
+    /// Returns whether this [`ScoreFlag`] concerns a particular [`ScoreType`].
+    pub fn concerns(&self, score_type: &ScoreType) -> bool {
+        match self {
+            ScoreFlag::SquashedTimeToMerge => matches!(score_type, ScoreType::TimeToMerge(_)),
+        }
+    }
+
+    /// Returns a short label for this [`ScoreFlag`], as shown next to the values it concerns.
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            ScoreFlag::SquashedTimeToMerge => "squashed",
+        }
+    }
+}
+
+/// A single [`Score`], labeled by where it comes from; as printed - one per line - in JSON-lines output.
+/// Aggregates are told apart by their lack of `pr_number` (and an organization's, by its lack of
+/// `repository` as well).
+#[derive(Debug, Serialize)]
+pub struct ScoreRecord<'a> {
+    pub owner: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pr_number: Option<u64>,
+    #[serde(flatten)]
+    pub score: &'a Score,
+}
+
+impl ScoreRecord<'_> {
+    /// Returns this [`ScoreRecord`] as compact JSON, guaranteed to fit in a single line.
+    pub fn to_json_line(&self) -> String {
+        serde_json::to_string(&self).unwrap_or_else(|e| {
+            error!("Could not construct JSON for ScoreRecord [{:#?}].", &self);
+            panic!("{}", e);
+        })
+    }
+}
+
+/// Statistics about how a repository's sample came to be. PRs that were fetched but then filtered out
+/// (for being merge-PRs, or for their size), as well as those that could not be fetched at all, do not take part in its
+/// [`Score`]; so a sample that shrunk considerably along the way deserves less confidence than its
+/// aggregates would suggest.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SampleStats {
+    /// The amount of PRs that were requested as sample (ie: `--sample-size`), as is; regardless of how many
+    /// of them GitHub actually listed.
+    pub requested: u64,
+    /// The amount of PRs that GitHub actually listed as sample; fewer than `requested` if the repository
+    /// does not hold that many PRs matching the selection (or if GitHub cut the listing short).
+    #[serde(default)]
+    pub actual_sample_size: u64,
+    /// The amount of PRs whose data was successfully fetched.
+    pub fetched: u64,
+    /// The amount of fetched PRs that were left out for being merge-PRs.
+    pub filtered_as_merge: u64,
+    /// The amount of fetched PRs that were left out for having too few (or too many) changes.
+    #[serde(default)]
+    pub filtered_by_size: u64,
+    /// The amount of PRs whose data could not be fetched.
+    pub errored: u64,
+    /// The amount of listed PRs that were never fetched, because the analysis got interrupted before
+    /// their turn came; see [`SampleStats::was_interrupted`].
+    #[serde(default)]
+    pub skipped_on_interruption: u64,
+}
+
+impl Display for SampleStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "{} out of {} requested PRs listed, {} fetched ({} left out as merge-PRs, {} for their size, {} errored)",
+            self.actual_sample_size,
+            self.requested,
+            self.fetched,
+            self.filtered_as_merge,
+            self.filtered_by_size,
+            self.errored
+        )?;
+
+        if self.was_interrupted() {
+            write!(f, "; INTERRUPTED, with {} PRs left unfetched", self.skipped_on_interruption)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl SampleStats {
+    /// Sums several [`SampleStats`] (ie: those of every repository of an organization) up into one.
+    pub fn sum<'a>(sample_stats: impl IntoIterator<Item = &'a SampleStats>) -> Self {
+        sample_stats.into_iter().fold(SampleStats::default(), |acc, sample_stats| SampleStats {
+            requested: acc.requested + sample_stats.requested,
+            actual_sample_size: acc.actual_sample_size + sample_stats.actual_sample_size,
+            fetched: acc.fetched + sample_stats.fetched,
+            filtered_as_merge: acc.filtered_as_merge + sample_stats.filtered_as_merge,
+            filtered_by_size: acc.filtered_by_size + sample_stats.filtered_by_size,
+            errored: acc.errored + sample_stats.errored,
+            skipped_on_interruption: acc.skipped_on_interruption
+                + sample_stats.skipped_on_interruption,
+        })
+    }
+
+    /// Returns whether the analysis got interrupted before the whole sample was fetched; in which case its
+    /// [`Score`] covers only those PRs that were fetched by then (or were being fetched at the time).
+    pub fn was_interrupted(&self) -> bool {
+        self.skipped_on_interruption > 0
+    }
+}
+
+/// Returns the (Levenshtein) edit distance between `a` and `b`; that is, the least amount of single-character
+/// insertions, deletions or substitutions it takes to turn one into the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous_row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current_row = vec![i + 1];
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous_row[j]
+                + if a_char == *b_char {
+                    0
+                } else {
+                    1
+                };
+            current_row.push(substitution.min(previous_row[j + 1] + 1).min(current_row[j] + 1));
+        }
+
+        previous_row = current_row;
+    }
+
+    previous_row[b.len()]
+}
+
+/// Escapes the characters of `text` that carry a meaning in HTML.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// A collection of [`Score`]s, one per analyzed repository (keyed by the repository's name).
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MultiRepoScore {
+    scores: BTreeMap<String, Score>,
+}
+
+impl MultiRepoScore {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self).unwrap_or_else(|e| {
+            error!("Could not construct JSON for MultiRepoScore [{:#?}].", &self);
+            panic!("{}", e);
+        })
+    }
+}
+
+impl FromIterator<(String, Score)> for MultiRepoScore {
+    fn from_iter<T: IntoIterator<Item = (String, Score)>>(iter: T) -> Self {
+        MultiRepoScore {
+            scores: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Display for MultiRepoScore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", &self.to_json())
+    }
+}
+
+/// A whole organization's [`Score`]: an `aggregate` over every analyzed PR, regardless of the repository
+/// it belongs to, alongside the per-repository breakdown.
+#[derive(Debug, Serialize)]
+pub struct OrganizationScore {
+    aggregate: Score,
+    repositories: MultiRepoScore,
+}
+
+impl OrganizationScore {
+    pub fn new(aggregate: Score, repositories: MultiRepoScore) -> Self {
+        OrganizationScore {
+            aggregate,
+            repositories,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self).unwrap_or_else(|e| {
+            error!("Could not construct JSON for OrganizationScore [{:#?}].", &self);
+            panic!("{}", e);
+        })
+    }
+}
+
+impl Display for OrganizationScore {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{}", &self.to_json())
+    }
+}
+
+#[cfg(test)]
+mod score_tests {
+    use std::collections::HashMap;
+    use std::iter::once;
+
+    use chrono::{Local, TimeZone};
+
+    use crate::scoring::grade::{GradeWeights, GradingConfig};
+    use crate::scoring::score::{
+        edit_distance, MultiRepoScore, OrganizationScore, SampleStats, Score, ScoreFlag,
+        ScoreRecord, ScoreType,
+    };
+
+    /// The JSON of a bare [`Score`] holding nothing but a [`ScoreType::TimeToMerge`].
+    fn time_to_merge_score_json(value: u64) -> serde_json::Value {
+        serde_json::json!({
+            "schema_version": 2,
+            "score": [{ "metric": "time_to_merge", "value": value, "unit": "days" }]
+        })
+    }
+
+    #[test]
+    fn every_score_type_is_serialized_as_a_flat_metric_record() {
+        let score_types: Vec<ScoreType> = ScoreType::get_iter().collect();
+        let json = serde_json::to_value(&score_types).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!([
+                { "metric": "after_hours_activity_ratio", "value": 0.0, "unit": "ratio" },
+                { "metric": "amount_of_participants", "value": 0, "unit": "count" },
+                { "metric": "amount_of_reviewers", "value": 0, "unit": "count" },
+                { "metric": "attachments", "value": 0, "unit": "count" },
+                { "metric": "author_commentary_to_changes_ratio", "value": 0.0, "unit": "ratio" },
+                { "metric": "author_response_latency", "value": 0, "unit": "hours" },
+                { "metric": "authorship_mismatch", "value": 0, "unit": "count" },
+                { "metric": "binary_files_changed", "value": 0, "unit": "count" },
+                { "metric": "business_days_lead_time", "value": 0, "unit": "days" },
+                { "metric": "checklist_completion", "value": 0.0, "unit": "ratio" },
+                { "metric": "commit_message_quality", "value": 0.0, "unit": "ratio" },
+                { "metric": "contentious_discussion", "value": 0, "unit": "count" },
+                { "metric": "deletion_ratio", "value": 0.0, "unit": "ratio" },
+                { "metric": "description_length", "value": 0, "unit": "characters" },
+                { "metric": "files_changed", "value": 0, "unit": "count" },
+                { "metric": "first_response_wait", "value": 0, "unit": "hours" },
+                { "metric": "green_merge_ratio", "value": 0.0, "unit": "ratio" },
+                { "metric": "linked_issues", "value": 0, "unit": "count" },
+                { "metric": "missing_description", "value": 0.0, "unit": "ratio" },
+                { "metric": "number_of_commits", "value": 0, "unit": "count" },
+                { "metric": "pull_requests_discussion_size", "value": 0, "unit": "count" },
+                { "metric": "pull_request_flow_ratio", "value": 0.0, "unit": "ratio" },
+                { "metric": "pull_request_lead_time", "value": 0, "unit": "days" },
+                { "metric": "pull_request_size", "value": 0, "unit": "lines" },
+                { "metric": "reaction_count", "value": 0, "unit": "count" },
+                { "metric": "rejection_rate", "value": 0.0, "unit": "ratio" },
+                { "metric": "review_iterations", "value": 0, "unit": "count" },
+                { "metric": "review_participation_breadth", "value": 0.0, "unit": "ratio" },
+                { "metric": "reviewed_file_coverage", "value": 0.0, "unit": "ratio" },
+                { "metric": "reviewer_diversity", "value": 0, "unit": "count" },
+                { "metric": "self_merged_prs", "value": 0, "unit": "count" },
+                { "metric": "signed_commit_ratio", "value": 0.0, "unit": "ratio" },
+                { "metric": "test_to_code_ratio", "value": 0.0, "unit": "ratio" },
+                { "metric": "time_in_draft", "value": 0, "unit": "hours" },
+                { "metric": "time_to_approval", "value": 0, "unit": "hours" },
+                { "metric": "time_to_first_review", "value": 0, "unit": "hours" },
+                { "metric": "time_to_merge", "value": 0, "unit": "days" },
+                { "metric": "unresolved_threads", "value": 0, "unit": "count" },
+            ])
+        );
+        assert_eq!(serde_json::from_value::<Vec<ScoreType>>(json).unwrap(), score_types);
+    }
+
+    #[test]
+    fn metric_records_are_read_back_by_name_and_value_only() {
+        let read = |record: serde_json::Value| serde_json::from_value::<ScoreType>(record);
+
+        assert_eq!(
+            read(serde_json::json!({ "metric": "time_to_merge", "value": 3 })).unwrap(),
+            ScoreType::TimeToMerge(3)
+        );
+        assert!(read(serde_json::json!({ "metric": "time_to_merge", "value": 0.5 })).is_err());
+        assert!(read(serde_json::json!({ "metric": "some_future_metric", "value": 1 })).is_err());
+        // as stored before SCORE_SCHEMA_VERSION 2
+        assert_eq!(
+            read(serde_json::json!({ "TimeToMerge": 3 })).unwrap(),
+            ScoreType::TimeToMerge(3)
+        );
+        assert!(read(serde_json::json!({ "TimeToMerge": 3, "unit": "days" })).is_err());
+    }
+
+    #[test]
+    fn score_types_are_found_by_either_name_and_typos_get_a_suggestion() {
+        assert_eq!(ScoreType::from_name("timetomerge"), Ok(ScoreType::TimeToMerge(0)));
+        assert_eq!(ScoreType::from_name("Time_To_Merge"), Ok(ScoreType::TimeToMerge(0)));
+        assert_eq!(
+            ScoreType::from_name("PullRequestLeadTime"),
+            Ok(ScoreType::PullRequestLeadTime(0))
+        );
+
+        assert_eq!(
+            ScoreType::from_name("time_to_mrege"),
+            Err(String::from("unknown metric [time_to_mrege]; did you mean [TimeToMerge]?"))
+        );
+        assert!(ScoreType::from_name("velocity")
+            .unwrap_err()
+            .contains("valid ones are [AfterHoursActivityRatio]"));
+    }
+
+    #[test]
+    fn edit_distance_counts_single_character_edits() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("same", "same"), 0);
+    }
+
+    #[test]
+    fn csv_header_lists_every_metric_by_name_after_the_pr_number() {
+        let metric_names: Vec<String> = ScoreType::get_iter()
+            .map(|score_type| {
+                serde_json::to_value(&score_type).unwrap()["metric"].as_str().unwrap().to_string()
+            })
+            .collect();
+
+        assert_eq!(
+            Score::to_csv_header(),
+            once(String::from("pr_number")).chain(metric_names).collect::<Vec<String>>().join(",")
+        );
+    }
+
+    #[test]
+    fn csv_row_places_values_under_their_columns_regardless_of_score_order() {
+        let score = Score::new(vec![
+            ScoreType::TimeToMerge(7),
+            ScoreType::TestToCodeRatio(0.5),
+            ScoreType::AmountOfParticipants(3),
+        ]);
+
+        let header = Score::to_csv_header();
+        let row = score.to_csv("42");
+        let cells: HashMap<&str, &str> = header.split(',').zip(row.split(',')).collect();
+
+        assert_eq!(row.split(',').count(), header.split(',').count());
+        assert_eq!(cells["pr_number"], "42");
+        assert_eq!(cells["time_to_merge"], "7");
+        assert_eq!(cells["test_to_code_ratio"], "0.5");
+        assert_eq!(cells["amount_of_participants"], "3");
+        // every other metric is left empty
+        assert_eq!(cells.values().filter(|cell| !cell.is_empty()).count(), 4);
+    }
+
+    #[test]
+    fn pretty_output_lists_every_metric_with_its_value_and_summary() {
+        console::set_colors_enabled(false);
+
+        let score = Score::new(vec![ScoreType::TimeToMerge(3), ScoreType::TestToCodeRatio(0.375)])
+            .graded(&GradeWeights::default(), &GradingConfig::default())
+            .with_stddev(vec![(String::from("time_to_merge"), 1.5)].into_iter().collect());
+
+        let pretty = score.to_pretty("acme/service-a");
+        let lines: Vec<&str> = pretty.lines().collect();
+
+        assert_eq!(lines[0], "acme/service-a");
+        assert!(lines[1].starts_with("Grade: "));
+        assert!(lines.iter().any(|line| line.contains("TimeToMerge") && line.contains("3 ±1.50")));
+        assert!(lines.iter().any(|line| line.contains("TestToCodeRatio")
+            && line.contains("0.38")
+            && line.ends_with(ScoreType::TestToCodeRatio(0.0).get_summary())));
+        assert!(!pretty.contains("PullRequestSize"));
+    }
+
+    #[test]
+    fn html_output_holds_one_escaped_section_per_score() {
+        let service_a =
+            Score::new(vec![ScoreType::TimeToMerge(3), ScoreType::TestToCodeRatio(0.1)])
+                .graded(&GradeWeights::default(), &GradingConfig::default());
+        let service_b = Score::new(vec![ScoreType::TimeToMerge(40)]);
+
+        let generated_at = Local.ymd(2021, 3, 1).and_hms(9, 30, 0);
+        let html = Score::to_html(
+            &[
+                (String::from("acme/service-a"), &service_a),
+                (String::from("acme/<service-b>"), &service_b),
+            ],
+            &generated_at,
+        );
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.trim_end().ends_with("</html>"));
+        // one per score, plus the legends'
+        assert_eq!(html.matches("<section>").count(), 3);
+        assert!(html.contains("<h2>acme/service-a</h2>\n<p>Grade: "));
+        assert!(html.contains("<h2>acme/&lt;service-b&gt;</h2>"));
+        assert!(
+            html.contains("<tr class=\"healthy\"><td>TimeToMerge</td><td class=\"value\">3</td>")
+        );
+        assert!(html
+            .contains("<tr class=\"poor\"><td>TestToCodeRatio</td><td class=\"value\">0.10</td>"));
+        assert!(html.contains("<tr class=\"poor\"><td>TimeToMerge</td><td class=\"value\">40</td>"));
+        // only reported metrics get their legend
+        assert!(html.contains("<dt>TimeToMerge</dt>"));
+        assert!(html.contains("<dt>TestToCodeRatio</dt>"));
+        assert!(!html.contains("<dt>PullRequestSize</dt>"));
+        assert!(html.contains(&format!("<time datetime=\"{}\">", generated_at.to_rfc3339())));
+    }
+
+    #[test]
+    fn prometheus_exposition_groups_samples_by_metric_and_skips_absent_ones() {
+        let service_a =
+            Score::new(vec![ScoreType::TimeToMerge(3), ScoreType::TestToCodeRatio(0.5)]);
+        let service_b = Score::new(vec![ScoreType::TimeToMerge(1)]);
+
+        let exposition = Score::to_prometheus(&[
+            (vec![("owner", "acme"), ("repo", "service-a")], &service_a),
+            (vec![("owner", "acme"), ("repo", "service-\"b\"")], &service_b),
+        ]);
+
+        assert!(exposition.contains(
+            "# TYPE prolice_test_to_code_ratio gauge\n\
+            prolice_test_to_code_ratio{owner=\"acme\",repo=\"service-a\"} 0.5\n"
+        ));
+        assert!(exposition.contains(
+            "# TYPE prolice_time_to_merge gauge\n\
+            prolice_time_to_merge{owner=\"acme\",repo=\"service-a\"} 3\n\
+            prolice_time_to_merge{owner=\"acme\",repo=\"service-\\\"b\\\"\"} 1\n"
+        ));
+        assert!(!exposition.contains("prolice_pull_request_size"));
+        assert_eq!(exposition.matches("# HELP ").count(), 2);
+        assert!(exposition.lines().all(|line| !line.is_empty()));
+    }
+
+    #[test]
+    fn multi_repo_score_is_keyed_by_repository() {
+        let multi_repo_score: MultiRepoScore = vec![
+            (String::from("service-b"), Score::new(vec![ScoreType::TimeToMerge(2)])),
+            (String::from("service-a"), Score::new(vec![ScoreType::TimeToMerge(1)])),
+        ]
+        .into_iter()
+        .collect();
+
+        let json: serde_json::Value = serde_json::from_str(&multi_repo_score.to_json()).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "service-a": time_to_merge_score_json(1),
+                "service-b": time_to_merge_score_json(2)
+            })
+        );
+    }
+
+    #[test]
+    fn organization_score_holds_aggregate_alongside_repositories() {
+        let organization_score = OrganizationScore::new(
+            Score::new(vec![ScoreType::TimeToMerge(3)]),
+            vec![(String::from("service-a"), Score::new(vec![ScoreType::TimeToMerge(1)]))]
+                .into_iter()
+                .collect(),
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&organization_score.to_json()).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "aggregate": time_to_merge_score_json(3),
+                "repositories": {
+                    "service-a": time_to_merge_score_json(1)
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn sample_stats_are_serialized_only_when_attached_and_add_up() {
+        let first_sample_stats = SampleStats {
+            requested: 100,
+            actual_sample_size: 100,
+            fetched: 70,
+            filtered_as_merge: 5,
+            filtered_by_size: 2,
+            errored: 30,
+            skipped_on_interruption: 0,
+        };
+        // GitHub listed fewer PRs than requested
+        let second_sample_stats = SampleStats {
+            requested: 10,
+            actual_sample_size: 8,
+            fetched: 8,
+            filtered_as_merge: 0,
+            filtered_by_size: 1,
+            errored: 0,
+            skipped_on_interruption: 0,
+        };
+
+        let sample_stats = SampleStats::sum(vec![&first_sample_stats, &second_sample_stats]);
+        assert_eq!(
+            sample_stats.to_string(),
+            "108 out of 110 requested PRs listed, 78 fetched (5 left out as merge-PRs, 3 for their size, 30 errored)"
+        );
+
+        let score = Score::new(vec![ScoreType::TimeToMerge(2)]).with_sample_stats(sample_stats);
+        let json: serde_json::Value = serde_json::from_str(&score.to_json()).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "schema_version": 2,
+                "score": [{ "metric": "time_to_merge", "value": 2, "unit": "days" }],
+                "sample_stats": {
+                    "requested": 110,
+                    "actual_sample_size": 108,
+                    "fetched": 78,
+                    "filtered_as_merge": 5,
+                    "filtered_by_size": 3,
+                    "errored": 30,
+                    "skipped_on_interruption": 0
+                }
+            })
+        );
+
+        let bare_json: serde_json::Value =
+            serde_json::from_str(&Score::new(vec![ScoreType::TimeToMerge(2)]).to_json()).unwrap();
+        assert_eq!(bare_json, time_to_merge_score_json(2));
+    }
+
+    #[test]
+    fn interrupted_samples_say_how_many_prs_were_left_unfetched() {
+        let complete_sample_stats = SampleStats {
+            requested: 10,
+            actual_sample_size: 10,
+            fetched: 10,
+            ..Default::default()
+        };
+        // the analysis got interrupted with 6 PRs fetched; the rest never started being fetched
+        let interrupted_sample_stats = SampleStats {
+            requested: 10,
+            actual_sample_size: 10,
+            fetched: 6,
+            skipped_on_interruption: 4,
+            ..Default::default()
+        };
+
+        assert!(!complete_sample_stats.was_interrupted());
+        assert!(interrupted_sample_stats.was_interrupted());
+        assert_eq!(
+            SampleStats::sum(vec![&complete_sample_stats, &interrupted_sample_stats]).to_string(),
+            "20 out of 20 requested PRs listed, 16 fetched (0 left out as merge-PRs, 0 for their size, 0 errored); INTERRUPTED, with 4 PRs left unfetched"
+        );
+    }
+
+    #[test]
+    fn flags_are_reported_only_when_raised_and_next_to_the_metrics_they_concern() {
+        let score = Score::new(vec![ScoreType::TimeToMerge(3), ScoreType::PullRequestLeadTime(3)]);
+        assert!(serde_json::to_value(&score).unwrap().get("flags").is_none());
+
+        let flagged_score = score.flagged(ScoreFlag::SquashedTimeToMerge);
+        assert_eq!(
+            serde_json::to_value(&flagged_score).unwrap()["flags"],
+            serde_json::json!(["squashed_time_to_merge"])
+        );
+
+        let pretty = flagged_score.to_pretty("acme/service-a");
+        let line_of = |name: &str| pretty.lines().find(|line| line.contains(name)).unwrap();
+        assert!(line_of("TimeToMerge").contains("3 (squashed)"));
+        assert!(!line_of("PullRequestLeadTime").contains("(squashed)"));
+    }
+
+    #[test]
+    fn score_records_are_flattened_into_a_single_line() {
+        let score = Score::new(vec![ScoreType::TimeToMerge(2)]);
+
+        let pr_record = ScoreRecord {
+            owner: "acme",
+            repository: Some("service-a"),
+            pr_number: Some(42),
+            score: &score,
+        }
+        .to_json_line();
+        let org_record = ScoreRecord {
+            owner: "acme",
+            repository: None,
+            pr_number: None,
+            score: &score,
+        }
+        .to_json_line();
+
+        assert!(!pr_record.contains('\n'));
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&pr_record).unwrap(),
+            serde_json::json!({
+                "owner": "acme",
+                "repository": "service-a",
+                "pr_number": 42,
+                "schema_version": 2,
+                "score": [{ "metric": "time_to_merge", "value": 2, "unit": "days" }]
+            })
+        );
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&org_record).unwrap(),
+            serde_json::json!({
+                "owner": "acme",
+                "schema_version": 2,
+                "score": [{ "metric": "time_to_merge", "value": 2, "unit": "days" }]
+            })
+        );
+    }
+}
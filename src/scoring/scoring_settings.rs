@@ -0,0 +1,792 @@
+//! Settings that alter how a [`PullRequestData`](crate::github::utils::pull_request_data::PullRequestData)
+//! gets interpreted while being scored.
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, Timelike, Utc, Weekday};
+use chrono_tz::Tz;
+use glob::Pattern;
+use lazy_static::lazy_static;
+use regex::Regex;
+use strum_macros::{Display, EnumString, EnumVariantNames};
+
+use crate::scoring::grade::{GradeWeights, GradingConfig};
+
+/// Paths of generated, lock and vendored files; whose changes dwarf those that were actually written by hand.
+pub const DEFAULT_EXCLUDED_PATHS: &[&str] = &[
+    "Cargo.lock",
+    "Gemfile.lock",
+    "composer.lock",
+    "go.sum",
+    "package-lock.json",
+    "poetry.lock",
+    "pnpm-lock.yaml",
+    "yarn.lock",
+    "*.min.css",
+    "*.min.js",
+    "**/node_modules/**",
+    "**/vendor/**",
+];
+
+/// User-tunable settings consumed while producing a [`Score`](crate::scoring::score::Score).
+#[derive(Debug, Clone)]
+pub struct ScoringSettings {
+    /// Pattern that identifies test files by their path. When absent, the default (naive) heuristic
+    /// is used instead. See [`ScoringSettings::is_test_file`].
+    pub test_file_pattern: Option<Regex>,
+    /// Patterns that identify files whose changes are left out of line-count metrics (ie: lock files).
+    /// See [`ScoringSettings::is_excluded_file`].
+    pub excluded_paths: Vec<Pattern>,
+    /// Heuristic used to tell merge-PRs apart from regular ones. See [`MergePrHeuristic`].
+    pub merge_pr_heuristic: MergePrHeuristic,
+    /// How PRs get merged, which tells whether their first commit can be trusted to date back to when
+    /// work on them started. See [`MergeStyle`].
+    pub merge_style: MergeStyle,
+    /// Whether merge-PRs take part in repository-wide scores (both in their samples and in their
+    /// Pull Request Flow Ratio).
+    pub include_merge_prs: bool,
+    /// Whether PRs that were closed without being merged (ie: abandoned, or rejected) take part in
+    /// repository-wide samples; scored for every metric that does not require a merge, and counted towards
+    /// the `RejectionRate`.
+    pub include_closed_unmerged: bool,
+    /// Which PRs the Pull Request Flow Ratio is calculated over. See [`FlowRatioSource`].
+    pub flow_ratio_source: FlowRatioSource,
+    /// How individual PRs' metrics collapse into a repository's. See [`AggregationStrategy`].
+    pub aggregation_strategy: AggregationStrategy,
+    /// How much each metric weighs on a [`Score`](crate::scoring::score::Score)'s overall grade. See
+    /// [`GradeWeights`].
+    pub grade_weights: GradeWeights,
+    /// The bands within which the most team-dependent metrics are deemed ideal when graded. See
+    /// [`GradingConfig`].
+    pub grading_config: GradingConfig,
+    /// Whether repository-wide scores carry the standard deviation of each metric across their sample,
+    /// on top of its aggregate.
+    pub with_variance: bool,
+    /// Whether each PR's contribution to repository-wide ratio metrics (those relative to its changes) is
+    /// weighted by its size, rather than every PR counting the same. See
+    /// [`AggregationStrategy::aggregate_weighted`].
+    pub weight_by_size: bool,
+    /// Whether the reviews PR authors leave on their own PRs (ie: to annotate them) count as reviews in
+    /// reviewer-centric metrics: `AmountOfReviewers`, `ReviewerDiversity` and `TimeToFirstReview`. They
+    /// always count towards commentary volume, and never towards metrics about the exchange between
+    /// authors and reviewers (`ReviewIterations`, `AuthorResponseLatency`) nor `SelfMergedPrs`.
+    pub count_self_reviews: bool,
+    /// The team's working hours, outside of which activity counts as after-hours. See [`WorkingHours`].
+    pub working_hours: WorkingHours,
+    /// The team's timezone, in which working hours are evaluated and days are told apart (ie: when
+    /// bucketing PRs by day). See [`Timezone`].
+    pub timezone: Timezone,
+    /// The team whose members alone count as participants and reviewers, if any; leaving outside
+    /// contributors (and bots) out of `AmountOfParticipants`, `AmountOfReviewers` and
+    /// `ReviewerDiversity`. See [`TeamMembers`].
+    pub team_members: Option<TeamMembers>,
+    /// Days off (on top of weekends) that do not count as business days. See [`Holidays`].
+    pub holidays: Holidays,
+    /// Markers of heated discussion, if any; `ContentiousDiscussion` is only scored when supplied. See
+    /// [`ContentionWords`].
+    pub contention_words: Option<ContentionWords>,
+}
+
+impl Default for ScoringSettings {
+    fn default() -> Self {
+        ScoringSettings {
+            test_file_pattern: None,
+            excluded_paths: DEFAULT_EXCLUDED_PATHS
+                .iter()
+                .map(|excluded_path| Pattern::new(excluded_path).unwrap())
+                .collect(),
+            merge_pr_heuristic: MergePrHeuristic::TitlePrefix,
+            merge_style: MergeStyle::Auto,
+            include_merge_prs: false,
+            include_closed_unmerged: false,
+            flow_ratio_source: FlowRatioSource::Sample,
+            aggregation_strategy: AggregationStrategy::Mean,
+            grade_weights: GradeWeights::default(),
+            grading_config: GradingConfig::default(),
+            with_variance: false,
+            weight_by_size: false,
+            count_self_reviews: false,
+            working_hours: WorkingHours::default(),
+            timezone: Timezone::default(),
+            team_members: None,
+            holidays: Holidays::default(),
+            contention_words: None,
+        }
+    }
+}
+
+impl ScoringSettings {
+    /// Determines if a [`PatchedFile`](unidiff::PatchedFile)'s affected file corresponds to a test suite
+    /// or not.
+    /// <br/><br/>
+    /// If a `test_file_pattern` was supplied, the file's path must match it. Otherwise, the default
+    /// implementation is quite 'naive' and depends on proper naming conventions (aka the file must have
+    /// the 'test' keyword somewhere in its name).
+    ///
+    /// **The default implementation may trigger false positives if the file contains the word within
+    /// another unrelated word - ie: 'contest'**.
+    pub fn is_test_file(&self, name: &str) -> bool {
+        match &self.test_file_pattern {
+            Some(test_file_pattern) => test_file_pattern.is_match(name),
+            None => name.to_ascii_lowercase().contains("test"),
+        }
+    }
+
+    /// Determines if a user (by `login`) counts as a participant or reviewer; that is, if no
+    /// [`TeamMembers`] were supplied or if they are one of them.
+    pub fn is_team_member(&self, login: &str) -> bool {
+        match &self.team_members {
+            Some(team_members) => team_members.contains(login),
+            None => true,
+        }
+    }
+
+    /// Determines the day a timestamp falls in, as told apart in the team's [`Timezone`]. Every metric that
+    /// buckets timestamps by day must go through it.
+    pub fn local_date(&self, date: &DateTime<Utc>) -> NaiveDate {
+        self.timezone.to_local(date).date()
+    }
+
+    /// Determines if a timestamp falls outside of the team's [`WorkingHours`] (as evaluated in its
+    /// [`Timezone`]), or on a weekend.
+    pub fn is_after_hours(&self, date: &DateTime<Utc>) -> bool {
+        self.working_hours.is_after_hours(&self.timezone.to_local(date))
+    }
+
+    /// Counts the business days elapsed between two timestamps; that is, the weekdays (other than
+    /// [`Holidays`]) that began after `from`, up until the one `to` falls in. Days are told apart in the
+    /// team's [`Timezone`].
+    pub fn count_business_days(&self, from: &DateTime<Utc>, to: &DateTime<Utc>) -> u64 {
+        let last_day = self.local_date(to);
+
+        self.local_date(from)
+            .iter_days()
+            .skip(1)
+            .take_while(|day| *day <= last_day)
+            .filter(|day| {
+                !matches!(day.weekday(), Weekday::Sat | Weekday::Sun)
+                    && !self.holidays.contains(day)
+            })
+            .count() as u64
+    }
+
+    /// Determines if a [`PatchedFile`](unidiff::PatchedFile)'s changes must be left out of line-count
+    /// metrics, according to the `excluded_paths`.
+    /// <br/><br/>
+    /// Patterns are matched against the file's whole path (relative to the repository's root); those
+    /// without any '/' are matched against its bare file name as well, so that - say - `Cargo.lock`
+    /// excludes every lock file in a workspace, not just the top-level one.
+    pub fn is_excluded_file(&self, path: &str) -> bool {
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+
+        self.excluded_paths.iter().any(|excluded_path| {
+            excluded_path.matches(path)
+                || (!excluded_path.as_str().contains('/') && excluded_path.matches(file_name))
+        })
+    }
+}
+
+/// An allowlist of GitHub logins making up a team. Logins are matched ignoring case, as GitHub does.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TeamMembers {
+    logins: HashSet<String>,
+}
+
+impl TeamMembers {
+    /// Reads the team's members from a plain-text file holding one login per line. Blank lines and
+    /// those starting with '#' are ignored.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let raw_members = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read team members file [{}]: {}", path.display(), e))?;
+
+        TeamMembers::from_list(&raw_members)
+            .map_err(|e| format!("Invalid team members file [{}]: {}", path.display(), e))
+    }
+
+    /// Reads the team's members from a list holding one login per line, just like
+    /// [`TeamMembers::from_file`] does.
+    pub fn from_list(raw_members: &str) -> Result<Self, String> {
+        let logins: HashSet<String> = raw_members
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|login| login.trim_start_matches('@').to_lowercase())
+            .collect();
+
+        if logins.is_empty() {
+            return Err(String::from("it lists no logins at all"));
+        }
+
+        Ok(TeamMembers {
+            logins,
+        })
+    }
+
+    pub fn contains(&self, login: &str) -> bool {
+        self.logins.contains(&login.to_lowercase())
+    }
+}
+
+/// Days off (ie: public holidays) that do not count as business days, on top of weekends.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Holidays {
+    dates: HashSet<NaiveDate>,
+}
+
+impl Holidays {
+    /// Reads the holidays from a plain-text file holding one date (ie: `2021-12-25`) per line. Blank lines
+    /// and those starting with '#' are ignored.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let raw_holidays = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read holidays file [{}]: {}", path.display(), e))?;
+
+        Holidays::from_list(&raw_holidays)
+            .map_err(|e| format!("Invalid holidays file [{}]: {}", path.display(), e))
+    }
+
+    /// Reads the holidays from a list holding one date per line, just like [`Holidays::from_file`] does.
+    pub fn from_list(raw_holidays: &str) -> Result<Self, String> {
+        let dates = raw_holidays
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|date| {
+                NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| {
+                    format!(
+                        "dates must be given as YYYY-MM-DD (ie: 2021-12-25), but found {}",
+                        date
+                    )
+                })
+            })
+            .collect::<Result<HashSet<NaiveDate>, String>>()?;
+
+        Ok(Holidays {
+            dates,
+        })
+    }
+
+    pub fn contains(&self, date: &NaiveDate) -> bool {
+        self.dates.contains(date)
+    }
+}
+
+/// Words (or phrases) marking a comment as part of a heated discussion, on top of the built-in escalation
+/// markers: runs of question marks (ie: '???' or '?!?') and all-caps sentences. Words are matched as a
+/// whole and ignoring case.
+/// <br/><br/>
+/// It is a rough, keyword-based heuristic; it knows nothing about context nor tone, so it can only ever
+/// hint at discussions worth a second look.
+#[derive(Debug, Clone)]
+pub struct ContentionWords {
+    words: Option<Regex>,
+}
+
+impl ContentionWords {
+    /// Reads the contention words from a plain-text file holding one word (or phrase) per line. Blank lines
+    /// and those starting with '#' are ignored; an empty file leaves the built-in markers alone.
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let raw_words = fs::read_to_string(path).map_err(|e| {
+            format!("Could not read contention words file [{}]: {}", path.display(), e)
+        })?;
+
+        Ok(ContentionWords::from_list(&raw_words))
+    }
+
+    /// Reads the contention words from a list holding one word (or phrase) per line, just like
+    /// [`ContentionWords::from_file`] does.
+    pub fn from_list(raw_words: &str) -> Self {
+        let words: Vec<String> = raw_words
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(regex::escape)
+            .collect();
+
+        ContentionWords {
+            words: if words.is_empty() {
+                None
+            } else {
+                Some(Regex::new(&format!(r"(?i)\b(?:{})\b", words.join("|"))).unwrap())
+            },
+        }
+    }
+
+    /// Determines whether a `comment` carries any escalation marker.
+    pub fn is_contentious(&self, comment: &str) -> bool {
+        lazy_static! {
+            static ref PUNCTUATION_RUN_REGEX: Regex = Regex::new(r"[?!]{3,}").unwrap();
+            static ref SENTENCE_END_REGEX: Regex = Regex::new(r"[.!?\n]+").unwrap();
+        }
+
+        // runs of exclamation marks alone are far more likely to be enthusiasm than exasperation
+        PUNCTUATION_RUN_REGEX.find_iter(comment).any(|run| run.as_str().contains('?'))
+            || SENTENCE_END_REGEX.split(comment).any(is_all_caps_sentence)
+            || self.words.iter().any(|words| words.is_match(comment))
+    }
+}
+
+/// Determines whether a `sentence` is being shouted; that is, it holds at least 3 words (of 2 letters or
+/// more), and none of its letters are lowercase. Shorter ones are most likely acronyms (ie: 'LGTM').
+fn is_all_caps_sentence(sentence: &str) -> bool {
+    let words: Vec<&str> = sentence
+        .split_whitespace()
+        .filter(|word| word.chars().filter(|c| c.is_alphabetic()).count() >= 2)
+        .collect();
+
+    words.len() >= 3 && words.iter().all(|word| !word.chars().any(char::is_lowercase))
+}
+
+/// The span of each weekday during which a team is expected to be working; activity outside of it (or
+/// on weekends) counts as after-hours. Timestamps are evaluated in the team's [`Timezone`] (see
+/// [`ScoringSettings::is_after_hours`]).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct WorkingHours {
+    /// Hour of the day (0-23) at which the working day starts.
+    pub start: u32,
+    /// Hour of the day (1-24) at which the working day ends; itself excluded.
+    pub end: u32,
+}
+
+impl Default for WorkingHours {
+    fn default() -> Self {
+        WorkingHours {
+            start: 9,
+            end: 18,
+        }
+    }
+}
+
+impl WorkingHours {
+    /// Parses a span of hours (ie: `9-18`) into its `(start, end)` hours.
+    pub fn parse_hours(value: &str) -> Result<(u32, u32), String> {
+        let hours: Vec<Option<u32>> =
+            value.splitn(2, '-').map(|hour| hour.trim().parse().ok()).collect();
+
+        match hours.as_slice() {
+            [Some(start), Some(end)] if start < end && *end <= 24 => Ok((*start, *end)),
+            _ => Err(format!(
+                "Supplied value must be a span of hours within 0-24 (ie: 9-18), but was {}",
+                value
+            )),
+        }
+    }
+
+    /// Determines if a wall-clock timestamp (already in the team's [`Timezone`]) falls outside of these
+    /// working hours, or on a weekend.
+    pub fn is_after_hours(&self, local_date: &NaiveDateTime) -> bool {
+        matches!(local_date.weekday(), Weekday::Sat | Weekday::Sun)
+            || local_date.hour() < self.start
+            || local_date.hour() >= self.end
+    }
+}
+
+/// A team's timezone; either an IANA one (ie: `Asia/Tokyo`), which follows its daylight saving time
+/// changes, or a fixed offset from UTC (ie: `+09:00`). Defaults to UTC.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Timezone {
+    Named(Tz),
+    UtcOffset(FixedOffset),
+}
+
+impl Default for Timezone {
+    fn default() -> Self {
+        Timezone::UtcOffset(FixedOffset::east(0))
+    }
+}
+
+impl Timezone {
+    /// Parses a timezone, given as either an IANA name (ie: `Asia/Tokyo`, `America/Argentina/Buenos_Aires`)
+    /// or an offset from UTC (ie: `+02:00`, `-0530`, `+9` or plain `UTC`).
+    pub fn parse(value: &str) -> Result<Self, String> {
+        lazy_static! {
+            static ref UTC_OFFSET_REGEX: Regex =
+                Regex::new(r"^([+-])(\d{1,2})(?::?(\d{2}))?$").unwrap();
+        }
+
+        if value.eq_ignore_ascii_case("utc") || value.eq_ignore_ascii_case("z") {
+            return Ok(Timezone::default());
+        }
+
+        if let Ok(tz) = value.parse::<Tz>() {
+            return Ok(Timezone::Named(tz));
+        }
+
+        UTC_OFFSET_REGEX
+            .captures(value)
+            .and_then(|captures| {
+                let hours: i32 = captures[2].parse().ok()?;
+                let minutes: i32 = captures.get(3).map_or(Ok(0), |minutes| minutes.as_str().parse()).ok()?;
+                let sign = if &captures[1] == "-" { -1 } else { 1 };
+
+                if hours > 14 || minutes > 59 {
+                    return None;
+                }
+
+                FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+            })
+            .map(Timezone::UtcOffset)
+            .ok_or_else(|| {
+                format!(
+                    "Supplied value must be an IANA timezone (ie: Asia/Tokyo) or an offset from UTC (ie: +02:00, -0530 or UTC), but was {}",
+                    value
+                )
+            })
+    }
+
+    /// Converts a timestamp into this timezone's wall-clock date and time.
+    pub fn to_local(self, date: &DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Timezone::Named(tz) => date.with_timezone(&tz).naive_local(),
+            Timezone::UtcOffset(utc_offset) => date.with_timezone(&utc_offset).naive_local(),
+        }
+    }
+}
+
+/// Heuristics available for determining whether a [`PullRequest`](octocrab::models::pulls::PullRequest)
+/// is a merge-PR (that is, a PR that is basically used to update branches between environments - ie:
+/// merging the 'develop' branch into the 'master' branch).
+#[derive(Display, EnumString, EnumVariantNames, Debug, Copy, Clone, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum MergePrHeuristic {
+    /// The PR's title starts with 'Merge' (ie: "Merge develop into QA"). Depends on proper naming
+    /// conventions, so back-merges titled otherwise (ie: "Sync develop → main") slip through, while
+    /// regular PRs whose title just happens to start with 'Merge' get wrongly flagged.
+    TitlePrefix,
+    /// The PR's head commit is a merge commit (it has more than one parent), or the PR carries no
+    /// net changes at all (as is the case for branches that were already up to date).
+    MultipleParents,
+    /// Both of the above must hold. The strictest (and least false-positive prone) of all heuristics.
+    Both,
+}
+
+/// How a team merges its PRs; which tells whether a PR's first commit dates back to when work on it
+/// started, or just to when that work got squashed into a single commit (making it useless for
+/// `TimeToMerge`).
+#[derive(Display, EnumString, EnumVariantNames, Debug, Copy, Clone, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum MergeStyle {
+    /// Squashed PRs are told apart one by one: those carrying a single commit that was authored after
+    /// they were opened (ie: force-pushed over the branch's history).
+    Auto,
+    /// Every PR's commits are taken as squashed.
+    Squash,
+    /// No PR's commits are taken as squashed; their first commit is always trusted.
+    Merge,
+}
+
+/// The PRs over which a repository's Pull Request Flow Ratio gets calculated.
+#[derive(Display, EnumString, EnumVariantNames, Debug, Copy, Clone, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum FlowRatioSource {
+    /// The sampled PRs alone. Cheap, but biased for small samples: PRs opened or closed around the
+    /// sampled ones that did not make it into the sample are simply not counted.
+    Sample,
+    /// Every PR opened or closed in the repository throughout the sample's time span, as reported by
+    /// GitHub. Costs a few extra (lightweight) API requests per repository.
+    Repository,
+}
+
+/// Strategies available for collapsing the individual metrics of a sample of PRs into a single
+/// repository-wide metric.
+#[derive(Display, EnumString, EnumVariantNames, Debug, Copy, Clone, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum AggregationStrategy {
+    /// The arithmetic mean. Sensitive to skew: a single 5000-line PR drags a whole sample's size up.
+    Mean,
+    /// The middle value of the sample (or the mean of the two middle ones, for even-sized samples).
+    /// Immune to a handful of outliers.
+    Median,
+    /// The value that 90% of the sample does not exceed (nearest-rank method). Useful for keeping an
+    /// eye on the worst offenders without being dominated by a single one of them.
+    P90,
+}
+
+impl AggregationStrategy {
+    /// Collapses a `sample` of values into a single one. Returns [`None`] if the `sample` is empty.
+    pub fn aggregate(&self, sample: &[f64]) -> Option<f64> {
+        if sample.is_empty() {
+            return None;
+        }
+
+        let mut sorted_sample = sample.to_vec();
+        sorted_sample.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        let len = sorted_sample.len();
+
+        let aggregate = match self {
+            AggregationStrategy::Mean => sorted_sample.iter().sum::<f64>() / len as f64,
+            // both indexes point to the same (middle) value for odd-sized samples
+            AggregationStrategy::Median => {
+                (sorted_sample[(len - 1) / 2] + sorted_sample[len / 2]) / 2.0
+            }
+            AggregationStrategy::P90 => {
+                let rank = (0.9 * len as f64).ceil() as usize;
+                sorted_sample[rank.max(1) - 1]
+            }
+        };
+
+        Some(aggregate)
+    }
+
+    /// Collapses a `sample` of `(value, weight)` pairs into a single value, where each value counts as
+    /// much as its weight does: [`AggregationStrategy::Mean`] becomes the weighted mean, while
+    /// [`AggregationStrategy::Median`] and [`AggregationStrategy::P90`] become the value at which the
+    /// accumulated weight reaches half (or 90%) of the total. Returns [`None`] if the `sample` is empty.
+    /// <br/><br/>
+    /// Negative weights count as zero. If no value weighs anything at all, every value counts the same.
+    pub fn aggregate_weighted(&self, sample: &[(f64, f64)]) -> Option<f64> {
+        let total_weight: f64 = sample.iter().map(|(_, weight)| weight.max(0.0)).sum();
+
+        if total_weight <= 0.0 {
+            return self.aggregate(&sample.iter().map(|(value, _)| *value).collect::<Vec<f64>>());
+        }
+
+        let mut sorted_sample = sample.to_vec();
+        sorted_sample.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+        let weighted_rank = |quantile: f64| {
+            let mut accumulated_weight = 0.0;
+
+            sorted_sample
+                .iter()
+                .find(|(_, weight)| {
+                    accumulated_weight += weight.max(0.0);
+                    accumulated_weight >= quantile * total_weight
+                })
+                .or_else(|| sorted_sample.last()) // guards against rounding errors
+                .map(|(value, _)| *value)
+        };
+
+        match self {
+            AggregationStrategy::Mean => Some(
+                sorted_sample.iter().map(|(value, weight)| value * weight.max(0.0)).sum::<f64>()
+                    / total_weight,
+            ),
+            AggregationStrategy::Median => weighted_rank(0.5),
+            AggregationStrategy::P90 => weighted_rank(0.9),
+        }
+    }
+}
+
+#[cfg(test)]
+mod scoring_settings_tests {
+    use regex::Regex;
+
+    use chrono::{FixedOffset, NaiveDate, TimeZone, Utc};
+
+    use crate::scoring::scoring_settings::{
+        AggregationStrategy, ContentionWords, Holidays, ScoringSettings, TeamMembers, Timezone,
+        WorkingHours,
+    };
+
+    #[test]
+    fn default_test_file_detection_relies_on_the_test_keyword() {
+        let scoring_settings = ScoringSettings::default();
+
+        assert!(!scoring_settings.is_test_file("src/foo.rs"));
+        assert!(scoring_settings.is_test_file("tests/foo_test.rs"));
+        assert!(scoring_settings.is_test_file("src/contest.rs")); // known false positive
+    }
+
+    #[test]
+    fn custom_test_file_pattern_overrides_default_heuristic() {
+        let scoring_settings = ScoringSettings {
+            test_file_pattern: Some(Regex::new(r"(^|/)tests?/|_test\.rs$").unwrap()),
+            ..Default::default()
+        };
+
+        assert!(!scoring_settings.is_test_file("src/foo.rs"));
+        assert!(scoring_settings.is_test_file("tests/foo_test.rs"));
+        assert!(!scoring_settings.is_test_file("src/contest.rs"));
+    }
+
+    #[test]
+    fn default_excluded_paths_cover_lock_minified_and_vendored_files() {
+        let scoring_settings = ScoringSettings::default();
+
+        assert!(scoring_settings.is_excluded_file("Cargo.lock"));
+        assert!(scoring_settings.is_excluded_file("web/package-lock.json"));
+        assert!(scoring_settings.is_excluded_file("static/js/app.min.js"));
+        assert!(scoring_settings.is_excluded_file("vendor/github.com/foo/bar.go"));
+        assert!(scoring_settings.is_excluded_file("web/node_modules/left-pad/index.js"));
+        assert!(!scoring_settings.is_excluded_file("src/lock.rs"));
+        assert!(!scoring_settings.is_excluded_file("src/vendors.rs"));
+    }
+
+    #[test]
+    fn aggregation_strategies_over_a_skewed_sample() {
+        let sample = [10.0, 20.0, 30.0, 40.0, 5000.0, 15.0, 25.0, 35.0, 45.0, 50.0];
+
+        assert_eq!(AggregationStrategy::Mean.aggregate(&sample), Some(527.0));
+        assert_eq!(AggregationStrategy::Median.aggregate(&sample), Some(32.5));
+        assert_eq!(AggregationStrategy::P90.aggregate(&sample), Some(50.0));
+        assert_eq!(AggregationStrategy::Median.aggregate(&[]), None);
+    }
+
+    #[test]
+    fn weighted_aggregation_strategies_favor_heavier_values() {
+        // a tiny PR full of commentary, and a huge one with barely any
+        let sample = [(1.0, 5.0), (0.01, 2000.0)];
+
+        let weighted_mean = AggregationStrategy::Mean.aggregate_weighted(&sample).unwrap();
+        assert!((weighted_mean - 0.012_468_827).abs() < 1e-6);
+        assert_eq!(AggregationStrategy::Median.aggregate_weighted(&sample), Some(0.01));
+        assert_eq!(AggregationStrategy::P90.aggregate_weighted(&sample), Some(0.01));
+
+        // weightless samples fall back to their plain aggregate
+        assert_eq!(
+            AggregationStrategy::Mean.aggregate_weighted(&[(1.0, 0.0), (3.0, 0.0)]),
+            Some(2.0)
+        );
+        assert_eq!(AggregationStrategy::Mean.aggregate_weighted(&[]), None);
+    }
+
+    #[test]
+    fn working_hours_and_timezones_are_parsed() {
+        assert_eq!(WorkingHours::parse_hours("9-18"), Ok((9, 18)));
+        assert_eq!(WorkingHours::parse_hours("0-24"), Ok((0, 24)));
+        assert!(WorkingHours::parse_hours("18-9").is_err());
+        assert!(WorkingHours::parse_hours("9-25").is_err());
+        assert!(WorkingHours::parse_hours("9").is_err());
+
+        assert_eq!(Timezone::parse("UTC"), Ok(Timezone::default()));
+        assert_eq!(Timezone::parse("+02:00"), Ok(Timezone::UtcOffset(FixedOffset::east(2 * 3600))));
+        assert_eq!(
+            Timezone::parse("-0530"),
+            Ok(Timezone::UtcOffset(FixedOffset::west(5 * 3600 + 1800)))
+        );
+        assert_eq!(Timezone::parse("+9"), Ok(Timezone::UtcOffset(FixedOffset::east(9 * 3600))));
+        assert_eq!(
+            Timezone::parse("Europe/Madrid"),
+            Ok(Timezone::Named(chrono_tz::Europe::Madrid))
+        );
+        assert!(Timezone::parse("Europe/Atlantis").is_err());
+        assert!(Timezone::parse("+25:00").is_err());
+    }
+
+    #[test]
+    fn after_hours_are_evaluated_in_the_team_timezone() {
+        let scoring_settings = ScoringSettings {
+            timezone: Timezone::UtcOffset(FixedOffset::west(5 * 3600)),
+            ..Default::default()
+        };
+
+        // monday, 10:00 UTC is 05:00 in the team's timezone
+        assert!(scoring_settings.is_after_hours(&Utc.ymd(2021, 3, 1).and_hms(10, 0, 0)));
+        assert!(!scoring_settings.is_after_hours(&Utc.ymd(2021, 3, 1).and_hms(14, 0, 0)));
+        // 18:00 itself is already after hours
+        assert!(scoring_settings.is_after_hours(&Utc.ymd(2021, 3, 1).and_hms(23, 0, 0)));
+        // saturday
+        assert!(scoring_settings.is_after_hours(&Utc.ymd(2021, 3, 6).and_hms(14, 0, 0)));
+        // saturday, 02:00 UTC is still friday in the team's timezone
+        assert!(!ScoringSettings {
+            working_hours: WorkingHours {
+                start: 0,
+                end: 24,
+            },
+            ..scoring_settings
+        }
+        .is_after_hours(&Utc.ymd(2021, 3, 6).and_hms(2, 0, 0)));
+    }
+
+    #[test]
+    fn named_timezones_follow_their_daylight_saving_time_changes() {
+        let scoring_settings = ScoringSettings {
+            timezone: Timezone::parse("Europe/Madrid").unwrap(),
+            ..Default::default()
+        };
+
+        // 23:30 UTC is already the next day in Madrid, be it in winter (UTC+1) or in summer (UTC+2)
+        assert_eq!(
+            scoring_settings.local_date(&Utc.ymd(2021, 1, 4).and_hms(23, 30, 0)),
+            NaiveDate::from_ymd(2021, 1, 5)
+        );
+        // but 22:30 UTC only is in summer
+        assert_eq!(
+            scoring_settings.local_date(&Utc.ymd(2021, 1, 4).and_hms(22, 30, 0)),
+            NaiveDate::from_ymd(2021, 1, 4)
+        );
+        assert_eq!(
+            scoring_settings.local_date(&Utc.ymd(2021, 7, 5).and_hms(22, 30, 0)),
+            NaiveDate::from_ymd(2021, 7, 6)
+        );
+    }
+
+    #[test]
+    fn team_members_are_read_one_per_line_ignoring_case_and_comments() {
+        let team_members =
+            TeamMembers::from_list("# backend team\n  Alice\n\n@bob\n# carol left\n").unwrap();
+
+        assert!(team_members.contains("alice"));
+        assert!(team_members.contains("ALICE"));
+        assert!(team_members.contains("bob"));
+        assert!(!team_members.contains("carol"));
+        assert!(TeamMembers::from_list("# nobody yet\n").is_err());
+
+        let scoring_settings = ScoringSettings {
+            team_members: Some(team_members),
+            ..Default::default()
+        };
+        assert!(scoring_settings.is_team_member("Bob"));
+        assert!(!scoring_settings.is_team_member("outsider"));
+        assert!(ScoringSettings::default().is_team_member("outsider"));
+    }
+
+    #[test]
+    fn comments_are_contentious_when_escalating_or_using_contention_words() {
+        let contention_words =
+            ContentionWords::from_list("# rough ones\nnonsense\nare you kidding\n");
+
+        assert!(contention_words.is_contentious("Why would you do this???"));
+        assert!(contention_words.is_contentious("Seriously?!?"));
+        assert!(contention_words.is_contentious("I said it twice. THIS IS NOT HOW WE DO IT."));
+        assert!(contention_words.is_contentious("This is Nonsense"));
+        assert!(contention_words.is_contentious("Are you kidding me"));
+
+        assert!(!contention_words.is_contentious("LGTM! Thanks for the fix :)"));
+        assert!(!contention_words.is_contentious("Great job!!!"));
+        assert!(!contention_words.is_contentious("Should this be a `const`? Not sure."));
+        assert!(!contention_words.is_contentious("Bumps the API to v2 (see RFC 42)."));
+        assert!(!contention_words.is_contentious("Nonsensical naming aside, it works"));
+        assert!(!ContentionWords::from_list("").is_contentious("This is nonsense"));
+    }
+
+    #[test]
+    fn holidays_are_read_one_date_per_line_ignoring_comments() {
+        let holidays = Holidays::from_list("# 2021\n2021-12-25\n\n  2021-01-01  \n").unwrap();
+
+        assert!(holidays.contains(&NaiveDate::from_ymd(2021, 12, 25)));
+        assert!(holidays.contains(&NaiveDate::from_ymd(2021, 1, 1)));
+        assert!(!holidays.contains(&NaiveDate::from_ymd(2021, 12, 24)));
+        assert_eq!(Holidays::from_list("# none yet\n"), Ok(Holidays::default()));
+        assert!(Holidays::from_list("25/12/2021").is_err());
+    }
+
+    #[test]
+    fn business_days_leave_weekends_and_holidays_out() {
+        let friday = Utc.ymd(2021, 3, 5).and_hms(15, 0, 0);
+        let monday = Utc.ymd(2021, 3, 8).and_hms(9, 0, 0);
+        let scoring_settings = ScoringSettings::default();
+
+        assert_eq!(scoring_settings.count_business_days(&friday, &friday), 0);
+        assert_eq!(scoring_settings.count_business_days(&friday, &monday), 1);
+        assert_eq!(scoring_settings.count_business_days(&monday, &friday), 0);
+
+        let with_holidays = ScoringSettings {
+            holidays: Holidays::from_list("2021-03-08").unwrap(),
+            ..Default::default()
+        };
+        assert_eq!(with_holidays.count_business_days(&friday, &monday), 0);
+
+        // 23:00 UTC on a Friday is already Saturday two hours east
+        let late_friday = Utc.ymd(2021, 3, 5).and_hms(23, 0, 0);
+        let east_of_utc = ScoringSettings {
+            timezone: Timezone::UtcOffset(FixedOffset::east(2 * 3600)),
+            ..Default::default()
+        };
+        assert_eq!(scoring_settings.count_business_days(&friday, &late_friday), 0);
+        assert_eq!(east_of_utc.count_business_days(&late_friday, &monday), 1);
+    }
+}
@@ -0,0 +1,152 @@
+//! Thresholds that a [`Score`]'s metrics must not breach; which turn an analysis into a quality gate (ie:
+//! for CI pipelines).
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use strum_macros::{Display, EnumString};
+
+use crate::scoring::score::{Score, ScoreType};
+
+/// Comparison operators available for a [`Threshold`].
+#[derive(Display, EnumString, Debug, Copy, Clone, PartialEq)]
+pub enum ComparisonOperator {
+    #[strum(serialize = ">")]
+    GreaterThan,
+    #[strum(serialize = ">=")]
+    GreaterThanOrEqual,
+    #[strum(serialize = "<")]
+    LessThan,
+    #[strum(serialize = "<=")]
+    LessThanOrEqual,
+    #[strum(serialize = "==")]
+    Equal,
+    #[strum(serialize = "!=")]
+    NotEqual,
+}
+
+impl ComparisonOperator {
+    fn holds(&self, value: f64, limit: f64) -> bool {
+        match self {
+            ComparisonOperator::GreaterThan => value > limit,
+            ComparisonOperator::GreaterThanOrEqual => value >= limit,
+            ComparisonOperator::LessThan => value < limit,
+            ComparisonOperator::LessThanOrEqual => value <= limit,
+            ComparisonOperator::Equal => (value - limit).abs() < f64::EPSILON,
+            ComparisonOperator::NotEqual => (value - limit).abs() >= f64::EPSILON,
+        }
+    }
+}
+
+/// A condition on a single metric that, when met, deems a [`Score`] as failing - ie:
+/// `pull_request_size>800`. Metrics are keyed by their CSV column name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Threshold {
+    metric: &'static str,
+    operator: ComparisonOperator,
+    limit: f64,
+}
+
+impl Threshold {
+    /// Returns the value of the `score`'s metric if it breaches this [`Threshold`]; or [`None`] if it
+    /// does not (metrics missing from the `score` cannot breach anything).
+    pub fn breached_by(&self, score: &Score) -> Option<f64> {
+        score
+            .score_types()
+            .iter()
+            .find(|score_type| score_type.get_csv_column() == self.metric)
+            .map(|score_type| score_type.get_value())
+            .filter(|value| self.operator.holds(*value, self.limit))
+    }
+}
+
+impl FromStr for Threshold {
+    type Err = String;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            static ref THRESHOLD_REGEX: Regex =
+                Regex::new(r"^\s*([a-z_]+)\s*(>=|<=|==|!=|>|<)\s*(\S+)\s*$").unwrap();
+        }
+
+        let captures = THRESHOLD_REGEX.captures(expression).ok_or_else(|| {
+            format!(
+                "invalid threshold [{}]; expected <metric><operator><value> (ie: pull_request_size>800)",
+                expression
+            )
+        })?;
+
+        let metric = ScoreType::get_iter()
+            .map(|score_type| score_type.get_csv_column())
+            .find(|metric| *metric == &captures[1])
+            .ok_or_else(|| {
+                format!(
+                    "unknown metric [{}]; valid ones are [{}]",
+                    &captures[1],
+                    ScoreType::get_iter()
+                        .map(|score_type| score_type.get_csv_column())
+                        .collect::<Vec<&str>>()
+                        .join("], [")
+                )
+            })?;
+
+        let limit = captures[3]
+            .parse::<f64>()
+            .ok()
+            .filter(|limit| limit.is_finite())
+            .ok_or_else(|| format!("threshold value [{}] must be a number", &captures[3]))?;
+
+        Ok(Threshold {
+            metric,
+            // the regex only lets valid operators through
+            operator: captures[2].parse().unwrap(),
+            limit,
+        })
+    }
+}
+
+impl Display for Threshold {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        write!(f, "{} {} {}", self.metric, self.operator, self.limit)
+    }
+}
+
+#[cfg(test)]
+mod threshold_tests {
+    use crate::scoring::score::{Score, ScoreType};
+    use crate::scoring::threshold::Threshold;
+
+    #[test]
+    fn thresholds_are_parsed_from_expressions() {
+        let threshold: Threshold = "pull_request_size>800".parse().unwrap();
+        assert_eq!(threshold.to_string(), "pull_request_size > 800");
+
+        let threshold: Threshold = " test_to_code_ratio <= 0.25 ".parse().unwrap();
+        assert_eq!(threshold.to_string(), "test_to_code_ratio <= 0.25");
+
+        assert!("pull_request_size".parse::<Threshold>().is_err());
+        assert!("pull_request_size=>800".parse::<Threshold>().is_err());
+        assert!("unknown_metric>1".parse::<Threshold>().is_err());
+        assert!("pull_request_size>huge".parse::<Threshold>().is_err());
+    }
+
+    #[test]
+    fn only_present_metrics_meeting_the_condition_breach_a_threshold() {
+        let score =
+            Score::new(vec![ScoreType::PullRequestSize(1200), ScoreType::TestToCodeRatio(0.5)]);
+
+        let breach =
+            |expression: &str| expression.parse::<Threshold>().unwrap().breached_by(&score);
+
+        assert_eq!(breach("pull_request_size>800"), Some(1200.0));
+        assert_eq!(breach("pull_request_size>=1200"), Some(1200.0));
+        assert_eq!(breach("pull_request_size<800"), None);
+        assert_eq!(breach("test_to_code_ratio<0.6"), Some(0.5));
+        assert_eq!(breach("test_to_code_ratio==0.5"), Some(0.5));
+        assert_eq!(breach("test_to_code_ratio!=0.5"), None);
+        // missing metrics cannot be judged
+        assert_eq!(breach("time_to_merge>=0"), None);
+    }
+}
@@ -0,0 +1,110 @@
+// shared with the library's own tests; not every one of its helpers is needed here
+#[allow(dead_code)]
+#[path = "../src/github/utils/fake_github_api.rs"]
+mod fake_github_api;
+
+#[cfg(test)]
+mod analyze_repo_tests {
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    use prolice::{
+        analyze_repo, AbusePolicy, AnalyzeError, AnalyzeOptions, RetryPolicy, ScoreType,
+    };
+
+    use crate::fake_github_api::{
+        commit_json, fake_github_api, http_response, pull_request_json, repository_json,
+        CannedGitHubApi, GitHubApiResponder, SINGLE_LINE_DIFF,
+    };
+
+    /// Serves a fake GitHub API, hosting a single organization ('acme') with a single repository
+    /// ('service') with a single merged PR; returns its base URL. GitHub's abuse detection mechanisms
+    /// reject every request for the `rejected_route` (if any).
+    async fn acme_api_rejecting(rejected_route: Option<&'static str>) -> String {
+        let pr_route = "/repos/acme/service/pulls/1";
+        let canned_api = CannedGitHubApi::default()
+            .json("/orgs/acme/repos", json!([repository_json("acme", "service")]))
+            .json(
+                "/repos/acme/service/pulls",
+                json!([pull_request_json("acme", "service", 1, "author")]),
+            )
+            .json(pr_route, pull_request_json("acme", "service", 1, "author"))
+            .diff(pr_route, SINGLE_LINE_DIFF)
+            .json("/repos/acme/service/issues/1/comments", json!([]))
+            .json("/repos/acme/service/issues/1/reactions", json!([]))
+            .json(&format!("{}/comments", pr_route), json!([]))
+            .json(&format!("{}/reviews", pr_route), json!([]))
+            .json(
+                &format!("{}/commits", pr_route),
+                json!([commit_json("Add bar", Utc.ymd(2021, 3, 1).and_hms(9, 0, 0), 1)]),
+            );
+
+        fake_github_api(move |request: &str| {
+            let path = request.split_whitespace().nth(1).unwrap_or_default();
+
+            if rejected_route.is_some() && path.split('?').next() == rejected_route {
+                let body = json!({ "message": "You have triggered an abuse detection mechanism." });
+                return http_response(
+                    "403 Forbidden",
+                    &["retry-after: 60".to_string()],
+                    &body.to_string(),
+                );
+            }
+
+            canned_api.respond(request)
+        })
+        .await
+    }
+
+    async fn acme_api() -> String {
+        acme_api_rejecting(None).await
+    }
+
+    fn options_against(base_url: String) -> AnalyzeOptions {
+        AnalyzeOptions {
+            github_base_url: Some(base_url),
+            sample_size: 1,
+            connection_pool_size: 2,
+            retry_policy: RetryPolicy {
+                max_retries: 0,
+                empty_response_retries: 0,
+                ..Default::default()
+            },
+            ..AnalyzeOptions::new("token")
+        }
+    }
+
+    #[tokio::test]
+    async fn repositories_are_analyzed_without_setting_up_a_connection_pool() {
+        let score =
+            analyze_repo("acme", "service", options_against(acme_api().await)).await.unwrap();
+
+        assert!(score.score_types().contains(&ScoreType::PullRequestLeadTime(1)));
+        assert!(score.score_types().contains(&ScoreType::TimeToMerge(1)));
+        assert!(score.score_types().contains(&ScoreType::PullRequestSize(1)));
+    }
+
+    #[tokio::test]
+    async fn unknown_repositories_are_reported_as_errors() {
+        let error = analyze_repo("acme", "unknown", options_against(acme_api().await)).await;
+
+        assert!(matches!(error, Err(AnalyzeError::RepositoryNotFoundError(_))));
+    }
+
+    #[tokio::test]
+    async fn rate_limited_pull_requests_abort_the_analysis_only_if_told_to() {
+        let base_url = acme_api_rejecting(Some("/repos/acme/service/pulls/1/reviews")).await;
+        let options_under = |abuse_policy| {
+            let mut options = options_against(base_url.clone());
+            options.retry_policy.abuse_policy = abuse_policy;
+            options
+        };
+
+        let failed = analyze_repo("acme", "service", options_under(AbusePolicy::Fail)).await;
+        assert!(matches!(failed, Err(e) if e.is_rate_limited()));
+
+        // the PR is merely left out of the sample
+        let skipped = analyze_repo("acme", "service", options_under(AbusePolicy::Skip)).await;
+        assert!(!skipped.unwrap().score_types().contains(&ScoreType::TimeToMerge(1)));
+    }
+}
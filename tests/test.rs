@@ -42,4 +42,122 @@ mod macro_tests {
 
         assert_eq!(aw!(traced_function(dummy_input)), aw!(non_traced_function(dummy_input)));
     }
+
+    #[test]
+    fn prolice_trace_time_routes_timings_to_custom_sink() {
+        use std::sync::Mutex;
+
+        use lazy_static::lazy_static;
+        use prpolice_lib::prolice_trace_time;
+
+        lazy_static! {
+            static ref SINK_INVOCATIONS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        }
+
+        fn record_timing(name: &str, _elapsed: time::Duration) {
+            SINK_INVOCATIONS.lock().unwrap().push(name.to_string());
+        }
+
+        #[prolice_trace_time(sink = "record_timing")]
+        fn traced_function(string: &str) -> usize {
+            string.len()
+        }
+
+        #[prolice_trace_time(sink = "record_timing")]
+        async fn async_traced_function(string: &str) -> usize {
+            string.len()
+        }
+
+        let dummy_input = "this is a dummy input";
+
+        assert_eq!(traced_function(dummy_input), dummy_input.len());
+        assert_eq!(aw!(async_traced_function(dummy_input)), dummy_input.len());
+
+        // timings are not tracked at all on release builds, unless explicitly requested
+        let expected_invocations: Vec<String> =
+            if cfg!(debug_assertions) || cfg!(feature = "trace-time-release") {
+                vec![String::from("traced_function"), String::from("async_traced_function")]
+            } else {
+                Vec::new()
+            };
+        assert_eq!(*SINK_INVOCATIONS.lock().unwrap(), expected_invocations);
+    }
+
+    #[test]
+    fn prolice_trace_time_logs_debug_and_non_debug_arguments_without_altering_results() {
+        use std::sync::{Mutex, Once};
+
+        use lazy_static::lazy_static;
+        use log::{LevelFilter, Log, Metadata, Record};
+        use prpolice_lib::prolice_trace_time;
+
+        lazy_static! {
+            static ref LOGGED_LINES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        }
+        static INIT_LOGGER: Once = Once::new();
+
+        /// Keeps every logged line, so that the traces can be asserted on.
+        struct CapturingLogger;
+
+        impl Log for CapturingLogger {
+            fn enabled(&self, _metadata: &Metadata) -> bool {
+                true
+            }
+
+            fn log(&self, record: &Record) {
+                LOGGED_LINES.lock().unwrap().push(record.args().to_string());
+            }
+
+            fn flush(&self) {}
+        }
+
+        INIT_LOGGER.call_once(|| {
+            log::set_boxed_logger(Box::new(CapturingLogger)).unwrap();
+            log::set_max_level(LevelFilter::Trace);
+        });
+
+        struct NonDebugInput(usize);
+
+        #[prolice_trace_time(log = [pr_number, input])]
+        fn traced_function(pr_number: u64, input: NonDebugInput, unlogged: &str) -> usize {
+            pr_number as usize + input.0 + unlogged.len()
+        }
+
+        #[prolice_trace_time(log = [pr_number])]
+        async fn async_traced_function(pr_number: u64, input: NonDebugInput) -> usize {
+            pr_number as usize + input.0
+        }
+
+        assert_eq!(traced_function(40, NonDebugInput(1), "a"), 42);
+        assert_eq!(aw!(async_traced_function(40, NonDebugInput(2))), 42);
+
+        // other tests may be logging concurrently, so only this test's traces are looked at
+        let logged_lines: Vec<String> = LOGGED_LINES
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|line| line.contains("traced_function(pr_number"))
+            .cloned()
+            .collect();
+
+        // timings are not tracked at all on release builds, unless explicitly requested
+        if cfg!(debug_assertions) || cfg!(feature = "trace-time-release") {
+            assert_eq!(logged_lines.len(), 4);
+            assert_eq!(
+                logged_lines[0],
+                "Tracing time for `fn traced_function(pr_number: 40, input: <non-Debug>)`..."
+            );
+            assert!(logged_lines[1].starts_with(
+                "Time elapsed for `fn traced_function(pr_number: 40, input: <non-Debug>)` was: "
+            ));
+            assert_eq!(
+                logged_lines[2],
+                "Tracing time for `fn async_traced_function(pr_number: 40)`..."
+            );
+            assert!(logged_lines[3]
+                .starts_with("Time elapsed for `fn async_traced_function(pr_number: 40)` was: "));
+        } else {
+            assert!(logged_lines.is_empty());
+        }
+    }
 }